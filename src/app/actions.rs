@@ -1,7 +1,17 @@
+use std::path::PathBuf;
+
 use im::Vector;
+use serde::{Deserialize, Serialize};
 use yandex_music::model::track::Track;
 
-use crate::audio::queue::PlaybackContext;
+use crate::{
+    app::views::ModalRequest,
+    audio::{local::QueueItem, outputs::OutputTarget, queue::PlaybackContext},
+    util::{
+        export::ExportFormat,
+        state::{ContextPosition, HistoryContext},
+    },
+};
 
 #[derive(Debug, Clone, Default)]
 pub enum Action {
@@ -12,9 +22,11 @@ pub enum Action {
     Back,
     Overlay(Route),
     DismissOverlay,
+    OpenModal(ModalRequest),
+    CloseModal,
     PlayContext {
         context: PlaybackContext,
-        tracks: Vector<Track>,
+        tracks: Vector<QueueItem>,
         start_index: usize,
     },
     PlayTrack(Track),
@@ -23,14 +35,36 @@ pub enum Action {
     PreviousTrack,
     SeekForward(u64),
     SeekBackward(u64),
+    SeekTo(u64),
     SetVolume(u8),
+    VolumeUp(u8),
+    VolumeDown(u8),
     ToggleMute,
     ToggleShuffle,
     CycleRepeat,
+    AdjustTrackGain(f32),
+    AdjustKaraoke(f32),
+    AdjustStereoWidth(f32),
+    AdjustPodcastSpeed(f32),
+    SeekChapter(i8),
+    ReloadTrack,
     LikeTrack(Track),
+    LikeTracks(Vec<Track>),
     UnlikeTrack(Track),
     DislikeTrack(Track),
+    ToggleEpisodePlayed(Track),
+    ResumeContext(ContextPosition),
     QueueTrack(Track),
+    QueueTracks(Vec<Track>),
+    AddToPlaylist {
+        kind: u32,
+        title: String,
+        tracks: Vec<Track>,
+    },
+    QueueLocal(String),
+    PlayLocal(PathBuf),
+    OpenFolder(PathBuf),
+    DownloadTracks(Vec<Track>),
     PlayNext(Track),
     RemoveFromQueue(usize),
     ClearQueue,
@@ -38,24 +72,38 @@ pub enum Action {
     DislikeContext,
     QueueAll,
     PlayAllNext,
-    FetchData {
-        source_id: String,
-        range: (usize, usize),
-    },
+    PlayDiscography,
     Refresh,
     Search(String),
+    FocusSearch(Option<String>),
     SearchNextPage,
     StartWave {
         seeds: Vec<String>,
         title: Option<String>,
         toast_message: Option<Vec<ratatui::text::Line<'static>>>,
+        lead: Vec<Track>,
     },
+    CancelWave,
     RefreshWaves,
+    RegenerateDailyMix,
+    ToggleProfiler,
     ToggleEffect(String),
     ToggleEqPreset(String),
     Quit,
     Toast(String),
-    Focus(String),
+    CopyToClipboard(String),
+    SaveLyrics(String),
+    OpenUrl(String),
+    RunCommand(String, Vec<(String, String)>),
+    DiscoverOutputs,
+    SwitchOutput(Option<OutputTarget>),
+    ImportPlaylist(String),
+    ImportChoose {
+        index: usize,
+        candidate: Option<usize>,
+    },
+    ImportCommit,
+    ExportTracks(ExportFormat),
     ScrollTop,
     ScrollBottom,
     Batch(Vec<Action>),
@@ -66,6 +114,19 @@ impl Action {
         matches!(self, Action::None)
     }
 
+    pub fn wave(seed: String, title: Option<String>) -> Action {
+        Action::StartWave {
+            seeds: vec![seed],
+            title,
+            toast_message: None,
+            lead: Vec::new(),
+        }
+    }
+
+    pub fn wave_for_track(track: &Track) -> Action {
+        Action::wave(format!("track:{}", track.id), track.title.clone())
+    }
+
     pub fn and(self, other: Action) -> Action {
         match (self, other) {
             (Action::None, other) => other,
@@ -87,20 +148,50 @@ impl Action {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Route {
     Home,
     Search,
     Playlists,
     Liked,
-    Playlist { kind: u32, title: String },
-    Album { id: String, title: String },
-    Artist { id: String, name: String },
-    Track { id: String },
+    Files,
+    /// `owner` is the playlist owner's uid, or 0 for the signed-in user.
+    Playlist {
+        kind: u32,
+        owner: u64,
+        title: String,
+    },
+    Album {
+        id: String,
+        title: String,
+    },
+    Podcast {
+        id: String,
+        title: String,
+    },
+    Artist {
+        id: String,
+        name: String,
+    },
+    ArtistAlbums {
+        id: String,
+        name: String,
+    },
+    SmartPlaylist {
+        name: String,
+    },
+    Track {
+        id: String,
+    },
     Lyrics,
     Queue,
+    History,
+    MostPlayed,
+    Disliked,
+    Notifications,
     Effects,
     ThemePicker,
+    Tasks,
     Settings,
 }
 
@@ -111,15 +202,34 @@ impl Route {
             Route::Search => "Search".to_string(),
             Route::Liked => "Liked Tracks".to_string(),
             Route::Playlists => "Playlists".to_string(),
+            Route::Files => "Local Files".to_string(),
             Route::Playlist { title, .. } => title.clone(),
-            Route::Album { title, .. } => title.clone(),
-            Route::Artist { name, .. } => name.clone(),
+            Route::Album { title, .. } | Route::Podcast { title, .. } => title.clone(),
+            Route::Artist { name, .. } | Route::SmartPlaylist { name } => name.clone(),
+            Route::ArtistAlbums { name, .. } => format!("{name} — Albums"),
             Route::Track { id } => format!("Track {}", id),
             Route::Lyrics => "Lyrics".to_string(),
             Route::Queue => "Queue".to_string(),
+            Route::History => "History".to_string(),
+            Route::MostPlayed => "Most Played".to_string(),
+            Route::Disliked => "Disliked Tracks".to_string(),
+            Route::Notifications => "Notifications".to_string(),
             Route::Effects => "Effects".to_string(),
             Route::ThemePicker => "Theme Picker".to_string(),
+            Route::Tasks => "Tasks".to_string(),
             Route::Settings => "Settings".to_string(),
         }
     }
+
+    pub fn history_context(&self) -> Option<HistoryContext> {
+        match self.clone() {
+            Route::Playlist { kind, owner, title } => {
+                Some(HistoryContext::Playlist { kind, owner, title })
+            }
+            Route::Album { id, title } => Some(HistoryContext::Album { id, title }),
+            Route::Podcast { id, title } => Some(HistoryContext::Podcast { id, title }),
+            Route::Artist { id, name } => Some(HistoryContext::Artist { id, name }),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,120 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{app::theme::theme, util::text};
+
+const COLLAPSED_LINES: usize = 2;
+const EXPANDED_LINES: usize = 8;
+
+pub struct Description {
+    text: String,
+    expanded: bool,
+    scroll: usize,
+}
+
+impl Description {
+    pub fn new(raw: &str) -> Option<Self> {
+        let text = text::strip_markdown(raw).trim().to_string();
+        (!text.is_empty()).then_some(Self {
+            text,
+            expanded: false,
+            scroll: 0,
+        })
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub fn toggle(&mut self) {
+        self.expanded = !self.expanded;
+        self.scroll = 0;
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    fn text_width(area_width: u16) -> usize {
+        area_width.saturating_sub(2) as usize
+    }
+
+    pub fn height(&self, width: u16) -> u16 {
+        let lines = text::wrap(&self.text, Self::text_width(width)).len();
+        let limit = if self.expanded {
+            EXPANDED_LINES
+        } else {
+            COLLAPSED_LINES
+        };
+        lines.min(limit) as u16
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let width = Self::text_width(area.width);
+        let wrapped = text::wrap(&self.text, width);
+        let text_style = Style::default().fg(colors.text.secondary);
+        let hint_style = Style::default().fg(colors.text.muted);
+
+        let visible = area.height as usize;
+        let lines: Vec<Line> = if self.expanded {
+            self.scroll = self.scroll.min(wrapped.len().saturating_sub(visible));
+            let mut lines: Vec<Line> = wrapped
+                .iter()
+                .skip(self.scroll)
+                .take(visible)
+                .map(|l| Line::from(Span::styled(l.clone(), text_style)))
+                .collect();
+            if wrapped.len() > visible
+                && let Some(last) = lines.last_mut()
+            {
+                let more = self.scroll + visible < wrapped.len();
+                let hint = if more { " ↓ [i] less" } else { " [i] less" };
+                *last = Line::from(vec![
+                    Span::styled(
+                        text::truncate(&last.to_string(), width.saturating_sub(hint.len())),
+                        text_style,
+                    ),
+                    Span::styled(hint, hint_style),
+                ]);
+            }
+            lines
+        } else {
+            let mut lines: Vec<Line> = wrapped
+                .iter()
+                .take(visible)
+                .map(|l| Line::from(Span::styled(l.clone(), text_style)))
+                .collect();
+            if wrapped.len() > visible
+                && let Some(last) = lines.last_mut()
+            {
+                let hint = " [i] more";
+                let cut = text::truncate(
+                    &format!("{} ", wrapped[visible - 1]),
+                    width.saturating_sub(hint.len()),
+                );
+                *last = Line::from(vec![
+                    Span::styled(cut, text_style),
+                    Span::styled(hint, hint_style),
+                ]);
+            }
+            lines
+        };
+
+        let text_area = Rect {
+            x: area.x.saturating_add(1),
+            width: area.width.saturating_sub(2),
+            ..area
+        };
+        frame.render_widget(Paragraph::new(lines), text_area);
+    }
+}
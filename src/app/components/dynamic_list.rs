@@ -1,14 +1,17 @@
-use std::sync::Arc;
+use std::{cell::RefCell, collections::BTreeSet, ops::Range, sync::Arc};
 
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{
         Block, Borders, ListState, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
     },
 };
 
+use im::Vector;
+
 use crate::{
     app::theme::theme,
     app::{
@@ -17,12 +20,13 @@ use crate::{
         data::{DataSource, ItemRenderer, ListItem, MatchHighlights, SearchScope},
         keymap::Key,
     },
-    cache::image::ImageCache,
+    cache::{art::CoverArt, image::ImageCache},
     framework::signals::Signal,
 };
-use ratatui_image::{StatefulImage, protocol::StatefulProtocol};
 use std::collections::HashMap;
 
+const MARK_SYMBOL: &str = "● ";
+
 #[derive(Debug, Clone, Default)]
 pub struct FuzzyFields {
     pub full: String,
@@ -31,6 +35,42 @@ pub struct FuzzyFields {
     pub album: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemGroup {
+    pub key: String,
+    pub label: String,
+}
+
+type Grouper<T> = Arc<dyn Fn(&T) -> Option<ItemGroup> + Send + Sync>;
+type MarkKey<T> = Arc<dyn Fn(&T) -> String + Send + Sync>;
+type Arranger<T> = Arc<dyn Fn(&Vector<T>) -> Vec<usize> + Send + Sync>;
+type Indices = Arc<[(usize, MatchHighlights)]>;
+
+struct Group {
+    key: String,
+    range: Range<usize>,
+    label: String,
+}
+
+struct Cached<K, V>(RefCell<Option<(K, V)>>);
+
+impl<K: PartialEq, V: Clone> Cached<K, V> {
+    fn new() -> Self {
+        Self(RefCell::new(None))
+    }
+
+    fn get(&self, key: K, compute: impl FnOnce() -> V) -> V {
+        if let Some((cached, value)) = &*self.0.borrow()
+            && *cached == key
+        {
+            return value.clone();
+        }
+        let value = compute();
+        *self.0.borrow_mut() = Some((key, value.clone()));
+        value
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DynamicListConfig {
     pub prefetch_distance: usize,
@@ -56,6 +96,9 @@ pub struct DynamicList<T> {
     source: Arc<dyn DataSource<T>>,
     renderer: Arc<dyn ItemRenderer<T>>,
     selection: Signal<usize>,
+    marked: Signal<BTreeSet<String>>,
+    mark_key: Option<MarkKey<T>>,
+    marks_synced: u64,
     playing_index: Signal<Option<usize>>,
     list_state: ListState,
     config: DynamicListConfig,
@@ -65,7 +108,15 @@ pub struct DynamicList<T> {
     search_mode: Signal<bool>,
     search_scope: Signal<SearchScope>,
     fuzzy_labeler: Option<Arc<dyn Fn(&T) -> FuzzyFields + Send + Sync>>,
-    image_protocols: HashMap<String, StatefulProtocol>,
+    grouper: Option<Grouper<T>>,
+    grouped: Signal<bool>,
+    collapsed: Signal<BTreeSet<String>>,
+    groups_cache: Cached<u64, Arc<Vec<Group>>>,
+    visible_cache: Cached<(u64, BTreeSet<String>), Option<Indices>>,
+    arrangement: Option<(Arranger<T>, Signal<u64>)>,
+    arrangement_revision: u64,
+    arranged_cache: Cached<(u64, u64, u64), Indices>,
+    image_protocols: HashMap<String, CoverArt>,
 }
 
 impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
@@ -80,6 +131,9 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
             source,
             renderer,
             selection: Signal::new(0),
+            marked: Signal::new(BTreeSet::new()),
+            mark_key: None,
+            marks_synced: 0,
             playing_index: Signal::new(None),
             list_state,
             config,
@@ -89,6 +143,14 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
             search_mode: Signal::new(false),
             search_scope: Signal::new(SearchScope::Full),
             fuzzy_labeler: None,
+            grouper: None,
+            grouped: Signal::new(false),
+            collapsed: Signal::new(BTreeSet::new()),
+            groups_cache: Cached::new(),
+            visible_cache: Cached::new(),
+            arrangement: None,
+            arrangement_revision: 0,
+            arranged_cache: Cached::new(),
             image_protocols: HashMap::new(),
         }
     }
@@ -101,6 +163,14 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
         self
     }
 
+    pub fn with_grouping<F>(mut self, grouper: F) -> Self
+    where
+        F: Fn(&T) -> Option<ItemGroup> + Send + Sync + 'static,
+    {
+        self.grouper = Some(Arc::new(grouper));
+        self
+    }
+
     pub fn with_config(mut self, config: DynamicListConfig) -> Self {
         self.config = config;
         self
@@ -111,6 +181,14 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
         self
     }
 
+    pub fn with_marking<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        self.mark_key = Some(Arc::new(key));
+        self
+    }
+
     pub fn with_playing_index(mut self, playing_index: Signal<Option<usize>>) -> Self {
         self.playing_index = playing_index;
         self
@@ -120,6 +198,64 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
         self.selection.clone()
     }
 
+    pub fn set_grouped(&mut self, grouped: bool) {
+        self.grouped.set(grouped && self.grouper.is_some());
+        self.collapsed.set(BTreeSet::new());
+    }
+
+    pub fn arrange<F>(&mut self, arrangement: F, dependencies: Signal<u64>)
+    where
+        F: Fn(&Vector<T>) -> Vec<usize> + Send + Sync + 'static,
+    {
+        self.arrangement = Some((Arc::new(arrangement), dependencies));
+        self.arrangement_revision += 1;
+        self.select_first();
+    }
+
+    pub fn clear_arrangement(&mut self) {
+        if self.arrangement.take().is_some() {
+            self.select_first();
+        }
+    }
+
+    pub fn display_order(&self) -> Option<Vec<usize>> {
+        Some(self.arranged()?.iter().map(|(idx, _)| *idx).collect())
+    }
+
+    fn arranged(&self) -> Option<Indices> {
+        let (arrangement, dependencies) = self.arrangement.as_ref()?;
+        let key = (
+            self.arrangement_revision,
+            self.source.changed_signal().get(),
+            dependencies.get(),
+        );
+        Some(self.arranged_cache.get(key, || {
+            arrangement(&self.source.range(0..usize::MAX))
+                .into_iter()
+                .map(|idx| (idx, MatchHighlights::default()))
+                .collect()
+        }))
+    }
+
+    pub fn toggle_group(&mut self) -> bool {
+        let selected = self.selection.get();
+        let groups = self.groups();
+        let Some(group) = groups.iter().find(|g| g.range.contains(&selected)) else {
+            return false;
+        };
+        self.collapsed.update(|c| {
+            if !c.remove(&group.key) {
+                c.insert(group.key.clone());
+            }
+        });
+        self.selection.set(group.range.start);
+        true
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.search_mode.get()
+    }
+
     pub fn selected(&self) -> usize {
         self.selection.get()
     }
@@ -129,6 +265,63 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
         self.source.range(idx..idx + 1).into_iter().next()
     }
 
+    pub fn has_marks(&self) -> bool {
+        self.marked.with(|m| !m.is_empty())
+    }
+
+    pub fn marked_items(&self) -> Vec<T> {
+        let Some(key) = &self.mark_key else {
+            return Vec::new();
+        };
+        let mut marked = self.marked.get();
+        if marked.is_empty() {
+            return Vec::new();
+        }
+        self.source
+            .range(0..usize::MAX)
+            .into_iter()
+            .filter(|item| marked.remove(&key(item)))
+            .collect()
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.set(BTreeSet::new());
+    }
+
+    fn toggle_mark(&mut self) {
+        let (Some(key), Some(item)) = (&self.mark_key, self.selected_item()) else {
+            return;
+        };
+        let id = key(&item);
+        self.marked.update(|m| {
+            if !m.remove(&id) {
+                m.insert(id);
+            }
+        });
+        self.select_next();
+    }
+
+    fn sync_marks(&mut self) {
+        let changed = self.source.changed_signal().get();
+        if changed == self.marks_synced {
+            return;
+        }
+        self.marks_synced = changed;
+        let Some(key) = &self.mark_key else {
+            return;
+        };
+        if !self.has_marks() {
+            return;
+        }
+        let present: BTreeSet<String> = self
+            .source
+            .range(0..usize::MAX)
+            .iter()
+            .map(|item| key(item))
+            .collect();
+        self.marked.update(|m| m.retain(|id| present.contains(id)));
+    }
+
     pub fn select(&mut self, index: usize) {
         let total = self.source.total().unwrap_or(0);
         let clamped = index.min(total.saturating_sub(1));
@@ -391,14 +584,105 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
         2
     }
 
-    fn active_indices(&self) -> Option<Vec<(usize, MatchHighlights)>> {
-        let query = self.search_query.get();
-        let labeler = self.fuzzy_labeler.as_ref()?;
+    fn groups(&self) -> Arc<Vec<Group>> {
+        let Some(grouper) = &self.grouper else {
+            return Arc::default();
+        };
+        if !self.grouped.get() || self.search_mode.get() || self.arrangement.is_some() {
+            return Arc::default();
+        }
+        self.groups_cache
+            .get(self.source.changed_signal().get(), || {
+                Arc::new(self.compute_groups(grouper))
+            })
+    }
 
-        if !self.search_mode.get() {
+    fn compute_groups(&self, grouper: &Grouper<T>) -> Vec<Group> {
+        let items = self.source.range(0..usize::MAX);
+        let mut groups = Vec::new();
+        let mut current: Option<(usize, ItemGroup)> = None;
+        for (idx, item) in items.iter().enumerate() {
+            let group = grouper(item);
+            if current.as_ref().map(|(_, g)| &g.key) == group.as_ref().map(|g| &g.key) {
+                continue;
+            }
+            if let Some((start, g)) = current.take()
+                && idx - start > 1
+            {
+                groups.push(Group {
+                    key: g.key,
+                    range: start..idx,
+                    label: g.label,
+                });
+            }
+            current = group.map(|g| (idx, g));
+        }
+        if let Some((start, g)) = current
+            && items.len() - start > 1
+        {
+            groups.push(Group {
+                key: g.key,
+                range: start..items.len(),
+                label: g.label,
+            });
+        }
+        groups
+    }
+
+    fn group_header(&self, groups: &[Group], index: usize) -> Option<Line<'static>> {
+        let group = groups.iter().find(|g| g.range.start == index)?;
+        let marker = if self.collapsed.with(|c| c.contains(&group.key)) {
+            "▸"
+        } else {
+            "▾"
+        };
+        Some(Line::from(Span::styled(
+            format!("  {} {} ({})", marker, group.label, group.range.len()),
+            theme().muted.add_modifier(Modifier::BOLD),
+        )))
+    }
+
+    fn visible_indices(&self) -> Option<Indices> {
+        if let Some(arranged) = self.arranged() {
+            return Some(arranged);
+        }
+
+        let collapsed = self.collapsed.get();
+        if collapsed.is_empty() {
+            return None;
+        }
+        let groups = self.groups();
+        if groups.is_empty() {
             return None;
         }
 
+        let key = (self.source.changed_signal().get(), collapsed);
+        self.visible_cache.get(key.clone(), || {
+            let (_, collapsed) = key;
+            let total = self.source.range(0..usize::MAX).len();
+            let mut hidden = vec![false; total];
+            for group in groups.iter().filter(|g| collapsed.contains(&g.key)) {
+                for idx in group.range.clone().skip(1) {
+                    hidden[idx] = true;
+                }
+            }
+            Some(
+                (0..total)
+                    .filter(|idx| !hidden[*idx])
+                    .map(|idx| (idx, MatchHighlights::default()))
+                    .collect(),
+            )
+        })
+    }
+
+    fn active_indices(&self) -> Option<Indices> {
+        if !self.search_mode.get() {
+            return self.visible_indices();
+        }
+
+        let query = self.search_query.get();
+        let labeler = self.fuzzy_labeler.as_ref()?;
+
         let scope = self.search_scope.get();
 
         if query.is_empty() {
@@ -558,6 +842,23 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
             );
         }
 
+        let marked = self.mark_key.as_ref().is_some_and(|key| {
+            let id = key(item);
+            self.marked.with(|m| m.contains(&id))
+        });
+        if marked {
+            let mark = Line::from(Span::styled(
+                MARK_SYMBOL,
+                Style::default()
+                    .fg(theme().accent.primary)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            match &mut rendered.prefix_lines {
+                Some(lines) if !lines.is_empty() => lines[0] = mark,
+                _ => rendered.prefix_lines = Some(vec![mark]),
+            }
+        }
+
         rendered
     }
 
@@ -626,6 +927,14 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
         }
 
         match key {
+            Key::Char('x') if self.mark_key.is_some() => {
+                self.toggle_mark();
+                Action::Redraw
+            }
+            Key::Esc if self.has_marks() => {
+                self.clear_marks();
+                Action::Redraw
+            }
             Key::Up | Key::Char('k') => {
                 self.select_prev();
                 Action::Redraw
@@ -656,6 +965,7 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
 
     pub fn view(&mut self, frame: &mut Frame, area: Rect) {
         let _ = ImageCache::global().version().track();
+        self.sync_marks();
 
         let colors = theme();
         let text_style = Style::default().fg(colors.text.primary);
@@ -681,6 +991,10 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
         let selected = self.selection.get();
         let item_stride = self.estimate_item_stride(list_area.width, selected);
         let active = self.active_indices();
+        let groups = self.groups();
+        if self.arrangement.is_some() {
+            self.source.request_range(0..usize::MAX);
+        }
 
         let (total, selected_pos, list_items): (usize, usize, Vec<ListItem<'static>>) =
             if let Some(active_indices) = active {
@@ -717,14 +1031,18 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
                         .map(|(actual_index, item, highlights)| {
                             let is_selected = *actual_index == selected_abs;
                             let is_playing = playing == Some(*actual_index);
-                            self.render_item(
+                            let rendered = self.render_item(
                                 item,
                                 *actual_index,
                                 is_selected,
                                 is_playing,
                                 list_area.width,
                                 highlights,
-                            )
+                            );
+                            match self.group_header(&groups, *actual_index) {
+                                Some(header) => rendered.with_header(header),
+                                None => rendered,
+                            }
                         })
                         .collect();
 
@@ -753,14 +1071,18 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
                         let actual_index = start + i;
                         let is_selected = actual_index == selected;
                         let is_playing = playing == Some(actual_index);
-                        self.render_item(
+                        let rendered = self.render_item(
                             item,
                             actual_index,
                             is_selected,
                             is_playing,
                             list_area.width,
                             &MatchHighlights::default(),
-                        )
+                        );
+                        match self.group_header(&groups, actual_index) {
+                            Some(header) => rendered.with_header(header),
+                            None => rendered,
+                        }
                     })
                     .collect();
 
@@ -827,11 +1149,23 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
                 break;
             }
 
+            let header_height = u16::from(item.header.is_some());
+            if let Some(header) = &item.header {
+                let header_area = Rect {
+                    x: inner_area.x,
+                    y: current_y,
+                    width: inner_area.width,
+                    height: 1,
+                };
+                frame.render_widget(Paragraph::new(header.clone()), header_area);
+            }
+            let row_height = item_height - header_height;
+
             let item_area = Rect {
                 x: inner_area.x,
-                y: current_y,
+                y: current_y + header_height,
                 width: inner_area.width,
-                height: item_height,
+                height: row_height,
             };
 
             if is_selected {
@@ -895,22 +1229,22 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
             if let Some(cover_url) = &item.cover_url {
                 if let Some(picker) = ImageCache::global_picker() {
                     if let Some(img) = ImageCache::global().get_or_fetch(cover_url) {
-                        let mut img_w = item_height * 2;
+                        let mut img_w = row_height * 2;
                         img_w = img_w.min(text_area.width / 4).max(2);
 
                         let img_rect = Rect {
                             x: text_area.x,
                             y: text_area.y,
                             width: img_w,
-                            height: item_height,
+                            height: row_height,
                         };
 
                         let proto = self
                             .image_protocols
                             .entry(cover_url.clone())
-                            .or_insert_with(|| picker.new_resize_protocol((*img).clone()));
+                            .or_insert_with(|| CoverArt::new(img, picker));
 
-                        frame.render_stateful_widget(StatefulImage::new(), img_rect, proto);
+                        proto.render(frame, img_rect);
 
                         let text_x = text_area.x + img_w + 1;
                         let text_w = text_area.width.saturating_sub(img_w + 1);
@@ -918,7 +1252,7 @@ impl<T: Clone + Send + Sync + 'static> DynamicList<T> {
                             x: text_x,
                             y: text_area.y,
                             width: text_w,
-                            height: item_height,
+                            height: row_height,
                         };
                     }
                 }
@@ -49,24 +49,7 @@ impl<T: FuzzyItem + Clone + Send + Sync + 'static> FuzzyDropdown<T> {
     }
 
     fn truncate(text: &str, width: usize) -> String {
-        use unicode_width::UnicodeWidthStr;
-        let display_width = text.width();
-        if display_width > width {
-            let mut result = String::new();
-            let mut current_width = 0;
-            for ch in text.chars() {
-                let ch_width = ch.width().unwrap_or(0);
-                if current_width + ch_width + 1 > width {
-                    result.push('…');
-                    break;
-                }
-                result.push(ch);
-                current_width += ch_width;
-            }
-            result
-        } else {
-            text.to_string()
-        }
+        crate::util::text::truncate(text, width)
     }
 
     fn create_highlighted_spans(
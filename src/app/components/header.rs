@@ -5,12 +5,12 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use ratatui_image::{picker::Picker, protocol::StatefulProtocol, StatefulImage};
+use ratatui_image::picker::Picker;
 use std::sync::Arc;
 
 use crate::{
     app::theme::theme,
-    cache::image::ImageCache,
+    cache::{art::CoverArt, image::ImageCache},
     framework::signals::Signal,
 };
 use im::Vector;
@@ -47,7 +47,7 @@ pub struct Header {
     height: u16,
     show_border: bool,
     cover_url: Option<String>,
-    cover_protocol: Option<StatefulProtocol>,
+    cover_protocol: Option<CoverArt>,
     last_cover: Option<Arc<DynamicImage>>,
 }
 
@@ -125,14 +125,14 @@ impl Header {
         if changed {
             self.cover_protocol = current
                 .as_ref()
-                .map(|img| picker.new_resize_protocol((**img).clone()));
+                .map(|img| CoverArt::new(img.clone(), picker.clone()));
             self.last_cover = current;
         }
 
         if self.cover_protocol.is_none() {
             if let Some(img) = &self.last_cover {
                 if img_height > 0 {
-                    self.cover_protocol = Some(picker.new_resize_protocol((**img).clone()));
+                    self.cover_protocol = Some(CoverArt::new(img.clone(), picker.clone()));
                 }
             }
         }
@@ -200,8 +200,8 @@ impl Header {
                 height: inner_h,
             };
 
-            if let Some(proto) = &mut self.cover_protocol {
-                frame.render_stateful_widget(StatefulImage::new(), img_area, proto);
+            if let Some(art) = &mut self.cover_protocol {
+                art.render(frame, img_area);
             }
 
             let text_x = inner_area.x + img_w + 2;
@@ -264,21 +264,37 @@ impl HeaderBuilder {
         owner: &str,
         track_count: usize,
         duration: Option<String>,
+        visibility: Option<&str>,
+        dates: Option<String>,
     ) -> Header {
+        let byline = match visibility {
+            Some(visibility) => format!("by {} • {}", owner, visibility),
+            None => format!("by {}", owner),
+        };
+        let stats = match duration {
+            Some(dur) => format!("{} tracks • {}", track_count, dur),
+            None => format!("{} tracks", track_count),
+        };
         let mut lines = vec![
             HeaderLine::title(title),
-            HeaderLine::subtitle(format!("by {}", owner)),
-            HeaderLine::text(format!("{} tracks", track_count)),
+            HeaderLine::subtitle(byline),
+            HeaderLine::text(stats),
         ];
 
-        if let Some(dur) = duration {
-            lines.push(HeaderLine::text(dur));
+        if let Some(dates) = dates {
+            lines.push(HeaderLine::subtitle(dates));
         }
 
         Header::new(lines)
     }
 
-    pub fn album(title: &str, artists: &str, year: Option<i32>, track_count: usize) -> Header {
+    pub fn album(
+        title: &str,
+        artists: &str,
+        year: Option<i32>,
+        track_count: usize,
+        details: Option<String>,
+    ) -> Header {
         let mut lines = vec![HeaderLine::title(title), HeaderLine::subtitle(artists)];
 
         if let Some(y) = year {
@@ -287,9 +303,25 @@ impl HeaderBuilder {
             lines.push(HeaderLine::text(format!("{} tracks", track_count)));
         }
 
+        if let Some(details) = details {
+            lines.push(HeaderLine::subtitle(details));
+        }
+
         Header::new(lines)
     }
 
+    pub fn podcast(title: &str) -> Header {
+        Header::new(vec![HeaderLine::title(title), HeaderLine::subtitle("Podcast")])
+    }
+
+    pub fn smart_playlist(title: &str, rule: &str, track_count: usize) -> Header {
+        Header::new(vec![
+            HeaderLine::title(title),
+            HeaderLine::subtitle(format!("Smart playlist • {}", rule)),
+            HeaderLine::text(format!("{} tracks", track_count)),
+        ])
+    }
+
     pub fn artist(name: &str, genres: &str, likes: u64, track_count: usize) -> Header {
         Header::new(vec![
             HeaderLine::title(name),
@@ -298,6 +330,14 @@ impl HeaderBuilder {
         ])
     }
 
+    pub fn folder(name: &str, path: &str, dir_count: usize, track_count: usize) -> Header {
+        Header::new(vec![
+            HeaderLine::title(name),
+            HeaderLine::subtitle(path),
+            HeaderLine::text(format!("{} folders • {} tracks", dir_count, track_count)),
+        ])
+    }
+
     pub fn search(query: &str, result_count: usize) -> Header {
         Header::new(vec![
             HeaderLine::title(format!("Search: {}", query)),
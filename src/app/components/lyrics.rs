@@ -5,21 +5,41 @@ use ratatui::{
     style::{Color, Modifier, Style},
     widgets::Widget,
 };
+use std::{cell::Cell, collections::HashMap};
 use unicode_width::UnicodeWidthStr;
 
-use crate::{app::theme::theme, framework::reactive::Signal};
+use crate::{
+    app::{actions::Action, keymap::Key, theme::theme},
+    framework::reactive::Signal,
+};
 
 use crate::app::signals::LyricsSignals;
 use crate::framework::reactive::{Memo, memo};
-use crate::util::animation::Animation;
+use crate::util::{
+    animation::Animation,
+    config::{self, LyricsLayout},
+};
+
+const KARAOKE_STEP: f32 = 0.1;
+const TRANSLATION_OPACITY: f64 = 0.6;
+const STATIC_PAGE_STEP: usize = 10;
 
 pub struct Lyrics {
     pub lines: Memo<Vec<(u64, String)>>,
     pub position: Signal<u64>,
+    pub translations: Memo<HashMap<String, String>>,
+    pub show_translation: Signal<bool>,
+    pub search: Signal<Option<String>>,
+    pub search_step: Signal<i32>,
+    pub static_layout: Signal<bool>,
+    pub scroll: Signal<Option<usize>>,
+    last_top: Cell<usize>,
 }
 
 impl Lyrics {
     pub fn new(signals: LyricsSignals, position: Signal<u64>) -> Self {
+        let translation = signals.translation.clone();
+        let translations = memo(move |_| translation.value().unwrap_or_default());
         let lines = memo(move |_| {
             if let Some(Some(content)) = signals.content.value() {
                 parse_lrc(&content)
@@ -28,24 +48,205 @@ impl Lyrics {
             }
         });
 
-        Self { lines, position }
+        Self {
+            lines,
+            position,
+            translations,
+            show_translation: Signal::new(true),
+            search: Signal::new(None),
+            search_step: Signal::new(0),
+            static_layout: Signal::new(config::get().lyrics.layout == LyricsLayout::Static),
+            scroll: Signal::new(None),
+            last_top: Cell::new(0),
+        }
+    }
+
+    pub fn handle_key(&self, key: &Key) -> Option<Action> {
+        if let Some(query) = self.search.get() {
+            return self.handle_search_key(key, query);
+        }
+
+        match key {
+            Key::Char('/') => {
+                if self.lines.with(|lines| lines.is_empty()) {
+                    return Some(Action::Toast("No lyrics for this track".to_string()));
+                }
+                self.search.set(Some(String::new()));
+                self.search_step.set(0);
+                self.scroll.set(None);
+                Some(Action::None)
+            }
+            Key::Tab => {
+                let static_layout = !self.static_layout.get();
+                self.static_layout.set(static_layout);
+                self.scroll.set(None);
+                Some(Action::None)
+            }
+            Key::Up if self.static_layout.get() => self.scroll_by(-1),
+            Key::Down if self.static_layout.get() => self.scroll_by(1),
+            Key::PageUp if self.static_layout.get() => self.scroll_by(-(STATIC_PAGE_STEP as i32)),
+            Key::PageDown if self.static_layout.get() => self.scroll_by(STATIC_PAGE_STEP as i32),
+            Key::Home if self.static_layout.get() => {
+                self.scroll.set(Some(0));
+                Some(Action::None)
+            }
+            Key::End if self.static_layout.get() => {
+                self.scroll.set(None);
+                Some(Action::None)
+            }
+            Key::Char('k') => Some(Action::ToggleEffect("karaoke".into())),
+            Key::Char('[') => Some(Action::AdjustKaraoke(-KARAOKE_STEP)),
+            Key::Char(']') => Some(Action::AdjustKaraoke(KARAOKE_STEP)),
+            Key::Char('y') => self.export(|lines| Action::CopyToClipboard(to_text(lines))),
+            Key::Char('S') => self.export(|lines| Action::SaveLyrics(to_lrc(lines))),
+            Key::Char('t') => {
+                if config::get().lyrics.translate_command.is_none() {
+                    return Some(Action::Toast(
+                        "Set lyrics.translate_command in config.toml to show translations"
+                            .to_string(),
+                    ));
+                }
+                let show = !self.show_translation.get();
+                self.show_translation.set(show);
+                Some(Action::Toast(
+                    if show {
+                        "Translation shown"
+                    } else {
+                        "Translation hidden"
+                    }
+                    .to_string(),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn scroll_by(&self, delta: i32) -> Option<Action> {
+        let top = self.scroll.get().unwrap_or(self.last_top.get());
+        self.scroll
+            .set(Some(top.saturating_add_signed(delta as isize)));
+        Some(Action::None)
+    }
+
+    fn handle_search_key(&self, key: &Key, mut query: String) -> Option<Action> {
+        match key {
+            Key::Esc => self.search.set(None),
+            Key::Enter => {
+                self.search.set(None);
+                return Some(match self.search_match(&query) {
+                    Some((index, _, _)) => Action::SeekTo(self.lines.with(|lines| lines[index].0)),
+                    None if query.is_empty() => Action::None,
+                    None => Action::Toast(format!("No line matches \"{query}\"")),
+                });
+            }
+            Key::Backspace => {
+                query.pop();
+                self.search.set(Some(query));
+                self.search_step.set(0);
+            }
+            Key::Char(c) => {
+                query.push(*c);
+                self.search.set(Some(query));
+                self.search_step.set(0);
+            }
+            Key::Down | Key::Tab => self.search_step.update(|step| *step += 1),
+            Key::Up | Key::BackTab => self.search_step.update(|step| *step -= 1),
+            Key::Ctrl(_) => return None,
+            _ => {}
+        }
+        Some(Action::None)
+    }
+
+    fn search_match(&self, query: &str) -> Option<(usize, usize, usize)> {
+        if query.is_empty() {
+            return None;
+        }
+        let query = query.to_lowercase();
+        let position = self.position.get();
+        self.lines.with(|lines| {
+            let matches: Vec<usize> = lines
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, line))| line.to_lowercase().contains(&query))
+                .map(|(i, _)| i)
+                .collect();
+            if matches.is_empty() {
+                return None;
+            }
+            let first = matches
+                .iter()
+                .position(|&i| lines[i].0 >= position)
+                .unwrap_or(0) as i32;
+            let selected =
+                (first + self.search_step.get()).rem_euclid(matches.len() as i32) as usize;
+            Some((matches[selected], selected + 1, matches.len()))
+        })
+    }
+
+    fn export(&self, action: impl FnOnce(&[(u64, String)]) -> Action) -> Option<Action> {
+        let lines = self.lines.get();
+        if lines.is_empty() {
+            return Some(Action::Toast("No lyrics for this track".to_string()));
+        }
+        Some(action(&lines))
     }
 
     pub fn view(&self, frame: &mut Frame, area: Rect) {
         let lines = self.lines.get();
-        let widget = LyricsWidget::new(&lines, self.position.get());
+        let translations = if self.show_translation.get() {
+            self.translations.get()
+        } else {
+            HashMap::new()
+        };
+        let mut position = self.position.get();
+        let prompt = self
+            .search
+            .get()
+            .map(|query| match self.search_match(&query) {
+                Some((index, n, total)) => {
+                    position = lines[index].0;
+                    format!("/{query}  {n}/{total}")
+                }
+                None if query.is_empty() => "/".to_string(),
+                None => format!("/{query}  no matches"),
+            });
+        if self.static_layout.get() {
+            let widget = StaticLyricsWidget {
+                lines: &lines,
+                translations: &translations,
+                position_ms: position,
+                prompt,
+                scroll: self.scroll.get(),
+                last_top: &self.last_top,
+            };
+            frame.render_widget(widget, area);
+            return;
+        }
+        let widget = LyricsWidget::new(&lines, &translations, position, prompt);
         frame.render_widget(widget, area);
     }
 }
 
 struct LyricsWidget<'a> {
     lines: &'a [(u64, String)],
+    translations: &'a HashMap<String, String>,
     position_ms: u64,
+    prompt: Option<String>,
 }
 
 impl<'a> LyricsWidget<'a> {
-    fn new(lines: &'a [(u64, String)], position_ms: u64) -> Self {
-        Self { lines, position_ms }
+    fn new(
+        lines: &'a [(u64, String)],
+        translations: &'a HashMap<String, String>,
+        position_ms: u64,
+        prompt: Option<String>,
+    ) -> Self {
+        Self {
+            lines,
+            translations,
+            position_ms,
+            prompt,
+        }
     }
 }
 
@@ -217,6 +418,17 @@ impl<'a> Widget for LyricsWidget<'a> {
             if !display_text.is_empty() || is_waiting {
                 render_line(buf, inner, y_float, &display_text, style);
             }
+
+            if !is_waiting && let Some(translated) = self.translations.get(line_text) {
+                let style =
+                    Style::default().fg(blend_color_bg(muted_color, opacity * TRANSLATION_OPACITY));
+                render_line(buf, inner, y_float + 1.0, translated, style);
+            }
+        }
+
+        if let Some(prompt) = &self.prompt {
+            render_prompt(buf, inner, prompt);
+            return;
         }
 
         let max_bar_w = inner.width.saturating_sub(8).min(30);
@@ -244,6 +456,80 @@ impl<'a> Widget for LyricsWidget<'a> {
     }
 }
 
+struct StaticLyricsWidget<'a> {
+    lines: &'a [(u64, String)],
+    translations: &'a HashMap<String, String>,
+    position_ms: u64,
+    prompt: Option<String>,
+    scroll: Option<usize>,
+    last_top: &'a Cell<usize>,
+}
+
+impl Widget for StaticLyricsWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let text_muted = theme().muted;
+        let accent_color = theme().accent.primary;
+        let muted_color = text_muted.fg.unwrap_or_default();
+        let inner = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(2),
+        };
+
+        if self.lines.is_empty() {
+            let hint = "No lyrics available";
+            let y = inner.y + inner.height / 2;
+            draw_centered(buf, inner, y, hint, text_muted);
+            return;
+        }
+
+        let current = self.lines.iter().rposition(|(t, _)| *t <= self.position_ms);
+
+        let mut rows: Vec<(&str, Style)> = Vec::with_capacity(self.lines.len());
+        let mut current_row = 0;
+        for (i, (_, line)) in self.lines.iter().enumerate() {
+            let style = if Some(i) == current {
+                current_row = rows.len();
+                Style::default()
+                    .fg(accent_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(muted_color)
+            };
+            rows.push((line, style));
+            if let Some(translated) = self.translations.get(line) {
+                let style = Style::default().fg(blend_color_bg(muted_color, TRANSLATION_OPACITY));
+                rows.push((translated, style));
+            }
+        }
+
+        let reserved = if self.prompt.is_some() { 2 } else { 0 };
+        let height = inner.height.saturating_sub(reserved) as usize;
+        let max_top = rows.len().saturating_sub(height);
+        let top = self
+            .scroll
+            .unwrap_or_else(|| current_row.saturating_sub(height / 3))
+            .min(max_top);
+        self.last_top.set(top);
+
+        for (offset, (text, style)) in rows.iter().skip(top).take(height).enumerate() {
+            draw_centered(buf, inner, inner.y + offset as u16, text, *style);
+        }
+
+        if let Some(prompt) = &self.prompt {
+            render_prompt(buf, inner, prompt);
+        }
+    }
+}
+
+fn render_prompt(buf: &mut Buffer, area: Rect, prompt: &str) {
+    if area.height >= 5 {
+        let style = Style::default().fg(theme().accent.primary);
+        draw_centered(buf, area, area.y + area.height - 2, prompt, style);
+    }
+}
+
 fn render_line(buf: &mut Buffer, area: Rect, y_float: f64, text: &str, style: Style) {
     let y_int = y_float.floor() as i32;
     let y_frac = y_float - y_float.floor();
@@ -377,6 +663,30 @@ fn draw_centered(buf: &mut Buffer, area: Rect, y: u16, text: &str, style: Style)
     buf.set_stringn(x, y, text, area.width as usize, style);
 }
 
+fn to_text(lines: &[(u64, String)]) -> String {
+    let mut out: Vec<&str> = Vec::with_capacity(lines.len());
+    for (_, line) in lines {
+        if !(line.is_empty() && out.last().is_none_or(|l| l.is_empty())) {
+            out.push(line);
+        }
+    }
+    out.join("\n").trim_end().to_string()
+}
+
+fn to_lrc(lines: &[(u64, String)]) -> String {
+    lines
+        .iter()
+        .map(|(ms, line)| {
+            format!(
+                "[{:02}:{:02}.{:02}]{line}\n",
+                ms / 60_000,
+                ms / 1000 % 60,
+                ms % 1000 / 10
+            )
+        })
+        .collect()
+}
+
 fn parse_lrc(text: &str) -> Vec<(u64, String)> {
     let mut out = Vec::new();
     let mut seq = 0usize;
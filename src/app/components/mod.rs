@@ -1,26 +1,33 @@
+mod description;
 mod dynamic_list;
 mod fuzzy;
 mod fuzzy_dropdown;
 mod header;
 mod lyrics;
 mod player;
+mod profiler;
 mod progress;
 mod sidebar;
+mod skeleton;
 mod spinner;
 mod toast;
 mod visualizer_gpu;
 pub mod widgets;
 
-pub use sidebar::Sidebar;
+pub use sidebar::{Sidebar, SidebarBadges};
 
-pub use dynamic_list::{DynamicList, FuzzyFields};
+pub use description::Description;
+pub use dynamic_list::{DynamicList, FuzzyFields, ItemGroup};
+pub use fuzzy::fuzzy_match_positioned;
 
 pub use crate::app::data::SearchScope;
 pub use fuzzy_dropdown::{DropdownAction, FuzzyDropdown, FuzzyItem};
 pub use header::{Header, HeaderBuilder, HeaderLine};
 pub use lyrics::Lyrics;
 pub use player::{PlayerBar, PlayerSignals};
+pub use profiler::Profiler;
 pub use progress::{AudioProgressBar, ProgressBar};
+pub use skeleton::Skeleton;
 pub use spinner::{Spinner, tick_global};
-pub use toast::ToastManager;
+pub use toast::{NotificationLevel, NotificationLog, ToastManager};
 pub use visualizer_gpu::Visualizer;
@@ -2,23 +2,31 @@ use image::DynamicImage;
 use ratatui::{
     Frame,
     buffer::Buffer,
-    layout::Rect,
+    layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     symbols::{self, border},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
-use ratatui_image::{StatefulImage, protocol::StatefulProtocol};
 use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
 
 use crate::{
-    app::theme::theme, audio::enums::RepeatMode, cache::image::ImageCache,
-    framework::signals::Signal, util::animation::Animation,
+    app::theme::theme,
+    audio::{enums::RepeatMode, signals::LoadStage},
+    cache::{art::CoverArt, image::ImageCache},
+    framework::signals::Signal,
+    util::{
+        animation::Animation,
+        chapters::{self, Chapter},
+        text,
+    },
 };
 
+const NEXT_UP_MIN_WIDTH: u16 = 16;
+
 pub struct PlayerSignals {
     pub track_title: Signal<Option<String>>,
     pub track_artists: Signal<Option<String>>,
@@ -27,7 +35,12 @@ pub struct PlayerSignals {
     pub is_disliked: Signal<bool>,
     pub position_ms: Signal<u64>,
     pub duration_ms: Signal<u64>,
+    pub chapters: Signal<Vec<Chapter>>,
     pub buffered_ratio: Signal<f32>,
+    pub buffered_ranges: Signal<Vec<(f32, f32)>>,
+    pub quality_downgraded: Signal<bool>,
+    pub load_stage: Signal<Option<LoadStage>>,
+    pub next_up: Signal<Option<String>>,
     pub volume: Signal<u8>,
     pub is_muted: Signal<bool>,
     pub is_shuffled: Signal<bool>,
@@ -45,7 +58,12 @@ impl PlayerSignals {
             is_disliked: Signal::new(false),
             position_ms: Signal::new(0),
             duration_ms: Signal::new(0),
+            chapters: Signal::new(Vec::new()),
             buffered_ratio: Signal::new(0.0),
+            buffered_ranges: Signal::new(Vec::new()),
+            quality_downgraded: Signal::new(false),
+            load_stage: Signal::new(None),
+            next_up: Signal::new(None),
             volume: Signal::new(50),
             is_muted: Signal::new(false),
             is_shuffled: Signal::new(false),
@@ -63,7 +81,7 @@ impl Default for PlayerSignals {
 
 pub struct PlayerBar {
     signals: PlayerSignals,
-    protocol: Option<StatefulProtocol>,
+    protocol: Option<CoverArt>,
     last_art: Option<Arc<DynamicImage>>,
     last_volume: u8,
     last_muted: bool,
@@ -219,7 +237,7 @@ impl PlayerBar {
         if art_changed {
             self.protocol = None;
             if let (Some(picker), Some(art)) = (ImageCache::global_picker(), &current_art) {
-                self.protocol = Some(picker.new_resize_protocol((**art).clone()));
+                self.protocol = Some(CoverArt::new(art.clone(), picker));
             }
             self.last_art = current_art;
         }
@@ -246,25 +264,31 @@ impl PlayerBar {
         let mut controls_center_x = inner.x + inner.width.saturating_sub(1) / 2;
 
         if img_w > 0 {
-            if let Some(proto) = &mut self.protocol {
-                frame.render_stateful_widget(
-                    StatefulImage::new(),
+            if let Some(art) = &mut self.protocol {
+                art.render(
+                    frame,
                     Rect {
                         x: inner.x,
                         y: inner.y,
                         width: img_w,
                         height: inner.height,
                     },
-                    proto,
                 );
             }
         }
 
         {
             let title = self.signals.track_title.get().unwrap_or(String::new());
+            let mut spans = vec![Span::styled(title, text_style.add_modifier(Modifier::BOLD))];
+            if self.signals.quality_downgraded.get() {
+                spans.push(Span::styled(" 󰓃", colors.warning_style));
+            }
+            if let Some(stage) = self.signals.load_stage.get() {
+                spans.push(Span::styled(format!("  {}…", stage.label()), muted_style));
+            }
 
             frame.render_widget(
-                Paragraph::new(Span::styled(title, text_style.add_modifier(Modifier::BOLD))),
+                Paragraph::new(Line::from(spans)),
                 Rect {
                     x: text_x,
                     y: row0_y,
@@ -272,12 +296,34 @@ impl PlayerBar {
                     height: 1,
                 },
             );
+
+            let next_w = text_aw.saturating_sub(left_w + 2);
+            if let Some(next) = self.signals.next_up.get()
+                && next_w > NEXT_UP_MIN_WIDTH
+            {
+                let next = text::truncate(&format!("Next: {next}"), next_w as usize);
+                frame.render_widget(
+                    Paragraph::new(Span::styled(next, muted_style)).alignment(Alignment::Right),
+                    Rect {
+                        x: text_x + left_w + 2,
+                        y: row0_y,
+                        width: next_w,
+                        height: 1,
+                    },
+                );
+            }
         }
 
         {
             let artist = self.signals.track_artists.get().unwrap_or_default();
+            let mut spans = vec![Span::styled(artist, muted_style)];
+            let chapters = self.signals.chapters.get();
+            if let Some(chapter) = chapters::current(&chapters, self.signals.position_ms.get()) {
+                spans.push(Span::styled(" · ", muted_style));
+                spans.push(Span::styled(chapter.title.clone(), text_style));
+            }
             frame.render_widget(
-                Paragraph::new(Span::styled(artist, muted_style)),
+                Paragraph::new(Line::from(spans)),
                 Rect {
                     x: text_x,
                     y: row1_y,
@@ -555,6 +601,17 @@ impl PlayerBar {
             };
             let buffered_target = (self.signals.buffered_ratio.get() as f64).min(1.0);
             let (played, buffered) = self.animation.ratios(played_target, buffered_target);
+            let ticks: Vec<f64> = if total > 0 {
+                self.signals
+                    .chapters
+                    .get()
+                    .iter()
+                    .filter(|c| c.start_ms > 0 && c.start_ms < total)
+                    .map(|c| c.start_ms as f64 / total as f64)
+                    .collect()
+            } else {
+                Vec::new()
+            };
 
             let current_label = format_duration(current);
             let total_label = format_duration(total);
@@ -618,6 +675,15 @@ impl PlayerBar {
             frame.render_widget(
                 CustomGauge::default()
                     .ratios(played, buffered)
+                    .ticks(ticks)
+                    .buffered_ranges(
+                        self.signals
+                            .buffered_ranges
+                            .get()
+                            .iter()
+                            .map(|&(start, end)| (start as f64, end as f64))
+                            .collect(),
+                    )
                     .played_style(progress_fg)
                     .buffered_style(progress_bg)
                     .delimiters("▕", "▏")
@@ -644,6 +710,8 @@ struct CustomGauge<'a> {
     block: Option<Block<'a>>,
     played_ratio: f64,
     buffered_ratio: f64,
+    ticks: Vec<f64>,
+    buffered_ranges: Vec<(f64, f64)>,
     use_unicode: bool,
     delimiters: Vec<&'a str>,
     delimiter_style: Style,
@@ -668,6 +736,16 @@ impl<'a> CustomGauge<'a> {
         self
     }
 
+    fn ticks(mut self, ticks: Vec<f64>) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    fn buffered_ranges(mut self, ranges: Vec<(f64, f64)>) -> Self {
+        self.buffered_ranges = ranges;
+        self
+    }
+
     const fn use_unicode(mut self, use_unicode: bool) -> Self {
         self.use_unicode = use_unicode;
         self
@@ -728,6 +806,22 @@ impl<'a> CustomGauge<'a> {
         let width = (fill_right - fill_left) as f64;
         let played_pos = width * self.played_ratio;
         let buffered_pos = width * self.buffered_ratio.max(self.played_ratio);
+        let segments: Vec<(f64, f64)> = if self.buffered_ranges.is_empty() {
+            vec![(0.0, buffered_pos)]
+        } else {
+            self.buffered_ranges
+                .iter()
+                .map(|&(start, end)| (width * start, width * end))
+                .collect()
+        };
+        let buffered_at_played = segments
+            .iter()
+            .any(|&(start, end)| start <= played_pos && end > played_pos);
+        let ticks: Vec<u16> = self
+            .ticks
+            .iter()
+            .map(|ratio| fill_left + (width * ratio).floor() as u16)
+            .collect();
 
         for y in gauge_area.top()..gauge_area.bottom() {
             for x in fill_left..fill_right {
@@ -740,21 +834,35 @@ impl<'a> CustomGauge<'a> {
                     style = self.played_style;
                     if self.use_unicode && pos + 1.0 > played_pos {
                         symbol = unicode_block(played_pos - pos);
-                        if buffered_pos > played_pos
-                            && let Some(buffered_fg) = self.buffered_style.fg
-                        {
+                        if buffered_at_played && let Some(buffered_fg) = self.buffered_style.fg {
                             style = style.bg(buffered_fg);
                         }
                     }
-                } else if pos < buffered_pos {
+                } else if let Some(&(_, end)) = segments
+                    .iter()
+                    .find(|&&(start, end)| pos >= start.floor() && pos < end)
+                {
                     style = self.buffered_style;
-                    if self.use_unicode && pos + 1.0 > buffered_pos {
-                        symbol = unicode_block(buffered_pos - pos);
+                    if self.use_unicode && pos + 1.0 > end {
+                        symbol = unicode_block(end - pos);
                     }
                 } else if self.use_unicode {
                     symbol = " ";
                 }
 
+                if ticks.contains(&x) {
+                    let bg = if symbol == symbols::block::FULL {
+                        style.fg
+                    } else {
+                        style.bg
+                    };
+                    buf[(x, y)]
+                        .set_symbol("▏")
+                        .set_fg(self.delimiter_style.fg.unwrap_or_default())
+                        .set_bg(bg.unwrap_or_default());
+                    continue;
+                }
+
                 buf[(x, y)]
                     .set_symbol(symbol)
                     .set_fg(style.fg.unwrap_or_default())
@@ -0,0 +1,172 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::theme::theme;
+
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+const MEMORY_INTERVAL: Duration = Duration::from_secs(1);
+const SMOOTHING: f64 = 0.1;
+const WIDTH: u16 = 34;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Timing {
+    average: f64,
+    peak: Duration,
+}
+
+impl Timing {
+    fn record(&mut self, elapsed: Duration) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        self.average = if self.average == 0.0 {
+            ms
+        } else {
+            self.average + (ms - self.average) * SMOOTHING
+        };
+        self.peak = self.peak.max(elapsed);
+    }
+
+    fn label(&self) -> String {
+        format!(
+            "{:>5.1} avg {:>5.1} max",
+            self.average,
+            self.peak.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct Profiler {
+    enabled: bool,
+    frames: VecDeque<Instant>,
+    frame: Timing,
+    sections: Vec<(String, Timing)>,
+    queue_depth: usize,
+    memory_kb: Option<u64>,
+    memory_checked: Option<Instant>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        self.frames.clear();
+        self.frame = Timing::default();
+        self.sections.clear();
+    }
+
+    pub fn record(&mut self, name: &str, elapsed: Duration) {
+        if !self.enabled {
+            return;
+        }
+        match self.sections.iter_mut().find(|(n, _)| n == name) {
+            Some((_, timing)) => timing.record(elapsed),
+            None => {
+                let mut timing = Timing::default();
+                timing.record(elapsed);
+                self.sections.push((name.to_string(), timing));
+            }
+        }
+    }
+
+    pub fn finish_frame(&mut self, started: Instant, queue_depth: usize) {
+        if !self.enabled {
+            return;
+        }
+        let now = Instant::now();
+        self.frame.record(now - started);
+        self.frames.push_back(now);
+        while self.frames.front().is_some_and(|t| now - *t > FPS_WINDOW) {
+            self.frames.pop_front();
+        }
+        self.queue_depth = queue_depth;
+
+        if self
+            .memory_checked
+            .is_none_or(|t| now - t >= MEMORY_INTERVAL)
+        {
+            self.memory_kb = resident_memory_kb();
+            self.memory_checked = Some(now);
+        }
+    }
+
+    pub fn view(&self, frame: &mut Frame, area: Rect) {
+        if !self.enabled {
+            return;
+        }
+        let colors = theme();
+        let label = Style::default().fg(colors.text.muted);
+        let value = Style::default().fg(colors.text.primary);
+        let row = |name: String, text: String| {
+            Line::from(vec![
+                Span::styled(format!(" {name:<10}"), label),
+                Span::styled(text, value),
+            ])
+        };
+
+        let memory = self.memory_kb.map_or_else(
+            || "n/a".to_string(),
+            |kb| format!("{:.1} MiB", kb as f64 / 1024.0),
+        );
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(" {} fps", self.frames.len()),
+                Style::default()
+                    .fg(colors.accent.primary)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            row("frame".to_string(), self.frame.label()),
+        ];
+        lines.extend(
+            self.sections
+                .iter()
+                .map(|(name, timing)| row(name.to_lowercase(), timing.label())),
+        );
+        lines.push(row("events".to_string(), self.queue_depth.to_string()));
+        lines.push(row("memory".to_string(), memory));
+
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let width = WIDTH.min(area.width);
+        let rect = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.y,
+            width,
+            height,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(colors.border.focused))
+            .title(" Profiler (ms) ")
+            .style(Style::default().bg(colors.bg.base));
+        frame.render_widget(Clear, rect);
+        frame.render_widget(Paragraph::new(lines).block(block), rect);
+    }
+}
+
+fn resident_memory_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
@@ -1,18 +1,79 @@
 use ratatui::{
+    Frame,
     layout::Rect,
-    style::Modifier,
+    style::{Modifier, Style},
     symbols::border,
-    widgets::{Block, Borders, List, ListItem},
-    Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::{app::actions::Route, app::theme::theme};
+use crate::{
+    app::actions::{Action, Route},
+    app::keymap::Key,
+    app::theme::theme,
+    framework::reactive::Memo,
+};
 
-pub struct Sidebar;
+const ITEMS: [(&str, Route); 5] = [
+    ("  Search", Route::Search),
+    ("󰐻  My Wave", Route::Home),
+    ("  My Favorites", Route::Liked),
+    ("  Playlists", Route::Playlists),
+    ("  Local Files", Route::Files),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SidebarBadges {
+    pub queue: usize,
+    pub tasks: usize,
+    pub notifications: usize,
+}
+
+impl SidebarBadges {
+    fn rows(&self) -> Vec<(&'static str, usize)> {
+        [
+            ("󰲸  Queue", self.queue),
+            ("󰑐  Background", self.tasks),
+            ("󰂚  Notifications", self.notifications),
+        ]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .collect()
+    }
+}
+
+pub struct Sidebar {
+    cursor: usize,
+    badges: Memo<SidebarBadges>,
+}
 
 impl Sidebar {
-    pub fn new() -> Self {
-        Self
+    pub fn new(badges: Memo<SidebarBadges>) -> Self {
+        Self { cursor: 0, badges }
+    }
+
+    pub fn handle_key(&mut self, key: &Key) -> Action {
+        match key {
+            Key::Up | Key::Char('k') => {
+                self.cursor = self.cursor.checked_sub(1).unwrap_or(ITEMS.len() - 1);
+                Action::Redraw
+            }
+            Key::Down | Key::Char('j') => {
+                self.cursor = (self.cursor + 1) % ITEMS.len();
+                Action::Redraw
+            }
+            Key::Enter | Key::Char('l') | Key::Right => {
+                Action::Navigate(ITEMS[self.cursor].1.clone())
+            }
+            _ => Action::None,
+        }
+    }
+
+    pub fn sync_cursor(&mut self, current_route: &Route) {
+        if let Some(idx) = ITEMS.iter().position(|(_, route)| route == current_route) {
+            self.cursor = idx;
+        }
     }
 
     pub fn view(
@@ -21,20 +82,19 @@ impl Sidebar {
         area: Rect,
         current_route: &Route,
         border_style: ratatui::style::Style,
+        focused: bool,
     ) {
         let selected = theme().selected;
+        let active_selected = theme().active_selected;
         let text_muted = theme().muted;
-        let items = [
-            ("  Search", Route::Search),
-            ("󰐻  My Wave", Route::Home),
-            ("  My Favorites", Route::Liked),
-            ("  Playlists", Route::Playlists),
-        ];
-
-        let list_items: Vec<ListItem> = items
+
+        let list_items: Vec<ListItem> = ITEMS
             .iter()
-            .map(|(label, route)| {
-                let style = if current_route == route {
+            .enumerate()
+            .map(|(i, (label, route))| {
+                let style = if focused && i == self.cursor {
+                    active_selected.add_modifier(Modifier::BOLD)
+                } else if current_route == route {
                     selected.add_modifier(Modifier::BOLD)
                 } else {
                     text_muted
@@ -43,15 +103,39 @@ impl Sidebar {
             })
             .collect();
 
-        let list = List::new(list_items).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_set(border::ROUNDED)
-                .border_style(border_style)
-                .title("yamusic")
-                .title_alignment(ratatui::layout::Alignment::Center),
-        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(border_style)
+            .title("yamusic")
+            .title_alignment(ratatui::layout::Alignment::Center);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        frame.render_widget(List::new(list_items), inner);
 
-        frame.render_widget(list, area);
+        let rows = self.badges.with(SidebarBadges::rows);
+        if rows.len() as u16 + ITEMS.len() as u16 >= inner.height {
+            return;
+        }
+        let accent = Style::default().fg(theme().accent.primary);
+        let width = inner.width as usize;
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .map(|(label, count)| {
+                let badge = format!(" {count} ");
+                let padding = width.saturating_sub(label.width() + badge.width() + 3);
+                Line::from(vec![
+                    Span::styled(format!("  {label}{}", " ".repeat(padding)), text_muted),
+                    Span::styled(badge, accent.add_modifier(Modifier::REVERSED)),
+                ])
+            })
+            .collect();
+        let height = lines.len() as u16;
+        let badge_area = Rect {
+            y: inner.bottom() - height,
+            height,
+            ..inner
+        };
+        frame.render_widget(Paragraph::new(lines), badge_area);
     }
 }
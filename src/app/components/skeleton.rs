@@ -0,0 +1,63 @@
+use ratatui::{Frame, layout::Rect, style::Style};
+
+use crate::app::theme::theme;
+
+const BAR: &str = "━";
+
+pub struct Skeleton {
+    lines: Vec<u16>,
+    gap: u16,
+    style: Style,
+}
+
+impl Default for Skeleton {
+    fn default() -> Self {
+        Self {
+            lines: vec![55, 30],
+            gap: 1,
+            style: Style::default().fg(theme().text.dim),
+        }
+    }
+}
+
+impl Skeleton {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_lines(mut self, lines: &[u16]) -> Self {
+        self.lines = lines.to_vec();
+        self
+    }
+
+    pub fn with_gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn view(&self, frame: &mut Frame, area: Rect) {
+        let stride = self.lines.len() as u16 + self.gap;
+        if stride == 0 || area.width == 0 {
+            return;
+        }
+
+        let buf = frame.buffer_mut();
+        let mut y = area.y;
+        let mut row = 0usize;
+        while y + self.lines.len() as u16 <= area.bottom() {
+            for (line, percent) in self.lines.iter().enumerate() {
+                let jitter = (row * 37 + line * 13) % 31;
+                let width = (area.width as usize * *percent as usize / 100) * (70 + jitter) / 100;
+                buf.set_stringn(
+                    area.x,
+                    y + line as u16,
+                    BAR.repeat(width.max(1)),
+                    area.width as usize,
+                    self.style,
+                );
+            }
+            y += stride;
+            row += 1;
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use std::time::Instant;
+use std::{collections::VecDeque, time::Instant};
 
+use chrono::{DateTime, Utc};
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
@@ -11,13 +12,73 @@ use ratatui::{
 use crate::{
     app::theme::theme,
     framework::signals::Signal,
-    util::animation::Animation,
+    util::{animation::Animation, clock},
 };
 
 const TOAST_DURATION: f32 = 2.0;
 const SLIDE_IN_DURATION: f32 = 0.4;
 const SLIDE_OUT_DURATION: f32 = 0.3;
 const REPLACE_OUT_DURATION: f32 = 0.2;
+const MAX_NOTIFICATIONS: usize = 200;
+const ERROR_ICON: &str = "";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub text: String,
+    pub icon: Option<String>,
+    pub level: NotificationLevel,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NotificationLog {
+    entries: VecDeque<Notification>,
+    unread: usize,
+}
+
+impl NotificationLog {
+    fn push(&mut self, notification: Notification) {
+        self.entries.push_front(notification);
+        self.entries.truncate(MAX_NOTIFICATIONS);
+        self.unread = (self.unread + 1).min(self.entries.len());
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Notification> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn unread(&self) -> usize {
+        self.unread
+    }
+
+    pub fn mark_read(&mut self) {
+        self.unread = 0;
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.entries.remove(index);
+        self.unread = self.unread.min(self.entries.len());
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.unread = 0;
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 enum ToastPhase {
@@ -38,6 +99,7 @@ pub struct ToastManager {
     current: Option<ToastEntry>,
     outgoing: Option<ToastEntry>,
     is_animating: Signal<bool>,
+    notifications: Signal<NotificationLog>,
 }
 
 impl ToastManager {
@@ -46,9 +108,25 @@ impl ToastManager {
             current: None,
             outgoing: None,
             is_animating: Signal::new(false),
+            notifications: Signal::new(NotificationLog::default()),
         }
     }
 
+    pub fn notifications(&self) -> Signal<NotificationLog> {
+        self.notifications.clone()
+    }
+
+    pub fn record(&self, text: String, icon: Option<String>, level: NotificationLevel) {
+        self.notifications.update(|log| {
+            log.push(Notification {
+                text,
+                icon,
+                level,
+                at: clock::now(),
+            })
+        });
+    }
+
     pub fn is_animating(&self) -> Signal<bool> {
         self.is_animating.clone()
     }
@@ -71,10 +149,43 @@ impl ToastManager {
         self.push_lines(vec![message], icon);
     }
 
+    pub fn push_error(&mut self, message: String) {
+        self.show(
+            vec![Line::from(vec![Span::styled(
+                message,
+                Style::default().add_modifier(Modifier::BOLD),
+            )])],
+            Some(ERROR_ICON.to_string()),
+            NotificationLevel::Error,
+        );
+    }
+
     pub fn push_lines(&mut self, message: Vec<Line<'static>>, icon: Option<String>) {
+        self.show(message, icon, NotificationLevel::Info);
+    }
+
+    fn show(
+        &mut self,
+        message: Vec<Line<'static>>,
+        icon: Option<String>,
+        level: NotificationLevel,
+    ) {
         let now = Instant::now();
         self.is_animating.set(true);
 
+        let text = message
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" • ");
+        self.record(text, icon.clone(), level);
+
         if let Some(mut old) = self.current.take() {
             old.phase = ToastPhase::FadeOutDown { started: now };
             self.outgoing = Some(old);
@@ -87,6 +198,38 @@ impl ToastManager {
         });
     }
 
+    pub fn progress(&mut self, message: String, icon: Option<String>) {
+        let message = vec![Line::from(vec![Span::styled(
+            message,
+            Style::default().add_modifier(Modifier::BOLD),
+        )])];
+        if let Some(entry) = &mut self.current
+            && entry.icon == icon
+            && matches!(
+                entry.phase,
+                ToastPhase::SlideIn { .. } | ToastPhase::Visible { .. }
+            )
+        {
+            entry.message = message;
+            if let ToastPhase::Visible { dismiss_at } = &mut entry.phase {
+                *dismiss_at = Instant::now() + std::time::Duration::from_secs_f32(TOAST_DURATION);
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        self.is_animating.set(true);
+        if let Some(mut old) = self.current.take() {
+            old.phase = ToastPhase::FadeOutDown { started: now };
+            self.outgoing = Some(old);
+        }
+        self.current = Some(ToastEntry {
+            message,
+            icon,
+            phase: ToastPhase::SlideIn { started: now },
+        });
+    }
+
     pub fn is_active(&self) -> bool {
         self.current.is_some() || self.outgoing.is_some()
     }
@@ -183,7 +326,9 @@ impl ToastManager {
     ) {
         let colors = theme();
         let text_style = Style::default().fg(colors.text.primary).bg(colors.bg.base);
-        let accent_style = Style::default().fg(colors.accent.primary).bg(colors.bg.base);
+        let accent_style = Style::default()
+            .fg(colors.accent.primary)
+            .bg(colors.bg.base);
         let mut msg_lines = entry.message.clone();
 
         if let Some(icon) = &entry.icon
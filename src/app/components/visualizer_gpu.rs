@@ -19,6 +19,7 @@ use crate::framework::{
     id::ComponentId,
     signals::Signal,
 };
+use crate::util::palette;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -471,7 +472,7 @@ impl Visualizer {
         let bg_rgb = color_to_rgb(colors.bg.base, (13, 13, 13));
 
         if let Some(track) = self.current_track.get()
-            && let Some(dc) = track.derived_colors
+            && let Some(dc) = palette::track_colors(&track)
         {
             let accent_rgb = parse_hex_color(&dc.accent)
                 .unwrap_or_else(|| color_to_rgb(colors.accent.primary, (247, 212, 75)));
@@ -1,4 +1,4 @@
-use std::{io::Write, sync::Arc};
+use std::{io::Write, sync::Arc, time::Duration};
 
 use ratatui_image::picker::Picker;
 
@@ -6,77 +6,151 @@ use flume::{Receiver, Sender};
 use ratatui::{
     Frame,
     crossterm::event::KeyEvent,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
 };
 use tokio::sync::RwLock;
 
 use crate::{
-    audio::{queue::PlaybackContext, system::AudioSystem},
+    audio::{
+        enums::RepeatMode,
+        local::{self, LocalTrack, QueueItem},
+        outputs::OutputTarget,
+        player::Player,
+        queue::PlaybackContext,
+        state::sync_liked_collection_with,
+    },
     cache::image::ImageCache,
-    event::events::Event,
-    http::ApiService,
+    event::{
+        bus::{EventBus, EventCategory},
+        events::Event,
+    },
+    framework::signals::Signal,
+    http::{MusicApi, throttle},
+    util::{
+        clock,
+        config::{self, StartupView},
+        export::ExportFormat,
+        format::{RowContext, RowFormat},
+        fs,
+        import::{ImportMatch, ImportSession, candidate_label},
+        instance::{self, InstanceListener},
+        link::{self, Invocation, ResolvedLink},
+        listen_along::{
+            self, MAX_DRIFT_MS, SessionEvent, SessionFrame, SessionRequest, SessionRole,
+        },
+        now_playing::NowPlaying,
+        process,
+        script::{ScriptCommand, Scripts},
+        smart::{self, Rule},
+        state::{ContextPosition, HistoryContext, SessionState},
+        track::podcast_id,
+    },
 };
 use im::Vector;
 
 use super::{
     actions::{Action, Route},
     components::{
-        Lyrics, PlayerBar, PlayerSignals, Sidebar, ToastManager, Visualizer, tick_global,
+        Lyrics, NotificationLevel, PlayerBar, PlayerSignals, Profiler, Sidebar, SidebarBadges,
+        Spinner, ToastManager, Visualizer, tick_global,
     },
     data::{
-        AlbumTracksSource, ArtistTracksSource, LikedTracksSource, PlaylistDataSource,
-        PlaylistTracksSource,
+        AlbumTracksSource, ArtistTracksSource, DislikedTracksSource, LikedTracksSource,
+        PlaylistDataSource, PlaylistTracksSource, StaticDataSource,
     },
+    focus::{FocusManager, Pane},
     keymap::{
         EffectIntent, Intent, Key, KeyResolver, NavigationIntent, PlaybackIntent, QueueIntent,
         Target, ViewIntent, normalize,
     },
     signals::{AppSignals, LibrarySignals, LyricsSignals, NavigationSignals},
-    state::{SearchState, WaveSessionState},
+    state::{DailyMixState, SearchState, WaveSessionState},
     terminal::{Terminal, TerminalEvent, TickRate},
     views::{
-        EffectsOverlay, HomeView, OverlayRenderer, PlaylistListView, SearchView, ThemePicker,
-        TrackListContext, TrackListView,
+        ArtistAlbumsView, EffectsOverlay, FileBrowserView, HistoryView, HomeView, ModalRequest,
+        ModalStack, MostPlayedView, NotificationsView, OverlayRenderer, PlaylistListView,
+        SearchView, ThemePicker, TrackDetailView, TrackListContext, TrackListView, screensaver,
     },
 };
 use crate::app::theme::{self as app_theme, Theme, theme};
 use crate::framework::component::Component;
 use crate::framework::reactive::{With, memo};
+use crate::framework::tasks::{TaskManager, TaskScope};
+
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+const COMMAND_OUTPUT_LINES: usize = 20;
+const RECENT_CONTEXTS_LIMIT: usize = 20;
+const STEREO_WIDTH_STEP: f32 = 0.1;
+const PODCAST_SPEED_STEP: f32 = 0.1;
+const EPISODE_SAVE_INTERVAL_MS: u64 = 10_000;
+const LIKED_SYNC_INTERVAL: Duration = Duration::from_secs(300);
+const LIKED_LIBRARY_CHUNK: usize = 200;
 
 pub struct App {
-    signals: Arc<AppSignals>,
-    audio: Arc<RwLock<AudioSystem>>,
-    api: Arc<ApiService>,
-    event_tx: Sender<Event>,
+    pub(super) signals: Arc<AppSignals>,
+    pub(super) audio: Arc<RwLock<dyn Player>>,
+    pub(super) api: Arc<dyn MusicApi>,
+    pub(super) event_tx: Sender<Event>,
     event_rx: Receiver<Event>,
+    events: Arc<EventBus>,
+    pub(super) tasks: Arc<TaskManager>,
     player_bar: PlayerBar,
-    visualizer: Visualizer,
+    pub(super) visualizer: Visualizer,
     lyrics: Lyrics,
     sidebar: Sidebar,
     sidebar_visible: bool,
-    should_quit: bool,
+    pub(super) focus: FocusManager,
+    pub(super) should_quit: bool,
     picker: Option<Picker>,
 
-    search_state: SearchState,
-    wave_state: WaveSessionState,
+    pub(super) search_state: SearchState,
+    pub(super) wave_state: WaveSessionState,
+    pub(super) daily_mix: DailyMixState,
+    pub(super) import: Option<ImportSession>,
 
     home_view: HomeView,
-    playlist_list_view: Option<PlaylistListView>,
-    liked_view: Option<TrackListView>,
-    search_view: SearchView,
-    track_list_view: Option<TrackListView>,
-
-    current_route: Route,
+    pub(super) playlist_list_view: Option<PlaylistListView>,
+    pub(super) file_browser: Option<FileBrowserView>,
+    focus_lost_at: Option<std::time::Instant>,
+    last_input_at: std::time::Instant,
+    pub(super) pending_selection: Option<usize>,
+    pub(super) route_selections: Vec<Option<usize>>,
+    screensaver: Signal<bool>,
+    auto_paused: bool,
+    episode_saved: Option<(String, u64)>,
+    pub(super) liked_view: Option<TrackListView>,
+    pub(super) search_view: SearchView,
+    pub(super) track_list_view: Option<TrackListView>,
+    pub(super) track_detail_view: Option<TrackDetailView>,
+    pub(super) artist_albums_view: Option<ArtistAlbumsView>,
+    pub(super) history_view: Option<HistoryView>,
+    pub(super) most_played_view: Option<MostPlayedView>,
+    pub(super) notifications_view: Option<NotificationsView>,
+    pub(super) playing_context: Option<HistoryContext>,
+    pub(super) resume_key: Option<String>,
+    context_saved: Option<(String, u64)>,
+    pub(super) pending_resume: Option<ContextPosition>,
+    pub(super) pending_wave: Option<(Action, Option<HistoryContext>)>,
+    liked_library: Vec<yandex_music::model::track::Track>,
+    liked_library_ids: Option<im::HashSet<String>>,
+
+    pub(super) current_route: Route,
     key_resolver: KeyResolver,
-    toast_manager: ToastManager,
+    pub(super) toast_manager: ToastManager,
+    pub(super) profiler: Profiler,
+    pub(super) scripts: Scripts,
+    now_playing: NowPlaying,
     effects_overlay: EffectsOverlay,
     theme_picker: ThemePicker,
+    pub(super) modals: ModalStack,
+    listen_along: Option<SessionRole>,
+    listen_along_tasks: TaskScope,
 }
 
 impl App {
-    pub async fn new(
-        audio: AudioSystem,
-        api: Arc<ApiService>,
+    pub async fn new<P: Player + 'static>(
+        audio: P,
+        api: Arc<dyn MusicApi>,
         event_tx: Sender<Event>,
         event_rx: Receiver<Event>,
     ) -> color_eyre::Result<Self> {
@@ -84,16 +158,19 @@ impl App {
 
         let audio_signals = audio.signals().clone();
         let effect_handles = audio.get_effect_handles();
+        let file_info = audio.file_info();
+        let tasks = audio.tasks();
 
-        let audio = Arc::new(RwLock::new(audio));
+        let audio: Arc<RwLock<dyn Player>> = Arc::new(RwLock::new(audio));
 
         let lyrics_signals = LyricsSignals::new(api.clone(), &audio_signals);
 
         let signals = Arc::new(AppSignals {
             audio: audio_signals,
             navigation: NavigationSignals::new(),
-            library: LibrarySignals::new(api.clone()),
+            library: LibrarySignals::new(api.clone(), tasks.clone()),
             lyrics: lyrics_signals.clone(),
+            file_info,
             theme: Arc::new(Theme::default()),
             is_focused: crate::framework::reactive::signal(true),
         });
@@ -122,6 +199,35 @@ impl App {
             }
         });
 
+        let next_up = memo({
+            let queue = signals.audio.queue.clone();
+            let index = signals.audio.queue_index.clone();
+            let repeat = signals.audio.repeat_mode.clone();
+            move |_| {
+                let index = index.get();
+                With::with(&queue, |queue| {
+                    let next = match queue.get(index + 1) {
+                        Some(item) => Some(item),
+                        None if repeat.get() == RepeatMode::All && queue.len() > 1 => queue.get(0),
+                        None => None,
+                    }?
+                    .to_track();
+                    let title = next.title.clone().unwrap_or_default();
+                    let artists = next
+                        .artists
+                        .iter()
+                        .filter_map(|a| a.name.as_deref())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    Some(if artists.is_empty() {
+                        title
+                    } else {
+                        format!("{title} — {artists}")
+                    })
+                })
+            }
+        });
+
         let player_signals = PlayerSignals {
             track_title: signals.audio.track_title.clone(),
             track_artists: signals.audio.track_artists.clone(),
@@ -130,7 +236,12 @@ impl App {
             is_disliked: is_current_disliked.0.clone(),
             position_ms: signals.audio.position_ms.clone(),
             duration_ms: signals.audio.duration_ms.clone(),
+            chapters: signals.audio.chapters.clone(),
             buffered_ratio: signals.audio.buffered_ratio.clone(),
+            buffered_ranges: signals.audio.buffered_ranges.clone(),
+            quality_downgraded: signals.audio.quality_downgraded.clone(),
+            load_stage: signals.audio.load_stage.clone(),
+            next_up: next_up.0.clone(),
             volume: signals.audio.volume.clone(),
             is_muted: signals.audio.is_muted.clone(),
             is_shuffled: signals.audio.is_shuffled.clone(),
@@ -147,24 +258,54 @@ impl App {
 
         let lyrics = Lyrics::new(signals.lyrics.clone(), signals.audio.position_ms.clone());
 
-        let wave_state = WaveSessionState::new(api.clone(), event_tx.clone());
+        let wave_state = WaveSessionState::new(api.clone(), event_tx.clone(), tasks.scope("wave"));
+        let listen_along_tasks = tasks.scope("listen_along");
+        let daily_mix = DailyMixState::new(
+            api.clone(),
+            signals.library.play_counts.clone(),
+            tasks.scope("daily_mix"),
+        );
         let search_state = SearchState::new();
 
+        let toast_manager = ToastManager::new();
+        let badges = memo({
+            let queue = signals.audio.queue_length.clone();
+            let running = tasks.running_signal();
+            let notifications = toast_manager.notifications();
+            move |_| SidebarBadges {
+                queue: queue.get(),
+                tasks: running.get(),
+                notifications: notifications.with(|log| log.unread()),
+            }
+        });
+
         let api_clone = api.clone();
         let event_tx_clone = event_tx.clone();
         let audio_clone = audio.clone();
-        tokio::spawn(async move {
+        tasks.spawn("sync_liked_collection", async move {
             let audio_guard = audio_clone.read().await;
             let state = audio_guard.state_handle();
             drop(audio_guard);
 
-            AudioSystem::sync_liked_collection_with(api_clone, state.clone()).await;
-            if let Ok(state_guard) = state.try_read() {
-                let _ =
-                    event_tx_clone.send(Event::LikedStatusUpdated(state_guard.liked.snapshot()));
+            let mut synced = None;
+            loop {
+                sync_liked_collection_with(api_clone.clone(), state.clone()).await;
+                let update = {
+                    let state_guard = state.read().await;
+                    let revision = state_guard.liked.revision;
+                    (revision.is_some() && revision != synced)
+                        .then(|| (revision, state_guard.liked.snapshot()))
+                };
+                if let Some((revision, snapshot)) = update {
+                    synced = revision;
+                    let _ = event_tx_clone.send(Event::LikedStatusUpdated(snapshot));
+                }
+                tokio::time::sleep(LIKED_SYNC_INTERVAL).await;
             }
         });
 
+        let events = Arc::new(EventBus::new());
+        let scripts = Scripts::spawn(event_tx.clone(), &events);
         let wave_state_waves = wave_state.waves.clone();
         let wave_state_loading = wave_state.is_loading.clone();
 
@@ -174,25 +315,56 @@ impl App {
             api: api.clone(),
             event_tx: event_tx.clone(),
             event_rx,
+            events,
+            tasks,
             player_bar,
-            sidebar: Sidebar::new(),
+            sidebar: Sidebar::new(badges),
             sidebar_visible: true,
+            focus: FocusManager::new(),
             should_quit: false,
             picker: None,
             search_state,
             wave_state,
-            home_view: HomeView::new(wave_state_waves, wave_state_loading),
+            import: None,
+            home_view: HomeView::new(wave_state_waves, wave_state_loading, &daily_mix),
+            daily_mix,
             playlist_list_view: None,
+            file_browser: None,
+            focus_lost_at: None,
+            last_input_at: std::time::Instant::now(),
+            pending_selection: None,
+            route_selections: Vec::new(),
+            screensaver: Signal::new(false),
+            auto_paused: false,
+            episode_saved: None,
             liked_view: None,
             search_view: SearchView::new(&signals),
             track_list_view: None,
+            track_detail_view: None,
+            artist_albums_view: None,
+            history_view: None,
+            most_played_view: None,
+            notifications_view: None,
+            playing_context: None,
+            resume_key: None,
+            context_saved: None,
+            pending_resume: None,
+            pending_wave: None,
+            liked_library: Vec::new(),
+            liked_library_ids: None,
             current_route: Route::Home,
             key_resolver: KeyResolver::new(),
             visualizer,
             lyrics,
-            toast_manager: ToastManager::new(),
+            toast_manager,
+            profiler: Profiler::new(),
+            scripts,
+            now_playing: NowPlaying::spawn(),
             effects_overlay: EffectsOverlay::new(effect_handles),
             theme_picker: ThemePicker::new(),
+            modals: ModalStack::new(),
+            listen_along: None,
+            listen_along_tasks,
         })
     }
 
@@ -201,7 +373,8 @@ impl App {
     }
 
     fn update_bridge_state(&self) {
-        let should_enable = self.signals.is_focused.get() && self.current_route == Route::Home;
+        let should_enable = self.signals.is_focused.get()
+            && (self.current_route == Route::Home || self.screensaver.get());
         self.signals.audio.monitor.set_enabled(should_enable);
         self.signals
             .audio
@@ -214,669 +387,968 @@ impl App {
     }
 
     pub async fn process_event(&mut self, event: Event) {
-        match event {
-            Event::TrackStarted(_, _) => {}
-            Event::PlaybackProgress(_) => {}
-            Event::QueueUpdated => {
-                self.audio.write().await.sync_queue().await;
+        if matches!(event, Event::TrackStarted(..)) {
+            self.scripts.set_context(self.playing_context.as_ref());
+        }
+        self.events.publish(&event);
+
+        match event.category() {
+            EventCategory::Playback => self.handle_playback_event(event).await,
+            EventCategory::Library => self.handle_library_event(event),
+            EventCategory::Search => self.handle_search_event(event),
+            EventCategory::Errors => self.handle_error_event(event),
+            EventCategory::System => self.handle_system_event(event).await,
+        }
+        self.sync_now_playing();
+    }
+
+    fn sync_episode_progress(&mut self, force: bool) {
+        let audio = &self.signals.audio;
+        let Some(track) = audio
+            .current_track
+            .get()
+            .filter(|t| podcast_id(t).is_some())
+        else {
+            return;
+        };
+        let position = audio.position_ms.get();
+        let due = match &self.episode_saved {
+            Some((id, saved)) if *id == track.id => {
+                position.abs_diff(*saved) >= EPISODE_SAVE_INTERVAL_MS
             }
-            Event::TrackEnded => {
-                let audio = self.audio.clone();
-                tokio::spawn(async move {
-                    let mut audio = audio.write().await;
-                    audio.on_track_ended().await;
-                });
+            _ => true,
+        };
+        if !(due || force) || position == 0 {
+            return;
+        }
+        let duration = audio.duration_ms.get();
+        self.signals
+            .library
+            .save_episode_position(&track.id, position, duration);
+        self.report_episode_progress(&track, position, duration);
+        self.episode_saved = Some((track.id, position));
+    }
+
+    pub(super) fn report_episode_progress(
+        &self,
+        track: &yandex_music::model::track::Track,
+        position_ms: u64,
+        duration_ms: u64,
+    ) {
+        let api = self.api.clone();
+        let track_id = track.id.clone();
+        let album_id = podcast_id(track);
+        self.tasks
+            .spawn_in_group("episode_progress", "report_episode_progress", async move {
+                let position = Duration::from_millis(position_ms);
+                let length = Duration::from_millis(duration_ms);
+                if let Err(e) = api
+                    .report_play_progress(track_id, album_id, position, length)
+                    .await
+                {
+                    tracing::warn!(error = %e, "episode_progress_report_failed");
+                }
+            });
+    }
+
+    fn resume_episode(&mut self, track: &yandex_music::model::track::Track) {
+        self.episode_saved = None;
+        if podcast_id(track).is_none() || self.signals.audio.position_ms.get() > 0 {
+            return;
+        }
+        let Some(position) = self
+            .signals
+            .library
+            .episode_state(&track.id)
+            .and_then(|state| state.resume_point())
+        else {
+            return;
+        };
+        self.episode_saved = Some((track.id.clone(), position));
+        let audio = self.audio.clone();
+        self.tasks.spawn("resume_episode", async move {
+            audio
+                .write()
+                .await
+                .seek_to(Duration::from_millis(position))
+                .await;
+        });
+    }
+
+    pub(super) async fn play_context(
+        &mut self,
+        context: PlaybackContext,
+        tracks: Vector<QueueItem>,
+        start_index: usize,
+    ) {
+        self.playing_context = match &context {
+            PlaybackContext::Folder(path) => Some(HistoryContext::folder(path)),
+            _ => self.current_route.history_context(),
+        };
+        self.resume_key = self.context_resume_key(&tracks);
+        self.context_saved = None;
+        let mut audio = self.audio.write().await;
+        audio.load_context(context, tracks, start_index).await;
+    }
+
+    /// Reads tags off the UI thread; `Event::LocalTracksRead` then plays or
+    /// queues whatever was found.
+    pub(super) fn read_local(&self, path: std::path::PathBuf, play: bool) {
+        let tx = self.event_tx.clone();
+        self.tasks.spawn("read_local", async move {
+            let dir = path.clone();
+            let result = tokio::task::spawn_blocking(move || fs::local_tracks(&dir))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|r| r.map_err(|e| e.to_string()));
+            let _ = tx.send(Event::LocalTracksRead(path, play, result));
+        });
+    }
+
+    fn context_resume_key(&self, tracks: &Vector<QueueItem>) -> Option<String> {
+        let minutes = config::get().playback.resume_context_minutes;
+        let total: Duration = tracks.iter().filter_map(|t| t.remote()?.duration).sum();
+        self.playing_context
+            .as_ref()
+            .filter(|_| minutes > 0 && total >= Duration::from_secs(minutes * 60))
+            .and_then(HistoryContext::resume_key)
+    }
+
+    fn sync_context_position(&mut self, force: bool) {
+        let Some(key) = self.resume_key.clone() else {
+            return;
+        };
+        let audio = &self.signals.audio;
+        let Some(track) = audio.current_track.get() else {
+            return;
+        };
+        let position = audio.position_ms.get();
+        let due = match &self.context_saved {
+            Some((id, saved)) if *id == track.id => {
+                position.abs_diff(*saved) >= EPISODE_SAVE_INTERVAL_MS
             }
-            Event::LikedStatusUpdated(snapshot) => {
-                let (liked, disliked) = snapshot;
-                self.signals.library.set_liked_snapshot(liked, disliked);
+            _ => true,
+        };
+        if !(due || force) || position == 0 {
+            return;
+        }
+        self.signals.library.save_context_position(
+            &key,
+            ContextPosition {
+                track_id: track.id.clone(),
+                title: track.title.clone().unwrap_or_default(),
+                position_ms: position,
+            },
+        );
+        self.context_saved = Some((track.id, position));
+    }
+
+    fn offer_context_resume(&mut self, route: &Route) {
+        if let Some(key) = route
+            .history_context()
+            .as_ref()
+            .and_then(HistoryContext::resume_key)
+        {
+            self.offer_resume(key);
+        }
+    }
+
+    pub(super) fn offer_resume(&mut self, key: String) {
+        if self.resume_key.as_ref() == Some(&key) {
+            return;
+        }
+        let Some(position) = self.signals.library.context_position(&key) else {
+            return;
+        };
+        let message = format!(
+            "Resume \"{}\" from {}?",
+            position.title,
+            format_position(position.position_ms)
+        );
+        self.modals.open(ModalRequest::confirm(
+            "Resume",
+            message,
+            Action::ResumeContext(position),
+        ));
+    }
+
+    fn resume_context_track(&mut self, track: &yandex_music::model::track::Track) {
+        let Some(position) = self
+            .pending_resume
+            .take_if(|p| p.track_id == track.id)
+            .map(|p| p.position_ms)
+        else {
+            return;
+        };
+        let audio = self.audio.clone();
+        self.tasks.spawn("resume_context", async move {
+            audio
+                .write()
+                .await
+                .seek_to(Duration::from_millis(position))
+                .await;
+        });
+    }
+
+    fn finish_episode(&mut self) {
+        let audio = &self.signals.audio;
+        if let Some(track) = audio
+            .current_track
+            .get()
+            .filter(|t| podcast_id(t).is_some())
+        {
+            let duration = audio.duration_ms.get();
+            if audio.position_ms.get() + EPISODE_SAVE_INTERVAL_MS >= duration {
+                self.signals
+                    .library
+                    .set_episode_played(&track.id, duration, true);
+                self.report_episode_progress(&track, duration, duration);
             }
-            Event::PlaylistsFetched(_)
-            | Event::PlaylistFetched(_)
-            | Event::PlaylistTracksFetched(_, _)
-            | Event::PlaylistTracksPageFetched(_, _, _) => {}
-            Event::SearchResults(results) => {
-                let optimal_tab = self.search_state.apply_results(results);
-                self.search_view.apply(
-                    self.search_state.tracks(),
-                    self.search_state.albums(),
-                    self.search_state.artists(),
-                    self.search_state.playlists(),
-                    optimal_tab,
-                );
+        }
+        self.episode_saved = None;
+    }
+
+    fn sync_now_playing(&mut self) {
+        let audio = &self.signals.audio;
+        let track = audio
+            .current_track
+            .get()
+            .filter(|_| !audio.is_stopped.get());
+        let liked = track
+            .as_ref()
+            .is_some_and(|t| self.signals.library.is_liked(&t.id));
+        self.now_playing
+            .update(track.as_ref(), audio.is_playing.get(), liked);
+    }
+
+    fn handle_command_finished(&mut self, command: String, result: Result<(bool, String), String>) {
+        let (success, output) = match result {
+            Ok(result) => result,
+            Err(e) => (false, e),
+        };
+        tracing::info!(command = %command, success, output = %output, "user_command_finished");
+
+        let level = if success {
+            NotificationLevel::Info
+        } else {
+            self.toast_manager
+                .push_error(format!("Command failed: {command}"));
+            NotificationLevel::Error
+        };
+        if output.is_empty() && success {
+            self.toast_manager
+                .record(format!("Ran: {command}"), None, level);
+        }
+        for line in output.lines().take(COMMAND_OUTPUT_LINES) {
+            self.toast_manager
+                .record(format!("{command}: {line}"), None, level);
+        }
+    }
+
+    async fn pick_output_target(&mut self, targets: Vec<OutputTarget>) {
+        let current = self.audio.read().await.output_target();
+        if targets.is_empty() && current.is_none() {
+            self.toast_manager
+                .push("No playback devices found".to_string());
+            return;
+        }
+
+        let selected = current
+            .as_ref()
+            .and_then(|c| targets.iter().position(|t| t == c))
+            .map_or(0, |i| i + 1);
+        let options = std::iter::once("This device".to_string())
+            .chain(targets.iter().map(OutputTarget::label))
+            .collect();
+        self.modals.open(ModalRequest::Select {
+            title: "Play on".to_string(),
+            options,
+            selected,
+            on_select: Arc::new(move |choice| {
+                Action::SwitchOutput(choice.checked_sub(1).and_then(|i| targets.get(i).cloned()))
+            }),
+        });
+    }
+
+    async fn handle_script_command(&mut self, command: ScriptCommand) {
+        match command {
+            ScriptCommand::Notify(text) => self.toast_manager.push(text),
+            ScriptCommand::Error(e) => self.toast_manager.push_error(format!("Script error: {e}")),
+            ScriptCommand::ClearQueue => self.audio.write().await.clear_queue(),
+            ScriptCommand::Queue { id, next } => {
+                let api = self.api.clone();
+                let audio = self.audio.clone();
+                self.tasks.spawn("script_queue", async move {
+                    let track = match id.strip_prefix(local::LOCAL_ID_PREFIX) {
+                        Some(path) => LocalTrack::read(path).map(QueueItem::Local),
+                        None => api
+                            .fetch_tracks(vec![id])
+                            .await
+                            .ok()
+                            .and_then(|t| t.into_iter().next())
+                            .map(QueueItem::Remote),
+                    };
+                    if let Some(track) = track {
+                        let mut audio = audio.write().await;
+                        if next {
+                            audio.play_track_next(track);
+                        } else {
+                            audio.queue_track(track);
+                        }
+                    }
+                });
             }
-            Event::SearchPageFetched(results, page) => {
-                self.search_state.merge_results(results, page);
-                self.search_view.apply_merged(
-                    self.search_state.tracks(),
-                    self.search_state.albums(),
-                    self.search_state.artists(),
-                    self.search_state.playlists(),
-                );
+        }
+    }
+
+    async fn handle_system_event(&mut self, event: Event) {
+        match event {
+            Event::Shutdown => self.should_quit = true,
+            Event::Reload => {
+                config::reload();
+                self.scripts.reload();
+                self.key_resolver = KeyResolver::new();
+                if app_theme::reload() {
+                    self.toast_manager
+                        .push("Config and theme reloaded".to_string());
+                } else {
+                    self.toast_manager
+                        .push_error("Config reloaded, but couldn't reload theme".to_string());
+                }
             }
-            Event::FetchError(_) => {
-                self.search_state.is_loading = false;
-                self.search_view.set_loading(false);
+            Event::Forwarded(args) => self.handle_forwarded(args),
+            Event::Script(command) => self.handle_script_command(command).await,
+            Event::CommandFinished(command, result) => {
+                self.handle_command_finished(command, result)
             }
-            Event::WaveReady(session, tracks) => {
-                let audio = self.audio.clone();
-                tokio::spawn(async move {
-                    let mut audio = audio.write().await;
-                    audio
-                        .load_context(PlaybackContext::Wave(session), Vector::from(tracks), 0)
-                        .await;
-                });
+            Event::OutputTargetsFound(Ok(targets)) => self.pick_output_target(targets).await,
+            Event::OutputTargetsFound(Err(e)) => {
+                self.toast_manager
+                    .push_error(format!("Device discovery failed: {e}"));
             }
-            Event::WaveBuffer(tracks) => {
-                let mut audio = self.audio.write().await;
-                audio.wave_update_buffer(tracks);
+            Event::LinkResolved(Ok(link), play) => self.open_link(link, play).await,
+            Event::LinkResolved(Err(e), _) => {
+                self.toast_manager
+                    .push_error(format!("Couldn't open link: {e}"));
             }
+            Event::ListenAlong(event) => self.handle_listen_along(event).await,
             _ => {}
         }
     }
 
-    pub async fn process_action(&mut self, action: Action) {
-        match action {
-            Action::None => {}
-            Action::Redraw => {}
-            Action::Quit => {
-                self.should_quit = true;
+    fn handle_forwarded(&mut self, args: Vec<String>) {
+        if args.is_empty() {
+            self.toast_manager
+                .push("yamusic is already running".to_string());
+            return;
+        }
+
+        let invocation = match Invocation::parse(&args) {
+            Ok(invocation) => invocation,
+            Err(e) => {
+                self.toast_manager.push_error(e.to_string());
+                return;
             }
-            Action::Navigate(route) => {
-                self.navigate(route).await;
+        };
+
+        if let Some(request) = invocation.session {
+            self.start_listen_along(request);
+        }
+        let Some(link) = invocation.link else {
+            return;
+        };
+        let api = self.api.clone();
+        let tx = self.event_tx.clone();
+        let play = invocation.play;
+        self.tasks
+            .spawn_in_group("link", "resolve link", async move {
+                let resolved = link.resolve(api.as_ref()).await.map_err(|e| e.to_string());
+                let _ = tx.send(Event::LinkResolved(resolved, play));
+            });
+    }
+
+    fn start_listen_along(&mut self, request: SessionRequest) {
+        self.listen_along_tasks.abort_all();
+        let was_active = self.listen_along.take().is_some();
+        let tx = self.event_tx.clone();
+        match request {
+            SessionRequest::Host(addr) => {
+                let (sender, frames) = tokio::sync::watch::channel(self.listen_along_frame());
+                let scope = self.listen_along_tasks.clone();
+                self.listen_along_tasks
+                    .spawn("host", listen_along::host(addr, frames, tx, scope));
+                self.listen_along = Some(SessionRole::Host(sender));
+            }
+            SessionRequest::Join(addr) => {
+                self.listen_along_tasks
+                    .spawn("follow", listen_along::follow(addr, tx));
+                self.listen_along = Some(SessionRole::Follower { syncing: None });
+            }
+            SessionRequest::Leave if was_active => {
+                self.toast_manager
+                    .push("Left listen-along session".to_string());
             }
-            Action::Back => {
-                self.signals.navigation.back();
-                self.current_route = self.signals.navigation.current_route.get();
+            SessionRequest::Leave => {}
+        }
+    }
+
+    fn listen_along_frame(&self) -> SessionFrame {
+        let audio = &self.signals.audio;
+        SessionFrame {
+            queue: audio.queue.with(|q| q.iter().map(QueueItem::id).collect()),
+            index: audio.queue_index.get(),
+            position_ms: audio.position_ms.get(),
+            playing: audio.is_playing.get(),
+        }
+    }
+
+    fn publish_listen_along(&self) {
+        let Some(SessionRole::Host(sender)) = &self.listen_along else {
+            return;
+        };
+        let frame = self.listen_along_frame();
+        sender.send_if_modified(|current| {
+            let changed = *current != frame;
+            *current = frame;
+            changed
+        });
+    }
+
+    async fn handle_listen_along(&mut self, event: SessionEvent) {
+        match event {
+            SessionEvent::Hosting(addr) => self
+                .toast_manager
+                .push(format!("Hosting listen-along on {addr}")),
+            SessionEvent::Joined(peer) => self
+                .toast_manager
+                .push(format!("{peer} is listening along")),
+            SessionEvent::Following(addr) => self
+                .toast_manager
+                .push(format!("Listening along with {addr}")),
+            SessionEvent::Ended(message) => {
+                self.listen_along = None;
+                self.listen_along_tasks.abort_all();
+                self.toast_manager.push_error(message);
+            }
+            SessionEvent::Frame(frame) => self.follow_frame(frame).await,
+            SessionEvent::SyncFailed(target) => {
+                if let Some(SessionRole::Follower { syncing }) = &mut self.listen_along
+                    && syncing.as_deref() == Some(target.as_str())
+                {
+                    *syncing = None;
+                }
             }
-            Action::Overlay(route) => {
-                self.signals.navigation.show_overlay(route);
+        }
+    }
+
+    async fn follow_frame(&mut self, frame: SessionFrame) {
+        let Some(SessionRole::Follower { syncing }) = &mut self.listen_along else {
+            return;
+        };
+        let Some(target) = frame
+            .current()
+            .filter(|id| !id.starts_with(local::LOCAL_ID_PREFIX))
+            .map(str::to_string)
+        else {
+            return;
+        };
+
+        let audio = &self.signals.audio;
+        if audio.current_track_id.get().as_deref() != Some(target.as_str()) {
+            if syncing.as_deref() == Some(target.as_str()) {
+                return;
             }
-            Action::DismissOverlay => {
-                self.signals.navigation.dismiss_overlay();
+            *syncing = Some(target.clone());
+            let player = self.audio.clone();
+            if let Some(index) = audio
+                .queue
+                .with(|q| q.iter().position(|t| t.id() == target))
+            {
+                self.listen_along_tasks.spawn("sync", async move {
+                    player.write().await.play_track_at_index(index).await;
+                });
+                return;
+            }
+            let api = self.api.clone();
+            let tx = self.event_tx.clone();
+            let queue: Vec<String> = frame
+                .queue
+                .into_iter()
+                .filter(|id| !id.starts_with(local::LOCAL_ID_PREFIX))
+                .collect();
+            self.listen_along_tasks.spawn("sync", async move {
+                let tracks = match api.fetch_tracks(queue).await {
+                    Ok(tracks) => tracks,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "listen_along_fetch_failed");
+                        let _ = tx.send(Event::ListenAlong(SessionEvent::SyncFailed(target)));
+                        return;
+                    }
+                };
+                let index = tracks.iter().position(|t| t.id == target).unwrap_or(0);
+                player
+                    .write()
+                    .await
+                    .load_context(
+                        PlaybackContext::Standalone,
+                        tracks.into_iter().map(QueueItem::Remote).collect(),
+                        index,
+                    )
+                    .await;
+            });
+            return;
+        }
+
+        *syncing = None;
+        let drifted = audio.position_ms.get().abs_diff(frame.position_ms) > MAX_DRIFT_MS;
+        let toggle = audio.is_playing.get() != frame.playing;
+        let mut player = self.audio.write().await;
+        if drifted {
+            player
+                .seek_to(Duration::from_millis(frame.position_ms))
+                .await;
+        }
+        if toggle {
+            player.play_pause().await;
+        }
+    }
+
+    async fn open_link(&mut self, link: ResolvedLink, play: bool) {
+        match link {
+            ResolvedLink::Track(track) => {
+                if let Some(album) = track.albums.first()
+                    && let Some(id) = album.id
+                {
+                    let title = album.title.clone().unwrap_or_default();
+                    self.navigate(Route::Album {
+                        id: id.to_string(),
+                        title,
+                    })
+                    .await;
+                }
+                if play {
+                    self.audio.write().await.play_single_track(track).await;
+                }
             }
-            Action::PlayContext {
-                context,
-                tracks,
-                start_index,
-            } => {
-                let mut audio = self.audio.write().await;
-                if self.current_route == Route::Queue {
-                    audio.play_track_at_index(start_index).await;
-                } else {
-                    audio.load_context(context, tracks, start_index).await;
+            ResolvedLink::Album(album) => {
+                self.navigate(Route::Album {
+                    id: album.id.unwrap_or_default().to_string(),
+                    title: album.title.clone().unwrap_or_default(),
+                })
+                .await;
+                if play {
+                    let tracks: Vector<_> = album
+                        .volumes
+                        .iter()
+                        .flatten()
+                        .cloned()
+                        .map(QueueItem::Remote)
+                        .collect();
+                    self.audio
+                        .write()
+                        .await
+                        .load_context(PlaybackContext::Album(album), tracks, 0)
+                        .await;
                 }
             }
-            Action::PlayTrack(track) => {
-                let mut audio = self.audio.write().await;
-                audio.play_single_track(track).await;
+            ResolvedLink::Artist { id, name, tracks } => {
+                self.navigate(Route::Artist {
+                    id: id.clone(),
+                    name,
+                })
+                .await;
+                if play {
+                    let context = tracks
+                        .iter()
+                        .flat_map(|t| t.artists.iter())
+                        .find(|a| a.id.as_deref() == Some(id.as_str()))
+                        .cloned()
+                        .map_or(PlaybackContext::Standalone, PlaybackContext::Artist);
+                    self.audio
+                        .write()
+                        .await
+                        .load_context(
+                            context,
+                            tracks.into_iter().map(QueueItem::Remote).collect(),
+                            0,
+                        )
+                        .await;
+                }
             }
-            Action::TogglePlayback => {
-                let mut audio = self.audio.write().await;
-                audio.play_pause().await;
+            ResolvedLink::Playlist(playlist, tracks) => {
+                self.navigate(Route::Playlist {
+                    kind: playlist.kind,
+                    owner: playlist.owner.uid,
+                    title: playlist.title.clone(),
+                })
+                .await;
+                if play {
+                    self.audio
+                        .write()
+                        .await
+                        .load_context(
+                            PlaybackContext::Playlist(playlist),
+                            tracks.into_iter().map(QueueItem::Remote).collect(),
+                            0,
+                        )
+                        .await;
+                }
             }
-            Action::NextTrack => {
-                let mut audio = self.audio.write().await;
-                audio.play_next().await;
+        }
+    }
+
+    async fn handle_playback_event(&mut self, event: Event) {
+        match event {
+            Event::TrackStarted(track, _) => {
+                self.signals
+                    .library
+                    .record_play(&track, self.playing_context.clone());
+                self.resume_episode(&track);
+                self.resume_context_track(&track);
             }
-            Action::PreviousTrack => {
-                let mut audio = self.audio.write().await;
-                audio.play_previous().await;
+            Event::QueueUpdated => {
+                self.audio.write().await.sync_queue().await;
             }
-            Action::SeekForward(secs) => {
-                let mut audio = self.audio.write().await;
-                audio.seek_forwards(secs).await;
+            Event::TrackEnded => {
+                self.finish_episode();
+                let audio = self.audio.clone();
+                self.tasks.spawn("track_ended", async move {
+                    let mut audio = audio.write().await;
+                    audio.on_track_ended().await;
+                });
             }
-            Action::SeekBackward(secs) => {
-                let mut audio = self.audio.write().await;
-                audio.seek_backwards(secs).await;
+            Event::QueueEnded => {
+                if let Some(key) = self.resume_key.take() {
+                    self.signals.library.clear_context_position(&key);
+                }
             }
-            Action::SetVolume(vol) => {
-                let mut audio = self.audio.write().await;
-                audio.set_volume(vol);
+            Event::WaveReady(session, tracks) => {
+                if !self.wave_state.finish_start() {
+                    return;
+                }
+                self.pending_wave = None;
+                let audio = self.audio.clone();
+                self.tasks.spawn("load_wave", async move {
+                    let mut audio = audio.write().await;
+                    audio
+                        .load_context(
+                            PlaybackContext::Wave(session),
+                            tracks.into_iter().map(QueueItem::Remote).collect(),
+                            0,
+                        )
+                        .await;
+                });
             }
-            Action::ToggleMute => {
-                let mut audio = self.audio.write().await;
-                audio.toggle_mute();
+            Event::WaveTimedOut => {
+                if !self.wave_state.finish_start() {
+                    return;
+                }
+                if let Some((action, previous)) = self.pending_wave.take() {
+                    self.playing_context = previous;
+                    self.modals.open(ModalRequest::confirm(
+                        "Wave is taking too long",
+                        "The wave service didn't respond in time. Try again?",
+                        action,
+                    ));
+                }
             }
-            Action::ToggleShuffle => {
-                let mut audio = self.audio.write().await;
-                audio.toggle_shuffle();
+            Event::WaveFailed(e) => {
+                if !self.wave_state.finish_start() {
+                    return;
+                }
+                if let Some((_, previous)) = self.pending_wave.take() {
+                    self.playing_context = previous;
+                }
+                self.toast_manager
+                    .push_error(format!("Couldn't start wave: {e}"));
             }
-            Action::CycleRepeat => {
+            Event::WaveBuffer(tracks) => {
                 let mut audio = self.audio.write().await;
-                audio.toggle_repeat_mode();
+                audio.wave_update_buffer(tracks);
             }
-            Action::QueueTrack(track) => {
-                let mut audio = self.audio.write().await;
-                let title = track
-                    .title
-                    .clone()
-                    .unwrap_or_else(|| "Unknown Track".to_string());
-                audio.queue_track(track);
-                self.toast_manager.push_line(
-                    ratatui::text::Line::from(vec![
-                        ratatui::text::Span::raw("Queued: "),
-                        ratatui::text::Span::styled(
-                            title,
-                            ratatui::style::Style::default()
-                                .add_modifier(ratatui::style::Modifier::BOLD),
-                        ),
-                    ]),
-                    Some("󰐍".to_string()),
-                );
+            Event::LocalTracksRead(path, true, Ok(tracks)) if !tracks.is_empty() => {
+                let tracks = tracks.into_iter().map(QueueItem::Local).collect();
+                self.play_context(PlaybackContext::Folder(path), tracks, 0)
+                    .await;
             }
-            Action::PlayNext(track) => {
+            Event::LocalTracksRead(_, false, Ok(tracks)) if !tracks.is_empty() => {
+                let count = tracks.len();
                 let mut audio = self.audio.write().await;
-                let title = track
-                    .title
-                    .clone()
-                    .unwrap_or_else(|| "Unknown Track".to_string());
-                audio.play_track_next(track);
-                self.toast_manager.push_line(
-                    ratatui::text::Line::from(vec![
-                        ratatui::text::Span::raw("Next: "),
-                        ratatui::text::Span::styled(
-                            title,
-                            ratatui::style::Style::default()
-                                .add_modifier(ratatui::style::Modifier::BOLD),
-                        ),
-                    ]),
-                    Some("󰐊".to_string()),
+                for track in tracks {
+                    audio.queue_track(QueueItem::Local(track));
+                }
+                self.toast_manager.push_with_icon(
+                    format!("Queued {count} local tracks"),
+                    Some("󰐍".to_string()),
                 );
             }
-            Action::RemoveFromQueue(idx) => {
-                let mut audio = self.audio.write().await;
-                audio.remove_from_queue(idx);
-                self.toast_manager
-                    .push_with_icon("Removed from queue".to_string(), Some("󰛌".to_string()));
-            }
-            Action::ClearQueue => {
-                let mut audio = self.audio.write().await;
-                audio.clear_queue();
-                self.toast_manager
-                    .push_with_icon("Queue cleared".to_string(), Some("󰛌".to_string()));
-            }
-            Action::LikeContext => {
-                let api = self.api.clone();
-                let audio_sys = self.audio.clone();
-                match &self.current_route {
-                    Route::Album { id, title } => {
-                        let album_id = id.parse::<u32>().unwrap_or(0);
-                        let title = title.clone();
-                        let audio = self.audio.clone();
-                        tokio::spawn(async move {
-                            let is_liked = audio.read().await.is_album_liked(album_id).await;
-                            if is_liked {
-                                let _ = api.remove_like_album(album_id).await;
-                            } else {
-                                let _ = api.add_like_album(album_id).await;
-                            }
-                            audio.write().await.sync_liked_collection().await;
-                        });
-
-                        let is_liked = audio_sys.read().await.is_album_liked(album_id).await;
-                        if is_liked {
-                            self.toast_manager.push_with_icon(
-                                format!("Removed Album: {title}"),
-                                Some("󰋕".to_string()),
-                            );
-                        } else {
-                            self.toast_manager.push_with_icon(
-                                format!("Liked Album: {title}"),
-                                Some("󰋑".to_string()),
-                            );
-                        }
-                    }
-                    Route::Artist { id, name } => {
-                        let id = id.clone();
-                        let name = name.clone();
-                        let audio = self.audio.clone();
-                        let id_clone = id.clone();
-                        tokio::spawn(async move {
-                            let is_liked = audio.read().await.is_artist_liked(&id_clone).await;
-                            if is_liked {
-                                let _ = api.remove_like_artist(id_clone).await;
-                            } else {
-                                let _ = api.add_like_artist(id_clone).await;
-                            }
-                            audio.write().await.sync_liked_collection().await;
-                        });
-
-                        let is_liked = audio_sys.read().await.is_artist_liked(&id).await;
-                        if is_liked {
-                            self.toast_manager.push_with_icon(
-                                format!("Unfollowed: {name}"),
-                                Some("󰋕".to_string()),
-                            );
-                        } else {
-                            self.toast_manager
-                                .push_with_icon(format!("Followed: {name}"), Some("󰋑".to_string()));
-                        }
-                    }
-                    Route::Playlist { kind, title } => {
-                        let kind = *kind;
-                        let title = title.clone();
-                        let uid = api.current_user_id();
-                        let audio = self.audio.clone();
-                        tokio::spawn(async move {
-                            let is_liked = audio.read().await.is_playlist_liked(uid, kind).await;
-                            if is_liked {
-                                let _ = api.remove_like_playlist(uid, kind).await;
-                            } else {
-                                let _ = api.add_like_playlist(uid, kind).await;
-                            }
-                            audio.write().await.sync_liked_collection().await;
-                        });
+            Event::LocalTracksRead(path, _, Ok(_)) => self
+                .toast_manager
+                .push_error(format!("No playable files in {}", path.display())),
+            Event::LocalTracksRead(path, _, Err(e)) => self
+                .toast_manager
+                .push_error(format!("Couldn't read {}: {e}", path.display())),
+            _ => {}
+        }
+    }
 
-                        let is_liked = audio_sys.read().await.is_playlist_liked(uid, kind).await;
-                        if is_liked {
-                            self.toast_manager.push_with_icon(
-                                format!("Removed Playlist: {title}"),
-                                Some("󰋕".to_string()),
-                            );
-                        } else {
-                            self.toast_manager.push_with_icon(
-                                format!("Liked Playlist: {title}"),
-                                Some("󰋑".to_string()),
-                            );
-                        }
-                    }
-                    _ => {
-                        self.toast_manager
-                            .push_with_icon("Nothing to like".to_string(), Some("".to_string()));
-                    }
-                };
+    fn handle_library_event(&mut self, event: Event) {
+        match event {
+            Event::LikedStatusUpdated((liked, disliked)) => {
+                self.signals.library.set_liked_snapshot(liked, disliked);
             }
-            Action::DislikeContext => {
-                let api = self.api.clone();
-                let audio_sys = self.audio.clone();
-                if let Route::Artist { id, name } = &self.current_route {
-                    let id = id.clone();
-                    let name = name.clone();
-                    let audio = self.audio.clone();
-                    let id_clone = id.clone();
-                    tokio::spawn(async move {
-                        let is_disliked = audio.read().await.is_artist_disliked(&id_clone).await;
-                        if is_disliked {
-                            let _ = api.remove_dislike_artist(id_clone.clone()).await;
-                        } else {
-                            let _ = api.add_dislike_artist(id_clone.clone()).await;
-                        }
-                        audio.write().await.sync_liked_collection().await;
-                    });
-
-                    let is_disliked = audio_sys.read().await.is_artist_disliked(&id).await;
-                    if is_disliked {
-                        self.toast_manager.push_with_icon(
-                            format!("Removed dislike: {name}"),
-                            Some("󰋖".to_string()),
-                        );
-                    } else {
-                        self.toast_manager
-                            .push_with_icon(format!("Disliked: {name}"), Some("󰋖".to_string()));
-                    }
-                } else {
-                    self.toast_manager
-                        .push_with_icon("Nothing to dislike".to_string(), Some("".to_string()));
-                }
+            Event::ImportResolved(Ok(session)) => {
+                self.import = Some(session);
+                self.review_import();
             }
-            Action::QueueAll => {
-                if let Some(view) = &self.track_list_view {
-                    let tracks = view.items();
-                    let count = tracks.len();
-                    let mut audio = self.audio.write().await;
-                    for track in tracks {
-                        audio.queue_track(track.clone());
-                    }
-                    self.toast_manager
-                        .push_with_icon(format!("Queued {count} tracks"), Some("󰐍".to_string()));
-                }
+            Event::ImportResolved(Err(e)) => {
+                self.toast_manager
+                    .push_error(format!("Couldn't import playlist: {e}"));
             }
-            Action::PlayAllNext => {
-                if let Some(view) = &self.track_list_view {
-                    let tracks = view.items();
-                    let count = tracks.len();
-                    let mut audio = self.audio.write().await;
-                    for track in tracks.into_iter().rev() {
-                        audio.play_track_next(track.clone());
-                    }
-                    self.toast_manager
-                        .push_with_icon(format!("Next: {count} tracks"), Some("󰐊".to_string()));
+            Event::FileSaved(label, Ok(path)) => {
+                self.toast_manager
+                    .push(format!("Saved {label} to {}", path.display()));
+            }
+            Event::SmartLibraryFetched(ids, Ok(tracks)) => {
+                self.liked_library = tracks;
+                self.liked_library_ids = Some(ids);
+                if matches!(self.current_route, Route::SmartPlaylist { .. }) {
+                    let route = self.current_route.clone();
+                    self.build_view(&route);
                 }
             }
-            Action::LikeTrack(track) => {
-                let id = track.id.clone();
-                let title = track
-                    .title
-                    .clone()
-                    .unwrap_or_else(|| "Unknown Track".to_string());
-                let was_liked = self.signals.library.is_liked(&id);
-                if was_liked {
-                    self.signals.library.remove_like(&id);
-                    let api = self.api.clone();
-                    let id_clone = id.clone();
-                    tokio::spawn(async move {
-                        let _ = api.remove_like_track(id_clone).await;
-                    });
-
-                    let audio = self.audio.clone();
-                    let track = track.clone();
-                    tokio::spawn(async move {
-                        audio.write().await.send_wave_unlike(&track);
-                    });
-
-                    self.toast_manager.push_line(
-                        ratatui::text::Line::from(vec![
-                            ratatui::text::Span::raw("Removed "),
-                            ratatui::text::Span::styled(
-                                title,
-                                ratatui::style::Style::default()
-                                    .add_modifier(ratatui::style::Modifier::BOLD),
-                            ),
-                            ratatui::text::Span::raw(" from liked"),
-                        ]),
-                        Some("󰋕".to_string()),
-                    );
-                } else {
-                    self.signals.library.add_like(id.clone());
-                    let api = self.api.clone();
-                    let id_clone = id.clone();
-                    tokio::spawn(async move {
-                        let _ = api.add_like_track(id_clone).await;
-                    });
-
-                    let audio = self.audio.clone();
-                    let track = track.clone();
-                    tokio::spawn(async move {
-                        audio.write().await.send_wave_like(&track);
-                    });
-
-                    self.toast_manager.push_line(
-                        ratatui::text::Line::from(vec![
-                            ratatui::text::Span::raw("Added "),
-                            ratatui::text::Span::styled(
-                                title,
-                                ratatui::style::Style::default()
-                                    .add_modifier(ratatui::style::Modifier::BOLD),
-                            ),
-                            ratatui::text::Span::raw(" to liked"),
-                        ]),
-                        Some("󰋑".to_string()),
-                    );
-
-                    if self.current_route == Route::Home {
-                        self.visualizer.trigger_like_glow();
-                    }
-                }
+            Event::SmartLibraryFetched(_, Err(e)) => {
+                tracing::warn!(error = %e, "smart_library_fetch_failed");
             }
-            Action::DislikeTrack(track) => {
-                let id = track.id.clone();
-                let title = track
-                    .title
-                    .clone()
-                    .unwrap_or_else(|| "Unknown Track".to_string());
-                if !self.signals.library.is_disliked(&id) {
-                    self.signals.library.add_dislike(id.clone());
-
-                    if self.signals.library.is_liked(&id) {
-                        self.signals.library.remove_like(&id);
-                        let api = self.api.clone();
-                        let id_clone = id.clone();
-                        tokio::spawn(async move {
-                            let _ = api.remove_like_track(id_clone).await;
-                        });
-                    }
-
-                    let api = self.api.clone();
-                    let id_for_api = id.clone();
-                    tokio::spawn(async move {
-                        let _ = api.add_dislike_track(id_for_api).await;
-                    });
-
-                    if let Some(current) = self.signals.audio.current_track_id.get()
-                        && current == id
-                    {
-                        let mut audio = self.audio.write().await;
-                        audio.send_wave_dislike_skip(&track).await;
-                    } else {
-                        let audio = self.audio.clone();
-                        let track = track.clone();
-                        tokio::spawn(async move {
-                            audio.write().await.send_wave_dislike(&track);
-                        });
-                    }
-                    self.toast_manager.push_line(
-                        ratatui::text::Line::from(vec![
-                            ratatui::text::Span::raw("Added "),
-                            ratatui::text::Span::styled(
-                                title,
-                                ratatui::style::Style::default()
-                                    .add_modifier(ratatui::style::Modifier::BOLD),
-                            ),
-                            ratatui::text::Span::raw(" to disliked"),
-                        ]),
-                        Some("󰋖".to_string()),
-                    );
-                } else {
-                    self.signals.library.remove_dislike(&id);
-                    let api = self.api.clone();
-                    let id_for_api = id.clone();
-                    tokio::spawn(async move {
-                        let _ = api.remove_dislike_track(id_for_api).await;
-                    });
-
-                    let audio = self.audio.clone();
-                    let track = track.clone();
-                    tokio::spawn(async move {
-                        audio.write().await.send_wave_undislike(&track);
-                    });
-
-                    self.toast_manager.push_line(
-                        ratatui::text::Line::from(vec![
-                            ratatui::text::Span::raw("Removed "),
-                            ratatui::text::Span::styled(
-                                title,
-                                ratatui::style::Style::default()
-                                    .add_modifier(ratatui::style::Modifier::BOLD),
-                            ),
-                            ratatui::text::Span::raw(" from disliked"),
-                        ]),
-                        Some("󰋕".to_string()),
-                    );
+            Event::FileSaved(label, Err(e)) => {
+                self.toast_manager
+                    .push_error(format!("Couldn't save {label}: {e}"));
+            }
+            Event::PlaylistCreated(Ok(playlist)) => {
+                self.signals.library.playlists.refetch();
+                self.toast_manager.push(format!(
+                    "Created playlist: {} ({} tracks)",
+                    playlist.title, playlist.track_count
+                ));
+            }
+            Event::LocalLibraryChanged(changes) => {
+                if let Some(view) = &mut self.file_browser {
+                    view.apply_changes(&changes);
                 }
             }
-            Action::Toast(msg) => {
-                self.toast_manager.push(msg);
-            }
-            Action::Batch(actions) => {
-                for action in actions {
-                    Box::pin(self.process_action(action)).await;
-                }
+            Event::PlaylistAppended(kind, title, count, Ok(revision)) => {
+                self.signals.library.note_playlist_revision(kind, revision);
+                self.signals.library.playlists.refetch();
+                self.toast_manager
+                    .push(format!("Added {count} tracks to {title}"));
             }
-            Action::Search(query) => {
-                self.search_state.begin_search();
-                let api = self.api.clone();
-                let tx = self.event_tx.clone();
-                tokio::spawn(async move {
-                    match api.search(&query).await {
-                        Ok(results) => {
-                            let _ = tx.send(Event::SearchResults(results));
-                        }
-                        Err(e) => {
-                            let _ = tx.send(Event::FetchError(e.to_string()));
-                        }
-                    }
+            Event::PlaylistAppended(_, title, count, Err(e)) => {
+                self.toast_manager
+                    .push_error(format!("Couldn't add {count} tracks to {title}: {e}"));
+            }
+            Event::DiscographyResolved(artist_id, Ok((tracks, album_ids))) => {
+                let context = tracks
+                    .iter()
+                    .flat_map(|t| t.artists.iter())
+                    .find(|a| a.id.as_deref() == Some(artist_id.as_str()))
+                    .cloned()
+                    .map_or(PlaybackContext::Standalone, PlaybackContext::Artist);
+                let albums = album_ids.len() + 1;
+                self.playing_context = self.current_route.history_context();
+                self.resume_key = None;
+                let audio = self.audio.clone();
+                self.tasks.spawn("load_discography", async move {
+                    let mut audio = audio.write().await;
+                    audio
+                        .load_context(
+                            context,
+                            tracks.into_iter().map(QueueItem::Remote).collect(),
+                            0,
+                        )
+                        .await;
+                    audio.queue_albums(album_ids);
                 });
+                self.toast_manager.push_with_icon(
+                    format!("Playing discography: {albums} albums"),
+                    Some("󰀥".to_string()),
+                );
             }
-            Action::SearchNextPage => {
-                let tab = self.search_view.current_tab();
-                let sel = self.search_view.current_selection();
-                let count = self.search_view.current_tab_count();
-                if self.search_state.should_load_more(tab, sel, count) {
-                    self.search_state.is_loading_more = true;
-                    self.search_view.set_loading_more(true);
-                    let query = self.search_view.query();
-                    let page = self.search_state.current_page + 1;
-                    let api = self.api.clone();
-                    let tx = self.event_tx.clone();
-                    tokio::spawn(async move {
-                        match api.search_paginated(&query, page).await {
-                            Ok(results) => {
-                                let _ = tx.send(Event::SearchPageFetched(results, page));
-                            }
-                            Err(e) => {
-                                let _ = tx.send(Event::FetchError(e.to_string()));
-                            }
-                        }
-                    });
-                }
-            }
-            Action::StartWave {
-                seeds,
-                title,
-                toast_message,
-            } => {
-                if let Some(lines) = toast_message {
-                    self.toast_manager.push_lines(lines, Some("󰎈".to_string()));
-                } else {
-                    let msg = if let Some(t) = title {
-                        ratatui::text::Line::from(vec![
-                            ratatui::text::Span::raw("Starting a wave for "),
-                            ratatui::text::Span::styled(
-                                t,
-                                ratatui::style::Style::default()
-                                    .add_modifier(ratatui::style::Modifier::BOLD),
-                            ),
-                        ])
-                    } else {
-                        ratatui::text::Line::from("Starting a new wave".to_string())
-                    };
-                    self.toast_manager
-                        .push_lines(vec![msg], Some("󰎈".to_string()));
-                }
-                self.wave_state.start_with_seeds(seeds);
+            Event::DiscographyResolved(_, Err(e)) => {
+                self.toast_manager
+                    .push_error(format!("Couldn't load discography: {e}"));
             }
-            Action::RefreshWaves => {
-                self.wave_state.fetch();
+            Event::LikeProgress(done, total) => {
+                self.toast_manager
+                    .progress(format!("Liking {done}/{total}"), Some("󰋑".to_string()));
             }
-            Action::ScrollTop => match &self.current_route {
-                Route::Search => self.search_view.scroll_top(),
-                Route::Liked => {
-                    if let Some(view) = &mut self.liked_view {
-                        view.scroll_top();
-                    }
-                }
-                Route::Playlists => {
-                    if let Some(view) = &mut self.playlist_list_view {
-                        view.scroll_top();
-                    }
-                }
-                Route::Playlist { .. }
-                | Route::Album { .. }
-                | Route::Artist { .. }
-                | Route::Queue => {
-                    if let Some(view) = &mut self.track_list_view {
-                        view.scroll_top();
-                    }
-                }
-                _ => {}
-            },
-            Action::ScrollBottom => match &self.current_route {
-                Route::Search => self.search_view.scroll_bottom(),
-                Route::Liked => {
-                    if let Some(view) = &mut self.liked_view {
-                        view.scroll_bottom();
-                    }
-                }
-                Route::Playlists => {
-                    if let Some(view) = &mut self.playlist_list_view {
-                        view.scroll_bottom();
-                    }
-                }
-                Route::Playlist { .. }
-                | Route::Album { .. }
-                | Route::Artist { .. }
-                | Route::Queue => {
-                    if let Some(view) = &mut self.track_list_view {
-                        view.scroll_bottom();
-                    }
+            Event::TracksLiked { liked, failed } => {
+                let count = liked.len();
+                for id in liked {
+                    self.signals.library.add_like(id);
                 }
-                _ => {}
-            },
-            Action::ToggleEffect(effect_name) => {
-                let audio = self.audio.read().await;
-                if audio.toggle_effect(&effect_name) {
-                    let status = if audio.is_effect_enabled(&effect_name).unwrap_or(false) {
-                        "enabled"
-                    } else {
-                        "disabled"
-                    };
+                if failed.is_empty() {
                     self.toast_manager
-                        .push(format!("󰐾 Effect {}: {}", effect_name, status));
+                        .push_with_icon(format!("Liked {count} tracks"), Some("󰋑".to_string()));
                 } else {
                     self.toast_manager
-                        .push(format!("󰐾 Effect {} not found", effect_name));
+                        .push_error(format!("Liked {count} tracks, {} failed", failed.len()));
+                    for (title, e) in failed {
+                        self.toast_manager.record(
+                            format!("Couldn't like {title}: {e}"),
+                            None,
+                            NotificationLevel::Error,
+                        );
+                    }
                 }
             }
-            Action::ToggleEqPreset(preset_name) => {
-                let audio = self.audio.read().await;
-                let preset_effects: Vec<String> = match preset_name.as_str() {
-                    "vocal" => vec!["eq_vocal_cut_mud".into(), "eq_vocal_presence".into()],
-                    "bass" => vec!["eq_bass_sub".into(), "eq_bass_punch".into()],
-                    "acoustic" => vec!["eq_acoustic_warmth".into(), "eq_acoustic_air".into()],
-                    "rock" => vec![
-                        "eq_rock_kick".into(),
-                        "eq_rock_mids".into(),
-                        "eq_rock_highs".into(),
-                    ],
-                    _ => vec![],
-                };
+            Event::PlaylistCreated(Err(e)) => {
+                self.toast_manager
+                    .push_error(format!("Couldn't create playlist: {e}"));
+            }
+            _ => {}
+        }
+    }
 
-                if preset_effects.is_empty() {
-                    self.toast_manager
-                        .push(format!("󰓃 Unknown EQ preset: {}", preset_name));
+    pub(super) fn without_local(
+        &mut self,
+        tracks: Vec<yandex_music::model::track::Track>,
+        verb: &str,
+    ) -> Vec<yandex_music::model::track::Track> {
+        let (local, tracks): (Vec<_>, Vec<_>) = tracks.into_iter().partition(local::is_local);
+        let message = match local.len() {
+            0 => return tracks,
+            1 => format!("Skipped a local file; local files can't be {verb}"),
+            n => format!("Skipped {n} local files; local files can't be {verb}"),
+        };
+        self.toast_manager.push_error(message);
+        tracks
+    }
+
+    pub(super) fn review_import(&mut self) {
+        let Some(session) = &self.import else {
+            return;
+        };
+
+        if let Some(index) = session.next_ambiguous() {
+            let entry = &session.entries[index];
+            let ImportMatch::Ambiguous(candidates) = &entry.status else {
+                return;
+            };
+            let count = candidates.len();
+            let mut options: Vec<String> = candidates.iter().map(candidate_label).collect();
+            options.push("Skip".to_string());
+            self.modals.open(ModalRequest::Select {
+                title: format!("Match: {}", entry.row.label()),
+                options,
+                selected: 0,
+                on_select: Arc::new(move |choice| Action::ImportChoose {
+                    index,
+                    candidate: (choice < count).then_some(choice),
+                }),
+            });
+            return;
+        }
+
+        let tracks = session.tracks().len();
+        let missing = session.missing();
+        let mut message = format!("Create \"{}\" with {tracks} tracks?", session.title);
+        if missing > 0 {
+            message.push_str(&format!(" {missing} rows had no match."));
+        }
+        self.modals.open(ModalRequest::confirm(
+            "Import playlist",
+            message,
+            Action::ImportCommit,
+        ));
+    }
+
+    fn handle_search_event(&mut self, event: Event) {
+        match event {
+            Event::SearchResults(query, results, podcasts) => {
+                self.search_state
+                    .remember(query.clone(), results.clone(), podcasts.clone());
+                if query != self.search_state.query {
+                    return;
+                }
+                if self.search_state.is_refreshing {
+                    self.search_state.refresh_results(results, podcasts);
+                    self.search_view.apply_merged(&self.search_state);
                 } else {
-                    let mut toggled = 0;
-                    for effect in &preset_effects {
-                        if audio.toggle_effect(effect) {
-                            toggled += 1;
-                        }
-                    }
-                    if toggled > 0 {
-                        let status = if audio.is_effect_enabled(&preset_effects[0]).unwrap_or(false)
-                        {
-                            "enabled"
-                        } else {
-                            "disabled"
-                        };
-                        self.toast_manager
-                            .push(format!("󰓃 EQ preset '{}': {}", preset_name, status));
-                    } else {
-                        self.toast_manager
-                            .push(format!("󰓃 EQ preset '{}' not loaded", preset_name));
-                    }
+                    let optimal_tab = self.search_state.apply_results(results, podcasts);
+                    self.search_view.apply(&self.search_state, optimal_tab);
                 }
             }
+            Event::SearchPageFetched(results, tab, page) => {
+                self.search_state.merge_results(results, tab, page);
+                self.search_view.apply_merged(&self.search_state);
+            }
+            Event::PodcastPageFetched(results, tab, page) => {
+                self.search_state.merge_podcasts(results, tab, page);
+                self.search_view.apply_merged(&self.search_state);
+            }
             _ => {}
         }
     }
 
-    async fn navigate(&mut self, route: Route) {
+    fn handle_error_event(&mut self, event: Event) {
+        if let Event::FetchError(e) = event {
+            self.toast_manager.record(e, None, NotificationLevel::Error);
+            self.search_state.is_loading = false;
+            self.search_view.set_loading(false);
+        }
+    }
+
+    pub(super) async fn navigate(&mut self, route: Route) {
         let is_top_level_nav = is_top_level(&self.current_route) && is_top_level(&route);
         if is_top_level_nav {
             self.signals.navigation.set_route(route.clone());
         } else {
+            self.route_selections.push(self.current_selection_index());
             self.signals.navigation.navigate(route.clone());
         }
         self.current_route = route.clone();
 
         self.update_bridge_state();
+        self.build_view(&route);
+        self.offer_context_resume(&route);
+    }
+
+    fn fetch_liked_library(&self, liked: &im::HashSet<String>) {
+        if self.liked_library_ids.as_ref() == Some(liked) {
+            return;
+        }
+        let ids = liked.clone();
+        let api = self.api.clone();
+        let tx = self.event_tx.clone();
+        self.tasks
+            .scope("smart")
+            .spawn("liked_library", async move {
+                let mut tracks = Vec::new();
+                let all: Vec<String> = ids.iter().cloned().collect();
+                for chunk in all.chunks(LIKED_LIBRARY_CHUNK) {
+                    match api.fetch_tracks(chunk.to_vec()).await {
+                        Ok(fetched) => tracks.extend(fetched),
+                        Err(e) => {
+                            let _ = tx.send(Event::SmartLibraryFetched(ids, Err(e.to_string())));
+                            return;
+                        }
+                    }
+                }
+                let _ = tx.send(Event::SmartLibraryFetched(ids, Ok(tracks)));
+            });
+    }
 
-        match &route {
+    pub(super) fn build_view(&mut self, route: &Route) {
+        match route {
+            Route::Home if self.daily_mix.is_stale() => self.daily_mix.generate(),
             Route::Liked => {
                 if self.liked_view.is_none() {
-                    let source = Arc::new(LikedTracksSource::new(self.api.clone()));
+                    let scope = self.tasks.scope("view:liked");
+                    let source = Arc::new(LikedTracksSource::new(
+                        self.api.clone(),
+                        self.signals.library.liked_track_ids.clone(),
+                        scope.clone(),
+                    ));
                     let playlist_info = source.playlist_info();
                     let context = TrackListContext::Playlist {
                         kind: 3,
@@ -885,9 +1357,11 @@ impl App {
                         owner_uid: 0,
                         track_count: 0,
                         cover_url: None,
+                        info: None,
                     };
                     let view = TrackListView::new(context, source.clone(), &self.signals)
-                        .with_playlist_info(playlist_info);
+                        .with_playlist_info(playlist_info)
+                        .with_tasks(scope.guard());
                     self.liked_view = Some(view);
                 }
             }
@@ -900,9 +1374,21 @@ impl App {
                     self.playlist_list_view = Some(view);
                 }
             }
-            Route::Playlist { kind, title } => {
-                let source = Arc::new(PlaylistTracksSource::new(*kind, self.api.clone()));
+            Route::Files if self.file_browser.is_none() => {
+                let scope = self.tasks.scope("view:files");
+                self.file_browser = Some(FileBrowserView::new(fs::music_dir(), scope));
+            }
+            Route::Playlist { kind, owner, title } => {
+                let scope = self.tasks.scope(format!("view:playlist:{owner}:{kind}"));
+                let source = Arc::new(PlaylistTracksSource::new(
+                    *kind,
+                    *owner,
+                    self.api.clone(),
+                    scope.clone(),
+                    self.signals.library.playlist_revisions.clone(),
+                ));
                 let playlist_info = source.playlist_info();
+                let remote_change = source.remote_change();
 
                 let context = TrackListContext::Playlist {
                     kind: *kind,
@@ -911,10 +1397,13 @@ impl App {
                     owner_uid: 0,
                     track_count: 0,
                     cover_url: None,
+                    info: None,
                 };
 
                 let view = TrackListView::new(context, source.clone(), &self.signals)
-                    .with_playlist_info(playlist_info);
+                    .with_playlist_info(playlist_info)
+                    .with_remote_change(remote_change)
+                    .with_tasks(scope.guard());
                 self.track_list_view = Some(view);
             }
             Route::Queue => {
@@ -932,9 +1421,52 @@ impl App {
                 let context = TrackListContext::Album {
                     id: id.clone(),
                     title: title.clone(),
-                    artists: String::new(),
-                    year: None,
-                    track_count: 0,
+                    cover_url: None,
+                    info: None,
+                };
+
+                let view = TrackListView::new(context, source.clone(), &self.signals)
+                    .with_album_info(source.info());
+                self.track_list_view = Some(view);
+            }
+            Route::SmartPlaylist { name } => {
+                let config = config::get();
+                let Some(smart) = config.smart_playlist(name) else {
+                    return;
+                };
+                let tracks = match Rule::parse(&smart.rule) {
+                    Ok(rule) => {
+                        let liked = self.signals.library.liked_track_ids.get();
+                        let counts = self.signals.library.play_counts.get();
+                        let history = self.signals.library.play_history.get();
+                        self.fetch_liked_library(&liked);
+                        let library = smart::Library {
+                            counts: &counts,
+                            history: &history,
+                            liked: &self.liked_library,
+                        };
+                        rule.evaluate(&library, &liked, clock::now(), smart.limit)
+                    }
+                    Err(e) => {
+                        self.toast_manager
+                            .push_error(format!("Invalid rule for {name}: {e}"));
+                        Vec::new()
+                    }
+                };
+                let context = TrackListContext::Smart {
+                    title: name.clone(),
+                    rule: smart.rule.clone(),
+                    track_count: tracks.len(),
+                };
+                let source = Arc::new(StaticDataSource::new(tracks));
+                self.track_list_view = Some(TrackListView::new(context, source, &self.signals));
+            }
+            Route::Podcast { id, title } => {
+                let album_id = id.parse::<u32>().unwrap_or(0);
+                let source = Arc::new(AlbumTracksSource::new(album_id, self.api.clone()));
+                let context = TrackListContext::Podcast {
+                    id: id.clone(),
+                    title: title.clone(),
                     cover_url: None,
                 };
 
@@ -953,9 +1485,42 @@ impl App {
                     cover_url: None,
                 };
 
-                let view = TrackListView::new(context, source.clone(), &self.signals);
+                let view = TrackListView::new(context, source.clone(), &self.signals)
+                    .with_artist_description(source.description());
                 self.track_list_view = Some(view);
             }
+            Route::ArtistAlbums { id, name } => {
+                self.artist_albums_view =
+                    Some(ArtistAlbumsView::new(id.clone(), name, self.api.clone()));
+            }
+            Route::Track { id } => {
+                self.track_detail_view = Some(TrackDetailView::new(
+                    id.clone(),
+                    self.api.clone(),
+                    &self.signals,
+                ));
+            }
+            Route::History => {
+                self.history_view = Some(HistoryView::new(&self.signals));
+            }
+            Route::MostPlayed => {
+                self.most_played_view = Some(MostPlayedView::new(&self.signals));
+            }
+            Route::Disliked => {
+                let scope = self.tasks.scope("view:disliked");
+                let source = Arc::new(DislikedTracksSource::new(
+                    self.api.clone(),
+                    self.signals.library.clone(),
+                    scope.clone(),
+                ));
+                let view = TrackListView::new(TrackListContext::Standalone, source, &self.signals)
+                    .with_tasks(scope.guard());
+                self.track_list_view = Some(view);
+            }
+            Route::Notifications => {
+                self.notifications_view =
+                    Some(NotificationsView::new(self.toast_manager.notifications()));
+            }
             _ => {}
         }
     }
@@ -969,6 +1534,32 @@ impl App {
             return Action::Quit;
         }
 
+        self.last_input_at = std::time::Instant::now();
+        self.pending_selection = None;
+        if self.screensaver.get() {
+            self.screensaver.set(false);
+            self.update_bridge_state();
+            return Action::Redraw;
+        }
+
+        if self.modals.is_open() {
+            self.key_resolver.reset();
+            let action = self.modals.handle_key(&key);
+            if self.import.is_some()
+                && !self.modals.is_open()
+                && !matches!(action, Action::ImportChoose { .. } | Action::ImportCommit)
+            {
+                self.import = None;
+                self.toast_manager.push("Import discarded".to_string());
+            }
+            return action;
+        }
+
+        if matches!(key, Key::Esc) && self.wave_state.is_starting.get() {
+            self.key_resolver.reset();
+            return Action::CancelWave;
+        }
+
         let in_overlay = self.signals.navigation.overlay.get().is_some();
 
         if in_overlay {
@@ -988,6 +1579,11 @@ impl App {
                         return action;
                     }
                 }
+                Some(Route::Lyrics) => {
+                    if let Some(action) = self.lyrics.handle_key(&key) {
+                        return action;
+                    }
+                }
                 Some(_) | None => {}
             }
 
@@ -1004,7 +1600,19 @@ impl App {
         }
 
         let prefix = self.key_resolver.peek_prefix();
-        let view_action = self.dispatch_to_view(&key, prefix).await;
+        let view_action = if self.focus.is_focused(Pane::Sidebar) {
+            if prefix.is_some() {
+                Action::None
+            } else {
+                let action = self.sidebar.handle_key(&key);
+                if matches!(action, Action::Navigate(_)) {
+                    self.focus.focus(Pane::Content);
+                }
+                action
+            }
+        } else {
+            self.dispatch_to_view(&key, prefix).await
+        };
         if !view_action.is_none() {
             self.key_resolver.reset();
             return view_action;
@@ -1035,13 +1643,26 @@ impl App {
                     Action::None
                 }
             }
-            Route::Playlist { .. } | Route::Album { .. } | Route::Artist { .. } | Route::Queue => {
+            Route::Files => {
+                if let Some(view) = &mut self.file_browser {
+                    view.handle_key(key, prefix)
+                } else {
+                    Action::None
+                }
+            }
+            Route::Playlist { .. }
+            | Route::Album { .. }
+            | Route::Podcast { .. }
+            | Route::SmartPlaylist { .. }
+            | Route::Artist { .. }
+            | Route::Queue
+            | Route::Disliked => {
                 if let Some(view) = &mut self.track_list_view {
                     let action = view.handle_key(key, prefix);
                     if matches!(self.current_route, Route::Queue) {
                         let cursor = view.selected_index();
                         let audio = self.audio.clone();
-                        tokio::spawn(async move {
+                        self.tasks.spawn("queue_prefetch", async move {
                             audio.write().await.maybe_trigger_fetch(cursor);
                         });
                     }
@@ -1050,6 +1671,41 @@ impl App {
                     Action::None
                 }
             }
+            Route::Track { .. } => {
+                if let Some(view) = &mut self.track_detail_view {
+                    view.handle_key(key, prefix)
+                } else {
+                    Action::None
+                }
+            }
+            Route::ArtistAlbums { .. } => {
+                if let Some(view) = &mut self.artist_albums_view {
+                    view.handle_key(key, prefix)
+                } else {
+                    Action::None
+                }
+            }
+            Route::History => {
+                if let Some(view) = &mut self.history_view {
+                    view.handle_key(key, prefix)
+                } else {
+                    Action::None
+                }
+            }
+            Route::MostPlayed => {
+                if let Some(view) = &mut self.most_played_view {
+                    view.handle_key(key, prefix)
+                } else {
+                    Action::None
+                }
+            }
+            Route::Notifications => {
+                if let Some(view) = &mut self.notifications_view {
+                    view.handle_key(key, prefix)
+                } else {
+                    Action::None
+                }
+            }
             _ => Action::None,
         }
     }
@@ -1062,9 +1718,38 @@ impl App {
             Intent::View(v) => self.execute_view_intent(v),
             Intent::Queue(q) => self.execute_queue_intent(q),
             Intent::Effect(e) => self.execute_effect_intent(e),
+            Intent::Command(command) => self.expand_command(&command),
         }
     }
 
+    fn expand_command(&self, command: &str) -> Action {
+        let command = command.strip_prefix("exec ").unwrap_or(command).trim();
+        let track = self
+            .signals
+            .audio
+            .current_track
+            .get()
+            .or_else(|| self.current_selection_track());
+        let format = RowFormat::parse(command);
+        let env = track
+            .as_ref()
+            .map(|track| {
+                let ctx = RowContext {
+                    track,
+                    index: 0,
+                    liked: "",
+                    state: "",
+                };
+                format
+                    .fields()
+                    .map(|field| (field.env_var(), field.value(&ctx)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let command = format.render_with(|field| process::env_reference(&field.env_var()));
+        Action::RunCommand(command, env)
+    }
+
     fn execute_effect_intent(&self, intent: EffectIntent) -> Action {
         use EffectIntent::*;
         match intent {
@@ -1077,6 +1762,7 @@ impl App {
             ToggleBandpass => Action::ToggleEffect("bandpass".into()),
             ToggleNotch => Action::ToggleEffect("notch".into()),
             ToggleDcBlock => Action::ToggleEffect("dc_block".into()),
+            ToggleNightMode => Action::ToggleEffect("night_mode".into()),
             ToggleEqPreset(name) => Action::ToggleEqPreset(name),
         }
     }
@@ -1086,19 +1772,30 @@ impl App {
             PlaybackIntent::Toggle => Action::TogglePlayback,
             PlaybackIntent::Next => Action::NextTrack,
             PlaybackIntent::Previous => Action::PreviousTrack,
-            PlaybackIntent::SeekForward(s) => Action::SeekForward(s),
-            PlaybackIntent::SeekBackward(s) => Action::SeekBackward(s),
-            PlaybackIntent::VolumeUp(n) => {
-                let vol = self.signals.audio.volume.get();
-                Action::SetVolume((vol + n).min(100))
+            PlaybackIntent::SeekForward(step) => {
+                Action::SeekForward(config::get().controls.seek_step(step))
+            }
+            PlaybackIntent::SeekBackward(step) => {
+                Action::SeekBackward(config::get().controls.seek_step(step))
             }
-            PlaybackIntent::VolumeDown(n) => {
-                let vol = self.signals.audio.volume.get();
-                Action::SetVolume(vol.saturating_sub(n))
+            PlaybackIntent::VolumeUp(step) => {
+                Action::VolumeUp(config::get().controls.volume_step(step))
+            }
+            PlaybackIntent::VolumeDown(step) => {
+                Action::VolumeDown(config::get().controls.volume_step(step))
             }
             PlaybackIntent::ToggleMute => Action::ToggleMute,
             PlaybackIntent::ToggleShuffle => Action::ToggleShuffle,
             PlaybackIntent::CycleRepeat => Action::CycleRepeat,
+            PlaybackIntent::TrackGain(step) => Action::AdjustTrackGain(step as f32),
+            PlaybackIntent::StereoWidth(step) => {
+                Action::AdjustStereoWidth(step as f32 * STEREO_WIDTH_STEP)
+            }
+            PlaybackIntent::PodcastSpeed(step) => {
+                Action::AdjustPodcastSpeed(step as f32 * PODCAST_SPEED_STEP)
+            }
+            PlaybackIntent::Chapter(step) => Action::SeekChapter(step),
+            PlaybackIntent::Reload => Action::ReloadTrack,
             PlaybackIntent::Like(Target::Current) => self
                 .signals
                 .audio
@@ -1106,10 +1803,15 @@ impl App {
                 .get()
                 .map(Action::LikeTrack)
                 .unwrap_or(Action::None),
-            PlaybackIntent::Like(Target::Selected) => self
-                .current_selection_track()
-                .map(Action::LikeTrack)
-                .unwrap_or(Action::None),
+            PlaybackIntent::Like(Target::Selected) => {
+                let marked = self.current_marked_tracks();
+                if !marked.is_empty() {
+                    return Action::LikeTracks(marked);
+                }
+                self.current_selection_track()
+                    .map(Action::LikeTrack)
+                    .unwrap_or(Action::None)
+            }
             PlaybackIntent::Dislike(Target::Current) => self
                 .signals
                 .audio
@@ -1126,19 +1828,11 @@ impl App {
                 .audio
                 .current_track
                 .get()
-                .map(|t| Action::StartWave {
-                    seeds: vec![format!("track:{}", t.id)],
-                    title: t.title.clone(),
-                    toast_message: None,
-                })
+                .map(|t| Action::wave_for_track(&t))
                 .unwrap_or(Action::None),
             PlaybackIntent::StartWave(Target::Selected) => self
                 .current_selection_track()
-                .map(|t| Action::StartWave {
-                    seeds: vec![format!("track:{}", t.id)],
-                    title: t.title.clone(),
-                    toast_message: None,
-                })
+                .map(|t| Action::wave_for_track(&t))
                 .unwrap_or(Action::None),
         }
     }
@@ -1149,66 +1843,213 @@ impl App {
             ViewIntent::Dislike => Action::DislikeContext,
             ViewIntent::QueueAll => Action::QueueAll,
             ViewIntent::PlayAllNext => Action::PlayAllNext,
+            ViewIntent::PlayDiscography => Action::PlayDiscography,
+            ViewIntent::Refresh => Action::Refresh,
             ViewIntent::StartWave => match &self.current_route {
-                Route::Album { id, title } => Action::StartWave {
-                    seeds: vec![format!("album:{id}")],
-                    title: Some(title.clone()),
-                    toast_message: None,
-                },
-                Route::Artist { id, name } => Action::StartWave {
-                    seeds: vec![format!("artist:{id}")],
-                    title: Some(name.clone()),
-                    toast_message: None,
-                },
-                Route::Playlist { kind, title } => {
-                    let owner = self.api.current_user_id();
-                    let owner = self
-                        .track_list_view
-                        .as_ref()
-                        .and_then(|view| {
-                            if let TrackListContext::Playlist { owner_uid, .. } = view.context() {
-                                if *owner_uid > 0 {
-                                    Some(*owner_uid)
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap_or(owner);
-
+                Route::Album { id, title } => {
+                    Action::wave(format!("album:{id}"), Some(title.clone()))
+                }
+                Route::Artist { id, name } => {
+                    Action::wave(format!("artist:{id}"), Some(name.clone()))
+                }
+                Route::Playlist { kind, title, .. } => {
+                    let owner = self.current_playlist_owner();
+                    let mut seeds = vec![format!("playlist:{owner}_{kind}")];
+                    let lead = match (self.current_view_tracks(), self.current_selection_index()) {
+                        (Some(tracks), Some(index)) if index < tracks.len() => {
+                            seeds.push(format!("track:{}", tracks[index].id));
+                            tracks.into_iter().skip(index).collect()
+                        }
+                        _ => Vec::new(),
+                    };
                     Action::StartWave {
-                        seeds: vec![format!("playlist:{owner}_{kind}")],
+                        seeds,
                         title: Some(title.clone()),
                         toast_message: None,
+                        lead,
                     }
                 }
-                _ => Action::StartWave {
-                    seeds: vec!["user:onyourwave".to_string()],
-                    title: None,
-                    toast_message: None,
-                },
+                _ => Action::wave("user:onyourwave".to_string(), None),
             },
+            ViewIntent::Share(Target::Current) => self
+                .signals
+                .audio
+                .current_track
+                .get()
+                .map(|t| Action::CopyToClipboard(link::track_url(&t)))
+                .unwrap_or(Action::None),
+            ViewIntent::Share(Target::Selected) => self
+                .current_selection_url()
+                .map(Action::CopyToClipboard)
+                .unwrap_or(Action::None),
+            ViewIntent::ShareContext => self
+                .current_context_url()
+                .map(Action::CopyToClipboard)
+                .unwrap_or(Action::None),
+            ViewIntent::ImportPlaylist => Action::OpenModal(ModalRequest::Input {
+                title: "Import playlist".to_string(),
+                prompt: "Path to a CSV or \"artist - title\" text file".to_string(),
+                initial: String::new(),
+                on_submit: Arc::new(Action::ImportPlaylist),
+            }),
+            ViewIntent::Export => {
+                if self.current_view_tracks().is_none() {
+                    return Action::None;
+                }
+                Action::OpenModal(ModalRequest::Select {
+                    title: "Export tracks".to_string(),
+                    options: ExportFormat::ALL
+                        .iter()
+                        .map(|f| f.label().to_string())
+                        .collect(),
+                    selected: 0,
+                    on_select: Arc::new(|i| Action::ExportTracks(ExportFormat::ALL[i])),
+                })
+            }
+            ViewIntent::TrackDetail(Target::Current) => self
+                .signals
+                .audio
+                .current_track
+                .get()
+                .map(|t| Action::Navigate(Route::Track { id: t.id }))
+                .unwrap_or(Action::None),
+            ViewIntent::TrackDetail(Target::Selected) => self
+                .current_selection_track()
+                .map(|t| Action::Navigate(Route::Track { id: t.id }))
+                .unwrap_or(Action::None),
+            ViewIntent::FocusSearch { prefill } => Action::FocusSearch(
+                prefill
+                    .then(|| {
+                        self.current_selection_track()
+                            .or_else(|| self.signals.audio.current_track.get())
+                    })
+                    .flatten()
+                    .and_then(|t| t.artists.first().and_then(|a| a.name.clone())),
+            ),
+            ViewIntent::ToggleProfiler => Action::ToggleProfiler,
+            ViewIntent::PickOutput => Action::DiscoverOutputs,
+            ViewIntent::RecentContexts => {
+                let contexts = self
+                    .signals
+                    .library
+                    .play_history
+                    .with(|h| h.recent_contexts(RECENT_CONTEXTS_LIMIT));
+                if contexts.is_empty() {
+                    return Action::Toast("Nothing played from a context yet".to_string());
+                }
+                let now = clock::now();
+                let options = contexts
+                    .iter()
+                    .map(|(context, played_at)| {
+                        (
+                            context.name().to_string(),
+                            format!("{} · {}", context.kind(), clock::relative(*played_at, now)),
+                        )
+                    })
+                    .collect();
+                let contexts: Vec<HistoryContext> = contexts.into_iter().map(|(c, _)| c).collect();
+                Action::OpenModal(ModalRequest::FuzzySelect {
+                    title: "Recently played".to_string(),
+                    options,
+                    on_select: Arc::new(move |i| context_action(contexts[i].clone())),
+                })
+            }
+            ViewIntent::OpenInBrowser => self
+                .current_selection_url()
+                .or_else(|| self.current_context_url())
+                .or_else(|| {
+                    self.signals
+                        .audio
+                        .current_track
+                        .get()
+                        .map(|t| link::track_url(&t))
+                })
+                .map(Action::OpenUrl)
+                .unwrap_or(Action::None),
+        }
+    }
+
+    pub(super) fn current_playlist_owner(&self) -> u64 {
+        self.track_list_view
+            .as_ref()
+            .and_then(|view| match view.context() {
+                TrackListContext::Playlist { owner_uid, .. } if *owner_uid > 0 => Some(*owner_uid),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.api.current_user_id())
+    }
+
+    fn current_selection_url(&self) -> Option<String> {
+        match &self.current_route {
+            Route::Search => self.search_view.selected_share_url(),
+            Route::Playlists => self
+                .playlist_list_view
+                .as_ref()?
+                .selected_item()
+                .map(|p| link::playlist_url(p.owner.uid, p.kind)),
+            Route::ArtistAlbums { .. } => self
+                .artist_albums_view
+                .as_ref()?
+                .selected_item()
+                .and_then(|a| a.id)
+                .map(link::album_url),
+            _ => self.current_selection_track().map(|t| link::track_url(&t)),
+        }
+    }
+
+    fn current_context_url(&self) -> Option<String> {
+        match &self.current_route {
+            Route::Album { id, .. } | Route::Podcast { id, .. } => Some(link::album_url(id)),
+            Route::Artist { id, .. } | Route::ArtistAlbums { id, .. } => Some(link::artist_url(id)),
+            Route::Playlist { kind, .. } => {
+                Some(link::playlist_url(self.current_playlist_owner(), *kind))
+            }
+            Route::Liked => Some(link::playlist_url(self.api.current_user_id(), 3)),
+            _ => None,
         }
     }
 
     fn execute_queue_intent(&self, intent: QueueIntent) -> Action {
         match intent {
-            QueueIntent::Add => self
-                .current_selection_track()
-                .map(Action::QueueTrack)
-                .unwrap_or(Action::None),
+            QueueIntent::Add => {
+                let marked = self.current_marked_tracks();
+                if !marked.is_empty() {
+                    return Action::QueueTracks(marked);
+                }
+                self.current_selection_track()
+                    .map(Action::QueueTrack)
+                    .unwrap_or(Action::None)
+            }
+            QueueIntent::AddToPlaylist => self.pick_playlist_for_tracks(),
+            QueueIntent::AddLocal => Action::OpenModal(ModalRequest::Input {
+                title: "Queue local files".to_string(),
+                prompt: "Path to an audio file or folder".to_string(),
+                initial: String::new(),
+                on_submit: Arc::new(Action::QueueLocal),
+            }),
             QueueIntent::PlayNext => self
                 .current_selection_track()
                 .map(Action::PlayNext)
                 .unwrap_or(Action::None),
+            QueueIntent::Download => {
+                let marked = self.current_marked_tracks();
+                if !marked.is_empty() {
+                    return Action::DownloadTracks(marked);
+                }
+                self.current_selection_track()
+                    .map(|track| Action::DownloadTracks(vec![track]))
+                    .unwrap_or(Action::None)
+            }
             QueueIntent::Remove => self
                 .track_list_view
                 .as_ref()
                 .map(|v| Action::RemoveFromQueue(v.selected_index()))
                 .unwrap_or(Action::None),
-            QueueIntent::Clear => Action::ClearQueue,
+            QueueIntent::Clear => Action::OpenModal(ModalRequest::confirm(
+                "Clear queue",
+                "Remove all upcoming tracks from the queue?",
+                Action::ClearQueue,
+            )),
         }
     }
 
@@ -1242,6 +2083,16 @@ impl App {
                 };
                 Action::Navigate(prev)
             }
+            NavigationIntent::FocusNext => {
+                self.focus.next();
+                self.sync_sidebar_cursor();
+                Action::Redraw
+            }
+            NavigationIntent::FocusPrev => {
+                self.focus.prev();
+                self.sync_sidebar_cursor();
+                Action::Redraw
+            }
             NavigationIntent::ShowOverlay(route) => Action::Overlay(route),
             NavigationIntent::DismissOverlay => Action::DismissOverlay,
             NavigationIntent::ScrollTop => Action::ScrollTop,
@@ -1249,17 +2100,112 @@ impl App {
         }
     }
 
+    fn sync_sidebar_cursor(&mut self) {
+        if self.focus.is_focused(Pane::Sidebar) {
+            self.sidebar.sync_cursor(&self.current_route);
+        }
+    }
+
+    pub(super) fn current_view_tracks(&self) -> Option<Vector<yandex_music::model::track::Track>> {
+        match &self.current_route {
+            Route::Playlist { .. }
+            | Route::Album { .. }
+            | Route::Podcast { .. }
+            | Route::SmartPlaylist { .. }
+            | Route::Artist { .. }
+            | Route::Queue
+            | Route::Disliked => Some(self.track_list_view.as_ref()?.items()),
+            Route::Liked => Some(self.liked_view.as_ref()?.items()),
+            Route::Files => Some(self.file_browser.as_ref()?.items()),
+            Route::MostPlayed => Some(self.most_played_view.as_ref()?.items()),
+            _ => None,
+        }
+    }
+
+    fn current_marked_tracks(&self) -> Vec<yandex_music::model::track::Track> {
+        match &self.current_route {
+            Route::Playlist { .. }
+            | Route::Album { .. }
+            | Route::Podcast { .. }
+            | Route::SmartPlaylist { .. }
+            | Route::Artist { .. }
+            | Route::Queue
+            | Route::Disliked => self.track_list_view.as_ref().map(|v| v.marked_items()),
+            Route::Liked => self.liked_view.as_ref().map(|v| v.marked_items()),
+            _ => None,
+        }
+        .unwrap_or_default()
+    }
+
+    pub(super) fn clear_marks(&mut self) {
+        if let Some(view) = &mut self.track_list_view {
+            view.clear_marks();
+        }
+        if let Some(view) = &mut self.liked_view {
+            view.clear_marks();
+        }
+    }
+
+    fn pick_playlist_for_tracks(&self) -> Action {
+        let mut tracks = self.current_marked_tracks();
+        if tracks.is_empty() {
+            tracks.extend(self.current_selection_track());
+        }
+        if tracks.is_empty() {
+            return Action::None;
+        }
+
+        let uid = self.api.current_user_id();
+        let playlists: Vec<_> = self
+            .signals
+            .library
+            .playlists
+            .value()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| p.uid == uid)
+            .collect();
+        if playlists.is_empty() {
+            return Action::Toast("You don't have any playlists yet".to_string());
+        }
+
+        let options = playlists
+            .iter()
+            .map(|p| (p.title.clone(), format!("{} tracks", p.track_count)))
+            .collect();
+        Action::OpenModal(ModalRequest::FuzzySelect {
+            title: format!("Add to playlist ({} tracks)", tracks.len()),
+            options,
+            on_select: Arc::new(move |i| Action::AddToPlaylist {
+                kind: playlists[i].kind,
+                title: playlists[i].title.clone(),
+                tracks: tracks.clone(),
+            }),
+        })
+    }
+
     fn current_selection_track(&self) -> Option<yandex_music::model::track::Track> {
         match &self.current_route {
-            Route::Playlist { .. } | Route::Album { .. } | Route::Artist { .. } | Route::Queue => {
-                self.track_list_view.as_ref()?.selected_item()
-            }
+            Route::Playlist { .. }
+            | Route::Album { .. }
+            | Route::Podcast { .. }
+            | Route::SmartPlaylist { .. }
+            | Route::Artist { .. }
+            | Route::Queue
+            | Route::Disliked => self.track_list_view.as_ref()?.selected_item(),
             Route::Liked => self.liked_view.as_ref()?.selected_item(),
+            Route::Files => self.file_browser.as_ref()?.selected_track(),
+            Route::Track { .. } => self.track_detail_view.as_ref()?.selected_track(),
+            Route::History => self.history_view.as_ref()?.selected_track(),
+            Route::MostPlayed => self.most_played_view.as_ref()?.selected_track(),
             _ => None,
         }
     }
 
     pub fn view(&mut self, frame: &mut Frame) {
+        let frame_started = std::time::Instant::now();
+        let _span =
+            tracing::trace_span!("render_frame", route = %self.current_route.title()).entered();
         tick_global();
 
         let area = frame.area();
@@ -1268,13 +2214,22 @@ impl App {
         let text_style = ratatui::style::Style::default()
             .fg(colors.text.primary)
             .bg(colors.bg.base);
-        let block = colors.unfocused_border;
-        let block_focused = colors.focused_border;
         frame.buffer_mut().set_style(area, text_style);
 
+        if self.screensaver.get() {
+            let track = self.signals.audio.current_track.get();
+            screensaver::render(frame, &self.visualizer, track.as_ref());
+            self.profiler.record("Screensaver", frame_started.elapsed());
+            self.profiler.view(frame, area);
+            self.profiler
+                .finish_frame(frame_started, self.event_rx.len());
+            return;
+        }
+
         let popup_open =
             matches!(self.current_route, Route::Home) && self.home_view.is_popup_open();
-        let bg_border = if popup_open { block } else { block_focused };
+        let sidebar_border = self.focus.border_style(Pane::Sidebar, popup_open);
+        let content_border = self.focus.border_style(Pane::Content, popup_open);
 
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -1287,14 +2242,19 @@ impl App {
                 .constraints([Constraint::Length(25), Constraint::Min(0)])
                 .split(main_chunks[0]);
 
-            self.sidebar
-                .view(frame, horizontal[0], &self.current_route, bg_border);
+            self.sidebar.view(
+                frame,
+                horizontal[0],
+                &self.current_route,
+                sidebar_border,
+                self.focus.is_focused(Pane::Sidebar),
+            );
 
             use ratatui::symbols::{self, border};
             use ratatui::widgets::{Block, Borders};
             let content_block = Block::default()
                 .borders(Borders::ALL)
-                .border_style(bg_border)
+                .border_style(content_border)
                 .border_set(border::Set {
                     ..symbols::border::ROUNDED
                 });
@@ -1306,11 +2266,15 @@ impl App {
             main_chunks[0]
         };
 
+        let view_started = std::time::Instant::now();
         match &self.current_route {
             Route::Home => {
                 self.visualizer.set_full_blocks(popup_open);
                 if self.signals.is_focused.get() {
+                    let visualizer_started = std::time::Instant::now();
                     self.visualizer.view(frame, content_area);
+                    self.profiler
+                        .record("Visualizer", visualizer_started.elapsed());
                 }
                 self.home_view.view(frame, content_area);
             }
@@ -1325,16 +2289,86 @@ impl App {
                     view.view(frame, content_area);
                 }
             }
-            Route::Playlist { .. } | Route::Album { .. } | Route::Artist { .. } | Route::Queue => {
+            Route::Files => {
+                if let Some(view) = &mut self.file_browser {
+                    view.view(frame, content_area);
+                }
+            }
+            Route::Playlist { .. }
+            | Route::Album { .. }
+            | Route::Podcast { .. }
+            | Route::SmartPlaylist { .. }
+            | Route::Artist { .. }
+            | Route::Queue
+            | Route::Disliked => {
                 if let Some(view) = &mut self.track_list_view {
                     view.view(frame, content_area);
                 }
             }
+            Route::Track { .. } => {
+                if let Some(view) = &mut self.track_detail_view {
+                    view.view(frame, content_area);
+                }
+            }
+            Route::ArtistAlbums { .. } => {
+                if let Some(view) = &mut self.artist_albums_view {
+                    view.view(frame, content_area);
+                }
+            }
+            Route::History => {
+                if let Some(view) = &mut self.history_view {
+                    view.view(frame, content_area);
+                }
+            }
+            Route::MostPlayed => {
+                if let Some(view) = &mut self.most_played_view {
+                    view.view(frame, content_area);
+                }
+            }
+            Route::Notifications => {
+                if let Some(view) = &mut self.notifications_view {
+                    view.view(frame, content_area);
+                }
+            }
             _ => {}
         }
+        if self.profiler.is_enabled() {
+            let name = match &self.current_route {
+                Route::Track { .. } => "Track".to_string(),
+                Route::SmartPlaylist { .. } => "Track list".to_string(),
+                route if route.history_context().is_some() => "Track list".to_string(),
+                route => route.title(),
+            };
+            self.profiler.record(&name, view_started.elapsed());
+        }
+
+        if self.wave_state.is_starting.get() {
+            let label = "Starting wave... (Esc to cancel)";
+            let width = (label.chars().count() as u16 + 2).min(content_area.width);
+            let spinner_area = Rect {
+                x: content_area.right().saturating_sub(width),
+                y: content_area.y,
+                width,
+                height: 1,
+            };
+            Spinner::new().with_label(label).view(frame, spinner_area);
+        }
 
         self.player_bar.view(frame, main_chunks[1]);
 
+        if let Some(wait) = throttle::remaining() {
+            let label = format!(" 󰔟 API throttled, retrying in {} s ", wait.as_secs() + 1);
+            let bar = main_chunks[1];
+            let width = (label.chars().count() as u16).min(bar.width.saturating_sub(4));
+            frame.buffer_mut().set_stringn(
+                bar.right().saturating_sub(width + 2),
+                bar.y,
+                &label,
+                width as usize,
+                colors.warning_style,
+            );
+        }
+
         if let Some(overlay) = self.signals.navigation.overlay.get() {
             OverlayRenderer::render(
                 frame,
@@ -1343,17 +2377,30 @@ impl App {
                 &mut self.lyrics,
                 &mut self.effects_overlay,
                 &mut self.theme_picker,
+                &self.tasks,
             );
         }
 
+        self.modals.view(frame, area);
         self.toast_manager.view(frame, area);
+        self.profiler.view(frame, content_area);
+        self.profiler
+            .finish_frame(frame_started, self.event_rx.len());
+    }
+}
+
+fn format_position(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs / 3600 {
+        0 => format!("{}:{:02}", secs / 60, secs % 60),
+        hours => format!("{hours}:{:02}:{:02}", secs / 60 % 60, secs % 60),
     }
 }
 
 fn is_top_level(route: &Route) -> bool {
     matches!(
         route,
-        Route::Home | Route::Search | Route::Liked | Route::Playlists
+        Route::Home | Route::Search | Route::Liked | Route::Playlists | Route::Files
     )
 }
 
@@ -1367,16 +2414,30 @@ impl App {
         self.picker = None;
 
         terminal.start();
+        self.restore_startup_view().await;
+
+        self.tasks.spawn(
+            "signals",
+            crate::event::signals::listen(self.event_tx.clone()),
+        );
+
+        if config::get().library.watch
+            && let Some(dir) = fs::user_music_dir()
+        {
+            crate::event::watcher::watch(dir, self.event_tx.clone(), &self.tasks);
+        }
 
         let tick_tx = terminal.tick_tx.clone();
         let current_route = self.signals.navigation.current_route.clone();
         let overlay = self.signals.navigation.overlay.clone();
         let animating = self.toast_manager.is_animating();
         let focused = self.signals.is_focused.clone();
+        let screensaver = self.screensaver.clone();
 
         crate::framework::reactive::effect(move || {
             let route = current_route.get();
             let overlay = overlay.get();
+            let screensaver = screensaver.get();
             let animating = animating.get();
             let focused = focused.get();
 
@@ -1384,6 +2445,8 @@ impl App {
                 TickRate::Idle
             } else if animating {
                 TickRate::Animation
+            } else if screensaver {
+                TickRate::High
             } else if overlay.is_some() {
                 TickRate::Normal
             } else {
@@ -1396,6 +2459,113 @@ impl App {
             let _ = tick_tx.send(rate);
         });
 
+        let result = self.event_loop(&mut terminal).await;
+
+        let _ = terminal.exit();
+        self.shutdown().await;
+
+        result
+    }
+
+    async fn check_focus_pause(&mut self) {
+        let Some(lost_at) = self.focus_lost_at else {
+            return;
+        };
+        let config = config::get();
+        if !config.playback.pause_on_focus_loss
+            || lost_at.elapsed() < Duration::from_secs(config.playback.focus_loss_pause_secs)
+        {
+            return;
+        }
+
+        self.focus_lost_at = None;
+        if self.signals.audio.is_playing.get() {
+            self.auto_paused = true;
+            self.process_action(Action::TogglePlayback).await;
+        }
+    }
+
+    async fn restore_startup_view(&mut self) {
+        match config::get().startup.view {
+            StartupView::Home => {}
+            StartupView::Liked => self.navigate(Route::Liked).await,
+            StartupView::Last => {
+                let Some(session) = SessionState::load() else {
+                    return;
+                };
+                let mut routes = session.routes;
+                let mut selections = session.selections;
+                selections.resize(routes.len(), None);
+                let Some(current) = routes.pop() else {
+                    return;
+                };
+                let selection = selections.pop().flatten();
+                if current != self.current_route {
+                    self.navigate(current).await;
+                }
+                self.signals.navigation.history.set(Vector::from(routes));
+                self.route_selections = selections;
+                self.sync_sidebar_cursor();
+                self.pending_selection = selection;
+            }
+        }
+    }
+
+    fn apply_pending_selection(&mut self) {
+        let Some(index) = self.pending_selection else {
+            return;
+        };
+        let restored = match &self.current_route {
+            Route::Playlist { .. }
+            | Route::Album { .. }
+            | Route::Podcast { .. }
+            | Route::SmartPlaylist { .. }
+            | Route::Artist { .. }
+            | Route::Queue
+            | Route::Disliked => self
+                .track_list_view
+                .as_mut()
+                .map(|v| v.restore_selection(index)),
+            Route::Liked => self.liked_view.as_mut().map(|v| v.restore_selection(index)),
+            Route::Playlists => self
+                .playlist_list_view
+                .as_mut()
+                .map(|v| v.restore_selection(index)),
+            _ => None,
+        };
+        if restored != Some(false) {
+            self.pending_selection = None;
+        }
+    }
+
+    fn current_selection_index(&self) -> Option<usize> {
+        match &self.current_route {
+            Route::Playlist { .. }
+            | Route::Album { .. }
+            | Route::Podcast { .. }
+            | Route::SmartPlaylist { .. }
+            | Route::Artist { .. }
+            | Route::Queue
+            | Route::Disliked => Some(self.track_list_view.as_ref()?.selected_index()),
+            Route::Liked => Some(self.liked_view.as_ref()?.selected_index()),
+            Route::Playlists => Some(self.playlist_list_view.as_ref()?.selected_index()),
+            _ => None,
+        }
+    }
+
+    fn check_idle(&mut self) {
+        let config = config::get();
+        if !config.screensaver.enabled || self.screensaver.get() || self.modals.is_open() {
+            return;
+        }
+        let idle = Duration::from_secs(config.screensaver.idle_minutes.max(1) * 60);
+        if self.last_input_at.elapsed() >= idle {
+            self.screensaver.set(true);
+            self.update_bridge_state();
+        }
+    }
+
+    async fn event_loop(&mut self, terminal: &mut Terminal) -> color_eyre::Result<()> {
         loop {
             tokio::select! {
                 Some(term_event) = terminal.next() => {
@@ -1405,14 +2575,29 @@ impl App {
                             self.process_action(action).await;
                         }
                         TerminalEvent::Tick => {
+                            self.tasks.cleanup();
+                            self.sync_now_playing();
+                            self.sync_episode_progress(false);
+                            self.sync_context_position(false);
+                            self.check_idle();
+                            self.apply_pending_selection();
+                            self.publish_listen_along();
+                            self.check_focus_pause().await;
                         }
                         TerminalEvent::FocusLost => {
                             self.signals.is_focused.set(false);
+                            self.focus_lost_at = Some(std::time::Instant::now());
                             self.update_bridge_state();
                         }
                         TerminalEvent::FocusGained => {
                             terminal.backend_mut().flush().unwrap();
                             self.signals.is_focused.set(true);
+                            self.focus_lost_at = None;
+                            if std::mem::take(&mut self.auto_paused)
+                                && !self.signals.audio.is_playing.get()
+                            {
+                                self.process_action(Action::TogglePlayback).await;
+                            }
                             self.update_bridge_state();
                         }
                         TerminalEvent::Resize(_, _) => {
@@ -1440,4 +2625,81 @@ impl App {
 
         Ok(())
     }
+
+    pub fn invoke(&self, args: Vec<String>) {
+        let _ = self.event_tx.send(Event::Forwarded(args));
+    }
+
+    pub fn serve_instance(&self, listener: InstanceListener) {
+        self.tasks
+            .spawn("instance", instance::serve(listener, self.event_tx.clone()));
+    }
+
+    fn session_snapshot(&self) -> SessionState {
+        let audio = &self.signals.audio;
+        SessionState {
+            queue: audio.queue.with(|q| q.iter().map(QueueItem::id).collect()),
+            queue_index: audio.queue_index.get(),
+            position_ms: audio.position_ms.get(),
+            volume: audio.volume.get(),
+            is_shuffled: audio.is_shuffled.get(),
+            repeat_mode: audio.repeat_mode.get(),
+            routes: self
+                .signals
+                .navigation
+                .history
+                .get()
+                .into_iter()
+                .chain(std::iter::once(self.current_route.clone()))
+                .collect(),
+            selections: self
+                .route_selections
+                .iter()
+                .copied()
+                .chain(std::iter::once(self.current_selection_index()))
+                .collect(),
+        }
+    }
+
+    async fn shutdown(&mut self) {
+        self.signals.library.flush_play_stats();
+        self.sync_episode_progress(true);
+        self.sync_context_position(true);
+        let session = self.session_snapshot();
+        let audio = self.audio.clone();
+        let tasks = self.tasks.clone();
+
+        let shutdown = async move {
+            if let Err(e) = session.save() {
+                tracing::warn!(error = %e, "session_save_failed");
+            }
+            audio.write().await.stop().await;
+            tasks.abort_all();
+        };
+
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, shutdown)
+            .await
+            .is_err()
+        {
+            tracing::warn!("shutdown_timed_out");
+        }
+    }
+}
+
+fn context_action(context: HistoryContext) -> Action {
+    match context {
+        HistoryContext::Playlist { kind, owner, title } => {
+            Action::Navigate(Route::Playlist { kind, owner, title })
+        }
+        HistoryContext::Album { id, title } => Action::Navigate(Route::Album { id, title }),
+        HistoryContext::Podcast { id, title } => Action::Navigate(Route::Podcast { id, title }),
+        HistoryContext::Artist { id, name } => Action::Navigate(Route::Artist { id, name }),
+        HistoryContext::Wave { seeds, title } => Action::StartWave {
+            seeds,
+            title,
+            toast_message: None,
+            lead: Vec::new(),
+        },
+        HistoryContext::Folder { path, .. } => Action::OpenFolder(path.into()),
+    }
 }
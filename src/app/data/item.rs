@@ -9,6 +9,7 @@ use crate::app::theme::theme;
 pub struct ListItem<'a> {
     pub content: Vec<Line<'a>>,
     pub prefix_lines: Option<Vec<Line<'a>>>,
+    pub header: Option<Line<'a>>,
     pub height: u16,
     pub style: Style,
     pub cover_url: Option<String>,
@@ -19,6 +20,7 @@ impl<'a> ListItem<'a> {
         Self {
             content: vec![Line::from(text.into())],
             prefix_lines: None,
+            header: None,
             height: 1,
             style: Style::default(),
             cover_url: None,
@@ -33,6 +35,7 @@ impl<'a> ListItem<'a> {
                 Line::from(Span::styled(subtitle.into(), muted_style)),
             ],
             prefix_lines: None,
+            header: None,
             height: 2,
             style: Style::default(),
             cover_url: None,
@@ -44,6 +47,7 @@ impl<'a> ListItem<'a> {
         Self {
             content: lines,
             prefix_lines: None,
+            header: None,
             height,
             style: Style::default(),
             cover_url: None,
@@ -65,6 +69,14 @@ impl<'a> ListItem<'a> {
         self
     }
 
+    pub fn with_header(mut self, header: Line<'a>) -> Self {
+        if self.header.is_none() {
+            self.height += 1;
+        }
+        self.header = Some(header);
+        self
+    }
+
     pub fn bold(mut self) -> Self {
         self.style = self.style.add_modifier(Modifier::BOLD);
         self
@@ -7,5 +7,6 @@ pub use item::{ItemRenderer, ListItem, MatchHighlights, SearchScope};
 pub use providers::*;
 pub use source::{DataChunk, DataSource, FetchState, SignalDataSource, StaticDataSource};
 pub use sources::{
-    AlbumTracksSource, ArtistTracksSource, LikedTracksSource, PlaylistInfo, PlaylistTracksSource,
+    AlbumFilter, AlbumInfo, AlbumTracksSource, ArtistAlbumsSource, ArtistTracksSource,
+    DislikedTracksSource, LikedTracksSource, PlaylistInfo, PlaylistTracksSource, RemoteChange,
 };
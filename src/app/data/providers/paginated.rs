@@ -96,6 +96,36 @@ where
         self.changed.update(|v| *v += 1);
     }
 
+    pub fn prepend_items(&self, ids: Vec<Id>, items: Vec<Item>) {
+        let count = ids.len();
+        {
+            let mut all_ids = self.all_ids.write().unwrap();
+            all_ids.splice(0..0, ids);
+            let mut items_lock = self.items.write().unwrap();
+            for item in items.into_iter().rev() {
+                items_lock.push_front(item);
+            }
+            *self.loaded_count.write().unwrap() += count;
+        }
+        self.changed.update(|v| *v += 1);
+    }
+
+    pub fn retain(&self, keep_id: impl Fn(&Id) -> bool, keep_item: impl Fn(&Item) -> bool) {
+        {
+            let mut all_ids = self.all_ids.write().unwrap();
+            let mut loaded_count = self.loaded_count.write().unwrap();
+            let removed = all_ids
+                .iter()
+                .take(*loaded_count)
+                .filter(|id| !keep_id(id))
+                .count();
+            all_ids.retain(|id| keep_id(id));
+            *loaded_count -= removed;
+            self.items.write().unwrap().retain(|item| keep_item(item));
+        }
+        self.changed.update(|v| *v += 1);
+    }
+
     pub fn has_more(&self) -> bool {
         let loaded = *self.loaded_count.read().unwrap();
         let total = self.all_ids.read().unwrap().len();
@@ -4,16 +4,17 @@ use im::Vector;
 use yandex_music::model::track::Track;
 
 use super::super::{DataSource, FetchState};
+use crate::audio::local::QueueItem;
 use crate::framework::reactive::{Update, With, create_effect};
 use crate::framework::signals::Signal;
 
 pub struct QueueDataSource {
-    queue: Signal<Vector<Track>>,
+    queue: Signal<Vector<QueueItem>>,
     changed: Signal<u64>,
 }
 
 impl QueueDataSource {
-    pub fn new(queue: Signal<Vector<Track>>) -> Self {
+    pub fn new(queue: Signal<Vector<QueueItem>>) -> Self {
         let changed: Signal<u64> = Signal::new(0);
 
         create_effect({
@@ -38,7 +39,12 @@ impl DataSource<Track> for QueueDataSource {
         self.queue.with(|queue| {
             let start = range.start.min(queue.len());
             let end = range.end.min(queue.len());
-            queue.clone().slice(start..end)
+            queue
+                .iter()
+                .skip(start)
+                .take(end - start)
+                .map(QueueItem::to_track)
+                .collect()
         })
     }
 
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::ops::Range;
 use std::sync::Arc;
 
@@ -6,7 +7,10 @@ use yandex_music::model::track::Track;
 
 use super::super::{DataSource, FetchState};
 use super::paginated::PaginatedDataSource;
-use crate::{framework::signals::Signal, http::ApiService};
+use crate::{
+    framework::{signals::Signal, tasks::TaskScope},
+    http::MusicApi,
+};
 
 const PAGE_SIZE: usize = 50;
 
@@ -17,7 +21,7 @@ pub struct TrackDataSource {
 }
 
 impl TrackDataSource {
-    pub fn new(source_id: u32, api: Arc<ApiService>) -> Self {
+    pub fn new(source_id: u32, api: Arc<dyn MusicApi>, tasks: TaskScope) -> Self {
         let inner = PaginatedDataSource::new(PAGE_SIZE, |_batch: Vec<String>, _end: usize| {});
 
         let inner_clone = inner.clone();
@@ -25,7 +29,7 @@ impl TrackDataSource {
             let api = api.clone();
             let inner = inner_clone.clone();
 
-            tokio::spawn(async move {
+            tasks.spawn_concurrent("fetch_tracks", async move {
                 match api.fetch_tracks_by_ids(batch).await {
                     Ok(tracks) => {
                         let tracks: Vec<_> = tracks
@@ -62,6 +66,18 @@ impl TrackDataSource {
         self.inner.append_items(new_tracks, new_loaded_count);
     }
 
+    pub fn prepend_tracks(&self, tracks: Vec<Track>) {
+        let ids = tracks.iter().map(|t| t.id.clone()).collect();
+        self.inner.prepend_items(ids, tracks);
+    }
+
+    pub fn remove_tracks(&self, track_ids: &HashSet<String>) {
+        self.inner.retain(
+            |id| !track_ids.contains(id.split(':').next().unwrap_or(id)),
+            |track| !track_ids.contains(&track.id),
+        );
+    }
+
     pub fn has_more(&self) -> bool {
         self.inner.has_more()
     }
@@ -2,6 +2,7 @@ use std::ops::Range;
 
 use im::Vector;
 
+use crate::framework::reactive::{Update, With, create_effect};
 use crate::framework::signals::Signal;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -140,10 +141,18 @@ pub struct SignalDataSource<T> {
 
 impl<T: Clone + Send + Sync + 'static> SignalDataSource<T> {
     pub fn new(items: Signal<Vector<T>>) -> Self {
-        Self {
-            items,
-            changed: Signal::new(0),
-        }
+        let changed: Signal<u64> = Signal::new(0);
+
+        create_effect({
+            let items = items.clone();
+            let changed = changed.clone();
+            move |_| {
+                let _len = With::with(&items, |items| items.len());
+                Update::update(&changed, |v| *v += 1);
+            }
+        });
+
+        Self { items, changed }
     }
 }
 
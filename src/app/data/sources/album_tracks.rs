@@ -1,23 +1,69 @@
 use std::ops::Range;
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use im::Vector;
-use yandex_music::model::track::Track;
+use yandex_music::model::{
+    album::{Album, Label, TrackPosition},
+    track::Track,
+};
 
 use super::super::{DataSource, FetchState};
 use crate::framework::reactive::{Resource, ResourceState, Update, create_effect, signal};
 use crate::framework::signals::Signal;
-use crate::http::ApiService;
+use crate::http::MusicApi;
+
+#[derive(Debug, Clone, Default)]
+pub struct AlbumInfo {
+    pub artists: String,
+    pub year: Option<i32>,
+    pub release_date: Option<DateTime<Utc>>,
+    pub label: Option<String>,
+    pub track_count: usize,
+    pub volumes: usize,
+    pub cover_uri: Option<String>,
+}
+
+impl From<&Album> for AlbumInfo {
+    fn from(album: &Album) -> Self {
+        Self {
+            artists: album
+                .artists
+                .iter()
+                .filter_map(|a| a.name.as_deref())
+                .collect::<Vec<_>>()
+                .join(", "),
+            year: album.year.map(i32::from),
+            release_date: album.release_date,
+            label: (!album.labels.is_empty()).then(|| {
+                album
+                    .labels
+                    .iter()
+                    .map(|l| match l {
+                        Label::TrackLabel(label) => label.name.as_str(),
+                        Label::String(name) => name.as_str(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+            track_count: album.volumes.iter().map(Vec::len).sum(),
+            volumes: album.volumes.len(),
+            cover_uri: album.cover_uri.clone(),
+        }
+    }
+}
 
 pub struct AlbumTracksSource {
-    resource: Resource<Vector<Track>>,
+    resource: Resource<(AlbumInfo, Vector<Track>)>,
+    info: Signal<Option<AlbumInfo>>,
     changed: Signal<u64>,
     album_id: u32,
 }
 
 impl AlbumTracksSource {
-    pub fn new(album_id: u32, api: Arc<ApiService>) -> Self {
+    pub fn new(album_id: u32, api: Arc<dyn MusicApi>) -> Self {
         let changed = signal(0u64);
+        let info: Signal<Option<AlbumInfo>> = signal(None);
 
         let resource = Resource::new({
             let api = api.clone();
@@ -26,8 +72,8 @@ impl AlbumTracksSource {
                 async move {
                     match api.fetch_album_with_tracks(album_id).await {
                         Ok(album) => {
-                            let tracks: Vec<_> = album.volumes.into_iter().flatten().collect();
-                            Ok(Vector::from(tracks))
+                            let info = AlbumInfo::from(&album);
+                            Ok((info, number_tracks(album_id, album.volumes)))
                         }
                         Err(e) => Err(e.to_string()),
                     }
@@ -38,14 +84,19 @@ impl AlbumTracksSource {
         create_effect({
             let resource = resource.clone();
             let changed = changed.clone();
+            let info = info.clone();
             move |_| {
                 resource.state.track();
+                if let Some((album, _)) = resource.value() {
+                    Update::update(&info, |i| *i = Some(album));
+                }
                 Update::update(&changed, |v| *v += 1);
             }
         });
 
         Self {
             resource,
+            info,
             changed,
             album_id,
         }
@@ -54,17 +105,39 @@ impl AlbumTracksSource {
     pub fn album_id(&self) -> u32 {
         self.album_id
     }
+
+    pub fn info(&self) -> Signal<Option<AlbumInfo>> {
+        self.info.clone()
+    }
+}
+
+fn number_tracks(album_id: u32, volumes: Vec<Vec<Track>>) -> Vector<Track> {
+    let mut tracks = Vector::new();
+    for (volume, volume_tracks) in volumes.into_iter().enumerate() {
+        for (index, mut track) in volume_tracks.into_iter().enumerate() {
+            if let Some(album) = track.albums.iter_mut().find(|a| a.id == Some(album_id))
+                && album.track_position.is_none()
+            {
+                album.track_position = Some(TrackPosition {
+                    volume: volume as u8 + 1,
+                    index: index as u32 + 1,
+                });
+            }
+            tracks.push_back(track);
+        }
+    }
+    tracks
 }
 
 impl DataSource<Track> for AlbumTracksSource {
     fn total(&self) -> Option<usize> {
-        self.resource.value().map(|v| v.len())
+        self.resource.value().map(|(_, tracks)| tracks.len())
     }
 
     fn range(&self, range: Range<usize>) -> Vector<Track> {
         self.resource
             .value()
-            .map(|tracks| {
+            .map(|(_, tracks)| {
                 let start = range.start.min(tracks.len());
                 let end = range.end.min(tracks.len());
                 tracks.clone().slice(start..end)
@@ -0,0 +1,153 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use im::Vector;
+use yandex_music::model::album::Album;
+
+use super::super::{DataSource, FetchState};
+use crate::framework::reactive::{Resource, ResourceState, Update, create_effect, signal};
+use crate::framework::signals::Signal;
+use crate::http::MusicApi;
+
+const PAGE_SIZE: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlbumFilter {
+    #[default]
+    Own,
+    AppearsOn,
+}
+
+impl AlbumFilter {
+    pub fn toggle(self) -> Self {
+        match self {
+            AlbumFilter::Own => AlbumFilter::AppearsOn,
+            AlbumFilter::AppearsOn => AlbumFilter::Own,
+        }
+    }
+}
+
+pub struct ArtistAlbumsSource {
+    resource: Resource<(Vector<Album>, Vector<Album>)>,
+    filter: Signal<AlbumFilter>,
+    changed: Signal<u64>,
+}
+
+impl ArtistAlbumsSource {
+    pub fn new(artist_id: String, api: Arc<dyn MusicApi>) -> Self {
+        let changed = signal(0u64);
+
+        let resource = Resource::new(move || {
+            let api = api.clone();
+            let artist_id = artist_id.clone();
+            async move {
+                fetch_albums(api, artist_id)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+        });
+
+        create_effect({
+            let resource = resource.clone();
+            let changed = changed.clone();
+            move |_| {
+                resource.state.track();
+                Update::update(&changed, |v| *v += 1);
+            }
+        });
+
+        Self {
+            resource,
+            filter: signal(AlbumFilter::Own),
+            changed,
+        }
+    }
+
+    pub fn filter(&self) -> AlbumFilter {
+        self.filter.get()
+    }
+
+    pub fn set_filter(&self, filter: AlbumFilter) {
+        self.filter.set(filter);
+        Update::update(&self.changed, |v| *v += 1);
+    }
+
+    pub fn counts(&self) -> (usize, usize) {
+        self.resource
+            .value()
+            .map(|(own, appears)| (own.len(), appears.len()))
+            .unwrap_or_default()
+    }
+
+    fn filtered(&self) -> Vector<Album> {
+        self.resource
+            .value()
+            .map(|(own, appears)| match self.filter.get() {
+                AlbumFilter::Own => own,
+                AlbumFilter::AppearsOn => appears,
+            })
+            .unwrap_or_default()
+    }
+}
+
+async fn fetch_albums(
+    api: Arc<dyn MusicApi>,
+    artist_id: String,
+) -> color_eyre::Result<(Vector<Album>, Vector<Album>)> {
+    let mut direct = Vec::new();
+    let mut page = 0;
+    loop {
+        let (batch, pager) = api
+            .fetch_artist_albums(artist_id.clone(), page, PAGE_SIZE)
+            .await?;
+        let done = batch.is_empty() || (page + 1) * pager.per_page >= pager.total;
+        direct.extend(batch);
+        if done {
+            break;
+        }
+        page += 1;
+    }
+
+    let (compilations, own): (Vec<Album>, Vec<Album>) = direct
+        .into_iter()
+        .partition(|a| a.item_type.as_deref() == Some("compilation"));
+    let mut appears = Vector::from(compilations);
+    appears.extend(api.fetch_artist_appearances(artist_id).await?);
+    Ok((Vector::from(own), appears))
+}
+
+impl DataSource<Album> for ArtistAlbumsSource {
+    fn total(&self) -> Option<usize> {
+        self.resource.value().map(|_| self.filtered().len())
+    }
+
+    fn range(&self, range: Range<usize>) -> Vector<Album> {
+        let mut albums = self.filtered();
+        let start = range.start.min(albums.len());
+        let end = range.end.min(albums.len());
+        albums.slice(start..end)
+    }
+
+    fn is_loaded(&self, _range: Range<usize>) -> bool {
+        self.resource.is_ready()
+    }
+
+    fn request_range(&self, _range: Range<usize>) {}
+
+    fn fetch_state(&self) -> FetchState {
+        match self.resource.get() {
+            ResourceState::Idle => FetchState::Idle,
+            ResourceState::Loading => FetchState::Loading,
+            ResourceState::Ready(_) | ResourceState::Stale(_) => FetchState::Loaded,
+            ResourceState::Error(e) => FetchState::Error(e),
+        }
+    }
+
+    fn changed_signal(&self) -> Signal<u64> {
+        self.changed.clone()
+    }
+
+    fn refresh(&self) {
+        self.resource.refetch();
+    }
+}
@@ -5,22 +5,51 @@ use im::Vector;
 use yandex_music::model::track::Track;
 
 use super::super::{DataSource, FetchState};
-use crate::framework::reactive::{Update, create_effect, signal};
+use crate::framework::reactive::{Resource, Update, create_effect, signal};
 use crate::framework::resources::{PaginatedResource, ResourceState};
 use crate::framework::signals::Signal;
-use crate::http::ApiService;
+use crate::http::MusicApi;
+use crate::util::config;
+
+const PAGE_SIZE: u32 = 50;
+const PREFETCH_CONCURRENCY: usize = 2;
 
 pub struct ArtistTracksSource {
     resource: PaginatedResource<Track>,
+    description: Signal<Option<String>>,
     changed: Signal<u64>,
     artist_id: String,
-    api: Arc<ApiService>,
+    api: Arc<dyn MusicApi>,
 }
 
 impl ArtistTracksSource {
-    pub fn new(artist_id: String, api: Arc<ApiService>) -> Self {
+    pub fn new(artist_id: String, api: Arc<dyn MusicApi>) -> Self {
         let changed = signal(0u64);
         let resource = PaginatedResource::new();
+        let description: Signal<Option<String>> = signal(None);
+
+        let info = Resource::new({
+            let api = api.clone();
+            let artist_id = artist_id.clone();
+            move || {
+                let api = api.clone();
+                let artist_id = artist_id.clone();
+                async move {
+                    api.fetch_artist_description(artist_id)
+                        .await
+                        .map_err(|e| e.to_string())
+                }
+            }
+        });
+
+        create_effect({
+            let description = description.clone();
+            move |_| {
+                if let Some(Some(text)) = info.value() {
+                    Update::update(&description, |d| *d = Some(text));
+                }
+            }
+        });
 
         create_effect({
             let resource = resource.clone();
@@ -31,8 +60,29 @@ impl ArtistTracksSource {
             }
         });
 
+        create_effect({
+            let resource = resource.clone();
+            let api = api.clone();
+            let artist_id = artist_id.clone();
+            move |_| {
+                let first_page_ready = resource
+                    .state()
+                    .with(|s| matches!(s, ResourceState::Ready(())));
+                if first_page_ready && resource.page() == 1 {
+                    let api = api.clone();
+                    let artist_id = artist_id.clone();
+                    resource.load_ahead(
+                        config::get().track_list.prefetch_pages,
+                        PREFETCH_CONCURRENCY,
+                        move |page| fetch_page(api.clone(), artist_id.clone(), page),
+                    );
+                }
+            }
+        });
+
         let source = Self {
             resource,
+            description,
             changed,
             artist_id,
             api,
@@ -47,6 +97,10 @@ impl ArtistTracksSource {
         &self.artist_id
     }
 
+    pub fn description(&self) -> Signal<Option<String>> {
+        self.description.clone()
+    }
+
     fn request_more(&self) {
         if self.resource.state().with(|s| s.is_loading()) {
             return;
@@ -55,22 +109,25 @@ impl ArtistTracksSource {
         let api = self.api.clone();
         let artist_id = self.artist_id.clone();
 
-        self.resource.load_next(move |page| {
-            let api = api.clone();
-            let artist_id = artist_id.clone();
-            async move {
-                match api
-                    .fetch_artist_tracks_paginated(artist_id, page as u32, 50)
-                    .await
-                {
-                    Ok((tracks, pager)) => {
-                        let has_more = (page as u32 + 1) * 50 < pager.total;
-                        Ok((tracks, has_more))
-                    }
-                    Err(e) => Err(e.to_string()),
-                }
-            }
-        });
+        self.resource
+            .load_next(move |page| fetch_page(api, artist_id, page));
+    }
+}
+
+async fn fetch_page(
+    api: Arc<dyn MusicApi>,
+    artist_id: String,
+    page: usize,
+) -> Result<(Vec<Track>, bool), String> {
+    match api
+        .fetch_artist_tracks_paginated(artist_id, page as u32, PAGE_SIZE)
+        .await
+    {
+        Ok((tracks, pager)) => {
+            let has_more = (page as u32 + 1) * PAGE_SIZE < pager.total;
+            Ok((tracks, has_more))
+        }
+        Err(e) => Err(e.to_string()),
     }
 }
 
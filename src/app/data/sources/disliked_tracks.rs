@@ -0,0 +1,110 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use im::Vector;
+use yandex_music::model::track::{PartialTrack, Track};
+
+use super::super::{DataSource, FetchState};
+use crate::app::data::providers::track::TrackDataSource;
+use crate::app::signals::LibrarySignals;
+use crate::framework::reactive::{Resource, ResourceState, Update, With, create_effect};
+use crate::framework::signals::Signal;
+use crate::framework::tasks::TaskScope;
+use crate::http::MusicApi;
+
+pub struct DislikedTracksSource {
+    dislikes_resource: Resource<Vec<PartialTrack>>,
+    track_source: TrackDataSource,
+}
+
+impl DislikedTracksSource {
+    pub fn new(api: Arc<dyn MusicApi>, library: LibrarySignals, tasks: TaskScope) -> Self {
+        let track_source = TrackDataSource::new(0, api.clone(), tasks);
+
+        let dislikes_resource = Resource::new({
+            let api = api.clone();
+            move || {
+                let api = api.clone();
+                async move { api.fetch_disliked_tracks().await.map_err(|e| e.to_string()) }
+            }
+        });
+
+        create_effect({
+            let resource = dislikes_resource.clone();
+            let disliked = library.disliked_track_ids.clone();
+            move |_| {
+                if let Some(tracks) = resource.value() {
+                    Update::update(&disliked, |ids| {
+                        ids.extend(tracks.iter().map(|t| t.id.clone()));
+                    });
+                }
+            }
+        });
+
+        create_effect({
+            let resource = dislikes_resource.clone();
+            let disliked = library.disliked_track_ids.clone();
+            let track_source = track_source.clone();
+            move |_| {
+                let Some(tracks) = resource.value() else {
+                    return;
+                };
+                let ids = With::with(&disliked, |disliked| {
+                    tracks
+                        .iter()
+                        .filter(|t| disliked.contains(&t.id))
+                        .map(|t| match t.album_id {
+                            Some(album_id) => format!("{}:{}", t.id, album_id),
+                            None => t.id.clone(),
+                        })
+                        .collect()
+                });
+                track_source.set_track_ids(ids);
+            }
+        });
+
+        Self {
+            dislikes_resource,
+            track_source,
+        }
+    }
+}
+
+impl DataSource<Track> for DislikedTracksSource {
+    fn total(&self) -> Option<usize> {
+        self.track_source.total()
+    }
+
+    fn range(&self, range: Range<usize>) -> Vector<Track> {
+        self.track_source.range(range)
+    }
+
+    fn is_loaded(&self, range: Range<usize>) -> bool {
+        self.track_source.is_loaded(range)
+    }
+
+    fn request_range(&self, range: Range<usize>) {
+        if let ResourceState::Idle = self.dislikes_resource.get() {
+            self.dislikes_resource.refetch();
+        }
+        self.track_source.request_range(range)
+    }
+
+    fn fetch_state(&self) -> FetchState {
+        match self.dislikes_resource.get() {
+            ResourceState::Idle => FetchState::Idle,
+            ResourceState::Loading => FetchState::Loading,
+            ResourceState::Ready(_) | ResourceState::Stale(_) => self.track_source.fetch_state(),
+            ResourceState::Error(e) => FetchState::Error(e),
+        }
+    }
+
+    fn changed_signal(&self) -> Signal<u64> {
+        self.track_source.changed_signal()
+    }
+
+    fn refresh(&self) {
+        self.dislikes_resource.refetch();
+        self.track_source.refresh();
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::ops::Range;
 use std::sync::Arc;
 
@@ -11,7 +12,8 @@ use crate::framework::reactive::{
     Memo, Resource, ResourceState, With, create_effect, memo, signal,
 };
 use crate::framework::signals::Signal;
-use crate::http::ApiService;
+use crate::framework::tasks::TaskScope;
+use crate::http::MusicApi;
 use yandex_music::model::playlist::PlaylistTracks;
 
 pub struct LikedTracksSource {
@@ -22,9 +24,13 @@ pub struct LikedTracksSource {
 }
 
 impl LikedTracksSource {
-    pub fn new(api: Arc<ApiService>) -> Self {
+    pub fn new(
+        api: Arc<dyn MusicApi>,
+        liked_ids: Signal<im::HashSet<String>>,
+        tasks: TaskScope,
+    ) -> Self {
         let playlist_info: Signal<Option<PlaylistInfo>> = signal(None);
-        let track_source = TrackDataSource::new(3, api.clone());
+        let track_source = TrackDataSource::new(3, api.clone(), tasks.clone());
 
         let playlist_resource = Resource::new({
             let api = api.clone();
@@ -73,6 +79,63 @@ impl LikedTracksSource {
             }
         });
 
+        create_effect({
+            let track_source = track_source.clone();
+            let playlist_info = playlist_info.clone();
+
+            move |previous: Option<im::HashSet<String>>| {
+                let current = liked_ids.get();
+                let Some(previous) = previous.filter(|p| !p.is_empty()) else {
+                    return current;
+                };
+
+                let removed: HashSet<String> = previous
+                    .iter()
+                    .filter(|id| !current.contains(*id))
+                    .cloned()
+                    .collect();
+                let added: Vec<String> = current
+                    .iter()
+                    .filter(|id| !previous.contains(*id))
+                    .cloned()
+                    .collect();
+                if !removed.is_empty() {
+                    track_source.remove_tracks(&removed);
+                }
+                if removed.len() != added.len() {
+                    playlist_info.update(|info| {
+                        if let Some(info) = info {
+                            info.track_count =
+                                (info.track_count + added.len()).saturating_sub(removed.len());
+                        }
+                    });
+                }
+                if !added.is_empty() {
+                    let api = api.clone();
+                    let track_source = track_source.clone();
+                    tasks.spawn_concurrent("fetch_liked_tracks", async move {
+                        match api.fetch_tracks(added).await {
+                            Ok(tracks) => {
+                                let total = track_source.total().unwrap_or(0);
+                                let present: HashSet<String> = track_source
+                                    .range(0..total)
+                                    .into_iter()
+                                    .map(|t| t.id)
+                                    .collect();
+                                let tracks = tracks
+                                    .into_iter()
+                                    .filter(|t| !present.contains(&t.id))
+                                    .collect();
+                                track_source.prepend_tracks(tracks);
+                            }
+                            Err(e) => tracing::warn!(error = %e, "liked_tracks_fetch_failed"),
+                        }
+                    });
+                }
+                current
+            }
+        });
+
         Self {
             playlist_resource,
             playlist_info,
@@ -1,11 +1,15 @@
 pub mod album_tracks;
+pub mod artist_albums;
 pub mod artist_tracks;
+pub mod disliked_tracks;
 pub mod liked_tracks;
 pub mod playlist_info;
 pub mod playlist_tracks;
 
-pub use album_tracks::AlbumTracksSource;
+pub use album_tracks::{AlbumInfo, AlbumTracksSource};
+pub use artist_albums::{AlbumFilter, ArtistAlbumsSource};
 pub use artist_tracks::ArtistTracksSource;
+pub use disliked_tracks::DislikedTracksSource;
 pub use liked_tracks::LikedTracksSource;
 pub use playlist_info::PlaylistInfo;
-pub use playlist_tracks::PlaylistTracksSource;
+pub use playlist_tracks::{PlaylistTracksSource, RemoteChange};
@@ -1,5 +1,8 @@
+use chrono::{DateTime, Utc};
 use yandex_music::model::playlist::Playlist;
 
+const COLLAGE_SIZE: usize = 4;
+
 #[derive(Debug, Clone, Default)]
 pub struct PlaylistInfo {
     pub title: String,
@@ -9,10 +12,28 @@ pub struct PlaylistInfo {
     pub duration_ms: Option<u64>,
     pub description: Option<String>,
     pub cover_uri: Option<String>,
+    pub collage_uris: Vec<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub visibility: Option<String>,
 }
 
 impl From<&Playlist> for PlaylistInfo {
     fn from(playlist: &Playlist) -> Self {
+        let is_mosaic =
+            playlist.cover.item_type.as_deref() == Some("mosaic") || playlist.cover.uri.is_none();
+        let collage_uris = if is_mosaic {
+            playlist
+                .cover
+                .items_uri
+                .iter()
+                .take(COLLAGE_SIZE)
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         Self {
             title: playlist.title.clone(),
             owner: playlist.owner.name.clone().unwrap_or_default(),
@@ -20,7 +41,15 @@ impl From<&Playlist> for PlaylistInfo {
             track_count: playlist.track_count as usize,
             duration_ms: Some(playlist.duration.as_millis() as u64),
             description: playlist.description.clone(),
-            cover_uri: playlist.cover.uri.clone(),
+            cover_uri: playlist
+                .cover
+                .uri
+                .clone()
+                .filter(|_| collage_uris.is_empty()),
+            collage_uris,
+            created: Some(playlist.created),
+            modified: Some(playlist.modified),
+            visibility: Some(playlist.visibility.clone()).filter(|v| !v.is_empty()),
         }
     }
 }
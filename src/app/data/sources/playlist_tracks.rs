@@ -1,7 +1,9 @@
+use std::collections::HashSet;
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
-use im::Vector;
+use im::{HashMap, Vector};
 use yandex_music::model::{playlist::Playlist, track::Track};
 
 use super::super::{DataSource, FetchState};
@@ -11,27 +13,76 @@ use crate::framework::reactive::{
     Memo, Resource, ResourceState, With, create_effect, memo, signal,
 };
 use crate::framework::signals::Signal;
-use crate::http::ApiService;
+use crate::framework::tasks::TaskScope;
+use crate::http::MusicApi;
 use yandex_music::model::playlist::PlaylistTracks;
 
+const REMOTE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteChange {
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl RemoteChange {
+    fn between(local: &Playlist, remote: &Playlist) -> Self {
+        let local: HashSet<String> = track_ids(local).into_iter().collect();
+        let remote: HashSet<String> = track_ids(remote).into_iter().collect();
+        Self {
+            added: remote.difference(&local).count(),
+            removed: local.difference(&remote).count(),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let tracks = |n: usize| match n {
+            1 => "1 track".to_string(),
+            n => format!("{n} tracks"),
+        };
+        match (self.added, self.removed) {
+            (0, 0) => "Playlist changed remotely".to_string(),
+            (added, 0) => format!("{} added remotely", tracks(added)),
+            (0, removed) => format!("{} removed remotely", tracks(removed)),
+            (added, removed) => format!("{} added, {removed} removed remotely", tracks(added)),
+        }
+    }
+}
+
 pub struct PlaylistTracksSource {
     playlist_resource: Resource<Playlist>,
     playlist_info: Signal<Option<PlaylistInfo>>,
+    remote_change: Signal<Option<RemoteChange>>,
     total_count: Memo<usize>,
     kind: u32,
     track_source: TrackDataSource,
 }
 
 impl PlaylistTracksSource {
-    pub fn new(kind: u32, api: Arc<ApiService>) -> Self {
+    pub fn new(
+        kind: u32,
+        owner: u64,
+        api: Arc<dyn MusicApi>,
+        tasks: TaskScope,
+        revisions: Signal<HashMap<u32, u32>>,
+    ) -> Self {
         let playlist_info: Signal<Option<PlaylistInfo>> = signal(None);
-        let track_source = TrackDataSource::new(kind, api.clone());
+        let remote_change: Signal<Option<RemoteChange>> = signal(None);
+        let track_source = TrackDataSource::new(kind, api.clone(), tasks.clone());
+        let owned = owner == 0 || owner == api.current_user_id();
 
         let playlist_resource = Resource::new({
             let api = api.clone();
             move || {
                 let api = api.clone();
-                async move { api.fetch_playlist(kind).await.map_err(|e| e.to_string()) }
+                async move {
+                    let playlist = if owned {
+                        api.fetch_playlist(kind).await
+                    } else {
+                        api.fetch_user_playlist(&owner.to_string(), kind).await
+                    };
+                    playlist.map_err(|e| e.to_string())
+                }
             }
         });
 
@@ -48,35 +99,49 @@ impl PlaylistTracksSource {
             move |_| {
                 if let Some(playlist) = resource.value() {
                     playlist_info.set(Some(PlaylistInfo::from(&playlist)));
-
-                    let ids: Vec<String> = match &playlist.tracks {
-                        Some(PlaylistTracks::Full(full_tracks)) => {
-                            full_tracks.iter().map(|t| t.id.clone()).collect()
-                        }
-                        Some(PlaylistTracks::WithInfo(infos)) => {
-                            infos.iter().map(|ti| ti.track.id.clone()).collect()
-                        }
-                        Some(PlaylistTracks::Partial(partial)) => partial
-                            .iter()
-                            .map(|p| {
-                                if let Some(album_id) = p.album_id {
-                                    format!("{}:{}", p.id, album_id)
-                                } else {
-                                    p.id.clone()
-                                }
-                            })
-                            .collect(),
-                        None => Vec::new(),
-                    };
-
-                    track_source.set_track_ids(ids);
+                    track_source.set_track_ids(track_ids(&playlist));
                 }
             }
         });
 
+        // Revision polling only covers the signed-in user's playlists; the
+        // bare endpoint can't fetch anyone else's.
+        if owned {
+            tasks.spawn("watch_remote_playlist", {
+                let resource = playlist_resource.clone();
+                let remote_change = remote_change.clone();
+                async move {
+                    let mut reported = 0;
+                    loop {
+                        tokio::time::sleep(REMOTE_CHECK_INTERVAL).await;
+                        let Some(local) = resource.value() else {
+                            continue;
+                        };
+                        let known = With::with(&revisions, |r| r.get(&kind).copied())
+                            .unwrap_or_default()
+                            .max(local.revision)
+                            .max(reported);
+                        let remote = match api.fetch_playlist_bare(kind).await {
+                            Ok(bare) if bare.revision > known => api.fetch_playlist(kind).await,
+                            Ok(_) => continue,
+                            Err(e) => Err(e),
+                        };
+                        match remote {
+                            Ok(remote) => {
+                                reported = remote.revision;
+                                remote_change.set(Some(RemoteChange::between(&local, &remote)));
+                            }
+                            Err(e) => tracing::debug!(error = %e, "playlist_remote_check_failed"),
+                        }
+                    }
+                }
+            });
+        }
+
         Self {
             playlist_resource,
             playlist_info,
+            remote_change,
             total_count,
             kind,
             track_source,
@@ -87,6 +152,10 @@ impl PlaylistTracksSource {
         self.playlist_info.clone()
     }
 
+    pub fn remote_change(&self) -> Signal<Option<RemoteChange>> {
+        self.remote_change.clone()
+    }
+
     pub fn total_count(&self) -> Memo<usize> {
         self.total_count.clone()
     }
@@ -134,7 +203,31 @@ impl DataSource<Track> for PlaylistTracksSource {
     }
 
     fn refresh(&self) {
+        self.remote_change.set(None);
+        self.playlist_info.set(None);
         self.playlist_resource.refetch();
         self.track_source.refresh();
     }
 }
+
+fn track_ids(playlist: &Playlist) -> Vec<String> {
+    match &playlist.tracks {
+        Some(PlaylistTracks::Full(full_tracks)) => {
+            full_tracks.iter().map(|t| t.id.clone()).collect()
+        }
+        Some(PlaylistTracks::WithInfo(infos)) => {
+            infos.iter().map(|ti| ti.track.id.clone()).collect()
+        }
+        Some(PlaylistTracks::Partial(partial)) => partial
+            .iter()
+            .map(|p| {
+                if let Some(album_id) = p.album_id {
+                    format!("{}:{}", p.id, album_id)
+                } else {
+                    p.id.clone()
+                }
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,90 @@
+use ratatui::style::Style;
+
+use crate::{
+    app::theme::theme,
+    framework::reactive::{Signal, signal},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Sidebar,
+    Content,
+}
+
+pub struct FocusManager {
+    panes: Vec<Pane>,
+    focused: Signal<Pane>,
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        Self {
+            panes: vec![Pane::Sidebar, Pane::Content],
+            focused: signal(Pane::Content),
+        }
+    }
+
+    pub fn focused(&self) -> Pane {
+        self.focused.get()
+    }
+
+    pub fn signal(&self) -> Signal<Pane> {
+        self.focused.clone()
+    }
+
+    pub fn is_focused(&self, pane: Pane) -> bool {
+        self.focused.get() == pane
+    }
+
+    pub fn focus(&self, pane: Pane) {
+        if self.panes.contains(&pane) {
+            self.focused.set(pane);
+        }
+    }
+
+    pub fn set_available(&mut self, pane: Pane, available: bool) {
+        let present = self.panes.contains(&pane);
+        if available && !present {
+            self.panes.push(pane);
+            self.panes.sort_by_key(|p| *p as u8);
+        } else if !available && present {
+            self.panes.retain(|p| *p != pane);
+            if self.focused.get() == pane {
+                self.focused.set(Pane::Content);
+            }
+        }
+    }
+
+    pub fn next(&self) {
+        self.step(1);
+    }
+
+    pub fn prev(&self) {
+        self.step(self.panes.len().saturating_sub(1));
+    }
+
+    fn step(&self, offset: usize) {
+        if self.panes.is_empty() {
+            return;
+        }
+        let current = self.focused.get();
+        let idx = self.panes.iter().position(|p| *p == current).unwrap_or(0);
+        self.focused
+            .set(self.panes[(idx + offset) % self.panes.len()]);
+    }
+
+    pub fn border_style(&self, pane: Pane, captured: bool) -> Style {
+        let colors = theme();
+        if !captured && self.is_focused(pane) {
+            colors.focused_border
+        } else {
+            colors.unfocused_border
+        }
+    }
+}
+
+impl Default for FocusManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,453 @@
+use crate::{
+    app::{
+        App,
+        actions::{Action, Route},
+    },
+    audio::local,
+    event::events::Event,
+    util::{config, export, import::ImportSession, script::Hook},
+};
+
+impl App {
+    pub(super) async fn handle_library_action(&mut self, action: Action) {
+        match action {
+            Action::AddToPlaylist {
+                kind,
+                title,
+                tracks,
+            } => {
+                self.clear_marks();
+                let tracks = self.without_local(tracks, "added to playlists");
+                if tracks.is_empty() {
+                    return;
+                }
+                let api = self.api.clone();
+                let tx = self.event_tx.clone();
+                self.tasks.spawn("add to playlist", async move {
+                    let count = tracks.len();
+                    let result = api
+                        .append_to_playlist(kind, &tracks)
+                        .await
+                        .map(|playlist| playlist.revision)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Event::PlaylistAppended(kind, title, count, result));
+                });
+            }
+            Action::DownloadTracks(tracks) => {
+                self.clear_marks();
+                let tracks = self.without_local(tracks, "downloaded");
+                if tracks.is_empty() {
+                    return;
+                }
+                if config::get().downloads.enabled {
+                    let count = tracks.len();
+                    self.audio.write().await.download_tracks(tracks);
+                    self.toast_manager.push_with_icon(
+                        format!("Downloading {count} tracks"),
+                        Some("󰇚".to_string()),
+                    );
+                } else {
+                    self.toast_manager.push_error(
+                        "Downloads are off; set downloads.enabled in config.toml".to_string(),
+                    );
+                }
+            }
+            Action::LikeContext => {
+                let api = self.api.clone();
+                let audio_sys = self.audio.clone();
+                match &self.current_route {
+                    Route::Album { id, title } | Route::Podcast { id, title } => {
+                        let album_id = id.parse::<u32>().unwrap_or(0);
+                        let title = title.clone();
+                        let audio = self.audio.clone();
+                        self.tasks.spawn("like_album", async move {
+                            let is_liked = audio.read().await.is_album_liked(album_id).await;
+                            if is_liked {
+                                let _ = api.remove_like_album(album_id).await;
+                            } else {
+                                let _ = api.add_like_album(album_id).await;
+                            }
+                            audio.write().await.sync_liked_collection().await;
+                        });
+
+                        let is_liked = audio_sys.read().await.is_album_liked(album_id).await;
+                        if is_liked {
+                            self.toast_manager.push_with_icon(
+                                format!("Removed Album: {title}"),
+                                Some("󰋕".to_string()),
+                            );
+                        } else {
+                            self.toast_manager.push_with_icon(
+                                format!("Liked Album: {title}"),
+                                Some("󰋑".to_string()),
+                            );
+                        }
+                    }
+                    Route::Artist { id, name } => {
+                        let id = id.clone();
+                        let name = name.clone();
+                        let audio = self.audio.clone();
+                        let id_clone = id.clone();
+                        self.tasks.spawn("like_artist", async move {
+                            let is_liked = audio.read().await.is_artist_liked(&id_clone).await;
+                            if is_liked {
+                                let _ = api.remove_like_artist(id_clone).await;
+                            } else {
+                                let _ = api.add_like_artist(id_clone).await;
+                            }
+                            audio.write().await.sync_liked_collection().await;
+                        });
+
+                        let is_liked = audio_sys.read().await.is_artist_liked(&id).await;
+                        if is_liked {
+                            self.toast_manager.push_with_icon(
+                                format!("Unfollowed: {name}"),
+                                Some("󰋕".to_string()),
+                            );
+                        } else {
+                            self.toast_manager
+                                .push_with_icon(format!("Followed: {name}"), Some("󰋑".to_string()));
+                        }
+                    }
+                    Route::Playlist { kind, title, .. } => {
+                        let kind = *kind;
+                        let title = title.clone();
+                        let uid = self.current_playlist_owner();
+                        let audio = self.audio.clone();
+                        self.tasks.spawn("like_playlist", async move {
+                            let is_liked = audio.read().await.is_playlist_liked(uid, kind).await;
+                            if is_liked {
+                                let _ = api.remove_like_playlist(uid, kind).await;
+                            } else {
+                                let _ = api.add_like_playlist(uid, kind).await;
+                            }
+                            audio.write().await.sync_liked_collection().await;
+                        });
+
+                        let is_liked = audio_sys.read().await.is_playlist_liked(uid, kind).await;
+                        if is_liked {
+                            self.toast_manager.push_with_icon(
+                                format!("Removed Playlist: {title}"),
+                                Some("󰋕".to_string()),
+                            );
+                        } else {
+                            self.toast_manager.push_with_icon(
+                                format!("Liked Playlist: {title}"),
+                                Some("󰋑".to_string()),
+                            );
+                        }
+                    }
+                    _ => {
+                        self.toast_manager.push_error("Nothing to like".to_string());
+                    }
+                };
+            }
+            Action::DislikeContext => {
+                let api = self.api.clone();
+                let audio_sys = self.audio.clone();
+                if let Route::Artist { id, name } = &self.current_route {
+                    let id = id.clone();
+                    let name = name.clone();
+                    let audio = self.audio.clone();
+                    let id_clone = id.clone();
+                    self.tasks.spawn("dislike_artist", async move {
+                        let is_disliked = audio.read().await.is_artist_disliked(&id_clone).await;
+                        if is_disliked {
+                            let _ = api.remove_dislike_artist(id_clone.clone()).await;
+                        } else {
+                            let _ = api.add_dislike_artist(id_clone.clone()).await;
+                        }
+                        audio.write().await.sync_liked_collection().await;
+                    });
+
+                    let is_disliked = audio_sys.read().await.is_artist_disliked(&id).await;
+                    if is_disliked {
+                        self.toast_manager.push_with_icon(
+                            format!("Removed dislike: {name}"),
+                            Some("󰋖".to_string()),
+                        );
+                    } else {
+                        self.toast_manager
+                            .push_with_icon(format!("Disliked: {name}"), Some("󰋖".to_string()));
+                    }
+                } else {
+                    self.toast_manager
+                        .push_error("Nothing to dislike".to_string());
+                }
+            }
+            Action::LikeTrack(track) => {
+                if local::is_local(&track) {
+                    self.toast_manager
+                        .push_error("Local files can't be liked".to_string());
+                    return;
+                }
+                let id = track.id.clone();
+                let title = track
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| "Unknown Track".to_string());
+                let was_liked = self.signals.library.is_liked(&id);
+                if was_liked {
+                    self.signals.library.remove_like(&id);
+                    let api = self.api.clone();
+                    let id_clone = id.clone();
+                    self.tasks.spawn("remove_like_track", async move {
+                        let _ = api.remove_like_track(id_clone).await;
+                    });
+
+                    let audio = self.audio.clone();
+                    let track = track.clone();
+                    self.tasks.spawn("wave_unlike", async move {
+                        audio.write().await.send_wave_unlike(&track);
+                    });
+
+                    self.toast_manager.push_line(
+                        ratatui::text::Line::from(vec![
+                            ratatui::text::Span::raw("Removed "),
+                            ratatui::text::Span::styled(
+                                title,
+                                ratatui::style::Style::default()
+                                    .add_modifier(ratatui::style::Modifier::BOLD),
+                            ),
+                            ratatui::text::Span::raw(" from liked"),
+                        ]),
+                        Some("󰋕".to_string()),
+                    );
+                } else {
+                    self.scripts.fire(Hook::Like, Some(&track));
+                    self.signals.library.add_like(id.clone());
+                    let api = self.api.clone();
+                    let id_clone = id.clone();
+                    self.tasks.spawn("add_like_track", async move {
+                        let _ = api.add_like_track(id_clone).await;
+                    });
+
+                    let audio = self.audio.clone();
+                    let track = track.clone();
+                    self.tasks.spawn("wave_like", async move {
+                        audio.write().await.send_wave_like(&track);
+                    });
+
+                    self.toast_manager.push_line(
+                        ratatui::text::Line::from(vec![
+                            ratatui::text::Span::raw("Added "),
+                            ratatui::text::Span::styled(
+                                title,
+                                ratatui::style::Style::default()
+                                    .add_modifier(ratatui::style::Modifier::BOLD),
+                            ),
+                            ratatui::text::Span::raw(" to liked"),
+                        ]),
+                        Some("󰋑".to_string()),
+                    );
+
+                    if self.current_route == Route::Home {
+                        self.visualizer.trigger_like_glow();
+                    }
+                }
+            }
+            Action::LikeTracks(tracks) => {
+                self.clear_marks();
+                let tracks = self.without_local(tracks, "liked");
+                if tracks.is_empty() {
+                    return;
+                }
+                let pending: Vec<_> = tracks
+                    .into_iter()
+                    .filter(|t| !self.signals.library.is_liked(&t.id))
+                    .collect();
+                if pending.is_empty() {
+                    self.toast_manager.push_with_icon(
+                        "Marked tracks are already liked".to_string(),
+                        Some("󰋑".to_string()),
+                    );
+                    return;
+                }
+
+                let total = pending.len();
+                self.toast_manager
+                    .progress(format!("Liking 0/{total}"), Some("󰋑".to_string()));
+                let api = self.api.clone();
+                let tx = self.event_tx.clone();
+                self.tasks.spawn("like tracks", async move {
+                    let mut liked = Vec::new();
+                    let mut failed = Vec::new();
+                    for (done, track) in pending.into_iter().enumerate() {
+                        match api.add_like_track(track.id.clone()).await {
+                            Ok(()) => liked.push(track.id),
+                            Err(e) => failed.push((
+                                track.title.unwrap_or_else(|| "Unknown Track".to_string()),
+                                e.to_string(),
+                            )),
+                        }
+                        let _ = tx.send(Event::LikeProgress(done + 1, total));
+                    }
+                    let _ = tx.send(Event::TracksLiked { liked, failed });
+                });
+            }
+            Action::UnlikeTrack(track) if self.signals.library.is_liked(&track.id) => {
+                Box::pin(self.process_action(Action::LikeTrack(track))).await;
+            }
+            Action::DislikeTrack(track) => {
+                if local::is_local(&track) {
+                    self.toast_manager
+                        .push_error("Local files can't be disliked".to_string());
+                    return;
+                }
+                let id = track.id.clone();
+                let title = track
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| "Unknown Track".to_string());
+                if !self.signals.library.is_disliked(&id) {
+                    self.signals.library.add_dislike(id.clone());
+
+                    if self.signals.library.is_liked(&id) {
+                        self.signals.library.remove_like(&id);
+                        let api = self.api.clone();
+                        let id_clone = id.clone();
+                        self.tasks.spawn("remove_like_track", async move {
+                            let _ = api.remove_like_track(id_clone).await;
+                        });
+                    }
+
+                    let api = self.api.clone();
+                    let id_for_api = id.clone();
+                    self.tasks.spawn("add_dislike_track", async move {
+                        let _ = api.add_dislike_track(id_for_api).await;
+                    });
+
+                    if let Some(current) = self.signals.audio.current_track_id.get()
+                        && current == id
+                    {
+                        let mut audio = self.audio.write().await;
+                        audio.send_wave_dislike_skip(&track).await;
+                        audio.remove_track_from_queue(&id);
+                    } else {
+                        let audio = self.audio.clone();
+                        let track = track.clone();
+                        self.tasks.spawn("wave_dislike", async move {
+                            let mut audio = audio.write().await;
+                            audio.send_wave_dislike(&track);
+                            audio.remove_track_from_queue(&track.id);
+                        });
+                    }
+                    self.toast_manager.push_line(
+                        ratatui::text::Line::from(vec![
+                            ratatui::text::Span::raw("Added "),
+                            ratatui::text::Span::styled(
+                                title,
+                                ratatui::style::Style::default()
+                                    .add_modifier(ratatui::style::Modifier::BOLD),
+                            ),
+                            ratatui::text::Span::raw(" to disliked"),
+                        ]),
+                        Some("󰋖".to_string()),
+                    );
+                } else {
+                    self.signals.library.remove_dislike(&id);
+                    let api = self.api.clone();
+                    let id_for_api = id.clone();
+                    self.tasks.spawn("remove_dislike_track", async move {
+                        let _ = api.remove_dislike_track(id_for_api).await;
+                    });
+
+                    let audio = self.audio.clone();
+                    let track = track.clone();
+                    self.tasks.spawn("wave_undislike", async move {
+                        audio.write().await.send_wave_undislike(&track);
+                    });
+
+                    self.toast_manager.push_line(
+                        ratatui::text::Line::from(vec![
+                            ratatui::text::Span::raw("Removed "),
+                            ratatui::text::Span::styled(
+                                title,
+                                ratatui::style::Style::default()
+                                    .add_modifier(ratatui::style::Modifier::BOLD),
+                            ),
+                            ratatui::text::Span::raw(" from disliked"),
+                        ]),
+                        Some("󰋕".to_string()),
+                    );
+                }
+            }
+            Action::ToggleEpisodePlayed(track) => {
+                let library = &self.signals.library;
+                let played = !library
+                    .episode_state(&track.id)
+                    .is_some_and(|state| state.played);
+                let duration = track.duration.map_or(0, |d| d.as_millis() as u64);
+                library.set_episode_played(&track.id, duration, played);
+                self.report_episode_progress(&track, if played { duration } else { 0 }, duration);
+                let message = if played {
+                    "Marked as played"
+                } else {
+                    "Marked as unplayed"
+                };
+                self.toast_manager
+                    .push_with_icon(message.to_string(), Some("󰄬".to_string()));
+            }
+            Action::ImportPlaylist(path) => {
+                let api = self.api.clone();
+                let tx = self.event_tx.clone();
+                self.toast_manager.push("Resolving tracks...".to_string());
+                self.tasks
+                    .spawn_in_group("import", "import playlist", async move {
+                        let session = ImportSession::from_file(api, &path)
+                            .await
+                            .map_err(|e| e.to_string());
+                        let _ = tx.send(Event::ImportResolved(session));
+                    });
+            }
+            Action::ImportChoose { index, candidate } => {
+                if let Some(session) = &mut self.import {
+                    session.choose(index, candidate);
+                }
+                self.review_import();
+            }
+            Action::ImportCommit => {
+                if let Some(session) = self.import.take() {
+                    let api = self.api.clone();
+                    let tx = self.event_tx.clone();
+                    self.tasks.spawn("create playlist", async move {
+                        let playlist = api
+                            .create_playlist(&session.title, &session.tracks())
+                            .await
+                            .map_err(|e| e.to_string());
+                        let _ = tx.send(Event::PlaylistCreated(playlist));
+                    });
+                }
+            }
+            Action::SaveLyrics(lrc) => {
+                let Some(track) = self.signals.audio.current_track.get() else {
+                    return;
+                };
+                let tx = self.event_tx.clone();
+                self.tasks.spawn("save_lyrics", async move {
+                    let result = export::write_lyrics(track, lrc)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Event::FileSaved("lyrics".to_string(), result));
+                });
+            }
+            Action::ExportTracks(format) => {
+                let tracks: Vec<_> = self
+                    .current_view_tracks()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect();
+                let label = format!("{} tracks", tracks.len());
+                let title = self.current_route.title();
+                let tx = self.event_tx.clone();
+                self.tasks.spawn("export_tracks", async move {
+                    let result = export::write(title, tracks, format)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Event::FileSaved(label, result));
+                });
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,122 @@
+mod library;
+mod navigation;
+mod playback;
+mod queue;
+mod search;
+mod system;
+mod wave;
+
+use super::{Action, App};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionCategory {
+    Navigation,
+    Playback,
+    Queue,
+    Wave,
+    Library,
+    Search,
+    System,
+}
+
+impl Action {
+    pub fn category(&self) -> ActionCategory {
+        match self {
+            Action::None
+            | Action::Redraw
+            | Action::Navigate(..)
+            | Action::Back
+            | Action::Overlay(..)
+            | Action::DismissOverlay
+            | Action::OpenModal(..)
+            | Action::CloseModal
+            | Action::OpenFolder(..)
+            | Action::ToggleProfiler
+            | Action::Refresh
+            | Action::ScrollTop
+            | Action::ScrollBottom => ActionCategory::Navigation,
+
+            Action::PlayContext { .. }
+            | Action::PlayTrack(..)
+            | Action::TogglePlayback
+            | Action::NextTrack
+            | Action::PreviousTrack
+            | Action::SeekForward(..)
+            | Action::SeekBackward(..)
+            | Action::SeekTo(..)
+            | Action::ReloadTrack
+            | Action::SetVolume(..)
+            | Action::VolumeUp(..)
+            | Action::VolumeDown(..)
+            | Action::ToggleMute
+            | Action::AdjustTrackGain(..)
+            | Action::AdjustKaraoke(..)
+            | Action::AdjustStereoWidth(..)
+            | Action::AdjustPodcastSpeed(..)
+            | Action::SeekChapter(..)
+            | Action::ToggleShuffle
+            | Action::CycleRepeat
+            | Action::PlayLocal(..)
+            | Action::ResumeContext(..)
+            | Action::DiscoverOutputs
+            | Action::SwitchOutput(..)
+            | Action::ToggleEffect(..)
+            | Action::ToggleEqPreset(..) => ActionCategory::Playback,
+
+            Action::QueueTrack(..)
+            | Action::QueueTracks(..)
+            | Action::QueueLocal(..)
+            | Action::PlayNext(..)
+            | Action::RemoveFromQueue(..)
+            | Action::ClearQueue
+            | Action::QueueAll
+            | Action::PlayAllNext
+            | Action::PlayDiscography => ActionCategory::Queue,
+
+            Action::StartWave { .. }
+            | Action::CancelWave
+            | Action::RefreshWaves
+            | Action::RegenerateDailyMix => ActionCategory::Wave,
+
+            Action::AddToPlaylist { .. }
+            | Action::DownloadTracks(..)
+            | Action::LikeContext
+            | Action::DislikeContext
+            | Action::LikeTrack(..)
+            | Action::LikeTracks(..)
+            | Action::UnlikeTrack(..)
+            | Action::DislikeTrack(..)
+            | Action::ToggleEpisodePlayed(..)
+            | Action::ImportPlaylist(..)
+            | Action::ImportChoose { .. }
+            | Action::ImportCommit
+            | Action::SaveLyrics(..)
+            | Action::ExportTracks(..) => ActionCategory::Library,
+
+            Action::FocusSearch(..) | Action::Search(..) | Action::SearchNextPage => {
+                ActionCategory::Search
+            }
+
+            Action::Quit
+            | Action::Toast(..)
+            | Action::CopyToClipboard(..)
+            | Action::OpenUrl(..)
+            | Action::RunCommand(..)
+            | Action::Batch(..) => ActionCategory::System,
+        }
+    }
+}
+
+impl App {
+    pub async fn process_action(&mut self, action: Action) {
+        match action.category() {
+            ActionCategory::Navigation => self.handle_navigation_action(action).await,
+            ActionCategory::Playback => self.handle_playback_action(action).await,
+            ActionCategory::Queue => self.handle_queue_action(action).await,
+            ActionCategory::Wave => self.handle_wave_action(action).await,
+            ActionCategory::Library => self.handle_library_action(action).await,
+            ActionCategory::Search => self.handle_search_action(action).await,
+            ActionCategory::System => self.handle_system_action(action).await,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    app::{
+        App,
+        actions::{Action, Route},
+    },
+    util::state::HistoryContext,
+};
+
+impl App {
+    pub(super) async fn handle_navigation_action(&mut self, action: Action) {
+        match action {
+            Action::None => {}
+            Action::Redraw => {}
+            Action::Navigate(route) => {
+                self.navigate(route).await;
+            }
+            Action::Back => {
+                let selection = self.route_selections.pop().flatten();
+                self.signals.navigation.back();
+                self.current_route = self.signals.navigation.current_route.get();
+                let route = self.current_route.clone();
+                self.build_view(&route);
+                self.pending_selection = selection;
+            }
+            Action::Overlay(route) => {
+                self.signals.navigation.show_overlay(route);
+            }
+            Action::DismissOverlay => {
+                self.signals.navigation.dismiss_overlay();
+            }
+            Action::OpenModal(request) => {
+                self.modals.open(request);
+            }
+            Action::CloseModal => {
+                self.modals.pop();
+            }
+            Action::OpenFolder(path) => {
+                self.navigate(Route::Files).await;
+                if let Some(view) = &mut self.file_browser {
+                    view.open_folder(path.clone());
+                }
+                self.offer_resume(
+                    HistoryContext::folder(&path)
+                        .resume_key()
+                        .unwrap_or_default(),
+                );
+            }
+            Action::ToggleProfiler => self.profiler.toggle(),
+            Action::Refresh => {
+                let route = self.current_route.clone();
+                match &route {
+                    Route::Home => self.wave_state.fetch(),
+                    Route::Liked => self.liked_view = None,
+                    Route::Playlists => {
+                        self.signals.library.playlists.refetch();
+                        self.playlist_list_view = None;
+                    }
+                    Route::Files => {
+                        if let Some(view) = &mut self.file_browser {
+                            view.refresh();
+                        }
+                    }
+                    _ => {}
+                }
+                self.build_view(&route);
+            }
+            Action::ScrollTop => match &self.current_route {
+                Route::Search => self.search_view.scroll_top(),
+                Route::Liked => {
+                    if let Some(view) = &mut self.liked_view {
+                        view.scroll_top();
+                    }
+                }
+                Route::Playlists => {
+                    if let Some(view) = &mut self.playlist_list_view {
+                        view.scroll_top();
+                    }
+                }
+                Route::Files => {
+                    if let Some(view) = &mut self.file_browser {
+                        view.scroll_top();
+                    }
+                }
+                Route::Playlist { .. }
+                | Route::Album { .. }
+                | Route::Podcast { .. }
+                | Route::SmartPlaylist { .. }
+                | Route::Artist { .. }
+                | Route::Queue
+                | Route::Disliked => {
+                    if let Some(view) = &mut self.track_list_view {
+                        view.scroll_top();
+                    }
+                }
+                Route::Track { .. } => {
+                    if let Some(view) = &mut self.track_detail_view {
+                        view.scroll_top();
+                    }
+                }
+                Route::ArtistAlbums { .. } => {
+                    if let Some(view) = &mut self.artist_albums_view {
+                        view.scroll_top();
+                    }
+                }
+                Route::History => {
+                    if let Some(view) = &mut self.history_view {
+                        view.scroll_top();
+                    }
+                }
+                Route::MostPlayed => {
+                    if let Some(view) = &mut self.most_played_view {
+                        view.scroll_top();
+                    }
+                }
+                Route::Notifications => {
+                    if let Some(view) = &mut self.notifications_view {
+                        view.scroll_top();
+                    }
+                }
+                _ => {}
+            },
+            Action::ScrollBottom => match &self.current_route {
+                Route::Search => self.search_view.scroll_bottom(),
+                Route::Liked => {
+                    if let Some(view) = &mut self.liked_view {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::Playlists => {
+                    if let Some(view) = &mut self.playlist_list_view {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::Files => {
+                    if let Some(view) = &mut self.file_browser {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::Playlist { .. }
+                | Route::Album { .. }
+                | Route::Podcast { .. }
+                | Route::SmartPlaylist { .. }
+                | Route::Artist { .. }
+                | Route::Queue
+                | Route::Disliked => {
+                    if let Some(view) = &mut self.track_list_view {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::Track { .. } => {
+                    if let Some(view) = &mut self.track_detail_view {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::ArtistAlbums { .. } => {
+                    if let Some(view) = &mut self.artist_albums_view {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::History => {
+                    if let Some(view) = &mut self.history_view {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::MostPlayed => {
+                    if let Some(view) = &mut self.most_played_view {
+                        view.scroll_bottom();
+                    }
+                }
+                Route::Notifications => {
+                    if let Some(view) = &mut self.notifications_view {
+                        view.scroll_bottom();
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
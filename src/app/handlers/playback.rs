@@ -0,0 +1,262 @@
+use std::time::Duration;
+
+use crate::{
+    app::{
+        App,
+        actions::{Action, Route},
+    },
+    audio::outputs,
+    event::events::Event,
+};
+
+const OUTPUT_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(3);
+const CHAPTER_RESTART_MS: u64 = 3_000;
+
+impl App {
+    pub(super) async fn handle_playback_action(&mut self, action: Action) {
+        match action {
+            Action::PlayContext {
+                context,
+                tracks,
+                start_index,
+            } => {
+                if self.current_route == Route::Queue {
+                    let mut audio = self.audio.write().await;
+                    audio.play_track_at_index(start_index).await;
+                } else {
+                    self.play_context(context, tracks, start_index).await;
+                }
+            }
+            Action::PlayTrack(track) => {
+                self.playing_context = self.current_route.history_context();
+                self.resume_key = None;
+                let mut audio = self.audio.write().await;
+                audio.play_single_track(track).await;
+            }
+            Action::TogglePlayback => {
+                let mut audio = self.audio.write().await;
+                audio.play_pause().await;
+            }
+            Action::NextTrack => {
+                let mut audio = self.audio.write().await;
+                audio.play_next().await;
+            }
+            Action::PreviousTrack => {
+                let mut audio = self.audio.write().await;
+                audio.play_previous().await;
+            }
+            Action::SeekForward(secs) => {
+                let mut audio = self.audio.write().await;
+                audio.seek_forwards(secs).await;
+            }
+            Action::SeekBackward(secs) => {
+                let mut audio = self.audio.write().await;
+                audio.seek_backwards(secs).await;
+            }
+            Action::SeekTo(ms) => {
+                let mut audio = self.audio.write().await;
+                audio.seek_to(Duration::from_millis(ms)).await;
+            }
+            Action::ReloadTrack => {
+                if self.signals.audio.current_track.get().is_none() {
+                    return;
+                }
+                self.audio.write().await.reload_track().await;
+                self.toast_manager.push_with_icon(
+                    "Reloading in original quality".to_string(),
+                    Some("󰓃".to_string()),
+                );
+            }
+            Action::SetVolume(vol) => {
+                let mut audio = self.audio.write().await;
+                audio.set_volume(vol);
+            }
+            Action::VolumeUp(step) => {
+                let mut audio = self.audio.write().await;
+                audio.volume_up(step);
+            }
+            Action::VolumeDown(step) => {
+                let mut audio = self.audio.write().await;
+                audio.volume_down(step);
+            }
+            Action::ToggleMute => {
+                let mut audio = self.audio.write().await;
+                audio.toggle_mute();
+            }
+            Action::AdjustTrackGain(delta) => {
+                let audio = self.audio.read().await;
+                if let Some(gain) = audio.adjust_track_gain(delta) {
+                    let message = if gain == 0.0 {
+                        "Track gain reset".to_string()
+                    } else {
+                        format!("Track gain: {gain:+.0} dB")
+                    };
+                    self.toast_manager
+                        .push_with_icon(message, Some("󰕾".to_string()));
+                }
+            }
+            Action::AdjustKaraoke(delta) => {
+                let audio = self.audio.read().await;
+                if let Some(strength) = audio.adjust_effect_param("karaoke", 0, delta) {
+                    self.toast_manager.push_with_icon(
+                        format!("Karaoke strength: {:.0}%", strength * 100.0),
+                        Some("󰍬".to_string()),
+                    );
+                }
+            }
+            Action::AdjustStereoWidth(delta) => {
+                let audio = self.audio.read().await;
+                if let Some(width) = audio.adjust_effect_param("stereo_width", 0, delta) {
+                    self.toast_manager.push_with_icon(
+                        format!("Stereo width: {:.0}%", width * 100.0),
+                        Some("󰗅".to_string()),
+                    );
+                }
+            }
+            Action::AdjustPodcastSpeed(delta) => {
+                let audio = self.audio.read().await;
+                let message = match audio.adjust_podcast_speed(delta) {
+                    Some(speed) => format!("Speed for this show: {speed:.1}×"),
+                    None => "Playback speed is only available for podcasts".to_string(),
+                };
+                self.toast_manager
+                    .push_with_icon(message, Some("󰾆".to_string()));
+            }
+            Action::SeekChapter(step) => {
+                let chapters = self.signals.audio.chapters.get();
+                let position = self.signals.audio.position_ms.get();
+                let target = if step > 0 {
+                    chapters.iter().find(|c| c.start_ms > position)
+                } else {
+                    let index = chapters.iter().rposition(|c| c.start_ms <= position);
+                    index.map(|i| {
+                        if position - chapters[i].start_ms < CHAPTER_RESTART_MS && i > 0 {
+                            &chapters[i - 1]
+                        } else {
+                            &chapters[i]
+                        }
+                    })
+                };
+                match target {
+                    Some(chapter) => {
+                        self.audio
+                            .write()
+                            .await
+                            .seek_to(Duration::from_millis(chapter.start_ms))
+                            .await;
+                        self.toast_manager
+                            .push_with_icon(chapter.title.clone(), Some("󰉋".to_string()));
+                    }
+                    None if chapters.is_empty() => {
+                        self.toast_manager.push_with_icon(
+                            "This track has no chapters".to_string(),
+                            Some("󰉋".to_string()),
+                        );
+                    }
+                    None => {}
+                }
+            }
+            Action::ToggleShuffle => {
+                let mut audio = self.audio.write().await;
+                audio.toggle_shuffle();
+            }
+            Action::CycleRepeat => {
+                let mut audio = self.audio.write().await;
+                audio.toggle_repeat_mode();
+            }
+            Action::PlayLocal(path) => {
+                self.read_local(path, true);
+            }
+            Action::ResumeContext(position) => {
+                let Some(view) = &self.track_list_view else {
+                    return;
+                };
+                let Some(index) = view.items().iter().position(|t| t.id == position.track_id)
+                else {
+                    self.toast_manager
+                        .push_error(format!("\"{}\" isn't in this list anymore", position.title));
+                    return;
+                };
+                let action = view.play_from(index);
+                self.pending_resume = Some(position);
+                Box::pin(self.process_action(action)).await;
+            }
+            Action::DiscoverOutputs => {
+                let tx = self.event_tx.clone();
+                self.toast_manager
+                    .push("Searching for playback devices...".to_string());
+                self.tasks.spawn("output_discovery", async move {
+                    let result =
+                        tokio::task::spawn_blocking(|| outputs::discover(OUTPUT_DISCOVERY_TIMEOUT))
+                            .await
+                            .map_err(|e| e.to_string())
+                            .and_then(|r| r.map_err(|e| e.to_string()));
+                    let _ = tx.send(Event::OutputTargetsFound(result));
+                });
+            }
+            Action::SwitchOutput(target) => {
+                let message = match &target {
+                    Some(target) => format!("Playing on {}", target.name),
+                    None => "Playing on this device".to_string(),
+                };
+                self.audio.write().await.switch_output(target).await;
+                self.toast_manager.push(message);
+            }
+            Action::ToggleEffect(effect_name) => {
+                let audio = self.audio.read().await;
+                if audio.toggle_effect(&effect_name) {
+                    let status = if audio.is_effect_enabled(&effect_name).unwrap_or(false) {
+                        "enabled"
+                    } else {
+                        "disabled"
+                    };
+                    self.toast_manager
+                        .push(format!("󰐾 Effect {}: {}", effect_name, status));
+                } else {
+                    self.toast_manager
+                        .push(format!("󰐾 Effect {} not found", effect_name));
+                }
+            }
+            Action::ToggleEqPreset(preset_name) => {
+                let audio = self.audio.read().await;
+                let preset_effects: Vec<String> = match preset_name.as_str() {
+                    "vocal" => vec!["eq_vocal_cut_mud".into(), "eq_vocal_presence".into()],
+                    "bass" => vec!["eq_bass_sub".into(), "eq_bass_punch".into()],
+                    "acoustic" => vec!["eq_acoustic_warmth".into(), "eq_acoustic_air".into()],
+                    "rock" => vec![
+                        "eq_rock_kick".into(),
+                        "eq_rock_mids".into(),
+                        "eq_rock_highs".into(),
+                    ],
+                    _ => vec![],
+                };
+
+                if preset_effects.is_empty() {
+                    self.toast_manager
+                        .push(format!("󰓃 Unknown EQ preset: {}", preset_name));
+                } else {
+                    let mut toggled = 0;
+                    for effect in &preset_effects {
+                        if audio.toggle_effect(effect) {
+                            toggled += 1;
+                        }
+                    }
+                    if toggled > 0 {
+                        let status = if audio.is_effect_enabled(&preset_effects[0]).unwrap_or(false)
+                        {
+                            "enabled"
+                        } else {
+                            "disabled"
+                        };
+                        self.toast_manager
+                            .push(format!("󰓃 EQ preset '{}': {}", preset_name, status));
+                    } else {
+                        self.toast_manager
+                            .push(format!("󰓃 EQ preset '{}' not loaded", preset_name));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use crate::{
+    app::{
+        App,
+        actions::{Action, Route},
+    },
+    audio::local::QueueItem,
+    event::events::Event,
+    http::MusicApi,
+    util::fs,
+};
+
+const DISCOGRAPHY_PAGE_SIZE: u32 = 50;
+
+impl App {
+    pub(super) async fn handle_queue_action(&mut self, action: Action) {
+        match action {
+            Action::QueueTrack(track) => {
+                let mut audio = self.audio.write().await;
+                let title = track
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| "Unknown Track".to_string());
+                audio.queue_track(QueueItem::from(track));
+                self.toast_manager.push_line(
+                    ratatui::text::Line::from(vec![
+                        ratatui::text::Span::raw("Queued: "),
+                        ratatui::text::Span::styled(
+                            title,
+                            ratatui::style::Style::default()
+                                .add_modifier(ratatui::style::Modifier::BOLD),
+                        ),
+                    ]),
+                    Some("󰐍".to_string()),
+                );
+            }
+            Action::QueueTracks(tracks) => {
+                self.clear_marks();
+                let count = tracks.len();
+                let mut audio = self.audio.write().await;
+                for track in tracks {
+                    audio.queue_track(QueueItem::from(track));
+                }
+                self.toast_manager
+                    .push_with_icon(format!("Queued {count} tracks"), Some("󰐍".to_string()));
+            }
+            Action::QueueLocal(path) => {
+                self.read_local(fs::expand_path(&path), false);
+            }
+            Action::PlayNext(track) => {
+                let mut audio = self.audio.write().await;
+                let title = track
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| "Unknown Track".to_string());
+                audio.play_track_next(QueueItem::from(track));
+                self.toast_manager.push_line(
+                    ratatui::text::Line::from(vec![
+                        ratatui::text::Span::raw("Next: "),
+                        ratatui::text::Span::styled(
+                            title,
+                            ratatui::style::Style::default()
+                                .add_modifier(ratatui::style::Modifier::BOLD),
+                        ),
+                    ]),
+                    Some("󰐊".to_string()),
+                );
+            }
+            Action::RemoveFromQueue(idx) => {
+                let mut audio = self.audio.write().await;
+                audio.remove_from_queue(idx);
+                self.toast_manager
+                    .push_with_icon("Removed from queue".to_string(), Some("󰛌".to_string()));
+            }
+            Action::ClearQueue => {
+                let mut audio = self.audio.write().await;
+                audio.clear_queue();
+                self.toast_manager
+                    .push_with_icon("Queue cleared".to_string(), Some("󰛌".to_string()));
+            }
+            Action::QueueAll => {
+                if let Some(view) = &self.track_list_view {
+                    let tracks = view.items();
+                    let count = tracks.len();
+                    let mut audio = self.audio.write().await;
+                    for track in tracks {
+                        audio.queue_track(QueueItem::from(track));
+                    }
+                    self.toast_manager
+                        .push_with_icon(format!("Queued {count} tracks"), Some("󰐍".to_string()));
+                }
+            }
+            Action::PlayAllNext => {
+                if let Some(view) = &self.track_list_view {
+                    let tracks = view.items();
+                    let count = tracks.len();
+                    let mut audio = self.audio.write().await;
+                    for track in tracks.into_iter().rev() {
+                        audio.play_track_next(QueueItem::from(track));
+                    }
+                    self.toast_manager
+                        .push_with_icon(format!("Next: {count} tracks"), Some("󰐊".to_string()));
+                }
+            }
+            Action::PlayDiscography => {
+                let (Route::Artist { id, name } | Route::ArtistAlbums { id, name }) =
+                    &self.current_route
+                else {
+                    self.toast_manager
+                        .push_error("Open an artist to play their discography".to_string());
+                    return;
+                };
+                self.toast_manager.progress(
+                    format!("Loading {name}'s discography"),
+                    Some("󰀥".to_string()),
+                );
+                let api = self.api.clone();
+                let tx = self.event_tx.clone();
+                let artist_id = id.clone();
+                self.tasks.spawn("discography", async move {
+                    let result = resolve_discography(api, artist_id.clone())
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Event::DiscographyResolved(artist_id, result));
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn resolve_discography(
+    api: Arc<dyn MusicApi>,
+    artist_id: String,
+) -> color_eyre::Result<(Vec<yandex_music::model::track::Track>, Vec<u32>)> {
+    let mut albums = Vec::new();
+    let mut page = 0;
+    loop {
+        let (batch, pager) = api
+            .fetch_artist_albums(artist_id.clone(), page, DISCOGRAPHY_PAGE_SIZE)
+            .await?;
+        let done = batch.is_empty() || (page + 1) * pager.per_page >= pager.total;
+        albums.extend(batch);
+        if done {
+            break;
+        }
+        page += 1;
+    }
+
+    albums.retain(|a| a.available.unwrap_or(true) && a.id.is_some());
+    albums.sort_by_key(|a| (a.year, a.release_date));
+    let mut ids = albums.into_iter().filter_map(|a| a.id);
+
+    let Some(first) = ids.next() else {
+        color_eyre::eyre::bail!("no albums found");
+    };
+    let album = api.fetch_album_with_tracks(first).await?;
+    let tracks = album
+        .volumes
+        .into_iter()
+        .flatten()
+        .filter(|t| t.available.unwrap_or(false))
+        .collect();
+    Ok((tracks, ids.collect()))
+}
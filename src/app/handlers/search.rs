@@ -0,0 +1,77 @@
+use crate::{
+    app::{
+        App,
+        actions::{Action, Route},
+        focus::Pane,
+    },
+    event::events::Event,
+};
+
+impl App {
+    pub(super) async fn handle_search_action(&mut self, action: Action) {
+        match action {
+            Action::FocusSearch(query) => {
+                if self.current_route != Route::Search {
+                    self.navigate(Route::Search).await;
+                }
+                self.focus.focus(Pane::Content);
+                self.search_view.focus_input(query);
+            }
+            Action::Search(query) => {
+                if self.search_state.begin_search(&query) {
+                    let optimal_tab = self
+                        .search_state
+                        .results
+                        .as_ref()
+                        .and_then(|r| self.search_state.optimal_tab_for(r));
+                    self.search_view.apply(&self.search_state, optimal_tab);
+                }
+                let api = self.api.clone();
+                let tx = self.event_tx.clone();
+                self.tasks.spawn_in_group("search", "search", async move {
+                    let (results, podcasts) =
+                        tokio::join!(api.search(&query), api.search_podcasts(&query, 0));
+                    if let Err(e) = &podcasts {
+                        tracing::warn!(error = %e, "podcast_search_failed");
+                    }
+                    match results {
+                        Ok(results) => {
+                            let _ = tx.send(Event::SearchResults(query, results, podcasts.ok()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(Event::FetchError(e.to_string()));
+                        }
+                    }
+                });
+            }
+            Action::SearchNextPage => {
+                let tab = self.search_view.current_tab();
+                let sel = self.search_view.current_selection();
+                let count = self.search_view.current_tab_count();
+                if self.search_state.should_load_more(tab, sel, count) {
+                    self.search_state.is_loading_more = true;
+                    self.search_view.set_loading_more(true);
+                    let query = self.search_view.query();
+                    let page = self.search_state.page(tab) + 1;
+                    let api = self.api.clone();
+                    let tx = self.event_tx.clone();
+                    self.tasks
+                        .spawn_in_group("search_page", "search_page", async move {
+                            let event = if tab.is_podcast() {
+                                api.search_podcasts(&query, page)
+                                    .await
+                                    .map(|results| Event::PodcastPageFetched(results, tab, page))
+                            } else {
+                                api.search_paginated(&query, page, tab.search_type())
+                                    .await
+                                    .map(|results| Event::SearchPageFetched(results, tab, page))
+                            };
+                            let _ =
+                                tx.send(event.unwrap_or_else(|e| Event::FetchError(e.to_string())));
+                        });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    app::{App, actions::Action},
+    event::events::Event,
+    util::{link, process},
+};
+
+impl App {
+    pub(super) async fn handle_system_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => {
+                self.should_quit = true;
+            }
+            Action::Toast(msg) => {
+                self.toast_manager.push(msg);
+            }
+            Action::CopyToClipboard(text) => {
+                let copied = crossterm::execute!(
+                    std::io::stdout(),
+                    crossterm::clipboard::CopyToClipboard::to_clipboard_from(&text)
+                );
+                let label = match text.lines().count() {
+                    n if n > 1 => format!("{n} lines"),
+                    _ => text.clone(),
+                };
+                match copied {
+                    Ok(()) => self.toast_manager.push(format!("Copied: {label}")),
+                    Err(e) => self
+                        .toast_manager
+                        .push_error(format!("Couldn't copy to clipboard: {e}")),
+                }
+            }
+            Action::OpenUrl(url) => {
+                if let Err(e) = link::open_in_browser(&url) {
+                    self.toast_manager
+                        .push_error(format!("Couldn't open browser: {e}"));
+                }
+            }
+            Action::RunCommand(command, env) => {
+                let tx = self.event_tx.clone();
+                self.tasks.spawn("user_command", async move {
+                    let shell = command.clone();
+                    let result =
+                        tokio::task::spawn_blocking(move || process::run_captured(&shell, &env))
+                            .await
+                            .unwrap_or_else(|e| Err(e.to_string()));
+                    let _ = tx.send(Event::CommandFinished(command, result));
+                });
+            }
+            Action::Batch(actions) => {
+                for action in actions {
+                    Box::pin(self.process_action(action)).await;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    app::{App, actions::Action},
+    util::state::HistoryContext,
+};
+
+impl App {
+    pub(super) async fn handle_wave_action(&mut self, action: Action) {
+        match action {
+            Action::StartWave {
+                seeds,
+                title,
+                toast_message,
+                lead,
+            } => {
+                let context = HistoryContext::Wave {
+                    seeds: seeds.clone(),
+                    title: title.clone(),
+                };
+                let retry = Action::StartWave {
+                    seeds: seeds.clone(),
+                    title: title.clone(),
+                    toast_message: None,
+                    lead: lead.clone(),
+                };
+                if !self.wave_state.start_with_seeds(seeds, lead) {
+                    self.toast_manager.push_with_icon(
+                        "A wave is already starting".to_string(),
+                        Some("󰎈".to_string()),
+                    );
+                    return;
+                }
+                self.pending_wave = Some((retry, self.playing_context.replace(context)));
+                self.resume_key = None;
+
+                if let Some(lines) = toast_message {
+                    self.toast_manager.push_lines(lines, Some("󰎈".to_string()));
+                } else {
+                    let msg = if let Some(t) = title {
+                        ratatui::text::Line::from(vec![
+                            ratatui::text::Span::raw("Starting a wave for "),
+                            ratatui::text::Span::styled(
+                                t,
+                                ratatui::style::Style::default()
+                                    .add_modifier(ratatui::style::Modifier::BOLD),
+                            ),
+                        ])
+                    } else {
+                        ratatui::text::Line::from("Starting a new wave".to_string())
+                    };
+                    self.toast_manager
+                        .push_lines(vec![msg], Some("󰎈".to_string()));
+                }
+            }
+            Action::CancelWave if self.wave_state.cancel() => {
+                if let Some((_, previous)) = self.pending_wave.take() {
+                    self.playing_context = previous;
+                }
+                self.toast_manager
+                    .push_with_icon("Wave cancelled".to_string(), Some("󰎈".to_string()));
+            }
+            Action::RefreshWaves => {
+                self.wave_state.fetch();
+            }
+            Action::RegenerateDailyMix => {
+                self.daily_mix.generate();
+            }
+            _ => {}
+        }
+    }
+}
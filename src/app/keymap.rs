@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::actions::Route;
+use crate::util::config;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Key {
@@ -15,8 +16,14 @@ pub enum Key {
     BackTab,
     Up,
     Down,
+    AltUp,
+    AltDown,
+    AltLeft,
+    AltRight,
     Left,
     Right,
+    ShiftLeft,
+    ShiftRight,
     Home,
     End,
     PageUp,
@@ -24,12 +31,70 @@ pub enum Key {
     F(u8),
 }
 
+impl Key {
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Some(Key::Char(c));
+        }
+
+        let lower = name.to_lowercase();
+        if let Some(c) = lower
+            .strip_prefix("ctrl+")
+            .or_else(|| lower.strip_prefix("c-"))
+        {
+            let mut chars = c.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(Key::Ctrl(c)),
+                _ => None,
+            };
+        }
+        if let Some(n) = lower.strip_prefix('f')
+            && let Ok(n) = n.parse::<u8>()
+        {
+            return (1..=12).contains(&n).then_some(Key::F(n));
+        }
+        match lower.as_str() {
+            "space" => Some(Key::Char(' ')),
+            "esc" | "escape" => Some(Key::Esc),
+            "enter" | "return" => Some(Key::Enter),
+            "backspace" => Some(Key::Backspace),
+            "tab" => Some(Key::Tab),
+            "up" => Some(Key::Up),
+            "down" => Some(Key::Down),
+            "left" => Some(Key::Left),
+            "right" => Some(Key::Right),
+            "home" => Some(Key::Home),
+            "end" => Some(Key::End),
+            "pageup" => Some(Key::PageUp),
+            "pagedown" => Some(Key::PageDown),
+            _ => None,
+        }
+    }
+}
+
 pub fn normalize(ev: KeyEvent) -> Option<Key> {
     if ev.modifiers.contains(KeyModifiers::CONTROL)
         && let KeyCode::Char(c) = ev.code
     {
         return Some(Key::Ctrl(c));
     }
+    if ev.modifiers.contains(KeyModifiers::SHIFT) {
+        match ev.code {
+            KeyCode::Left => return Some(Key::ShiftLeft),
+            KeyCode::Right => return Some(Key::ShiftRight),
+            _ => {}
+        }
+    }
+    if ev.modifiers.contains(KeyModifiers::ALT) {
+        match ev.code {
+            KeyCode::Up => return Some(Key::AltUp),
+            KeyCode::Down => return Some(Key::AltDown),
+            KeyCode::Left => return Some(Key::AltLeft),
+            KeyCode::Right => return Some(Key::AltRight),
+            _ => {}
+        }
+    }
     match ev.code {
         KeyCode::Char(c) => Some(Key::Char(c)),
         KeyCode::Esc => Some(Key::Esc),
@@ -63,18 +128,29 @@ pub enum Target {
     Selected,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Coarse,
+    Fine,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PlaybackIntent {
     Toggle,
     Next,
     Previous,
-    SeekForward(u64),
-    SeekBackward(u64),
-    VolumeUp(u8),
-    VolumeDown(u8),
+    SeekForward(Step),
+    SeekBackward(Step),
+    VolumeUp(Step),
+    VolumeDown(Step),
     ToggleMute,
     ToggleShuffle,
     CycleRepeat,
+    TrackGain(i8),
+    StereoWidth(i8),
+    PodcastSpeed(i8),
+    Chapter(i8),
+    Reload,
     Like(Target),
     Dislike(Target),
     StartWave(Target),
@@ -86,13 +162,28 @@ pub enum ViewIntent {
     Dislike,
     QueueAll,
     PlayAllNext,
+    PlayDiscography,
     StartWave,
+    Refresh,
+    Share(Target),
+    ShareContext,
+    OpenInBrowser,
+    ImportPlaylist,
+    Export,
+    TrackDetail(Target),
+    FocusSearch { prefill: bool },
+    RecentContexts,
+    ToggleProfiler,
+    PickOutput,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QueueIntent {
     Add,
+    AddLocal,
+    AddToPlaylist,
     PlayNext,
+    Download,
     Remove,
     Clear,
 }
@@ -108,6 +199,7 @@ pub enum EffectIntent {
     ToggleBandpass,
     ToggleNotch,
     ToggleDcBlock,
+    ToggleNightMode,
     ToggleEqPreset(String),
 }
 
@@ -117,6 +209,8 @@ pub enum NavigationIntent {
     Back,
     NextTab,
     PrevTab,
+    FocusNext,
+    FocusPrev,
     ShowOverlay(Route),
     DismissOverlay,
     ScrollTop,
@@ -131,6 +225,7 @@ pub enum Intent {
     View(ViewIntent),
     Queue(QueueIntent),
     Effect(EffectIntent),
+    Command(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -143,6 +238,14 @@ impl KeySeq {
     pub fn chord(first: Key, second: Key) -> Self {
         Self(vec![first, second])
     }
+
+    pub fn parse(binding: &str) -> Option<Self> {
+        let keys = binding
+            .split_whitespace()
+            .map(Key::parse)
+            .collect::<Option<Vec<_>>>()?;
+        (1..=2).contains(&keys.len()).then_some(Self(keys))
+    }
 }
 
 pub struct Keymap {
@@ -154,6 +257,17 @@ impl Keymap {
         Self { bindings }
     }
 
+    fn bind_commands(&mut self, commands: &BTreeMap<String, String>) {
+        for (binding, command) in commands {
+            match KeySeq::parse(binding) {
+                Some(seq) => {
+                    self.bindings.insert(seq, Intent::Command(command.clone()));
+                }
+                None => tracing::warn!(binding = %binding, "keybind_parse_failed"),
+            }
+        }
+    }
+
     fn lookup(&self, seq: &KeySeq) -> Option<Intent> {
         self.bindings.get(seq).cloned()
     }
@@ -172,9 +286,11 @@ pub struct KeyResolver {
 
 impl KeyResolver {
     pub fn new() -> Self {
+        let mut keymap = build_global_keymap();
+        keymap.bind_commands(&config::get().keybind);
         Self {
             state: KeyState::Idle,
-            keymap: build_global_keymap(),
+            keymap,
         }
     }
 
@@ -237,14 +353,40 @@ fn build_global_keymap() -> Keymap {
         (KeySeq::single(Char(' ')), Playback(Toggle)),
         (KeySeq::single(Char('.')), Playback(Next)),
         (KeySeq::single(Char(',')), Playback(Previous)),
-        (KeySeq::single(Char('+')), Playback(VolumeUp(5))),
-        (KeySeq::single(Char('=')), Playback(VolumeUp(5))),
-        (KeySeq::single(Char('-')), Playback(VolumeDown(5))),
+        (KeySeq::single(Char('=')), Playback(VolumeUp(Step::Coarse))),
+        (KeySeq::single(Char('+')), Playback(VolumeUp(Step::Fine))),
+        (
+            KeySeq::single(Char('-')),
+            Playback(VolumeDown(Step::Coarse)),
+        ),
+        (KeySeq::single(Char('_')), Playback(VolumeDown(Step::Fine))),
         (KeySeq::single(Char('m')), Playback(ToggleMute)),
         (KeySeq::single(Char('s')), Playback(ToggleShuffle)),
         (KeySeq::single(Char('r')), Playback(CycleRepeat)),
-        (KeySeq::single(Char('<')), Playback(SeekBackward(10))),
-        (KeySeq::single(Char('>')), Playback(SeekForward(10))),
+        (KeySeq::single(AltUp), Playback(TrackGain(1))),
+        (KeySeq::single(AltDown), Playback(TrackGain(-1))),
+        (KeySeq::single(AltRight), Playback(StereoWidth(1))),
+        (KeySeq::single(AltLeft), Playback(StereoWidth(-1))),
+        (KeySeq::single(Char('}')), Playback(PodcastSpeed(1))),
+        (KeySeq::single(Char('{')), Playback(PodcastSpeed(-1))),
+        (KeySeq::single(Char(')')), Playback(Chapter(1))),
+        (KeySeq::single(Char('(')), Playback(Chapter(-1))),
+        (
+            KeySeq::single(Char('<')),
+            Playback(SeekBackward(Step::Coarse)),
+        ),
+        (
+            KeySeq::single(Char('>')),
+            Playback(SeekForward(Step::Coarse)),
+        ),
+        (
+            KeySeq::single(ShiftLeft),
+            Playback(SeekBackward(Step::Fine)),
+        ),
+        (
+            KeySeq::single(ShiftRight),
+            Playback(SeekForward(Step::Fine)),
+        ),
         (KeySeq::single(Char('f')), Playback(Like(Selected))),
         (KeySeq::single(Char('d')), Playback(Dislike(Selected))),
         (KeySeq::chord(Char('c'), Char('f')), Playback(Like(Current))),
@@ -265,15 +407,42 @@ fn build_global_keymap() -> Keymap {
             KeySeq::chord(Char('v'), Char('n')),
             View(ViewIntent::PlayAllNext),
         ),
+        (
+            KeySeq::chord(Char('v'), Char('a')),
+            View(ViewIntent::PlayDiscography),
+        ),
         (
             KeySeq::chord(Char('c'), Char('w')),
             Playback(StartWave(Current)),
         ),
+        (KeySeq::chord(Char('c'), Char('r')), Playback(Reload)),
         (KeySeq::single(Char('w')), Playback(StartWave(Selected))),
         (
             KeySeq::chord(Char('v'), Char('w')),
             View(ViewIntent::StartWave),
         ),
+        (
+            KeySeq::chord(Char('v'), Char('r')),
+            View(ViewIntent::Refresh),
+        ),
+        (KeySeq::single(Char('y')), View(ViewIntent::Share(Selected))),
+        (KeySeq::single(Ctrl('o')), View(ViewIntent::OpenInBrowser)),
+        (
+            KeySeq::chord(Char('c'), Char('y')),
+            View(ViewIntent::Share(Current)),
+        ),
+        (
+            KeySeq::chord(Char('v'), Char('y')),
+            View(ViewIntent::ShareContext),
+        ),
+        (
+            KeySeq::chord(Char('v'), Char('i')),
+            View(ViewIntent::ImportPlaylist),
+        ),
+        (
+            KeySeq::chord(Char('v'), Char('x')),
+            View(ViewIntent::Export),
+        ),
         (KeySeq::chord(Char('g'), Char('g')), Navigate(ScrollTop)),
         (
             KeySeq::chord(Char('g'), Char('y')),
@@ -283,6 +452,22 @@ fn build_global_keymap() -> Keymap {
             KeySeq::chord(Char('g'), Char('q')),
             Navigate(Go(Route::Queue)),
         ),
+        (
+            KeySeq::chord(Char('g'), Char('h')),
+            Navigate(Go(Route::History)),
+        ),
+        (
+            KeySeq::chord(Char('g'), Char('m')),
+            Navigate(Go(Route::MostPlayed)),
+        ),
+        (
+            KeySeq::chord(Char('g'), Char('d')),
+            Navigate(Go(Route::Disliked)),
+        ),
+        (
+            KeySeq::chord(Char('g'), Char('n')),
+            Navigate(Go(Route::Notifications)),
+        ),
         (
             KeySeq::chord(Char('g'), Char('e')),
             Navigate(ShowOverlay(Route::Effects)),
@@ -291,19 +476,59 @@ fn build_global_keymap() -> Keymap {
             KeySeq::chord(Char('g'), Char('t')),
             Navigate(ShowOverlay(Route::ThemePicker)),
         ),
+        (
+            KeySeq::chord(Char('g'), Char('T')),
+            Navigate(ShowOverlay(Route::Tasks)),
+        ),
+        (
+            KeySeq::chord(Char('g'), Char('i')),
+            View(ViewIntent::TrackDetail(Selected)),
+        ),
+        (
+            KeySeq::chord(Char('c'), Char('i')),
+            View(ViewIntent::TrackDetail(Current)),
+        ),
+        (
+            KeySeq::chord(Char('c'), Char('c')),
+            View(ViewIntent::PickOutput),
+        ),
         (KeySeq::single(Char('G')), Navigate(ScrollBottom)),
-        (KeySeq::single(Tab), Navigate(NextTab)),
-        (KeySeq::single(BackTab), Navigate(PrevTab)),
+        (KeySeq::single(Tab), Navigate(FocusNext)),
+        (KeySeq::single(BackTab), Navigate(FocusPrev)),
+        (KeySeq::single(Char(']')), Navigate(NextTab)),
+        (KeySeq::single(Char('[')), Navigate(PrevTab)),
         (KeySeq::single(Esc), Navigate(Back)),
-        (KeySeq::single(Char('/')), Navigate(Go(Route::Search))),
+        (
+            KeySeq::single(Char('/')),
+            View(ViewIntent::FocusSearch { prefill: false }),
+        ),
+        (
+            KeySeq::single(Ctrl('f')),
+            View(ViewIntent::FocusSearch { prefill: true }),
+        ),
+        (KeySeq::single(Ctrl('e')), View(ViewIntent::RecentContexts)),
+        (KeySeq::single(F(12)), View(ViewIntent::ToggleProfiler)),
         (KeySeq::single(Char('2')), Navigate(Go(Route::Home))),
         (KeySeq::single(Char('3')), Navigate(Go(Route::Liked))),
         (KeySeq::single(Char('4')), Navigate(Go(Route::Playlists))),
+        (KeySeq::single(Char('5')), Navigate(Go(Route::Files))),
         (KeySeq::chord(Char('q'), Char('a')), Queue(QueueIntent::Add)),
+        (
+            KeySeq::chord(Char('q'), Char('l')),
+            Queue(QueueIntent::AddLocal),
+        ),
         (
             KeySeq::chord(Char('q'), Char('n')),
             Queue(QueueIntent::PlayNext),
         ),
+        (
+            KeySeq::chord(Char('q'), Char('p')),
+            Queue(QueueIntent::AddToPlaylist),
+        ),
+        (
+            KeySeq::chord(Char('q'), Char('s')),
+            Queue(QueueIntent::Download),
+        ),
         (
             KeySeq::chord(Char('q'), Char('d')),
             Queue(QueueIntent::Remove),
@@ -348,6 +573,10 @@ fn build_global_keymap() -> Keymap {
             KeySeq::chord(Char('e'), Char('d')),
             Effect(EffectIntent::ToggleDcBlock),
         ),
+        (
+            KeySeq::chord(Char('e'), Char('q')),
+            Effect(EffectIntent::ToggleNightMode),
+        ),
         (
             KeySeq::chord(Char('e'), Char('1')),
             Effect(EffectIntent::ToggleEqPreset("vocal".into())),
@@ -2,6 +2,8 @@ pub mod actions;
 pub mod components;
 pub mod core;
 pub mod data;
+pub mod focus;
+pub mod handlers;
 pub mod keymap;
 pub mod signals;
 pub mod state;
@@ -1,10 +1,26 @@
-use im::{HashSet, Vector};
+use im::{HashMap, HashSet, Vector};
 
-use yandex_music::model::playlist::Playlist;
+use yandex_music::model::{playlist::Playlist, track::Track};
 
 use crate::framework::reactive::{Memo, Resource, Set, Signal, With, batch, memo, signal};
-use crate::http::ApiService;
-use std::sync::Arc;
+use crate::framework::tasks::{DebouncedTask, TaskManager};
+use crate::http::MusicApi;
+use crate::util::{
+    clock,
+    state::{
+        ContextPosition, ContextPositions, EpisodeProgress, EpisodeState, HistoryContext,
+        HistoryEntry, PlayCounts, PlayHistory, PlaylistPreferences, PlaylistPrefs,
+    },
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+const PLAY_STATS_SAVE_DELAY: Duration = Duration::from_secs(5);
 
 #[derive(Clone)]
 pub struct LibrarySignals {
@@ -19,10 +35,26 @@ pub struct LibrarySignals {
     pub liked_count: Memo<usize>,
 
     pub playlist_count: Memo<usize>,
+
+    pub play_history: Signal<PlayHistory>,
+
+    pub play_counts: Signal<PlayCounts>,
+
+    pub episode_progress: Signal<EpisodeProgress>,
+
+    pub context_positions: Signal<ContextPositions>,
+
+    pub playlist_prefs: Signal<PlaylistPreferences>,
+
+    pub playlist_revisions: Signal<HashMap<u32, u32>>,
+
+    play_stats_save: Arc<DebouncedTask>,
+
+    play_stats_dirty: Arc<AtomicBool>,
 }
 
 impl LibrarySignals {
-    pub fn new(api: Arc<ApiService>) -> Self {
+    pub fn new(api: Arc<dyn MusicApi>, tasks: Arc<TaskManager>) -> Self {
         let liked_track_ids = signal::<HashSet<String>>(HashSet::new());
 
         let playlists = Resource::new({
@@ -58,9 +90,27 @@ impl LibrarySignals {
             is_loading: signal(false),
             liked_count,
             playlist_count,
+            play_history: signal(PlayHistory::load()),
+            play_counts: signal(PlayCounts::load()),
+            episode_progress: signal(EpisodeProgress::load()),
+            context_positions: signal(ContextPositions::load()),
+            playlist_prefs: signal(PlaylistPreferences::load()),
+            playlist_revisions: signal(HashMap::new()),
+            play_stats_save: Arc::new(DebouncedTask::new(
+                tasks,
+                "library:play_stats_save",
+                PLAY_STATS_SAVE_DELAY,
+            )),
+            play_stats_dirty: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    pub fn note_playlist_revision(&self, kind: u32, revision: u32) {
+        crate::framework::reactive::Update::update(&self.playlist_revisions, |revisions| {
+            revisions.insert(kind, revision);
+        });
+    }
+
     pub fn is_liked(&self, track_id: &str) -> bool {
         With::with(&self.liked_track_ids, |ids| ids.contains(track_id))
     }
@@ -109,4 +159,91 @@ impl LibrarySignals {
     pub fn set_playlists(&self, playlist_list: Vec<Playlist>) {
         self.playlists.set(Vector::from(playlist_list));
     }
+
+    pub fn episode_state(&self, track_id: &str) -> Option<EpisodeState> {
+        With::with(&self.episode_progress, |progress| progress.get(track_id))
+    }
+
+    pub fn save_episode_position(&self, track_id: &str, position_ms: u64, duration_ms: u64) {
+        crate::framework::reactive::Update::update(&self.episode_progress, |progress| {
+            progress.update(track_id, position_ms, duration_ms);
+            if let Err(e) = progress.save() {
+                tracing::warn!(error = %e, "episode_progress_save_failed");
+            }
+        });
+    }
+
+    pub fn set_episode_played(&self, track_id: &str, duration_ms: u64, played: bool) {
+        crate::framework::reactive::Update::update(&self.episode_progress, |progress| {
+            progress.set_played(track_id, duration_ms, played);
+            if let Err(e) = progress.save() {
+                tracing::warn!(error = %e, "episode_progress_save_failed");
+            }
+        });
+    }
+
+    pub fn context_position(&self, key: &str) -> Option<ContextPosition> {
+        With::with(&self.context_positions, |positions| positions.get(key))
+    }
+
+    pub fn save_context_position(&self, key: &str, position: ContextPosition) {
+        crate::framework::reactive::Update::update(&self.context_positions, |positions| {
+            positions.set(key, position);
+            if let Err(e) = positions.save() {
+                tracing::warn!(error = %e, "context_positions_save_failed");
+            }
+        });
+    }
+
+    pub fn clear_context_position(&self, key: &str) {
+        crate::framework::reactive::Update::update(&self.context_positions, |positions| {
+            if positions.remove(key)
+                && let Err(e) = positions.save()
+            {
+                tracing::warn!(error = %e, "context_positions_save_failed");
+            }
+        });
+    }
+
+    pub fn playlist_prefs(&self, key: &str) -> PlaylistPrefs {
+        With::with(&self.playlist_prefs, |prefs| prefs.get(key))
+    }
+
+    pub fn save_playlist_prefs(&self, key: &str, prefs: PlaylistPrefs) {
+        crate::framework::reactive::Update::update(&self.playlist_prefs, |all| {
+            all.set(key, prefs);
+            if let Err(e) = all.save() {
+                tracing::warn!(error = %e, "playlist_prefs_save_failed");
+            }
+        });
+    }
+
+    pub fn record_play(&self, track: &Track, context: Option<HistoryContext>) {
+        let now = clock::now();
+        let entry = HistoryEntry::new(track, now, context);
+        crate::framework::reactive::Update::update(&self.play_history, |history| {
+            history.record(entry);
+        });
+        crate::framework::reactive::Update::update(&self.play_counts, |counts| {
+            counts.record(track, now);
+        });
+
+        self.play_stats_dirty.store(true, Ordering::Release);
+        let library = self.clone();
+        self.play_stats_save.schedule(move || async move {
+            let _ = tokio::task::spawn_blocking(move || library.flush_play_stats()).await;
+        });
+    }
+
+    pub fn flush_play_stats(&self) {
+        if !self.play_stats_dirty.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        if let Err(e) = With::with(&self.play_history, PlayHistory::save) {
+            tracing::warn!(error = %e, "history_save_failed");
+        }
+        if let Err(e) = With::with(&self.play_counts, PlayCounts::save) {
+            tracing::warn!(error = %e, "play_counts_save_failed");
+        }
+    }
 }
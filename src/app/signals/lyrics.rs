@@ -1,17 +1,19 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use yandex_music::model::info::lyrics::LyricsFormat;
 
 use super::AudioSignals;
-use crate::framework::reactive::Resource;
-use crate::http::ApiService;
+use crate::framework::reactive::{Resource, ResourceState};
+use crate::http::MusicApi;
+use crate::util::{config, process};
 
 #[derive(Clone)]
 pub struct LyricsSignals {
     pub content: Resource<Option<String>>,
+    pub translation: Resource<HashMap<String, String>>,
 }
 
 impl LyricsSignals {
-    pub fn new(api: Arc<ApiService>, audio: &AudioSignals) -> Self {
+    pub fn new(api: Arc<dyn MusicApi>, audio: &AudioSignals) -> Self {
         let content = Resource::new({
             let api = api.clone();
             let current_track = audio.current_track.clone();
@@ -57,6 +59,56 @@ impl LyricsSignals {
             }
         });
 
-        Self { content }
+        let translation = Resource::new({
+            let content = content.clone();
+
+            move || {
+                let command = config::get().lyrics.translate_command.clone();
+                let text = match content.get() {
+                    ResourceState::Ready(Some(text)) => Some(text),
+                    _ => None,
+                };
+
+                async move {
+                    let (Some(command), Some(text)) = (command, text) else {
+                        return Ok(HashMap::new());
+                    };
+                    let lines: Vec<String> = text
+                        .lines()
+                        .map(|line| line.rsplit(']').next().unwrap_or_default().trim())
+                        .filter(|line| !line.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    if lines.is_empty() {
+                        return Ok(HashMap::new());
+                    }
+
+                    let input = lines.join("\n");
+                    let result = tokio::task::spawn_blocking(move || {
+                        process::run_with_input(&command, &input)
+                    })
+                    .await
+                    .map_err(|e| e.to_string())?;
+                    match result {
+                        Ok(output) => Ok(lines
+                            .into_iter()
+                            .zip(output.lines().map(|l| l.trim().to_string()))
+                            .filter(|(source, translated)| {
+                                !translated.is_empty() && source != translated
+                            })
+                            .collect()),
+                        Err(e) => {
+                            tracing::warn!("Failed to translate lyrics: {}", e);
+                            Ok(HashMap::new())
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            content,
+            translation,
+        }
     }
 }
@@ -6,7 +6,10 @@ pub use library::LibrarySignals;
 pub use lyrics::LyricsSignals;
 pub use navigation::NavigationSignals;
 
-use crate::{app::theme::Theme, audio::signals::AudioSignals};
+use crate::{
+    app::theme::Theme,
+    audio::{file_info::FileInfoResolver, signals::AudioSignals},
+};
 use std::sync::Arc;
 
 pub struct AppSignals {
@@ -14,23 +17,7 @@ pub struct AppSignals {
     pub navigation: NavigationSignals,
     pub library: LibrarySignals,
     pub lyrics: LyricsSignals,
+    pub file_info: FileInfoResolver,
     pub theme: Arc<Theme>,
     pub is_focused: crate::framework::reactive::Signal<bool>,
 }
-
-impl AppSignals {
-    pub fn new(api: Arc<crate::http::ApiService>) -> Self {
-        let audio = AudioSignals::new();
-        let library = LibrarySignals::new(api.clone());
-        let lyrics = LyricsSignals::new(api.clone(), &audio);
-
-        Self {
-            audio,
-            navigation: NavigationSignals::new(),
-            library,
-            lyrics,
-            theme: Arc::new(Theme::default()),
-            is_focused: crate::framework::reactive::signal(true),
-        }
-    }
-}
@@ -0,0 +1,194 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::NaiveDate;
+use color_eyre::eyre::eyre;
+use im::Vector;
+use yandex_music::model::track::Track;
+
+use crate::{
+    audio::local,
+    framework::{signals::Signal, tasks::TaskScope},
+    http::MusicApi,
+    util::{
+        clock,
+        state::{DailyMix, PlayCounts, PlayRange},
+        track::extract_ids,
+    },
+};
+
+const MIX_SIZE: usize = 40;
+const TOP_ARTISTS: usize = 4;
+const TRACKS_PER_ARTIST: usize = 5;
+const RECENT_LIKES: usize = 10;
+const SIMILAR_SEEDS: usize = 3;
+const TRACKS_PER_SEED: usize = 4;
+
+pub struct DailyMixState {
+    pub tracks: Signal<Vector<Track>>,
+    pub generated_on: Signal<Option<NaiveDate>>,
+    pub is_generating: Signal<bool>,
+    api: Arc<dyn MusicApi>,
+    counts: Signal<PlayCounts>,
+    tasks: TaskScope,
+}
+
+impl DailyMixState {
+    pub fn new(api: Arc<dyn MusicApi>, counts: Signal<PlayCounts>, tasks: TaskScope) -> Self {
+        let state = Self {
+            tracks: Signal::new(Vector::new()),
+            generated_on: Signal::new(None),
+            is_generating: Signal::new(false),
+            api,
+            counts,
+            tasks,
+        };
+        match DailyMix::load() {
+            Some(mix) if mix.date == clock::today() => state.restore(mix),
+            _ => state.generate(),
+        }
+        state
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.generated_on.get() != Some(clock::today())
+    }
+
+    fn restore(&self, mix: DailyMix) {
+        self.is_generating.set(true);
+        let api = self.api.clone();
+        let tracks = self.tracks.clone();
+        let generated_on = self.generated_on.clone();
+        let generating = self.is_generating.clone();
+        self.tasks.spawn("restore", async move {
+            match api.fetch_tracks(mix.track_ids).await {
+                Ok(t) => {
+                    tracks.set(Vector::from(t));
+                    generated_on.set(Some(mix.date));
+                }
+                Err(e) => tracing::warn!(error = %e, "daily_mix_restore_failed"),
+            }
+            generating.set(false);
+        });
+    }
+
+    pub fn generate(&self) {
+        if self.is_generating.get() {
+            return;
+        }
+        self.is_generating.set(true);
+        let api = self.api.clone();
+        let counts = self.counts.get();
+        let tracks = self.tracks.clone();
+        let generated_on = self.generated_on.clone();
+        let generating = self.is_generating.clone();
+        self.tasks.spawn("generate", async move {
+            match build(api, counts).await {
+                Ok(mix) => {
+                    let date = clock::today();
+                    let saved = DailyMix {
+                        date,
+                        track_ids: mix.iter().map(|t| t.id.clone()).collect(),
+                    };
+                    if let Err(e) = saved.save() {
+                        tracing::warn!(error = %e, "daily_mix_save_failed");
+                    }
+                    tracks.set(Vector::from(mix));
+                    generated_on.set(Some(date));
+                }
+                Err(e) => tracing::warn!(error = %e, "daily_mix_generate_failed"),
+            }
+            generating.set(false);
+        });
+    }
+}
+
+async fn build(api: Arc<dyn MusicApi>, counts: PlayCounts) -> color_eyre::Result<Vec<Track>> {
+    let (artist_ids, seed_ids) = {
+        let top = counts.most_played(PlayRange::Month, clock::now());
+        let mut artist_plays: HashMap<&str, u32> = HashMap::new();
+        for (stat, count) in &top {
+            for id in &stat.track.artist_ids {
+                *artist_plays.entry(id.as_str()).or_default() += count;
+            }
+        }
+        let mut artists: Vec<(&str, u32)> = artist_plays.into_iter().collect();
+        artists.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        let artist_ids: Vec<String> = artists
+            .into_iter()
+            .take(TOP_ARTISTS)
+            .map(|(id, _)| id.to_string())
+            .collect();
+        let seed_ids: Vec<String> = top
+            .iter()
+            .map(|(stat, _)| stat.track.track_id.clone())
+            .filter(|id| !local::is_local_id(id))
+            .take(SIMILAR_SEEDS)
+            .collect();
+        (artist_ids, seed_ids)
+    };
+
+    let mut sources: Vec<Vec<Track>> = Vec::new();
+    for id in artist_ids {
+        match api.fetch_artist_tracks(id).await {
+            Ok(tracks) => sources.push(tracks.into_iter().take(TRACKS_PER_ARTIST).collect()),
+            Err(e) => tracing::warn!(error = %e, "daily_mix_artist_failed"),
+        }
+    }
+
+    let liked_ids: Vec<String> = match api.fetch_liked_tracks().await {
+        Ok(playlist) => playlist
+            .tracks
+            .as_ref()
+            .map(extract_ids)
+            .unwrap_or_default()
+            .into_iter()
+            .take(RECENT_LIKES)
+            .collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "daily_mix_likes_failed");
+            Vec::new()
+        }
+    };
+    if !liked_ids.is_empty() {
+        match api.fetch_tracks(liked_ids).await {
+            Ok(tracks) => sources.push(tracks),
+            Err(e) => tracing::warn!(error = %e, "daily_mix_likes_failed"),
+        }
+    }
+
+    for id in seed_ids {
+        match api.fetch_similar_tracks(id).await {
+            Ok(tracks) => sources.push(tracks.into_iter().take(TRACKS_PER_SEED).collect()),
+            Err(e) => tracing::warn!(error = %e, "daily_mix_similar_failed"),
+        }
+    }
+
+    let mix = interleave(sources);
+    if mix.is_empty() {
+        return Err(eyre!("Not enough listening history for a daily mix"));
+    }
+    Ok(mix)
+}
+
+fn interleave(sources: Vec<Vec<Track>>) -> Vec<Track> {
+    let mut iters: Vec<_> = sources.into_iter().map(Vec::into_iter).collect();
+    let mut seen = std::collections::HashSet::new();
+    let mut mix = Vec::new();
+    while mix.len() < MIX_SIZE {
+        let mut progressed = false;
+        for iter in &mut iters {
+            if let Some(track) = iter.next() {
+                progressed = true;
+                if seen.insert(track.id.clone()) {
+                    mix.push(track);
+                }
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+    mix.truncate(MIX_SIZE);
+    mix
+}
@@ -1,5 +1,7 @@
+pub mod daily_mix;
 pub mod search;
 pub mod wave;
 
-pub use search::{SearchState, SearchTab};
+pub use daily_mix::DailyMixState;
+pub use search::{DurationFilter, SearchFilters, SearchState, SearchTab};
 pub use wave::WaveSessionState;
@@ -1,5 +1,16 @@
+use std::{collections::VecDeque, time::Duration};
+
 use yandex_music::model::{
-    album::Album, artist::Artist, playlist::Playlist, search::Search as SearchModel, track::Track,
+    album::Album,
+    artist::Artist,
+    playlist::Playlist,
+    search::{Search as SearchModel, SearchType},
+    track::Track,
+};
+
+use crate::{
+    http::PodcastSearch,
+    util::track::{is_explicit, is_explicit_album},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,6 +19,8 @@ pub enum SearchTab {
     Albums,
     Artists,
     Playlists,
+    Podcasts,
+    Episodes,
 }
 
 impl SearchTab {
@@ -17,6 +30,8 @@ impl SearchTab {
             SearchTab::Albums,
             SearchTab::Artists,
             SearchTab::Playlists,
+            SearchTab::Podcasts,
+            SearchTab::Episodes,
         ]
     }
 
@@ -26,6 +41,8 @@ impl SearchTab {
             SearchTab::Albums => "Albums",
             SearchTab::Artists => "Artists",
             SearchTab::Playlists => "Playlists",
+            SearchTab::Podcasts => "Podcasts",
+            SearchTab::Episodes => "Episodes",
         }
     }
 
@@ -35,6 +52,8 @@ impl SearchTab {
             SearchTab::Albums => 1,
             SearchTab::Artists => 2,
             SearchTab::Playlists => 3,
+            SearchTab::Podcasts => 4,
+            SearchTab::Episodes => 5,
         }
     }
 
@@ -44,10 +63,26 @@ impl SearchTab {
             1 => SearchTab::Albums,
             2 => SearchTab::Artists,
             3 => SearchTab::Playlists,
+            4 => SearchTab::Podcasts,
+            5 => SearchTab::Episodes,
             _ => SearchTab::Tracks,
         }
     }
 
+    pub fn search_type(&self) -> SearchType {
+        match self {
+            SearchTab::Tracks => SearchType::Tracks,
+            SearchTab::Albums => SearchType::Albums,
+            SearchTab::Artists => SearchType::Artists,
+            SearchTab::Playlists => SearchType::All,
+            SearchTab::Podcasts | SearchTab::Episodes => SearchType::Podcasts,
+        }
+    }
+
+    pub fn is_podcast(&self) -> bool {
+        matches!(self, SearchTab::Podcasts | SearchTab::Episodes)
+    }
+
     pub fn next(&self) -> Self {
         Self::from_index((self.index() + 1) % Self::all().len())
     }
@@ -62,64 +97,267 @@ impl SearchTab {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DurationFilter {
+    #[default]
+    Any,
+    Short,
+    Medium,
+    Long,
+}
+
+impl DurationFilter {
+    const SHORT_SECS: u64 = 180;
+    const LONG_SECS: u64 = 360;
+
+    pub fn next(self) -> Self {
+        match self {
+            DurationFilter::Any => DurationFilter::Short,
+            DurationFilter::Short => DurationFilter::Medium,
+            DurationFilter::Medium => DurationFilter::Long,
+            DurationFilter::Long => DurationFilter::Any,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DurationFilter::Any => "any length",
+            DurationFilter::Short => "under 3 min",
+            DurationFilter::Medium => "3-6 min",
+            DurationFilter::Long => "over 6 min",
+        }
+    }
+
+    pub fn matches(self, duration: Option<Duration>) -> bool {
+        let Some(secs) = duration.map(|d| d.as_secs()) else {
+            return self == DurationFilter::Any;
+        };
+        match self {
+            DurationFilter::Any => true,
+            DurationFilter::Short => secs < Self::SHORT_SECS,
+            DurationFilter::Medium => (Self::SHORT_SECS..=Self::LONG_SECS).contains(&secs),
+            DurationFilter::Long => secs > Self::LONG_SECS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchFilters {
+    pub hide_explicit: bool,
+    pub duration: DurationFilter,
+}
+
+impl SearchFilters {
+    pub fn is_active(&self) -> bool {
+        self.hide_explicit || self.duration != DurationFilter::Any
+    }
+
+    pub fn allows_track(&self, track: &Track) -> bool {
+        !(self.hide_explicit && is_explicit(track)) && self.duration.matches(track.duration)
+    }
+
+    pub fn allows_album(&self, album: &Album) -> bool {
+        !(self.hide_explicit && is_explicit_album(album))
+    }
+
+    pub fn label(&self) -> Option<String> {
+        if !self.is_active() {
+            return None;
+        }
+        let mut parts = Vec::new();
+        if self.hide_explicit {
+            parts.push("no explicit");
+        }
+        if self.duration != DurationFilter::Any {
+            parts.push(self.duration.label());
+        }
+        Some(parts.join(", "))
+    }
+}
+
+const RECENT_SEARCHES: usize = 10;
+
+struct CachedSearch {
+    query: String,
+    results: SearchModel,
+    podcasts: Option<PodcastSearch>,
+}
+
 pub struct SearchState {
     pub results: Option<SearchModel>,
-    pub current_page: u32,
+    pub podcasts: Option<PodcastSearch>,
+    pub pages: [u32; 6],
     pub is_loading: bool,
     pub is_loading_more: bool,
+    pub query: String,
+    pub is_refreshing: bool,
+    recent: VecDeque<CachedSearch>,
 }
 
 impl SearchState {
     pub fn new() -> Self {
         Self {
             results: None,
-            current_page: 0,
+            podcasts: None,
+            pages: [0; 6],
             is_loading: false,
             is_loading_more: false,
+            query: String::new(),
+            is_refreshing: false,
+            recent: VecDeque::new(),
         }
     }
 
-    pub fn begin_search(&mut self) {
-        self.results = None;
-        self.current_page = 0;
-        self.is_loading = true;
+    pub fn begin_search(&mut self, query: &str) -> bool {
+        self.query = query.to_string();
+        self.pages = [0; 6];
         self.is_loading_more = false;
+        match self.recent.iter().find(|c| c.query == query) {
+            Some(cached) => {
+                self.results = Some(cached.results.clone());
+                self.podcasts = cached.podcasts.clone();
+                self.is_loading = false;
+                self.is_refreshing = true;
+            }
+            None => {
+                self.results = None;
+                self.podcasts = None;
+                self.is_loading = true;
+                self.is_refreshing = false;
+            }
+        }
+        self.is_refreshing
     }
 
-    pub fn apply_results(&mut self, results: SearchModel) -> Option<SearchTab> {
+    pub fn remember(
+        &mut self,
+        query: String,
+        results: SearchModel,
+        podcasts: Option<PodcastSearch>,
+    ) {
+        self.recent.retain(|c| c.query != query);
+        self.recent.push_front(CachedSearch {
+            query,
+            results,
+            podcasts,
+        });
+        self.recent.truncate(RECENT_SEARCHES);
+    }
+
+    pub fn refresh_results(&mut self, results: SearchModel, podcasts: Option<PodcastSearch>) {
+        self.results = Some(results);
+        self.podcasts = podcasts;
+        self.pages = [0; 6];
+        self.is_refreshing = false;
+        self.is_loading_more = false;
+    }
+
+    pub fn apply_results(
+        &mut self,
+        results: SearchModel,
+        podcasts: Option<PodcastSearch>,
+    ) -> Option<SearchTab> {
         let tab = self.optimal_tab_for(&results);
+        self.is_refreshing = false;
         self.results = Some(results);
+        self.podcasts = podcasts;
         self.is_loading = false;
         self.is_loading_more = false;
-        self.current_page = 0;
+        self.pages = [0; 6];
         tab
     }
 
-    pub fn merge_results(&mut self, additional: SearchModel, page: u32) {
+    pub fn page(&self, tab: SearchTab) -> u32 {
+        self.pages[tab.index()]
+    }
+
+    pub fn merge_results(&mut self, additional: SearchModel, tab: SearchTab, page: u32) {
         if let Some(existing) = &mut self.results {
-            if let (Some(existing_tracks), Some(new_tracks)) =
-                (&mut existing.tracks, additional.tracks)
-            {
-                existing_tracks.results.extend(new_tracks.results);
+            match tab {
+                SearchTab::Tracks => {
+                    if let (Some(existing_tracks), Some(new_tracks)) =
+                        (&mut existing.tracks, additional.tracks)
+                    {
+                        existing_tracks.results.extend(new_tracks.results);
+                    }
+                }
+                SearchTab::Albums => {
+                    if let (Some(existing_albums), Some(new_albums)) =
+                        (&mut existing.albums, additional.albums)
+                    {
+                        existing_albums.results.extend(new_albums.results);
+                    }
+                }
+                SearchTab::Artists => {
+                    if let (Some(existing_artists), Some(new_artists)) =
+                        (&mut existing.artists, additional.artists)
+                    {
+                        existing_artists.results.extend(new_artists.results);
+                    }
+                }
+                SearchTab::Playlists => {
+                    if let (Some(existing_playlists), Some(new_playlists)) =
+                        (&mut existing.playlists, additional.playlists)
+                    {
+                        existing_playlists.results.extend(new_playlists.results);
+                    }
+                }
+                SearchTab::Podcasts | SearchTab::Episodes => {}
             }
-            if let (Some(existing_albums), Some(new_albums)) =
-                (&mut existing.albums, additional.albums)
-            {
-                existing_albums.results.extend(new_albums.results);
+        }
+        self.pages[tab.index()] = page;
+        self.is_loading_more = false;
+    }
+
+    pub fn merge_podcasts(&mut self, additional: PodcastSearch, tab: SearchTab, page: u32) {
+        if let Some(existing) = &mut self.podcasts {
+            match tab {
+                SearchTab::Podcasts => {
+                    if let (Some(existing_podcasts), Some(new_podcasts)) =
+                        (&mut existing.podcasts, additional.podcasts)
+                    {
+                        existing_podcasts.results.extend(new_podcasts.results);
+                    }
+                }
+                SearchTab::Episodes => {
+                    if let (Some(existing_episodes), Some(new_episodes)) =
+                        (&mut existing.podcast_episodes, additional.podcast_episodes)
+                    {
+                        existing_episodes.results.extend(new_episodes.results);
+                    }
+                }
+                _ => {}
             }
-            if let (Some(existing_artists), Some(new_artists)) =
-                (&mut existing.artists, additional.artists)
-            {
-                existing_artists.results.extend(new_artists.results);
+        }
+        self.pages[tab.index()] = page;
+        self.is_loading_more = false;
+    }
+
+    pub fn loaded_count(&self, tab: SearchTab) -> usize {
+        let podcasts = self.podcasts.as_ref();
+        match tab {
+            SearchTab::Podcasts => {
+                return podcasts
+                    .and_then(|p| p.podcasts.as_ref())
+                    .map_or(0, |r| r.results.len());
             }
-            if let (Some(existing_playlists), Some(new_playlists)) =
-                (&mut existing.playlists, additional.playlists)
-            {
-                existing_playlists.results.extend(new_playlists.results);
+            SearchTab::Episodes => {
+                return podcasts
+                    .and_then(|p| p.podcast_episodes.as_ref())
+                    .map_or(0, |r| r.results.len());
             }
+            _ => {}
+        }
+        let Some(results) = &self.results else {
+            return 0;
+        };
+        match tab {
+            SearchTab::Tracks => results.tracks.as_ref().map_or(0, |t| t.results.len()),
+            SearchTab::Albums => results.albums.as_ref().map_or(0, |a| a.results.len()),
+            SearchTab::Artists => results.artists.as_ref().map_or(0, |a| a.results.len()),
+            SearchTab::Playlists => results.playlists.as_ref().map_or(0, |p| p.results.len()),
+            SearchTab::Podcasts | SearchTab::Episodes => 0,
         }
-        self.current_page = page;
-        self.is_loading_more = false;
     }
 
     pub fn optimal_tab_for(&self, results: &SearchModel) -> Option<SearchTab> {
@@ -128,11 +366,25 @@ impl SearchState {
             "album" => SearchTab::Albums,
             "artist" => SearchTab::Artists,
             "playlist" => SearchTab::Playlists,
+            "podcast" => SearchTab::Podcasts,
+            "podcast_episode" => SearchTab::Episodes,
             _ => SearchTab::Tracks,
         })
     }
 
     pub fn has_more_for_tab(&self, tab: SearchTab, current_count: usize) -> bool {
+        if tab.is_podcast() {
+            return self.podcasts.as_ref().is_some_and(|p| match tab {
+                SearchTab::Podcasts => p
+                    .podcasts
+                    .as_ref()
+                    .is_some_and(|r| current_count < r.total as usize),
+                _ => p
+                    .podcast_episodes
+                    .as_ref()
+                    .is_some_and(|r| current_count < r.total as usize),
+            });
+        }
         let results = match &self.results {
             Some(r) => r,
             None => return false,
@@ -154,6 +406,7 @@ impl SearchState {
                 .playlists
                 .as_ref()
                 .is_some_and(|p| current_count < p.total as usize),
+            SearchTab::Podcasts | SearchTab::Episodes => false,
         }
     }
 
@@ -161,12 +414,12 @@ impl SearchState {
         &self,
         tab: SearchTab,
         selected_index: usize,
-        loaded_count: usize,
+        visible_count: usize,
     ) -> bool {
-        if self.is_loading_more || !self.has_more_for_tab(tab, loaded_count) {
+        if self.is_loading_more || !self.has_more_for_tab(tab, self.loaded_count(tab)) {
             return false;
         }
-        loaded_count > 0 && selected_index >= loaded_count.saturating_sub(2)
+        visible_count == 0 || selected_index >= visible_count.saturating_sub(2)
     }
 
     pub fn has_results(&self) -> bool {
@@ -204,6 +457,22 @@ impl SearchState {
             .map(|p| p.results.clone())
             .unwrap_or_default()
     }
+
+    pub fn podcasts(&self) -> Vec<Album> {
+        self.podcasts
+            .as_ref()
+            .and_then(|p| p.podcasts.as_ref())
+            .map(|p| p.results.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn episodes(&self) -> Vec<Track> {
+        self.podcasts
+            .as_ref()
+            .and_then(|p| p.podcast_episodes.as_ref())
+            .map(|e| e.results.clone())
+            .unwrap_or_default()
+    }
 }
 
 impl Default for SearchState {
@@ -1,13 +1,19 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use flume::Sender;
 use im::Vector;
 use std::borrow::Cow;
+use yandex_music::model::track::Track;
 
 use crate::{
-    app::components::FuzzyItem, event::events::Event, framework::signals::Signal, http::ApiService,
+    app::components::FuzzyItem,
+    event::events::Event,
+    framework::{signals::Signal, tasks::TaskScope},
+    http::MusicApi,
 };
 
+const START_TIMEOUT: Duration = Duration::from_secs(15);
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct StationCategory {
     pub title: String,
@@ -29,17 +35,21 @@ impl FuzzyItem for StationItem {
 pub struct WaveSessionState {
     pub waves: Signal<Vector<StationCategory>>,
     pub is_loading: Signal<bool>,
-    api: Arc<ApiService>,
+    pub is_starting: Signal<bool>,
+    api: Arc<dyn MusicApi>,
     event_tx: Sender<Event>,
+    scope: TaskScope,
 }
 
 impl WaveSessionState {
-    pub fn new(api: Arc<ApiService>, event_tx: Sender<Event>) -> Self {
+    pub fn new(api: Arc<dyn MusicApi>, event_tx: Sender<Event>, scope: TaskScope) -> Self {
         let state = Self {
             waves: Signal::new(Vector::new()),
             is_loading: Signal::new(false),
+            is_starting: Signal::new(false),
             api,
             event_tx,
+            scope,
         };
         state.fetch();
         state
@@ -98,22 +108,53 @@ impl WaveSessionState {
         });
     }
 
-    pub fn start_with_seeds(&self, seeds: Vec<String>) {
-        self.is_loading.set(true);
+    pub fn start_with_seeds(&self, seeds: Vec<String>, lead: Vec<Track>) -> bool {
+        if self.is_starting.get() {
+            return false;
+        }
+
+        self.is_starting.set(true);
         let api = self.api.clone();
         let tx = self.event_tx.clone();
-        let loading = self.is_loading.clone();
-        tokio::spawn(async move {
-            match api.create_session(seeds).await {
+        self.scope.spawn("start", async move {
+            let Ok(result) = tokio::time::timeout(START_TIMEOUT, api.create_session(seeds)).await
+            else {
+                tracing::warn!("wave_session_timeout");
+                let _ = tx.send(Event::WaveTimedOut);
+                return;
+            };
+            match result {
                 Ok(session) => {
-                    let tracks = session.sequence.iter().map(|s| s.track.clone()).collect();
+                    let lead_ids: HashSet<String> = lead.iter().map(|t| t.id.clone()).collect();
+                    let tracks = lead
+                        .into_iter()
+                        .chain(
+                            session
+                                .sequence
+                                .iter()
+                                .map(|s| s.track.clone())
+                                .filter(|t| !lead_ids.contains(&t.id)),
+                        )
+                        .collect();
                     let _ = tx.send(Event::WaveReady(session, tracks));
                 }
                 Err(e) => {
-                    let _ = tx.send(Event::FetchError(e.to_string()));
+                    tracing::warn!(error = %e, "wave_session_failed");
+                    let _ = tx.send(Event::WaveFailed(e.to_string()));
                 }
             }
-            loading.set(false);
         });
+        true
+    }
+
+    pub fn finish_start(&self) -> bool {
+        let was_starting = self.is_starting.get();
+        self.is_starting.set(false);
+        was_starting
+    }
+
+    pub fn cancel(&self) -> bool {
+        self.scope.abort_all();
+        self.finish_start()
     }
 }
@@ -1,4 +1,4 @@
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, OnceLock, RwLock};
 
 use opaline::{
     Gradient, ThemeInfo, app_theme_dirs, load_from_str, load_theme_by_name_in_dirs,
@@ -465,6 +465,7 @@ impl Default for Theme {
 
 static THEME_SNAPSHOT: OnceLock<Signal<ThemeSnapshot>> = OnceLock::new();
 static THEME_INIT: OnceLock<()> = OnceLock::new();
+static ACTIVE_THEME: RwLock<String> = RwLock::new(String::new());
 
 fn all_theme_dirs() -> Vec<std::path::PathBuf> {
     let mut builtin = theme_dirs();
@@ -482,12 +483,17 @@ fn embedded_theme(theme_id: &str) -> Option<opaline::Theme> {
 }
 
 fn load_inner(theme_id: &str) -> bool {
-    if let Some(theme) = embedded_theme(theme_id) {
+    let loaded = if let Some(theme) = embedded_theme(theme_id) {
         set_theme(theme);
-        return true;
-    }
+        true
+    } else {
+        load_theme_by_name_in_dirs(theme_id, all_theme_dirs()).is_ok()
+    };
 
-    load_theme_by_name_in_dirs(theme_id, all_theme_dirs()).is_ok()
+    if loaded && let Ok(mut active) = ACTIVE_THEME.write() {
+        *active = theme_id.to_string();
+    }
+    loaded
 }
 
 pub fn bootstrap() {
@@ -588,6 +594,14 @@ pub fn load(theme_id: &str) -> bool {
     true
 }
 
+pub fn reload() -> bool {
+    let theme_id = ACTIVE_THEME.read().map(|id| id.clone()).unwrap_or_default();
+    if theme_id.is_empty() {
+        return false;
+    }
+    load(&theme_id)
+}
+
 pub fn refresh() {
     let theme = current();
     apply(&theme);
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Modifier,
+    widgets::{Block, Borders, Tabs},
+};
+use yandex_music::model::album::Album;
+
+use crate::{
+    app::{
+        actions::{Action, Route},
+        components::{DynamicList, Spinner},
+        data::{AlbumFilter, ArtistAlbumsSource, DataSource, FetchState},
+        keymap::Key,
+        theme::theme,
+        views::AlbumRenderer,
+    },
+    http::MusicApi,
+};
+
+pub struct ArtistAlbumsView {
+    source: Arc<ArtistAlbumsSource>,
+    list: DynamicList<Album>,
+}
+
+impl ArtistAlbumsView {
+    pub fn new(artist_id: String, name: &str, api: Arc<dyn MusicApi>) -> Self {
+        let source = Arc::new(ArtistAlbumsSource::new(artist_id, api));
+        let list = DynamicList::new(source.clone(), Arc::new(AlbumRenderer::new()))
+            .with_title(format!("{name} — Albums"))
+            .with_fuzzy(|album| {
+                use crate::app::components::FuzzyFields;
+                let title = album.title.clone().unwrap_or_default();
+                FuzzyFields {
+                    full: title.clone(),
+                    title: Some(title),
+                    artist: None,
+                    album: None,
+                }
+            });
+
+        Self { source, list }
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.list.select_first();
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.list.select_last();
+    }
+
+    pub fn selected_item(&self) -> Option<Album> {
+        self.list.selected_item()
+    }
+
+    pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        let list_action = self.list.handle_key(key, prefix);
+        if !list_action.is_none() || prefix.is_some() {
+            return list_action;
+        }
+
+        match key {
+            Key::Tab | Key::BackTab | Key::Left | Key::Right | Key::Char('h') | Key::Char('l') => {
+                self.source.set_filter(self.source.filter().toggle());
+                self.list.select_first();
+                Action::Redraw
+            }
+            Key::Enter => self
+                .list
+                .selected_item()
+                .and_then(|album| {
+                    let id = album.id?.to_string();
+                    let title = album.title.clone().unwrap_or_default();
+                    Some(Action::Navigate(Route::Album { id, title }))
+                })
+                .unwrap_or(Action::None),
+            _ => Action::None,
+        }
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if matches!(self.source.fetch_state(), FetchState::Loading)
+            && self.source.total().is_none_or(|t| t == 0)
+        {
+            let spinner = Spinner::new()
+                .with_label("Loading albums...")
+                .with_style(ratatui::style::Style::default().fg(theme().accent.primary));
+            spinner.view(frame, area);
+            return;
+        }
+
+        let [tabs_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).areas(area);
+        self.render_tabs(frame, tabs_area);
+        self.list.view(frame, list_area);
+    }
+
+    fn render_tabs(&self, frame: &mut Frame, area: Rect) {
+        let (own, appears) = self.source.counts();
+        let selected = match self.source.filter() {
+            AlbumFilter::Own => 0,
+            AlbumFilter::AppearsOn => 1,
+        };
+        let themed = theme();
+        let tabs = Tabs::new([
+            format!("󰀥 Albums ({own})"),
+            format!("󰲸 Compilations & appears on ({appears})"),
+        ])
+        .block(Block::default().borders(Borders::BOTTOM))
+        .select(selected)
+        .style(themed.muted)
+        .highlight_style(themed.selected.add_modifier(Modifier::BOLD));
+        frame.render_widget(tabs, area);
+    }
+}
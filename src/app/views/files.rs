@@ -0,0 +1,355 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use im::Vector;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::{
+        actions::Action,
+        components::{DynamicList, FuzzyFields, Header, HeaderBuilder},
+        data::StaticDataSource,
+        keymap::Key,
+        theme::theme,
+        views::FileRenderer,
+    },
+    audio::{
+        local::{self, LocalTrack, QueueItem},
+        queue::PlaybackContext,
+    },
+    cache::image::ImageCache,
+    event::watcher::LibraryChanges,
+    framework::tasks::TaskScope,
+    util::fs::{self, FileEntry},
+};
+
+pub struct FileBrowserView {
+    path: PathBuf,
+    entries: Vec<FileEntry>,
+    error: Option<String>,
+    source: Arc<StaticDataSource<FileEntry>>,
+    list: DynamicList<FileEntry>,
+    header: Header,
+    tasks: TaskScope,
+    tags: Vec<flume::Receiver<LocalTrack>>,
+}
+
+impl FileBrowserView {
+    pub fn new(path: PathBuf, tasks: TaskScope) -> Self {
+        let source = Arc::new(StaticDataSource::new(Vec::new()));
+        let list =
+            DynamicList::new(source.clone(), Arc::new(FileRenderer::new())).with_fuzzy(|entry| {
+                match entry {
+                    FileEntry::Dir { name, .. } => FuzzyFields {
+                        full: name.clone(),
+                        title: Some(name.clone()),
+                        ..Default::default()
+                    },
+                    FileEntry::Track(track) => FuzzyFields {
+                        full: format!(
+                            "{} {} {}",
+                            track.title,
+                            track.artist.as_deref().unwrap_or_default(),
+                            track.album.as_deref().unwrap_or_default()
+                        ),
+                        title: Some(track.title.clone()),
+                        artist: track.artist.clone(),
+                        album: track.album.clone(),
+                    },
+                }
+            });
+
+        let mut view = Self {
+            path: PathBuf::new(),
+            entries: Vec::new(),
+            error: None,
+            source,
+            list,
+            header: Header::new(Vec::new()),
+            tasks,
+            tags: Vec::new(),
+        };
+        view.open(path, None);
+        view
+    }
+
+    pub fn refresh(&mut self) {
+        let path = self.path.clone();
+        let selected = self.list.selected();
+        self.open(path, None);
+        self.list.select(selected);
+    }
+
+    pub fn apply_changes(&mut self, changes: &LibraryChanges) {
+        if !self.path.is_dir() {
+            let mut path = self.path.clone();
+            while !path.is_dir() && path.pop() {}
+            self.open(path, None);
+            return;
+        }
+
+        let in_view = |path: &PathBuf| path.parent() == Some(self.path.as_path());
+        let removed: Vec<&PathBuf> = changes.removed.iter().filter(|p| in_view(p)).collect();
+        let added: Vec<FileEntry> = changes
+            .added
+            .iter()
+            .filter(|p| in_view(p))
+            .filter_map(|p| FileEntry::scan(p.clone()))
+            .collect();
+        if removed.is_empty() && added.is_empty() {
+            return;
+        }
+
+        let selected = self.list.selected_item().map(|e| e.path().to_path_buf());
+        self.entries.retain(|e| {
+            !removed.iter().any(|p| e.path() == p.as_path())
+                && !added.iter().any(|a| a.path() == e.path())
+        });
+        let tracks = added
+            .iter()
+            .filter_map(|e| match e {
+                FileEntry::Track(track) => Some(track.path.clone()),
+                FileEntry::Dir { .. } => None,
+            })
+            .collect();
+        self.entries.extend(added);
+        self.read_tags(tracks);
+        fs::sort_entries(&mut self.entries);
+        self.source.set_items(self.entries.clone());
+        self.header = self.build_header();
+
+        let index = selected
+            .and_then(|path| self.entries.iter().position(|e| e.path() == path))
+            .unwrap_or_else(|| {
+                self.list
+                    .selected()
+                    .min(self.entries.len().saturating_sub(1))
+            });
+        self.list.select(index);
+    }
+
+    pub fn open_folder(&mut self, path: PathBuf) {
+        self.open(path, None);
+    }
+
+    fn open(&mut self, path: PathBuf, select: Option<&Path>) {
+        match fs::list_dir(&path) {
+            Ok(entries) => {
+                self.entries = entries;
+                self.error = None;
+            }
+            Err(e) => {
+                self.entries.clear();
+                self.error = Some(format!("Couldn't read {}: {e}", path.display()));
+            }
+        }
+        self.path = path;
+        self.source.set_items(self.entries.clone());
+        self.header = self.build_header();
+        self.tags.clear();
+        self.read_tags(self.tracks().iter().map(|t| t.path.clone()).collect());
+
+        let index = select
+            .and_then(|target| {
+                self.entries
+                    .iter()
+                    .position(|e| matches!(e, FileEntry::Dir { path, .. } if path == target))
+            })
+            .unwrap_or(0);
+        self.list.select(index);
+    }
+
+    /// Reads tags for `paths` in the background; `apply_tags` swaps the
+    /// results into the listing as they arrive.
+    fn read_tags(&mut self, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = flume::unbounded();
+        self.tags.push(rx);
+        let dir = self.path.clone();
+        self.tasks.spawn_concurrent("read_tags", async move {
+            let _ = tokio::task::spawn_blocking(move || {
+                let cover = local::find_cover(&dir);
+                for path in paths {
+                    if let Some(track) = LocalTrack::read_with_cover(path, cover.clone())
+                        && tx.send(track).is_err()
+                    {
+                        break;
+                    }
+                }
+            })
+            .await;
+        });
+    }
+
+    fn apply_tags(&mut self) {
+        if self.tags.is_empty() {
+            return;
+        }
+        let tracks: Vec<LocalTrack> = self.tags.iter().flat_map(|rx| rx.try_iter()).collect();
+        self.tags
+            .retain(|rx| !rx.is_disconnected() || !rx.is_empty());
+        let done = self.tags.is_empty();
+        if tracks.is_empty() && !done {
+            return;
+        }
+
+        for track in tracks {
+            if let Some(entry) = self.entries.iter_mut().find(|e| e.path() == track.path) {
+                *entry = FileEntry::Track(track);
+            }
+        }
+        if !done {
+            self.source.set_items(self.entries.clone());
+            return;
+        }
+
+        let selected = self.list.selected_item().map(|e| e.path().to_path_buf());
+        fs::sort_entries(&mut self.entries);
+        self.source.set_items(self.entries.clone());
+        self.header = self.build_header();
+        if let Some(index) =
+            selected.and_then(|path| self.entries.iter().position(|e| e.path() == path))
+        {
+            self.list.select(index);
+        }
+    }
+
+    fn build_header(&self) -> Header {
+        let tracks = self.tracks();
+        let name = tracks
+            .first()
+            .and_then(|t| t.album.clone())
+            .or_else(|| {
+                self.path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| self.path.display().to_string());
+        let dirs = self.entries.len() - tracks.len();
+        let cover = local::find_cover(&self.path).map(|c| format!("file://{}", c.display()));
+
+        HeaderBuilder::folder(&name, &self.path.display().to_string(), dirs, tracks.len())
+            .with_cover_url(cover)
+    }
+
+    fn tracks(&self) -> Vec<&local::LocalTrack> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                FileEntry::Track(track) => Some(track),
+                FileEntry::Dir { .. } => None,
+            })
+            .collect()
+    }
+
+    fn go_up(&mut self) -> Action {
+        let Some(parent) = self.path.parent().map(Path::to_path_buf) else {
+            return Action::None;
+        };
+        let current = self.path.clone();
+        self.open(parent, Some(&current));
+        Action::Redraw
+    }
+
+    fn play_folder(&self, start: Option<&Path>) -> Action {
+        let tracks = self.tracks();
+        if tracks.is_empty() {
+            return Action::None;
+        }
+        let start_index = start
+            .and_then(|path| tracks.iter().position(|t| t.path == path))
+            .unwrap_or(0);
+        Action::PlayContext {
+            context: PlaybackContext::Folder(self.path.clone()),
+            tracks: tracks.into_iter().cloned().map(QueueItem::Local).collect(),
+            start_index,
+        }
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.list.select_first();
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.list.select_last();
+    }
+
+    pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        let list_action = self.list.handle_key(key, prefix);
+        if !list_action.is_none() {
+            return list_action;
+        }
+
+        if prefix.is_some() {
+            return Action::None;
+        }
+
+        match key {
+            Key::Backspace | Key::Left | Key::Char('h') => self.go_up(),
+            Key::Enter | Key::Right | Key::Char('l') => match self.list.selected_item() {
+                Some(FileEntry::Dir { path, .. }) => {
+                    self.open(path, None);
+                    Action::Redraw
+                }
+                Some(FileEntry::Track(track)) if *key == Key::Enter => {
+                    self.play_folder(Some(&track.path))
+                }
+                _ => Action::None,
+            },
+            Key::Char('p') => match self.list.selected_item() {
+                Some(FileEntry::Dir { path, .. }) => Action::PlayLocal(path),
+                _ => self.play_folder(None),
+            },
+            _ => Action::None,
+        }
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.apply_tags();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(self.header.height()), Constraint::Min(0)])
+            .split(area);
+
+        if let Some(mut picker) = ImageCache::global_picker() {
+            self.header.view_with_picker(frame, chunks[0], &mut picker);
+        } else {
+            self.header.view(frame, chunks[0]);
+        }
+
+        let message = match &self.error {
+            Some(error) => Some(error.as_str()),
+            None if self.entries.is_empty() => Some("No folders or audio files here"),
+            None => None,
+        };
+        match message {
+            Some(message) => frame.render_widget(
+                Paragraph::new(message).style(Style::default().fg(theme().text.muted)),
+                chunks[1],
+            ),
+            None => self.list.view(frame, chunks[1]),
+        }
+    }
+
+    pub fn selected_track(&self) -> Option<yandex_music::model::track::Track> {
+        match self.list.selected_item()? {
+            FileEntry::Track(track) => Some(track.to_track()),
+            FileEntry::Dir { .. } => None,
+        }
+    }
+
+    pub fn items(&self) -> Vector<yandex_music::model::track::Track> {
+        self.tracks().iter().map(|t| t.to_track()).collect()
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+use yandex_music::model::track::Track;
+
+use crate::{
+    app::{actions::Action, keymap::Key, signals::AppSignals, theme::theme},
+    framework::reactive::{Signal, Update, create_effect, signal},
+    util::{clock, state::PlayHistory, text},
+};
+
+#[derive(Debug, Clone)]
+enum HistoryRow {
+    Day(String),
+    Entry {
+        title: String,
+        artists: String,
+        played_at: DateTime<Utc>,
+        index: usize,
+    },
+}
+
+pub struct HistoryView {
+    history: Signal<PlayHistory>,
+    changed: Signal<u64>,
+    built: Option<(u64, NaiveDate)>,
+    rows: Vec<HistoryRow>,
+    state: ListState,
+}
+
+impl HistoryView {
+    pub fn new(signals: &AppSignals) -> Self {
+        let history = signals.library.play_history.clone();
+        let changed = signal(0u64);
+        create_effect({
+            let history = history.clone();
+            let changed = changed.clone();
+            move |_| {
+                history.track();
+                Update::update(&changed, |v| *v += 1);
+            }
+        });
+
+        Self {
+            history,
+            changed,
+            built: None,
+            rows: Vec::new(),
+            state: ListState::default(),
+        }
+    }
+
+    pub fn selected_track(&self) -> Option<Track> {
+        let index = match self.state.selected().and_then(|i| self.rows.get(i))? {
+            HistoryRow::Entry { index, .. } => *index,
+            HistoryRow::Day(_) => return None,
+        };
+        self.history
+            .with(|h| h.entries().nth(index).map(|e| e.to_track()))
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.state.select(None);
+        self.step(1);
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.state.select(Some(self.rows.len()));
+        self.step(-1);
+    }
+
+    pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        if prefix.is_some() {
+            return Action::None;
+        }
+
+        match key {
+            Key::Up | Key::Char('k') => self.step(-1),
+            Key::Down | Key::Char('j') => self.step(1),
+            Key::Enter => {
+                return self
+                    .selected_track()
+                    .map_or(Action::None, Action::PlayTrack);
+            }
+            _ => return Action::None,
+        }
+        Action::Redraw
+    }
+
+    fn step(&mut self, delta: isize) {
+        let len = self.rows.len() as isize;
+        let mut index = self.state.selected().map_or(-1, |i| i as isize);
+        loop {
+            index += delta;
+            if index < 0 || index >= len {
+                return;
+            }
+            if matches!(self.rows[index as usize], HistoryRow::Entry { .. }) {
+                self.state.select(Some(index as usize));
+                return;
+            }
+        }
+    }
+
+    fn rebuild(&mut self, now: DateTime<Utc>) {
+        let key = (self.changed.get(), clock::today());
+        if self.built == Some(key) {
+            return;
+        }
+        self.built = Some(key);
+
+        let mut rows = Vec::new();
+        let mut current_day = None;
+        self.history.with(|h| {
+            for (index, entry) in h.entries().enumerate() {
+                let day = clock::day_label(entry.played_at, now);
+                if current_day.as_ref() != Some(&day) {
+                    rows.push(HistoryRow::Day(day.clone()));
+                    current_day = Some(day);
+                }
+                rows.push(HistoryRow::Entry {
+                    title: entry.title.clone(),
+                    artists: entry.artists.join(", "),
+                    played_at: entry.played_at,
+                    index,
+                });
+            }
+        });
+        self.rows = rows;
+
+        if self
+            .state
+            .selected()
+            .and_then(|i| self.rows.get(i))
+            .is_none_or(|row| matches!(row, HistoryRow::Day(_)))
+        {
+            self.state.select(None);
+            self.step(1);
+        }
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let now = clock::now();
+        self.rebuild(now);
+
+        if self.rows.is_empty() {
+            let message =
+                Paragraph::new(" Nothing played yet").style(Style::default().fg(colors.text.muted));
+            frame.render_widget(message, area);
+            return;
+        }
+
+        let width = area.width as usize;
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| match row {
+                HistoryRow::Day(label) => ListItem::new(Line::from(Span::styled(
+                    format!(" {label}"),
+                    Style::default()
+                        .fg(colors.accent.primary)
+                        .add_modifier(Modifier::BOLD),
+                ))),
+                HistoryRow::Entry {
+                    title,
+                    artists,
+                    played_at,
+                    ..
+                } => {
+                    let ago = clock::relative(*played_at, now);
+                    let available = width.saturating_sub(ago.width() + 5);
+                    let label = if artists.is_empty() {
+                        title.clone()
+                    } else {
+                        format!("{title} — {artists}")
+                    };
+                    let label = text::truncate(&label, available);
+                    let padding = available.saturating_sub(label.width());
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("   {label}{} ", " ".repeat(padding)),
+                            Style::default().fg(colors.text.primary),
+                        ),
+                        Span::styled(ago, Style::default().fg(colors.text.muted)),
+                    ]))
+                }
+            })
+            .collect();
+
+        let list = List::new(items).highlight_style(colors.active_selected);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
@@ -1,24 +1,33 @@
+use chrono::NaiveDate;
 use im::Vector;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
+use yandex_music::model::track::Track;
 
 use crate::{
     app::{
         actions::Action,
-        components::{DropdownAction, FuzzyDropdown, Spinner},
+        components::{DropdownAction, FuzzyDropdown, Skeleton},
         keymap::Key,
-        state::wave::{StationCategory, StationItem},
+        state::{
+            DailyMixState,
+            wave::{StationCategory, StationItem},
+        },
         theme::theme,
     },
+    audio::{local::QueueItem, queue::PlaybackContext},
     framework::signals::Signal,
+    util::clock,
 };
 use std::collections::HashSet;
 
+const SKELETON_SHELVES: usize = 4;
+
 pub struct HomeView {
     waves: Signal<Vector<StationCategory>>,
     loading: Signal<bool>,
@@ -26,10 +35,17 @@ pub struct HomeView {
     show_settings: Signal<bool>,
     focused_index: usize,
     dropdown: Option<FuzzyDropdown<StationItem>>,
+    mix_tracks: Signal<Vector<Track>>,
+    mix_date: Signal<Option<NaiveDate>>,
+    mix_generating: Signal<bool>,
 }
 
 impl HomeView {
-    pub fn new(waves: Signal<Vector<StationCategory>>, loading: Signal<bool>) -> Self {
+    pub fn new(
+        waves: Signal<Vector<StationCategory>>,
+        loading: Signal<bool>,
+        daily_mix: &DailyMixState,
+    ) -> Self {
         Self {
             waves,
             loading,
@@ -37,6 +53,9 @@ impl HomeView {
             show_settings: Signal::new(false),
             focused_index: 0,
             dropdown: None,
+            mix_tracks: daily_mix.tracks.clone(),
+            mix_date: daily_mix.generated_on.clone(),
+            mix_generating: daily_mix.is_generating.clone(),
         }
     }
 
@@ -129,6 +148,7 @@ impl HomeView {
                     seeds,
                     title: None,
                     toast_message: Some(toast_message),
+                    lead: Vec::new(),
                 };
             } else {
                 self.show_settings.set(true);
@@ -142,6 +162,18 @@ impl HomeView {
 
         match key {
             Key::Char('r') => Action::RefreshWaves,
+            Key::Char('D') => Action::RegenerateDailyMix,
+            Key::Enter => {
+                let tracks = self.mix_tracks.get();
+                if tracks.is_empty() {
+                    return Action::None;
+                }
+                Action::PlayContext {
+                    context: PlaybackContext::Standalone,
+                    tracks: tracks.into_iter().map(QueueItem::from).collect(),
+                    start_index: 0,
+                }
+            }
             _ => Action::None,
         }
     }
@@ -222,6 +254,7 @@ impl HomeView {
                     seeds,
                     title: None,
                     toast_message: Some(toast_message),
+                    lead: Vec::new(),
                 };
             }
             _ => {}
@@ -233,9 +266,53 @@ impl HomeView {
     pub fn view(&mut self, frame: &mut Frame, area: Rect) {
         if self.show_settings.get() {
             self.render_settings(frame, area);
+        } else {
+            self.render_daily_mix(frame, area);
         }
     }
 
+    fn render_daily_mix(&self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let count = self.mix_tracks.with(|t| t.len());
+        let status = if self.mix_generating.get() {
+            "Generating...".to_string()
+        } else if count == 0 {
+            "Play some music to get a mix".to_string()
+        } else {
+            let day = match self.mix_date.get() {
+                Some(date) if date == clock::today() => "today".to_string(),
+                Some(date) => date.format("%-d %B").to_string(),
+                None => "earlier".to_string(),
+            };
+            format!("{count} tracks • made {day}")
+        };
+
+        let card = Rect {
+            x: area.x + 1,
+            y: area.bottom().saturating_sub(5),
+            width: 36.min(area.width.saturating_sub(2)),
+            height: 4.min(area.height),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(colors.unfocused_border)
+            .title(Span::styled(
+                " Daily Mix ",
+                Style::default()
+                    .fg(colors.accent.primary)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        let lines = vec![
+            Line::from(Span::styled(
+                status,
+                Style::default().fg(colors.text.primary),
+            )),
+            Line::from(Span::styled("Enter: Play | D: Regenerate", colors.muted)),
+        ];
+        frame.render_widget(Clear, card);
+        frame.render_widget(Paragraph::new(lines).block(block), card);
+    }
+
     fn render_settings(&mut self, frame: &mut Frame, area: Rect) {
         let colors = theme();
         let text_style = Style::default().fg(colors.text.primary);
@@ -251,19 +328,6 @@ impl HomeView {
         let overlay_area = centered_rect(area, 60, 80);
         f_render_block(frame, overlay_area, " My Wave Settings ");
 
-        if self.loading.get() {
-            let spinner_area = Rect {
-                x: overlay_area.x + 1,
-                y: overlay_area.y + overlay_area.height / 2,
-                width: overlay_area.width.saturating_sub(2),
-                height: 1,
-            };
-            Spinner::new()
-                .with_label("Loading waves...")
-                .view(frame, spinner_area);
-            return;
-        }
-
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(0), Constraint::Length(3)])
@@ -271,9 +335,15 @@ impl HomeView {
             .split(overlay_area);
 
         let content_area = chunks[0];
+        let loading = self.loading.get();
+        let shelves = if loading {
+            SKELETON_SHELVES
+        } else {
+            waves.len()
+        };
 
         let mut constraints: Vec<Constraint> = Vec::new();
-        for _ in 0..waves.len() {
+        for _ in 0..shelves {
             constraints.push(Constraint::Length(3));
         }
         constraints.push(Constraint::Min(0));
@@ -284,6 +354,21 @@ impl HomeView {
             .margin(1)
             .split(content_area);
 
+        if loading {
+            for row in rows.iter().take(shelves) {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style);
+                let inner = block.inner(*row);
+                frame.render_widget(block, *row);
+                Skeleton::new()
+                    .with_lines(&[40])
+                    .with_gap(0)
+                    .view(frame, inner);
+            }
+            return;
+        }
+
         for (i, wave) in waves.iter().enumerate() {
             let is_focused = i == self.focused_index;
             let indices = &self.selections[i];
@@ -1,15 +1,29 @@
+mod artist_albums;
+mod files;
+mod history;
 mod home;
+mod most_played;
+mod notifications;
 mod overlay;
 mod playlist_list;
 mod renderers;
+pub mod screensaver;
 mod search;
+mod track_detail;
 mod track_list;
 
+pub use artist_albums::ArtistAlbumsView;
+pub use files::FileBrowserView;
+pub use history::HistoryView;
 pub use home::HomeView;
+pub use most_played::MostPlayedView;
+pub use notifications::NotificationsView;
 pub use overlay::EffectsOverlay;
 pub use overlay::OverlayRenderer;
 pub use overlay::ThemePicker;
+pub use overlay::{Modal, ModalOutcome, ModalRequest, ModalStack};
 pub use playlist_list::PlaylistListView;
 pub use renderers::*;
 pub use search::SearchView;
+pub use track_detail::TrackDetailView;
 pub use track_list::{TrackListContext, TrackListView};
@@ -0,0 +1,193 @@
+use chrono::NaiveDate;
+use im::Vector;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+use yandex_music::model::track::Track;
+
+use crate::{
+    app::{actions::Action, keymap::Key, signals::AppSignals, theme::theme},
+    audio::{local::QueueItem, queue::PlaybackContext},
+    framework::reactive::{Signal, Update, create_effect, signal},
+    util::{
+        clock,
+        state::{PlayCounts, PlayRange},
+        text,
+    },
+};
+
+struct MostPlayedRow {
+    track: Track,
+    label: String,
+    count: u32,
+}
+
+pub struct MostPlayedView {
+    counts: Signal<PlayCounts>,
+    changed: Signal<u64>,
+    built: Option<(u64, PlayRange, NaiveDate)>,
+    range: PlayRange,
+    rows: Vec<MostPlayedRow>,
+    state: ListState,
+}
+
+impl MostPlayedView {
+    pub fn new(signals: &AppSignals) -> Self {
+        let counts = signals.library.play_counts.clone();
+        let changed = signal(0u64);
+        create_effect({
+            let counts = counts.clone();
+            let changed = changed.clone();
+            move |_| {
+                counts.track();
+                Update::update(&changed, |v| *v += 1);
+            }
+        });
+
+        Self {
+            counts,
+            changed,
+            built: None,
+            range: PlayRange::default(),
+            rows: Vec::new(),
+            state: ListState::default(),
+        }
+    }
+
+    pub fn selected_track(&self) -> Option<Track> {
+        let index = self.state.selected()?;
+        self.rows.get(index).map(|row| row.track.clone())
+    }
+
+    pub fn items(&self) -> Vector<Track> {
+        self.rows.iter().map(|row| row.track.clone()).collect()
+    }
+
+    pub fn scroll_top(&mut self) {
+        if !self.rows.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        if !self.rows.is_empty() {
+            self.state.select(Some(self.rows.len() - 1));
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        if prefix.is_some() {
+            return Action::None;
+        }
+
+        match key {
+            Key::Up | Key::Char('k') => self.state.select_previous(),
+            Key::Down | Key::Char('j') => self.state.select_next(),
+            Key::Char('t') => {
+                self.range = self.range.next();
+                self.state.select(Some(0));
+            }
+            Key::Enter => {
+                let Some(start_index) = self.state.selected() else {
+                    return Action::None;
+                };
+                return Action::PlayContext {
+                    context: PlaybackContext::Standalone,
+                    tracks: self.items().into_iter().map(QueueItem::from).collect(),
+                    start_index,
+                };
+            }
+            _ => return Action::None,
+        }
+        Action::Redraw
+    }
+
+    fn rebuild(&mut self) {
+        let key = (self.changed.get(), self.range, clock::today());
+        if self.built == Some(key) {
+            return;
+        }
+        self.built = Some(key);
+
+        let now = clock::now();
+        self.rows = self.counts.with(|counts| {
+            counts
+                .most_played(self.range, now)
+                .into_iter()
+                .map(|(stat, count)| {
+                    let entry = &stat.track;
+                    let label = if entry.artists.is_empty() {
+                        entry.title.clone()
+                    } else {
+                        format!("{} — {}", entry.title, entry.artists.join(", "))
+                    };
+                    MostPlayedRow {
+                        track: entry.to_track(),
+                        label,
+                        count,
+                    }
+                })
+                .collect()
+        });
+
+        match self.state.selected() {
+            _ if self.rows.is_empty() => self.state.select(None),
+            Some(i) if i >= self.rows.len() => self.state.select(Some(self.rows.len() - 1)),
+            None => self.state.select(Some(0)),
+            _ => {}
+        }
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        self.rebuild();
+
+        let block = Block::default()
+            .borders(Borders::BOTTOM)
+            .title_top(Line::from(format!(" {} ", self.range.label())).right_aligned());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.rows.is_empty() {
+            let message = Paragraph::new(" No plays in this range")
+                .style(Style::default().fg(colors.text.muted));
+            frame.render_widget(message, inner);
+            return;
+        }
+
+        let width = inner.width as usize;
+        let rank_width = self.rows.len().to_string().len();
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let plays = if row.count == 1 {
+                    "1 play".to_string()
+                } else {
+                    format!("{} plays", row.count)
+                };
+                let rank = format!(" {:>rank_width$}  ", i + 1);
+                let available = width.saturating_sub(rank.width() + plays.width() + 2);
+                let label = text::truncate(&row.label, available);
+                let padding = available.saturating_sub(label.width());
+                ListItem::new(Line::from(vec![
+                    Span::styled(rank, Style::default().fg(colors.text.muted)),
+                    Span::styled(
+                        format!("{label}{} ", " ".repeat(padding)),
+                        Style::default().fg(colors.text.primary),
+                    ),
+                    Span::styled(plays, Style::default().fg(colors.text.muted)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).highlight_style(colors.active_selected);
+        frame.render_stateful_widget(list, inner, &mut self.state);
+    }
+}
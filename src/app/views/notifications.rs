@@ -0,0 +1,105 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::{
+        actions::Action,
+        components::{NotificationLevel, NotificationLog},
+        keymap::Key,
+        theme::theme,
+    },
+    framework::signals::Signal,
+    util::{clock, text},
+};
+
+pub struct NotificationsView {
+    log: Signal<NotificationLog>,
+    state: ListState,
+}
+
+impl NotificationsView {
+    pub fn new(log: Signal<NotificationLog>) -> Self {
+        let state = ListState::default().with_selected(Some(0));
+        Self { log, state }
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.state.select_first();
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.state
+            .select(Some(self.log.with(|l| l.len()).saturating_sub(1)));
+    }
+
+    pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        if prefix.is_some() {
+            return Action::None;
+        }
+
+        match key {
+            Key::Up | Key::Char('k') => self.state.select_previous(),
+            Key::Down | Key::Char('j') => self.state.select_next(),
+            Key::Char('x') => {
+                if let Some(index) = self.state.selected() {
+                    self.log.update(|l| {
+                        if index < l.len() {
+                            l.remove(index);
+                        }
+                    });
+                }
+            }
+            Key::Char('X') => self.log.update(|l| l.clear()),
+            _ => return Action::None,
+        }
+        Action::Redraw
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        if self.log.with(|l| l.unread() > 0) {
+            self.log.update(|l| l.mark_read());
+        }
+
+        let now = clock::now();
+        let width = area.width as usize;
+        let items: Vec<ListItem> = self.log.with(|log| {
+            log.entries()
+                .map(|n| {
+                    let ago = clock::relative(n.at, now);
+                    let icon = n.icon.as_deref().unwrap_or("\u{f0f3}");
+                    let available = width.saturating_sub(ago.width() + icon.width() + 5);
+                    let label = text::truncate(&n.text, available);
+                    let padding = available.saturating_sub(label.width());
+                    let style = match n.level {
+                        NotificationLevel::Error => colors.error_style,
+                        NotificationLevel::Info => Style::default().fg(colors.text.primary),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!(" {icon} "), style),
+                        Span::styled(format!("{label}{} ", " ".repeat(padding)), style),
+                        Span::styled(ago, colors.muted),
+                    ]))
+                })
+                .collect()
+        });
+
+        if items.is_empty() {
+            let message = Paragraph::new(" No notifications").style(colors.muted);
+            frame.render_widget(message, area);
+            return;
+        }
+        if self.state.selected().is_none_or(|i| i >= items.len()) {
+            self.state.select(Some(items.len() - 1));
+        }
+
+        let list = List::new(items).highlight_style(colors.active_selected);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
@@ -1,9 +1,12 @@
 pub mod fx;
+pub mod modal;
+pub mod tasks;
 pub mod theme_picker;
 
 use crate::{
     app::theme::theme,
     app::{actions::Route, components::Lyrics},
+    framework::tasks::TaskManager,
 };
 use ratatui::{
     Frame,
@@ -13,6 +16,7 @@ use ratatui::{
 pub use theme_picker::ThemePicker;
 
 pub use fx::EffectsOverlay;
+pub use modal::{Modal, ModalOutcome, ModalRequest, ModalStack};
 
 pub struct OverlayRenderer;
 
@@ -24,6 +28,7 @@ impl OverlayRenderer {
         lyrics: &mut Lyrics,
         effects: &mut EffectsOverlay,
         theme_picker: &mut ThemePicker,
+        tasks: &TaskManager,
     ) {
         let colors = theme();
         let text_style = ratatui::style::Style::default()
@@ -44,6 +49,9 @@ impl OverlayRenderer {
             Route::ThemePicker => {
                 theme_picker.view(frame, content_area);
             }
+            Route::Tasks => {
+                tasks::render_tasks(frame, content_area, tasks);
+            }
             _ => {
                 let block = Block::default()
                     .borders(Borders::ALL)
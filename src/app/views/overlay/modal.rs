@@ -0,0 +1,571 @@
+use std::{fmt, sync::Arc};
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::{actions::Action, components::fuzzy_match_positioned, keymap::Key, theme::theme},
+    util::text,
+};
+
+#[allow(clippy::large_enum_variant)]
+pub enum ModalOutcome {
+    Pending,
+    Dismiss,
+    Submit(Action),
+}
+
+pub trait Modal: Send {
+    fn title(&self) -> &str;
+
+    fn size(&self) -> (u16, u16);
+
+    fn handle_key(&mut self, key: &Key) -> ModalOutcome;
+
+    fn view(&mut self, frame: &mut Frame, area: Rect);
+}
+
+pub type InputHandler = Arc<dyn Fn(String) -> Action + Send + Sync>;
+pub type SelectHandler = Arc<dyn Fn(usize) -> Action + Send + Sync>;
+
+#[derive(Clone)]
+pub enum ModalRequest {
+    Confirm {
+        title: String,
+        message: String,
+        on_confirm: Box<Action>,
+    },
+    Input {
+        title: String,
+        prompt: String,
+        initial: String,
+        on_submit: InputHandler,
+    },
+    Select {
+        title: String,
+        options: Vec<String>,
+        selected: usize,
+        on_select: SelectHandler,
+    },
+    FuzzySelect {
+        title: String,
+        options: Vec<(String, String)>,
+        on_select: SelectHandler,
+    },
+}
+
+impl fmt::Debug for ModalRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModalRequest::Confirm { title, .. } => f.debug_tuple("Confirm").field(title).finish(),
+            ModalRequest::Input { title, .. } => f.debug_tuple("Input").field(title).finish(),
+            ModalRequest::Select { title, .. } => f.debug_tuple("Select").field(title).finish(),
+            ModalRequest::FuzzySelect { title, .. } => {
+                f.debug_tuple("FuzzySelect").field(title).finish()
+            }
+        }
+    }
+}
+
+impl ModalRequest {
+    pub fn confirm(title: impl Into<String>, message: impl Into<String>, action: Action) -> Self {
+        ModalRequest::Confirm {
+            title: title.into(),
+            message: message.into(),
+            on_confirm: Box::new(action),
+        }
+    }
+
+    pub fn into_modal(self) -> Box<dyn Modal> {
+        match self {
+            ModalRequest::Confirm {
+                title,
+                message,
+                on_confirm,
+            } => Box::new(ConfirmModal::new(title, message, *on_confirm)),
+            ModalRequest::Input {
+                title,
+                prompt,
+                initial,
+                on_submit,
+            } => Box::new(InputModal::new(title, prompt, initial, on_submit)),
+            ModalRequest::Select {
+                title,
+                options,
+                selected,
+                on_select,
+            } => Box::new(SelectModal::new(title, options, selected, on_select)),
+            ModalRequest::FuzzySelect {
+                title,
+                options,
+                on_select,
+            } => Box::new(FuzzySelectModal::new(title, options, on_select)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ModalStack {
+    stack: Vec<Box<dyn Modal>>,
+}
+
+impl ModalStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, modal: Box<dyn Modal>) {
+        self.stack.push(modal);
+    }
+
+    pub fn open(&mut self, request: ModalRequest) {
+        self.push(request.into_modal());
+    }
+
+    pub fn pop(&mut self) -> Option<Box<dyn Modal>> {
+        self.stack.pop()
+    }
+
+    pub fn clear(&mut self) {
+        self.stack.clear();
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    pub fn handle_key(&mut self, key: &Key) -> Action {
+        let Some(top) = self.stack.last_mut() else {
+            return Action::None;
+        };
+
+        if *key == Key::Esc {
+            self.stack.pop();
+            return Action::Redraw;
+        }
+
+        match top.handle_key(key) {
+            ModalOutcome::Pending => Action::Redraw,
+            ModalOutcome::Dismiss => {
+                self.stack.pop();
+                Action::Redraw
+            }
+            ModalOutcome::Submit(action) => {
+                self.stack.pop();
+                action
+            }
+        }
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.stack.is_empty() {
+            return;
+        }
+
+        let colors = theme();
+        let depth = self.stack.len();
+
+        for (i, modal) in self.stack.iter_mut().enumerate() {
+            frame
+                .buffer_mut()
+                .set_style(area, Style::default().add_modifier(Modifier::DIM));
+
+            let (width, height) = modal.size();
+            let rect = centered_fixed(area, width, height);
+            let base = Style::default().fg(colors.text.primary).bg(colors.bg.base);
+            let border_style = if i + 1 == depth {
+                colors.focused_border
+            } else {
+                colors.unfocused_border
+            };
+
+            frame.render_widget(Clear, rect);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_set(border::ROUNDED)
+                .border_style(border_style)
+                .style(base)
+                .title(format!(" {} ", modal.title()));
+            let inner = block.inner(rect);
+            frame.render_widget(block, rect);
+
+            modal.view(frame, inner);
+        }
+    }
+}
+
+pub struct ConfirmModal {
+    title: String,
+    message: String,
+    on_confirm: Option<Action>,
+    confirm_selected: bool,
+}
+
+impl ConfirmModal {
+    pub fn new(title: String, message: String, on_confirm: Action) -> Self {
+        Self {
+            title,
+            message,
+            on_confirm: Some(on_confirm),
+            confirm_selected: true,
+        }
+    }
+
+    fn confirm(&mut self) -> ModalOutcome {
+        ModalOutcome::Submit(self.on_confirm.take().unwrap_or_default())
+    }
+}
+
+impl Modal for ConfirmModal {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn size(&self) -> (u16, u16) {
+        let width = (self.message.chars().count() as u16 + 6).clamp(32, 64);
+        (width, 7)
+    }
+
+    fn handle_key(&mut self, key: &Key) -> ModalOutcome {
+        match key {
+            Key::Char('y') | Key::Char('Y') => self.confirm(),
+            Key::Char('n') | Key::Char('N') => ModalOutcome::Dismiss,
+            Key::Left | Key::Right | Key::Tab | Key::BackTab | Key::Char('h') | Key::Char('l') => {
+                self.confirm_selected = !self.confirm_selected;
+                ModalOutcome::Pending
+            }
+            Key::Enter if self.confirm_selected => self.confirm(),
+            Key::Enter => ModalOutcome::Dismiss,
+            _ => ModalOutcome::Pending,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .margin(1)
+            .split(area);
+
+        let message = Paragraph::new(self.message.as_str())
+            .style(Style::default().fg(colors.text.primary))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(message, chunks[0]);
+
+        let button = |label: &'static str, active: bool| {
+            if active {
+                Span::styled(label, colors.active_selected)
+            } else {
+                Span::styled(label, Style::default().fg(colors.text.muted))
+            }
+        };
+        let buttons = Line::from(vec![
+            button(" [y] Yes ", self.confirm_selected),
+            Span::raw("   "),
+            button(" [n] No ", !self.confirm_selected),
+        ]);
+        frame.render_widget(
+            Paragraph::new(buttons).alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+}
+
+pub struct InputModal {
+    title: String,
+    prompt: String,
+    value: String,
+    on_submit: InputHandler,
+}
+
+impl InputModal {
+    pub fn new(title: String, prompt: String, initial: String, on_submit: InputHandler) -> Self {
+        Self {
+            title,
+            prompt,
+            value: initial,
+            on_submit,
+        }
+    }
+}
+
+impl Modal for InputModal {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn size(&self) -> (u16, u16) {
+        (56, 6)
+    }
+
+    fn handle_key(&mut self, key: &Key) -> ModalOutcome {
+        match key {
+            Key::Enter => ModalOutcome::Submit((self.on_submit)(std::mem::take(&mut self.value))),
+            Key::Backspace => {
+                self.value.pop();
+                ModalOutcome::Pending
+            }
+            Key::Ctrl('u') => {
+                self.value.clear();
+                ModalOutcome::Pending
+            }
+            Key::Char(c) => {
+                self.value.push(*c);
+                ModalOutcome::Pending
+            }
+            _ => ModalOutcome::Pending,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1)])
+            .margin(1)
+            .split(area);
+
+        frame.render_widget(
+            Paragraph::new(self.prompt.as_str()).style(Style::default().fg(colors.text.muted)),
+            chunks[0],
+        );
+
+        let input = Line::from(vec![
+            Span::styled("> ", Style::default().fg(colors.accent.primary)),
+            Span::styled(
+                self.value.as_str(),
+                Style::default().fg(colors.text.primary),
+            ),
+            Span::styled("█", Style::default().fg(colors.accent.primary)),
+        ]);
+        frame.render_widget(Paragraph::new(input), chunks[1]);
+    }
+}
+
+pub struct SelectModal {
+    title: String,
+    options: Vec<String>,
+    state: ListState,
+    on_select: SelectHandler,
+}
+
+impl SelectModal {
+    pub fn new(
+        title: String,
+        options: Vec<String>,
+        selected: usize,
+        on_select: SelectHandler,
+    ) -> Self {
+        let selected = (!options.is_empty()).then(|| selected.min(options.len() - 1));
+        Self {
+            title,
+            options,
+            state: ListState::default().with_selected(selected),
+            on_select,
+        }
+    }
+}
+
+impl Modal for SelectModal {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn size(&self) -> (u16, u16) {
+        let width = self
+            .options
+            .iter()
+            .map(|o| o.chars().count() as u16)
+            .max()
+            .unwrap_or(0)
+            .saturating_add(8)
+            .clamp(32, 64);
+        let height = (self.options.len() as u16).saturating_add(2).clamp(4, 18);
+        (width, height)
+    }
+
+    fn handle_key(&mut self, key: &Key) -> ModalOutcome {
+        match key {
+            Key::Up | Key::Char('k') => self.state.select_previous(),
+            Key::Down | Key::Char('j') => self.state.select_next(),
+            Key::Home | Key::Char('g') => self.state.select_first(),
+            Key::End | Key::Char('G') => self.state.select_last(),
+            Key::Enter => {
+                return match self.state.selected() {
+                    Some(idx) if idx < self.options.len() => {
+                        ModalOutcome::Submit((self.on_select)(idx))
+                    }
+                    _ => ModalOutcome::Dismiss,
+                };
+            }
+            _ => {}
+        }
+        ModalOutcome::Pending
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let items: Vec<ListItem> = self
+            .options
+            .iter()
+            .map(|o| ListItem::new(format!(" {o}")))
+            .collect();
+        let list = List::new(items)
+            .style(Style::default().fg(colors.text.primary))
+            .highlight_style(colors.active_selected);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
+
+pub struct FuzzySelectModal {
+    title: String,
+    options: Vec<(String, String)>,
+    query: String,
+    matches: Vec<(usize, Vec<usize>)>,
+    state: ListState,
+    on_select: SelectHandler,
+}
+
+impl FuzzySelectModal {
+    pub fn new(title: String, options: Vec<(String, String)>, on_select: SelectHandler) -> Self {
+        let mut modal = Self {
+            title,
+            options,
+            query: String::new(),
+            matches: Vec::new(),
+            state: ListState::default(),
+            on_select,
+        };
+        modal.refilter();
+        modal
+    }
+
+    fn refilter(&mut self) {
+        self.matches = fuzzy_match_positioned(
+            &self.query,
+            self.options
+                .iter()
+                .enumerate()
+                .map(|(i, (label, _))| (i, label.clone())),
+        );
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+}
+
+impl Modal for FuzzySelectModal {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn size(&self) -> (u16, u16) {
+        let height = (self.options.len() as u16).saturating_add(4).clamp(6, 20);
+        (72, height)
+    }
+
+    fn handle_key(&mut self, key: &Key) -> ModalOutcome {
+        match key {
+            Key::Up | Key::Ctrl('p') => self.state.select_previous(),
+            Key::Down | Key::Ctrl('n') => self.state.select_next(),
+            Key::Enter => {
+                return match self.state.selected().and_then(|i| self.matches.get(i)) {
+                    Some((idx, _)) => ModalOutcome::Submit((self.on_select)(*idx)),
+                    None => ModalOutcome::Dismiss,
+                };
+            }
+            Key::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            Key::Ctrl('u') => {
+                self.query.clear();
+                self.refilter();
+            }
+            Key::Char(c) => {
+                self.query.push(*c);
+                self.refilter();
+            }
+            _ => {}
+        }
+        ModalOutcome::Pending
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+
+        let input = Line::from(vec![
+            Span::styled(" > ", Style::default().fg(colors.accent.primary)),
+            Span::styled(
+                self.query.as_str(),
+                Style::default().fg(colors.text.primary),
+            ),
+            Span::styled("█", Style::default().fg(colors.accent.primary)),
+        ]);
+        frame.render_widget(Paragraph::new(input), chunks[0]);
+
+        if self.matches.is_empty() {
+            frame.render_widget(
+                Paragraph::new(" No matches").style(Style::default().fg(colors.text.muted)),
+                chunks[1],
+            );
+            return;
+        }
+
+        let width = chunks[1].width as usize;
+        let base = Style::default().fg(colors.text.primary);
+        let highlight = Style::default()
+            .fg(colors.accent.primary)
+            .add_modifier(Modifier::BOLD);
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|(idx, positions)| {
+                let (label, detail) = &self.options[*idx];
+                let available = width.saturating_sub(detail.width() + 3);
+                let label = text::truncate(label, available);
+                let mut spans = vec![Span::raw(" ")];
+                spans.extend(label.chars().enumerate().map(|(i, ch)| {
+                    let style = if positions.contains(&i) {
+                        highlight
+                    } else {
+                        base
+                    };
+                    Span::styled(ch.to_string(), style)
+                }));
+                spans.push(Span::raw(
+                    " ".repeat(available.saturating_sub(label.width()) + 1),
+                ));
+                spans.push(Span::styled(
+                    detail.clone(),
+                    Style::default().fg(colors.text.muted),
+                ));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(colors.active_selected);
+        frame.render_stateful_widget(list, chunks[1], &mut self.state);
+    }
+}
+
+fn centered_fixed(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
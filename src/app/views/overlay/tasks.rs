@@ -0,0 +1,85 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+use crate::{app::theme::theme, framework::tasks::TaskManager, stream::stream_stats};
+
+pub fn render_tasks(frame: &mut Frame, area: Rect, tasks: &TaskManager) {
+    let colors = theme();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(colors.focused_border)
+        .title(" Tasks ");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(inner);
+
+    let stats = tasks.stats();
+    let summary = Line::from(vec![
+        Span::styled(
+            format!(" {} running", stats.running),
+            Style::default().fg(colors.accent.primary),
+        ),
+        Span::styled(
+            format!(
+                "   {} spawned · {} completed · {} aborted",
+                stats.spawned, stats.completed, stats.aborted
+            ),
+            colors.muted,
+        ),
+    ]);
+    let audio = stream_stats();
+    let underruns = Line::from(vec![
+        Span::styled(
+            format!(" {} underruns", audio.underruns),
+            if audio.underruns > 0 {
+                colors.warning_style
+            } else {
+                Style::default().fg(colors.accent.primary)
+            },
+        ),
+        Span::styled(
+            format!(
+                "   {:.1}s filled with silence · decoder priority {}",
+                audio.underrun_time.as_secs_f32(),
+                if audio.decoder_elevated {
+                    "elevated"
+                } else {
+                    "normal"
+                }
+            ),
+            colors.muted,
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(vec![summary, underruns]), chunks[0]);
+
+    let mut running = tasks.running_tasks();
+    running.sort_by_key(|task| std::cmp::Reverse(task.2));
+
+    let items: Vec<ListItem> = running
+        .into_iter()
+        .map(|(_, description, elapsed)| {
+            let description = if description.is_empty() {
+                "(unnamed)".to_string()
+            } else {
+                description
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {:>7.1}s  ", elapsed.as_secs_f32()), colors.muted),
+                Span::styled(description, Style::default().fg(colors.text.primary)),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items), chunks[1]);
+}
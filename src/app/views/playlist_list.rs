@@ -1,6 +1,12 @@
 use std::sync::Arc;
 
-use ratatui::{Frame, layout::Rect};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+};
 use yandex_music::model::playlist::Playlist;
 
 use crate::{
@@ -13,11 +19,16 @@ use crate::{
         views::PlaylistRenderer,
     },
     framework::signals::Signal,
+    util::config::{self, SmartPlaylistConfig},
 };
 
+const MAX_SMART_ROWS: u16 = 6;
+
 pub struct PlaylistListView {
     pub source: Arc<PlaylistDataSource>,
     list: DynamicList<Playlist>,
+    smart_state: ListState,
+    smart_focused: bool,
 }
 
 impl PlaylistListView {
@@ -37,7 +48,12 @@ impl PlaylistListView {
                 }
             });
 
-        Self { source, list }
+        Self {
+            source,
+            list,
+            smart_state: ListState::default(),
+            smart_focused: false,
+        }
     }
 
     pub fn set_loading(&self, _loading: bool) {}
@@ -50,7 +66,45 @@ impl PlaylistListView {
         self.list.select_last();
     }
 
+    fn smart_playlists() -> Vec<SmartPlaylistConfig> {
+        config::get().smart_playlists.clone()
+    }
+
+    fn handle_smart_key(&mut self, key: &Key, smart: &[SmartPlaylistConfig]) -> Action {
+        let selected = self.smart_state.selected().unwrap_or(0);
+        match key {
+            Key::Up | Key::Char('k') => self.smart_state.select(Some(selected.saturating_sub(1))),
+            Key::Down | Key::Char('j') if selected + 1 < smart.len() => {
+                self.smart_state.select(Some(selected + 1));
+            }
+            Key::Down | Key::Char('j') => self.smart_focused = false,
+            Key::Enter => {
+                return smart.get(selected).map_or(Action::None, |p| {
+                    Action::Navigate(Route::SmartPlaylist {
+                        name: p.name.clone(),
+                    })
+                });
+            }
+            _ => return Action::None,
+        }
+        Action::Redraw
+    }
+
     pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        let smart = Self::smart_playlists();
+        if smart.is_empty() {
+            self.smart_focused = false;
+        } else if prefix.is_none() && !self.list.is_searching() {
+            if self.smart_focused {
+                return self.handle_smart_key(key, &smart);
+            }
+            if matches!(key, Key::Up | Key::Char('k')) && self.list.selection_signal().get() == 0 {
+                self.smart_focused = true;
+                self.smart_state.select(Some(smart.len() - 1));
+                return Action::Redraw;
+            }
+        }
+
         let list_action = self.list.handle_key(key, prefix);
         if !list_action.is_none() {
             return list_action;
@@ -63,9 +117,11 @@ impl PlaylistListView {
         if *key == Key::Enter
             && let Some(playlist) = self.list.selected_item()
         {
-            let kind = playlist.kind;
-            let title = playlist.title.clone();
-            return Action::Navigate(Route::Playlist { kind, title });
+            return Action::Navigate(Route::Playlist {
+                kind: playlist.kind,
+                owner: playlist.owner.uid,
+                title: playlist.title.clone(),
+            });
         }
 
         Action::None
@@ -86,7 +142,63 @@ impl PlaylistListView {
             return;
         }
 
-        self.list.view(frame, area);
+        let smart = Self::smart_playlists();
+        if smart.is_empty() {
+            self.list.view(frame, area);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length((smart.len() as u16).min(MAX_SMART_ROWS) + 2),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        self.render_smart(frame, chunks[0], &smart);
+        self.list.view(frame, chunks[1]);
+    }
+
+    fn render_smart(&mut self, frame: &mut Frame, area: Rect, smart: &[SmartPlaylistConfig]) {
+        let colors = theme();
+        let block = Block::default()
+            .borders(Borders::BOTTOM)
+            .title(" Smart Playlists ")
+            .title_style(Style::default().fg(colors.accent.primary));
+        let items: Vec<ListItem> = smart
+            .iter()
+            .map(|p| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {}  ", p.name),
+                        Style::default().fg(colors.text.primary),
+                    ),
+                    Span::styled(p.rule.clone(), Style::default().fg(colors.text.muted)),
+                ]))
+            })
+            .collect();
+
+        let mut list = List::new(items).block(block);
+        if self.smart_focused {
+            list = list.highlight_style(colors.active_selected);
+        }
+        frame.render_stateful_widget(list, area, &mut self.smart_state);
+    }
+
+    pub fn selected_item(&self) -> Option<Playlist> {
+        self.list.selected_item()
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.list.selected()
+    }
+
+    pub fn restore_selection(&mut self, index: usize) -> bool {
+        if self.source.total().is_none_or(|total| total <= index) {
+            return false;
+        }
+        self.list.select(index);
+        true
     }
 
     pub fn selection_signal(&self) -> Signal<usize> {
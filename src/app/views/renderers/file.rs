@@ -0,0 +1,72 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::icons::{FILE_ICON, FOLDER_ICON};
+use crate::app::data::{ItemRenderer, ListItem};
+use crate::app::theme::theme;
+use crate::util::fs::FileEntry;
+
+pub struct FileRenderer;
+
+impl FileRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for FileRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ItemRenderer<FileEntry> for FileRenderer {
+    fn render(
+        &self,
+        entry: &FileEntry,
+        _index: usize,
+        is_selected: bool,
+        is_playing: bool,
+    ) -> ListItem<'static> {
+        let colors = theme();
+        let text_muted = colors.muted;
+        let text_style = Style::default().fg(colors.text.primary);
+        let accent_style = Style::default().fg(colors.accent.primary);
+
+        let mut spans = Vec::new();
+        match entry {
+            FileEntry::Dir { name, .. } => {
+                spans.push(Span::styled(format!("{} ", FOLDER_ICON), accent_style));
+                spans.push(Span::styled(
+                    name.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ));
+            }
+            FileEntry::Track(track) => {
+                spans.push(Span::styled(format!("{} ", FILE_ICON), accent_style));
+                if let Some(number) = track.track_number {
+                    spans.push(Span::styled(format!("{:>2}. ", number), text_muted));
+                }
+                spans.push(Span::raw(track.title.clone()));
+                if let Some(artist) = &track.artist {
+                    spans.push(Span::styled(format!(" - {}", artist), text_muted));
+                }
+                if let Some(album) = &track.album {
+                    spans.push(Span::styled(format!(" • {}", album), text_muted));
+                }
+            }
+        }
+
+        let style = if is_selected {
+            colors.selected.add_modifier(Modifier::BOLD)
+        } else if is_playing {
+            accent_style
+        } else {
+            text_style
+        };
+
+        ListItem::from_lines(vec![Line::from(spans)]).style(style)
+    }
+}
@@ -4,3 +4,5 @@ pub const HEART_CROSSED: &str = "󰝙";
 pub const PLAYLIST_ICON: &str = "";
 pub const ALBUM_ICON: &str = "";
 pub const ARTIST_ICON: &str = "";
+pub const FOLDER_ICON: &str = "";
+pub const FILE_ICON: &str = "󰎇";
@@ -1,10 +1,12 @@
 pub mod album;
 pub mod artist;
+pub mod file;
 pub mod icons;
 pub mod playlist;
 pub mod track;
 
 pub use album::AlbumRenderer;
 pub use artist::ArtistRenderer;
+pub use file::FileRenderer;
 pub use playlist::PlaylistRenderer;
 pub use track::TrackRenderer;
@@ -4,7 +4,7 @@ use ratatui::{
 };
 use std::time::{SystemTime, UNIX_EPOCH};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
-use yandex_music::model::track::Track;
+use yandex_music::model::{album::TrackPosition, track::Track};
 
 use super::icons::{ARTIST_ICON, HEART_EMPTY, HEART_FILLED};
 use crate::{
@@ -14,10 +14,21 @@ use crate::{
         theme::theme,
         views::icons::HEART_CROSSED,
     },
-    cache::image::ImageCache,
+    audio::file_info::FileInfoResolver,
+    cache::image::{CoverSize, ImageCache},
     framework::signals::Signal,
+    util::{
+        config::{self, TrackColumn, TrackLayout},
+        format::{RowContext, RowFormat},
+    },
 };
 
+const INDEX_WIDTH: usize = 4;
+const LIKED_WIDTH: usize = 2;
+const DURATION_WIDTH: usize = 6;
+const EPISODE_BAR_WIDTH: usize = 10;
+const QUALITY_WIDTH: usize = 5;
+
 fn active_track_icon(is_playing: bool) -> &'static str {
     if is_playing {
         const FRAME_STEP_MS: u64 = 100;
@@ -47,14 +58,63 @@ fn active_track_icon(is_playing: bool) -> &'static str {
     }
 }
 
+fn highlight_spans(
+    text: &str,
+    width: usize,
+    base_style: Style,
+    match_positions: &[usize],
+    highlight_style: Style,
+) -> Vec<Span<'static>> {
+    let mut result = Vec::new();
+    let mut current_segment = String::new();
+    let mut current_width = 0usize;
+    let match_set: std::collections::HashSet<usize> = match_positions.iter().copied().collect();
+
+    for (i, ch) in text.chars().enumerate() {
+        let ch_width = ch.width().unwrap_or(0);
+        if current_width + ch_width + 1 > width {
+            if !current_segment.is_empty() {
+                result.push(Span::styled(current_segment.clone(), base_style));
+                current_segment.clear();
+            }
+            result.push(Span::styled("…".to_string(), base_style));
+            current_width += 1;
+            break;
+        }
+
+        if !match_positions.is_empty() && match_set.contains(&i) {
+            if !current_segment.is_empty() {
+                result.push(Span::styled(current_segment.clone(), base_style));
+                current_segment.clear();
+            }
+            result.push(Span::styled(ch.to_string(), highlight_style));
+        } else {
+            current_segment.push(ch);
+        }
+        current_width += ch_width;
+    }
+
+    if !current_segment.is_empty() {
+        result.push(Span::styled(current_segment, base_style));
+    }
+
+    if current_width < width {
+        result.push(Span::styled(" ".repeat(width - current_width), base_style));
+    }
+
+    result
+}
+
 pub struct TrackRenderer {
     library: LibrarySignals,
     playing_id: Signal<Option<String>>,
     playing_index: Option<Signal<usize>>,
     is_playing: Signal<bool>,
+    file_info: Option<FileInfoResolver>,
     show_album: bool,
     show_duration: bool,
     show_number: bool,
+    album: Option<(u32, Signal<usize>)>,
 }
 
 impl TrackRenderer {
@@ -68,9 +128,11 @@ impl TrackRenderer {
             playing_id,
             playing_index: None,
             is_playing,
+            file_info: None,
             show_album: true,
             show_duration: true,
             show_number: false,
+            album: None,
         }
     }
 
@@ -79,6 +141,11 @@ impl TrackRenderer {
         self
     }
 
+    pub fn with_file_info(mut self, file_info: FileInfoResolver) -> Self {
+        self.file_info = Some(file_info);
+        self
+    }
+
     pub fn with_album(mut self, show: bool) -> Self {
         self.show_album = show;
         self
@@ -94,6 +161,30 @@ impl TrackRenderer {
         self
     }
 
+    pub fn with_album_positions(mut self, album_id: u32, volumes: Signal<usize>) -> Self {
+        self.album = Some((album_id, volumes));
+        self.show_number = true;
+        self
+    }
+
+    fn album_position<'a>(&self, track: &'a Track) -> Option<&'a TrackPosition> {
+        let (album_id, _) = self.album.as_ref()?;
+        track
+            .albums
+            .iter()
+            .find(|a| a.id == Some(*album_id))?
+            .track_position
+            .as_ref()
+    }
+
+    fn quality_badge(codec: &str, bitrate: u32) -> String {
+        if codec.contains("flac") {
+            "FLAC".to_string()
+        } else {
+            format!("{bitrate}k")
+        }
+    }
+
     fn format_duration(duration: std::time::Duration) -> String {
         let secs = duration.as_secs();
         let mins = secs / 60;
@@ -101,48 +192,265 @@ impl TrackRenderer {
         format!("{}:{:02}", mins, secs)
     }
 
-    #[allow(dead_code)]
-    fn truncate_or_pad(s: &str, width: usize) -> String {
-        use unicode_width::UnicodeWidthStr;
-        let display_width = s.width();
-        if display_width > width {
-            let mut result = String::new();
-            let mut current_width = 0;
-            for ch in s.chars() {
-                let ch_width = ch.width().unwrap_or(0);
-                if current_width + ch_width + 1 > width {
-                    result.push('…');
-                    break;
-                }
-                result.push(ch);
-                current_width += ch_width;
-            }
-            result
+    fn render_formatted(
+        &self,
+        track: &Track,
+        index: usize,
+        is_selected: bool,
+        available_width: u16,
+        format: &RowFormat,
+    ) -> ListItem<'static> {
+        let colors = theme();
+        let selected_bg = colors.selected.bg.unwrap_or(colors.bg.selection);
+        let accent_style = Style::default().fg(colors.accent.primary);
+        let is_liked = self.library.is_liked(&track.id);
+        let is_disliked = self.library.is_disliked(&track.id);
+        let is_current = match &self.playing_index {
+            Some(idx_signal) => idx_signal.get() == index,
+            None => self.playing_id.get().as_ref() == Some(&track.id),
+        };
+
+        let prefix = if is_selected {
+            Span::styled(
+                "> ",
+                accent_style.bg(selected_bg).add_modifier(Modifier::BOLD),
+            )
+        } else if is_current {
+            Span::styled(
+                format!("{} ", active_track_icon(self.is_playing.get())),
+                accent_style,
+            )
         } else {
-            format!("{}{}", s, " ".repeat(width - display_width))
-        }
+            Span::raw("  ")
+        };
+
+        let liked = if is_disliked {
+            HEART_CROSSED
+        } else if is_liked {
+            HEART_FILLED
+        } else {
+            HEART_EMPTY
+        };
+        let text = format.render(&RowContext {
+            track,
+            index,
+            liked,
+            state: "",
+        });
+        let text = Self::truncate_or_pad(&text, available_width as usize);
+
+        let style = if is_selected {
+            colors.selected
+        } else if is_disliked {
+            colors.muted.add_modifier(Modifier::DIM)
+        } else {
+            Style::default().fg(colors.text.primary)
+        };
+        let text_style = if is_current {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        };
+        ListItem::from_lines(vec![Line::from(Span::styled(text, text_style))])
+            .style(style)
+            .with_prefix(vec![Line::from(prefix)])
     }
-}
 
-impl ItemRenderer<Track> for TrackRenderer {
-    fn render(
+    fn render_columns(
         &self,
         track: &Track,
         index: usize,
         is_selected: bool,
-        _is_playing: bool,
+        available_width: u16,
+        highlights: &MatchHighlights,
+        columns: &[TrackColumn],
     ) -> ListItem<'static> {
-        self.render_with_context(
-            track,
-            index,
-            is_selected,
-            _is_playing,
-            120,
-            &MatchHighlights::default(),
+        let colors = theme();
+        let selected_bg = colors.selected.bg.unwrap_or(colors.bg.selection);
+        let accent_style = Style::default().fg(colors.accent.primary);
+        let row_muted_style = if is_selected {
+            colors.muted.bg(selected_bg)
+        } else {
+            colors.muted
+        };
+        let base_text_style = if is_selected {
+            Style::default()
+                .fg(colors.selected.fg.unwrap_or(colors.text.primary))
+                .bg(selected_bg)
+        } else {
+            Style::default().fg(colors.text.primary)
+        };
+        let hl_style = if is_selected {
+            accent_style.bg(selected_bg).add_modifier(Modifier::BOLD)
+        } else {
+            accent_style.add_modifier(Modifier::BOLD)
+        };
+
+        let is_liked = self.library.is_liked(&track.id);
+        let is_disliked = self.library.is_disliked(&track.id);
+        let is_current = match &self.playing_index {
+            Some(idx_signal) => idx_signal.get() == index,
+            None => self.playing_id.get().as_ref() == Some(&track.id),
+        };
+
+        let prefix = if is_selected {
+            Span::styled(
+                "> ",
+                accent_style.bg(selected_bg).add_modifier(Modifier::BOLD),
+            )
+        } else if is_current {
+            Span::styled(
+                format!("{} ", active_track_icon(self.is_playing.get())),
+                accent_style,
+            )
+        } else {
+            Span::raw("  ")
+        };
+
+        let fixed: usize = columns
+            .iter()
+            .map(|c| match c {
+                TrackColumn::Index => INDEX_WIDTH,
+                TrackColumn::Liked => LIKED_WIDTH,
+                TrackColumn::Duration => DURATION_WIDTH,
+                _ => 0,
+            })
+            .sum();
+        let weight = |c: &TrackColumn| match c {
+            TrackColumn::Title => 4,
+            TrackColumn::Artist | TrackColumn::Album => 3,
+            _ => 0,
+        };
+        let total_weight: usize = columns.iter().map(weight).sum();
+        let gaps = columns.len().saturating_sub(1);
+        let flexible = (available_width as usize).saturating_sub(fixed + gaps);
+
+        let (title_style, artist_style, album_style) = match highlights.search_scope {
+            Some(SearchScope::Full) => (base_text_style, base_text_style, base_text_style),
+            Some(SearchScope::Title) => (base_text_style, row_muted_style, row_muted_style),
+            Some(SearchScope::Artist) => (row_muted_style, base_text_style, row_muted_style),
+            Some(SearchScope::Album) => (row_muted_style, row_muted_style, base_text_style),
+            None => (base_text_style, row_muted_style, row_muted_style),
+        };
+        let title_style = if is_current {
+            title_style.add_modifier(Modifier::BOLD)
+        } else if is_disliked {
+            row_muted_style.add_modifier(Modifier::DIM)
+        } else {
+            title_style
+        };
+
+        let mut spans = Vec::new();
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" ", row_muted_style));
+            }
+            let width = match weight(column) {
+                0 => 0,
+                w => flexible * w / total_weight.max(1),
+            };
+            match column {
+                TrackColumn::Index => spans.push(Span::styled(
+                    format!("{:>width$}", index + 1, width = INDEX_WIDTH),
+                    row_muted_style,
+                )),
+                TrackColumn::Liked => {
+                    let heart = if is_disliked {
+                        HEART_CROSSED
+                    } else if is_liked {
+                        HEART_FILLED
+                    } else {
+                        HEART_EMPTY
+                    };
+                    let style = if is_liked {
+                        row_muted_style.add_modifier(Modifier::BOLD)
+                    } else {
+                        row_muted_style
+                    };
+                    spans.push(Span::styled(
+                        Self::truncate_or_pad(heart, LIKED_WIDTH),
+                        style,
+                    ));
+                }
+                TrackColumn::Title => spans.extend(highlight_spans(
+                    track.title.as_deref().unwrap_or("Unknown"),
+                    width,
+                    title_style,
+                    &highlights.title,
+                    hl_style,
+                )),
+                TrackColumn::Artist => {
+                    let artists = track
+                        .artists
+                        .iter()
+                        .filter_map(|a| a.name.as_deref())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    spans.extend(highlight_spans(
+                        &artists,
+                        width,
+                        artist_style,
+                        &highlights.artist,
+                        hl_style,
+                    ));
+                }
+                TrackColumn::Album => spans.extend(highlight_spans(
+                    track
+                        .albums
+                        .first()
+                        .and_then(|a| a.title.as_deref())
+                        .unwrap_or_default(),
+                    width,
+                    album_style,
+                    &highlights.album,
+                    hl_style,
+                )),
+                TrackColumn::Duration => spans.push(Span::styled(
+                    format!(
+                        "{:>width$}",
+                        track
+                            .duration
+                            .map(Self::format_duration)
+                            .unwrap_or_default(),
+                        width = DURATION_WIDTH
+                    ),
+                    row_muted_style,
+                )),
+            }
+        }
+
+        let line_width = Line::from(spans.clone()).width();
+        if line_width < available_width as usize {
+            spans.push(Span::styled(
+                " ".repeat(available_width as usize - line_width),
+                row_muted_style,
+            ));
+        }
+
+        let style = if is_selected {
+            colors.selected
+        } else if is_disliked {
+            colors.muted.add_modifier(Modifier::DIM)
+        } else {
+            Style::default().fg(colors.text.primary)
+        };
+        ListItem::from_lines(vec![Line::from(spans)])
+            .style(style)
+            .with_prefix(vec![Line::from(prefix)])
+    }
+
+    fn truncate_or_pad(s: &str, width: usize) -> String {
+        use unicode_width::UnicodeWidthStr;
+        let truncated = crate::util::text::truncate(s, width);
+        let display_width = truncated.width();
+        format!(
+            "{}{}",
+            truncated,
+            " ".repeat(width.saturating_sub(display_width))
         )
     }
 
-    fn render_with_context(
+    fn render_row(
         &self,
         track: &Track,
         index: usize,
@@ -151,6 +459,22 @@ impl ItemRenderer<Track> for TrackRenderer {
         available_width: u16,
         highlights: &MatchHighlights,
     ) -> ListItem<'static> {
+        let config = config::get();
+        if let Some(format) = &config.track_list.row_format {
+            return self.render_formatted(track, index, is_selected, available_width, format);
+        }
+        if config.track_list.layout == TrackLayout::Columns && !config.track_list.columns.is_empty()
+        {
+            return self.render_columns(
+                track,
+                index,
+                is_selected,
+                available_width,
+                highlights,
+                &config.track_list.columns,
+            );
+        }
+
         let colors = theme();
         let text_style = Style::default().fg(colors.text.primary);
         let accent_style = Style::default().fg(colors.accent.primary);
@@ -184,7 +508,7 @@ impl ItemRenderer<Track> for TrackRenderer {
             .cover_uri
             .as_ref()
             .or_else(|| track.albums.first().and_then(|a| a.cover_uri.as_ref()))
-            .map(|uri| ImageCache::resolve_cover_uri(uri, "100x100"));
+            .map(|uri| ImageCache::resolve_cover_uri(uri, CoverSize::Thumbnail));
         let duration_str = if self.show_duration && track.duration.is_some() {
             Self::format_duration(track.duration.unwrap())
         } else {
@@ -210,10 +534,22 @@ impl ItemRenderer<Track> for TrackRenderer {
         let line2_prefix_width = format!("{}  ", ARTIST_ICON).width();
         let album_icon_width = if self.show_album { " 󰀥 ".width() } else { 0 };
 
-        let line1_text_budget =
-            (available_width as usize).saturating_sub(line1_prefix_width + duration_segment_width);
-        let line2_text_budget =
-            (available_width as usize).saturating_sub(line2_prefix_width + album_icon_width);
+        let quality = self.file_info.as_ref().map(|info| info.get(track));
+        let quality_width = if quality.is_some() {
+            QUALITY_WIDTH + 1
+        } else {
+            0
+        };
+        let line1_text_budget = (available_width as usize)
+            .saturating_sub(line1_prefix_width + duration_segment_width + quality_width);
+        let episode = self.library.episode_state(&track.id);
+        let episode_width = if episode.is_some() {
+            EPISODE_BAR_WIDTH + 2
+        } else {
+            0
+        };
+        let line2_text_budget = (available_width as usize)
+            .saturating_sub(line2_prefix_width + album_icon_width + episode_width);
 
         let (artist_width, album_width) = if self.show_album {
             let artist_w = (line2_text_budget * 65) / 100;
@@ -279,53 +615,6 @@ impl ItemRenderer<Track> for TrackRenderer {
             accent_style.add_modifier(Modifier::BOLD)
         };
 
-        let highlight_spans = |text: &str,
-                               width: usize,
-                               base_style: Style,
-                               match_positions: &[usize],
-                               highlight_style: Style|
-         -> Vec<Span<'static>> {
-            let mut result = Vec::new();
-            let mut current_segment = String::new();
-            let mut current_width = 0usize;
-            let match_set: std::collections::HashSet<usize> =
-                match_positions.iter().copied().collect();
-
-            for (i, ch) in text.chars().enumerate() {
-                let ch_width = ch.width().unwrap_or(0);
-                if current_width + ch_width + 1 > width {
-                    if !current_segment.is_empty() {
-                        result.push(Span::styled(current_segment.clone(), base_style));
-                        current_segment.clear();
-                    }
-                    result.push(Span::styled("…".to_string(), base_style));
-                    current_width += 1;
-                    break;
-                }
-
-                if !match_positions.is_empty() && match_set.contains(&i) {
-                    if !current_segment.is_empty() {
-                        result.push(Span::styled(current_segment.clone(), base_style));
-                        current_segment.clear();
-                    }
-                    result.push(Span::styled(ch.to_string(), highlight_style));
-                } else {
-                    current_segment.push(ch);
-                }
-                current_width += ch_width;
-            }
-
-            if !current_segment.is_empty() {
-                result.push(Span::styled(current_segment, base_style));
-            }
-
-            if current_width < width {
-                result.push(Span::styled(" ".repeat(width - current_width), base_style));
-            }
-
-            result
-        };
-
         let base_text_style = if is_selected {
             Style::default()
                 .fg(selected_style.fg.unwrap_or(colors.text.primary))
@@ -381,6 +670,38 @@ impl ItemRenderer<Track> for TrackRenderer {
             ));
         }
 
+        if let Some(state) = episode {
+            line2.push(Span::styled("  ", row_muted_style));
+            if state.played {
+                line2.push(Span::styled(
+                    format!("{:<width$}", "󰄬 played", width = EPISODE_BAR_WIDTH),
+                    row_muted_style,
+                ));
+            } else {
+                let filled = (state.ratio() * EPISODE_BAR_WIDTH as f64).round() as usize;
+                let bar_style = if is_selected {
+                    selected_accent_style
+                } else {
+                    accent_style
+                };
+                line2.push(Span::styled("━".repeat(filled), bar_style));
+                line2.push(Span::styled(
+                    "─".repeat(EPISODE_BAR_WIDTH - filled),
+                    row_muted_style,
+                ));
+            }
+        }
+
+        if let Some(quality) = quality {
+            let badge = quality
+                .map(|(codec, bitrate)| Self::quality_badge(&codec, bitrate))
+                .unwrap_or_default();
+            line1.push(Span::styled(
+                format!(" {badge:>width$}", width = QUALITY_WIDTH),
+                row_muted_style,
+            ));
+        }
+
         if self.show_duration && !duration_str.is_empty() {
             line1.push(Span::styled(" ", row_muted_style));
             line1.push(Span::styled("󰚭", row_muted_style));
@@ -425,3 +746,65 @@ impl ItemRenderer<Track> for TrackRenderer {
             .with_cover(cover_url)
     }
 }
+
+impl ItemRenderer<Track> for TrackRenderer {
+    fn render(
+        &self,
+        track: &Track,
+        index: usize,
+        is_selected: bool,
+        _is_playing: bool,
+    ) -> ListItem<'static> {
+        self.render_with_context(
+            track,
+            index,
+            is_selected,
+            _is_playing,
+            120,
+            &MatchHighlights::default(),
+        )
+    }
+
+    fn render_with_context(
+        &self,
+        track: &Track,
+        index: usize,
+        is_selected: bool,
+        is_playing: bool,
+        available_width: u16,
+        highlights: &MatchHighlights,
+    ) -> ListItem<'static> {
+        let Some(position) = self.album_position(track) else {
+            return self.render_row(
+                track,
+                index,
+                is_selected,
+                is_playing,
+                available_width,
+                highlights,
+            );
+        };
+
+        let item = self.render_row(
+            track,
+            (position.index as usize).saturating_sub(1),
+            is_selected,
+            is_playing,
+            available_width,
+            highlights,
+        );
+        let multi_disc = self
+            .album
+            .as_ref()
+            .is_some_and(|(_, volumes)| volumes.get() > 1);
+        if multi_disc && position.index == 1 {
+            let colors = theme();
+            item.with_header(Line::from(Span::styled(
+                format!("  Disc {}", position.volume),
+                colors.muted.add_modifier(Modifier::BOLD),
+            )))
+        } else {
+            item
+        }
+    }
+}
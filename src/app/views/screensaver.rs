@@ -0,0 +1,49 @@
+use chrono::Local;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+use yandex_music::model::track::Track;
+
+use crate::{
+    app::{components::Visualizer, theme::theme},
+    framework::Component,
+};
+
+pub fn render(frame: &mut Frame, visualizer: &Visualizer, track: Option<&Track>) {
+    let colors = theme();
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+    visualizer.view(frame, area);
+
+    let mut lines = vec![Line::from(Span::styled(
+        Local::now().format("%H:%M").to_string(),
+        Style::default()
+            .fg(colors.accent.primary)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    if let Some(track) = track {
+        let artists = track
+            .artists
+            .iter()
+            .filter_map(|a| a.name.as_deref())
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(Span::styled(
+            track.title.clone().unwrap_or_default(),
+            Style::default().fg(colors.text.primary),
+        )));
+        lines.push(Line::from(Span::styled(artists, colors.muted)));
+    }
+
+    let height = lines.len() as u16;
+    let info = Rect {
+        y: area.bottom().saturating_sub(height + 2),
+        height,
+        ..area
+    };
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), info);
+}
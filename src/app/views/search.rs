@@ -3,23 +3,28 @@ use std::sync::Arc;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::Modifier,
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Tabs},
 };
-use yandex_music::model::{album::Album, artist::Artist, playlist::Playlist, track::Track};
+use yandex_music::model::{
+    album::Album, artist::Artist, playlist::Playlist, search::search_result_item::SearchResultItem,
+    track::Track,
+};
 
 use crate::{
     app::{
         actions::{Action, Route},
-        components::{DynamicList, Spinner},
+        components::{DynamicList, Skeleton},
         data::{DataSource, StaticDataSource},
         keymap::Key,
         signals::AppSignals,
-        state::SearchTab,
+        state::{SearchFilters, SearchState, SearchTab},
         theme::theme,
         views::{AlbumRenderer, ArtistRenderer, PlaylistRenderer, TrackRenderer},
     },
     framework::signals::Signal,
+    util::link,
 };
 
 pub struct SearchView {
@@ -39,11 +44,23 @@ pub struct SearchView {
     playlist_source: Arc<StaticDataSource<Playlist>>,
     playlist_list: DynamicList<Playlist>,
 
+    podcast_source: Arc<StaticDataSource<Album>>,
+    podcast_list: DynamicList<Album>,
+
+    episode_source: Arc<StaticDataSource<Track>>,
+    episode_list: DynamicList<Track>,
+
     is_loading: Signal<bool>,
     is_loading_more: Signal<bool>,
 
     has_searched: bool,
 
+    best: Option<SearchResultItem>,
+    hero_focused: bool,
+
+    filters: SearchFilters,
+    all_tracks: Vec<Track>,
+    all_albums: Vec<Album>,
 }
 
 impl SearchView {
@@ -97,6 +114,17 @@ impl SearchView {
                 }
             });
 
+        let podcast_source = Arc::new(StaticDataSource::new(Vec::new()));
+        let podcast_list = DynamicList::new(podcast_source.clone(), Arc::new(AlbumRenderer::new()));
+
+        let episode_source = Arc::new(StaticDataSource::new(Vec::new()));
+        let episode_renderer = Arc::new(TrackRenderer::new(
+            signals.library.clone(),
+            signals.audio.current_track_id.clone(),
+            signals.audio.is_playing.clone(),
+        ));
+        let episode_list = DynamicList::new(episode_source.clone(), episode_renderer);
+
         let artist_source = Arc::new(StaticDataSource::new(Vec::new()));
         let artist_renderer = Arc::new(ArtistRenderer::new());
         let artist_list = DynamicList::new(artist_source.clone(), artist_renderer)
@@ -146,9 +174,18 @@ impl SearchView {
             artist_list,
             playlist_source,
             playlist_list,
+            podcast_source,
+            podcast_list,
+            episode_source,
+            episode_list,
             is_loading: Signal::new(false),
             is_loading_more: Signal::new(false),
             has_searched: false,
+            best: None,
+            hero_focused: false,
+            filters: SearchFilters::default(),
+            all_tracks: Vec::new(),
+            all_albums: Vec::new(),
         }
     }
 
@@ -162,6 +199,37 @@ impl SearchView {
             SearchTab::Albums => self.album_list.selected(),
             SearchTab::Artists => self.artist_list.selected(),
             SearchTab::Playlists => self.playlist_list.selected(),
+            SearchTab::Podcasts => self.podcast_list.selected(),
+            SearchTab::Episodes => self.episode_list.selected(),
+        }
+    }
+
+    pub fn selected_share_url(&self) -> Option<String> {
+        match self.current_tab.get() {
+            SearchTab::Tracks => self.track_list.selected_item().map(|t| link::track_url(&t)),
+            SearchTab::Albums => self
+                .album_list
+                .selected_item()
+                .and_then(|a| a.id)
+                .map(link::album_url),
+            SearchTab::Artists => self
+                .artist_list
+                .selected_item()
+                .and_then(|a| a.id)
+                .map(|id| link::artist_url(&id)),
+            SearchTab::Playlists => self
+                .playlist_list
+                .selected_item()
+                .map(|p| link::playlist_url(p.owner.uid, p.kind)),
+            SearchTab::Podcasts => self
+                .podcast_list
+                .selected_item()
+                .and_then(|a| a.id)
+                .map(link::album_url),
+            SearchTab::Episodes => self
+                .episode_list
+                .selected_item()
+                .map(|t| link::track_url(&t)),
         }
     }
 
@@ -171,6 +239,8 @@ impl SearchView {
             SearchTab::Albums => self.album_source.total().unwrap_or(0),
             SearchTab::Artists => self.artist_source.total().unwrap_or(0),
             SearchTab::Playlists => self.playlist_source.total().unwrap_or(0),
+            SearchTab::Podcasts => self.podcast_source.total().unwrap_or(0),
+            SearchTab::Episodes => self.episode_source.total().unwrap_or(0),
         }
     }
 
@@ -182,14 +252,25 @@ impl SearchView {
         self.query.get()
     }
 
-    pub fn apply(
-        &mut self,
-        tracks: Vec<yandex_music::model::track::Track>,
-        albums: Vec<yandex_music::model::album::Album>,
-        artists: Vec<yandex_music::model::artist::Artist>,
-        playlists: Vec<yandex_music::model::playlist::Playlist>,
-        optimal_tab: Option<SearchTab>,
-    ) {
+    pub fn focus_input(&mut self, query: Option<String>) {
+        if let Some(query) = query {
+            self.query.set(query);
+        }
+        self.input_mode.set(true);
+    }
+
+    pub fn apply(&mut self, state: &SearchState, optimal_tab: Option<SearchTab>) {
+        let tracks = state.tracks();
+        let albums = state.albums();
+        let artists = state.artists();
+        let playlists = state.playlists();
+        self.best = state
+            .results
+            .as_ref()
+            .and_then(|r| r.best.as_ref())
+            .map(|b| b.result.clone())
+            .filter(|item| hero_summary(item).is_some());
+        self.hero_focused = self.best.is_some();
         if let Some(tab) = optimal_tab {
             self.current_tab.set(tab);
         } else if tracks.is_empty() && !albums.is_empty() {
@@ -204,35 +285,63 @@ impl SearchView {
             self.current_tab.set(SearchTab::Playlists);
         }
 
-        self.track_source.set_items(tracks);
-        self.album_source.set_items(albums);
+        self.all_tracks = tracks;
+        self.all_albums = albums;
+        self.apply_filters();
         self.artist_source.set_items(artists);
         self.playlist_source.set_items(playlists);
+        self.podcast_source.set_items(state.podcasts());
+        self.episode_source.set_items(state.episodes());
 
         self.track_list.select_first();
         self.album_list.select_first();
         self.artist_list.select_first();
         self.playlist_list.select_first();
+        self.podcast_list.select_first();
+        self.episode_list.select_first();
 
         self.is_loading.set(false);
         self.is_loading_more.set(false);
         self.has_searched = true;
     }
 
-    pub fn apply_merged(
-        &mut self,
-        tracks: Vec<yandex_music::model::track::Track>,
-        albums: Vec<yandex_music::model::album::Album>,
-        artists: Vec<yandex_music::model::artist::Artist>,
-        playlists: Vec<yandex_music::model::playlist::Playlist>,
-    ) {
-        self.track_source.set_items(tracks);
-        self.album_source.set_items(albums);
-        self.artist_source.set_items(artists);
-        self.playlist_source.set_items(playlists);
+    pub fn apply_merged(&mut self, state: &SearchState) {
+        self.all_tracks = state.tracks();
+        self.all_albums = state.albums();
+        self.apply_filters();
+        self.artist_source.set_items(state.artists());
+        self.playlist_source.set_items(state.playlists());
+        self.podcast_source.set_items(state.podcasts());
+        self.episode_source.set_items(state.episodes());
         self.is_loading_more.set(false);
     }
 
+    fn apply_filters(&mut self) {
+        let filters = self.filters;
+        self.track_source.set_items(
+            self.all_tracks
+                .iter()
+                .filter(|t| filters.allows_track(t))
+                .cloned()
+                .collect(),
+        );
+        self.album_source.set_items(
+            self.all_albums
+                .iter()
+                .filter(|a| filters.allows_album(a))
+                .cloned()
+                .collect(),
+        );
+    }
+
+    fn update_filters(&mut self, f: impl FnOnce(&mut SearchFilters)) -> Action {
+        f(&mut self.filters);
+        self.apply_filters();
+        self.track_list.select_first();
+        self.album_list.select_first();
+        Action::SearchNextPage
+    }
+
     pub fn set_loading(&self, loading: bool) {
         self.is_loading.set(loading);
     }
@@ -242,20 +351,26 @@ impl SearchView {
     }
 
     pub fn scroll_top(&mut self) {
+        self.hero_focused = self.best.is_some();
         match self.current_tab.get() {
             SearchTab::Tracks => self.track_list.select_first(),
             SearchTab::Albums => self.album_list.select_first(),
             SearchTab::Artists => self.artist_list.select_first(),
             SearchTab::Playlists => self.playlist_list.select_first(),
+            SearchTab::Podcasts => self.podcast_list.select_first(),
+            SearchTab::Episodes => self.episode_list.select_first(),
         }
     }
 
     pub fn scroll_bottom(&mut self) {
+        self.hero_focused = false;
         match self.current_tab.get() {
             SearchTab::Tracks => self.track_list.select_last(),
             SearchTab::Albums => self.album_list.select_last(),
             SearchTab::Artists => self.artist_list.select_last(),
             SearchTab::Playlists => self.playlist_list.select_last(),
+            SearchTab::Podcasts => self.podcast_list.select_last(),
+            SearchTab::Episodes => self.episode_list.select_last(),
         }
     }
 
@@ -315,8 +430,25 @@ impl SearchView {
                     self.current_tab.update(|t| *t = t.prev());
                     return Action::Redraw;
                 }
+                Key::Char('x') => {
+                    return self.update_filters(|f| f.hide_explicit = !f.hide_explicit);
+                }
+                Key::Char('L') => {
+                    return self.update_filters(|f| f.duration = f.duration.next());
+                }
                 _ => {}
             }
+
+            if self.hero_focused {
+                return self.handle_hero_key(key);
+            }
+            if self.best.is_some()
+                && matches!(key, Key::Up | Key::Char('k'))
+                && self.current_selection() == 0
+            {
+                self.hero_focused = true;
+                return Action::Redraw;
+            }
         }
 
         let action = match self.current_tab.get() {
@@ -361,9 +493,34 @@ impl SearchView {
                     && *key == Key::Enter
                     && let Some(playlist) = self.playlist_list.selected_item()
                 {
-                    let kind = playlist.kind;
-                    let title = playlist.title.clone();
-                    return Action::Navigate(Route::Playlist { kind, title });
+                    return Action::Navigate(Route::Playlist {
+                        kind: playlist.kind,
+                        owner: playlist.owner.uid,
+                        title: playlist.title.clone(),
+                    });
+                }
+                action
+            }
+            SearchTab::Podcasts => {
+                let action = self.podcast_list.handle_key(key, prefix);
+                if prefix.is_none()
+                    && *key == Key::Enter
+                    && let Some(podcast) = self.podcast_list.selected_item()
+                    && let Some(podcast_id) = podcast.id
+                {
+                    let id = podcast_id.to_string();
+                    let title = podcast.title.clone().unwrap_or_default();
+                    return Action::Navigate(Route::Podcast { id, title });
+                }
+                action
+            }
+            SearchTab::Episodes => {
+                let action = self.episode_list.handle_key(key, prefix);
+                if prefix.is_none()
+                    && *key == Key::Enter
+                    && let Some(episode) = self.episode_list.selected_item()
+                {
+                    return Action::QueueTrack(episode);
                 }
                 action
             }
@@ -385,11 +542,86 @@ impl SearchView {
         Action::None
     }
 
+    fn handle_hero_key(&mut self, key: &Key) -> Action {
+        let Some(best) = self.best.clone() else {
+            return Action::None;
+        };
+        match key {
+            Key::Down | Key::Char('j') => {
+                self.hero_focused = false;
+                Action::Redraw
+            }
+            Key::Enter => match best {
+                SearchResultItem::Track(track) | SearchResultItem::PodcastEpisode(track) => {
+                    Action::PlayTrack(track)
+                }
+                SearchResultItem::Album(album) | SearchResultItem::Single(album) => album
+                    .id
+                    .map(|id| {
+                        Action::Navigate(Route::Album {
+                            id: id.to_string(),
+                            title: album.title.clone().unwrap_or_default(),
+                        })
+                    })
+                    .unwrap_or(Action::None),
+                SearchResultItem::Podcast(album) => album
+                    .id
+                    .map(|id| {
+                        Action::Navigate(Route::Podcast {
+                            id: id.to_string(),
+                            title: album.title.clone().unwrap_or_default(),
+                        })
+                    })
+                    .unwrap_or(Action::None),
+                SearchResultItem::Artist(artist) => artist
+                    .id
+                    .map(|id| {
+                        Action::Navigate(Route::Artist {
+                            id,
+                            name: artist.name.clone().unwrap_or_default(),
+                        })
+                    })
+                    .unwrap_or(Action::None),
+                SearchResultItem::Playlist(playlist) => Action::Navigate(Route::Playlist {
+                    kind: playlist.kind,
+                    owner: playlist.owner.uid,
+                    title: playlist.title,
+                }),
+                _ => Action::None,
+            },
+            Key::Char('w') => match best {
+                SearchResultItem::Track(track) => Action::wave_for_track(&track),
+                SearchResultItem::Album(album) | SearchResultItem::Single(album) => album
+                    .id
+                    .map(|id| Action::wave(format!("album:{id}"), album.title.clone()))
+                    .unwrap_or(Action::None),
+                SearchResultItem::Artist(artist) => artist
+                    .id
+                    .map(|id| Action::wave(format!("artist:{id}"), artist.name.clone()))
+                    .unwrap_or(Action::None),
+                SearchResultItem::Playlist(playlist) => Action::wave(
+                    format!("playlist:{}_{}", playlist.uid, playlist.kind),
+                    Some(playlist.title),
+                ),
+                _ => Action::None,
+            },
+            Key::Char('a') => match best {
+                SearchResultItem::Track(track) | SearchResultItem::PodcastEpisode(track) => {
+                    Action::QueueTrack(track)
+                }
+                _ => Action::None,
+            },
+            _ => Action::None,
+        }
+    }
+
     pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let hero_height = if self.best.is_some() { 4 } else { 0 };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
+                Constraint::Length(hero_height),
                 Constraint::Length(3),
                 Constraint::Min(0),
             ])
@@ -397,9 +629,52 @@ impl SearchView {
 
         self.render_input(frame, chunks[0]);
 
-        self.render_tabs(frame, chunks[1]);
+        self.render_hero(frame, chunks[1]);
+
+        self.render_tabs(frame, chunks[2]);
 
-        self.render_results(frame, chunks[2]);
+        self.render_results(frame, chunks[3]);
+    }
+
+    fn render_hero(&self, frame: &mut Frame, area: Rect) {
+        let Some((kind, title, subtitle)) = self.best.as_ref().and_then(hero_summary) else {
+            return;
+        };
+        let colors = theme();
+        let in_list = !self.hero_focused || self.input_mode.get();
+        let border_style = if in_list {
+            colors.unfocused_border
+        } else {
+            colors.focused_border
+        };
+        let hints = match self.best {
+            Some(SearchResultItem::Track(_)) => "[Enter] play  [w] wave  [a] add",
+            Some(SearchResultItem::PodcastEpisode(_)) => "[Enter] play  [a] add",
+            Some(SearchResultItem::Podcast(_)) => "[Enter] open",
+            _ => "[Enter] open  [w] wave",
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Top result ")
+            .title_bottom(Line::from(format!(" {hints} ")).right_aligned())
+            .border_style(border_style);
+        let lines = vec![
+            Line::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(colors.text.primary)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(vec![
+                Span::styled(kind, Style::default().fg(colors.accent.primary)),
+                Span::styled(
+                    subtitle.map(|s| format!(" • {s}")).unwrap_or_default(),
+                    Style::default().fg(colors.text.muted),
+                ),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(lines).block(block), area);
     }
 
     fn render_input(&self, frame: &mut Frame, area: Rect) {
@@ -437,6 +712,8 @@ impl SearchView {
                     SearchTab::Albums => "󰀥",
                     SearchTab::Artists => "",
                     SearchTab::Playlists => "",
+                    SearchTab::Podcasts => "󰦔",
+                    SearchTab::Episodes => "󰍬",
                 };
                 format!("{} {}", icon, t.title())
             })
@@ -445,8 +722,13 @@ impl SearchView {
         let text_muted = themed.muted;
         let selected = themed.selected;
 
+        let mut block = Block::default().borders(Borders::BOTTOM);
+        if let Some(label) = self.filters.label() {
+            block = block.title_top(Line::from(format!(" {label} ")).right_aligned());
+        }
+
         let tabs = Tabs::new(titles)
-            .block(Block::default().borders(Borders::BOTTOM))
+            .block(block)
             .select(tab.index())
             .style(text_muted)
             .highlight_style(selected.add_modifier(Modifier::BOLD));
@@ -455,12 +737,18 @@ impl SearchView {
     }
 
     fn render_results(&mut self, frame: &mut Frame, area: Rect) {
+        let area = if self.is_loading_more.get() && area.height > 1 {
+            let [list_area, footer] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+            let footer_text = Paragraph::new(" Loading more...").style(theme().muted);
+            frame.render_widget(footer_text, footer);
+            list_area
+        } else {
+            area
+        };
+
         if self.is_loading.get() && !self.has_searched {
-            let colors = theme();
-            let spinner = Spinner::new()
-                .with_label("Searching...")
-                .with_style(ratatui::style::Style::default().fg(colors.accent.primary));
-            spinner.view(frame, area);
+            Skeleton::new().view(frame, area);
             return;
         }
 
@@ -507,8 +795,62 @@ impl SearchView {
                     self.playlist_list.view(frame, area);
                 }
             }
+            SearchTab::Podcasts => {
+                if self.podcast_source.total().is_none_or(|t| t == 0) && self.has_searched {
+                    let paragraph = Paragraph::new("No podcasts found")
+                        .style(text_muted)
+                        .block(Block::default().borders(Borders::NONE));
+                    frame.render_widget(paragraph, area);
+                } else {
+                    self.podcast_list.view(frame, area);
+                }
+            }
+            SearchTab::Episodes => {
+                if self.episode_source.total().is_none_or(|t| t == 0) && self.has_searched {
+                    let paragraph = Paragraph::new("No episodes found")
+                        .style(text_muted)
+                        .block(Block::default().borders(Borders::NONE));
+                    frame.render_widget(paragraph, area);
+                } else {
+                    self.episode_list.view(frame, area);
+                }
+            }
         }
+    }
+}
 
-        if self.is_loading_more.get() {}
+fn hero_summary(item: &SearchResultItem) -> Option<(&'static str, String, Option<String>)> {
+    let artists = |artists: &[Artist]| {
+        let names: Vec<&str> = artists.iter().filter_map(|a| a.name.as_deref()).collect();
+        (!names.is_empty()).then(|| names.join(", "))
+    };
+    match item {
+        SearchResultItem::Track(track) => {
+            Some(("Track", track.title.clone()?, artists(&track.artists)))
+        }
+        SearchResultItem::PodcastEpisode(track) => Some((
+            "Episode",
+            track.title.clone()?,
+            track.albums.first().and_then(|a| a.title.clone()),
+        )),
+        SearchResultItem::Album(album) | SearchResultItem::Single(album) => {
+            Some(("Album", album.title.clone()?, artists(&album.artists)))
+        }
+        SearchResultItem::Podcast(album) => Some(("Podcast", album.title.clone()?, None)),
+        SearchResultItem::Artist(artist) => Some((
+            "Artist",
+            artist.name.clone()?,
+            artist
+                .genres
+                .as_ref()
+                .filter(|g| !g.is_empty())
+                .map(|g| g.join(", ")),
+        )),
+        SearchResultItem::Playlist(playlist) => Some((
+            "Playlist",
+            playlist.title.clone(),
+            playlist.owner.name.clone(),
+        )),
+        _ => None,
     }
 }
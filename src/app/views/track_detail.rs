@@ -0,0 +1,592 @@
+use std::{str::FromStr, sync::Arc};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph, Tabs},
+};
+use yandex_music::model::track::{Track, TrackDerivedColors};
+
+use crate::{
+    app::{
+        actions::{Action, Route},
+        components::{DynamicList, Header, HeaderLine, Spinner},
+        data::{DataSource, StaticDataSource},
+        keymap::Key,
+        signals::AppSignals,
+        theme::theme,
+        views::TrackRenderer,
+    },
+    audio::local::{self, LocalTrack},
+    cache::image::{CoverSize, ImageCache},
+    framework::{
+        reactive::{Resource, ResourceState, Update, create_effect, signal},
+        signals::Signal,
+    },
+    http::MusicApi,
+    util::track::is_explicit,
+};
+
+#[derive(Debug, Clone)]
+enum DetailRow {
+    Section(&'static str),
+    Field {
+        label: &'static str,
+        value: String,
+    },
+    Artist {
+        id: String,
+        name: String,
+        role: &'static str,
+    },
+    Album {
+        id: u32,
+        title: String,
+        year: Option<u16>,
+    },
+    Colors(TrackDerivedColors),
+}
+
+impl DetailRow {
+    fn target(&self) -> Option<Route> {
+        match self {
+            DetailRow::Artist { id, name, .. } => Some(Route::Artist {
+                id: id.clone(),
+                name: name.clone(),
+            }),
+            DetailRow::Album { id, title, .. } => Some(Route::Album {
+                id: id.to_string(),
+                title: title.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Details,
+    Similar,
+}
+
+pub struct TrackDetailView {
+    track_id: String,
+    api: Arc<dyn MusicApi>,
+    track: Resource<Track>,
+    formats: Resource<Vec<(String, u32)>>,
+    similar: Option<Resource<Vec<Track>>>,
+    similar_source: Arc<StaticDataSource<Track>>,
+    similar_list: DynamicList<Track>,
+    similar_filled: bool,
+    tab: Tab,
+    header: Option<Header>,
+    rows: Vec<DetailRow>,
+    state: ListState,
+    changed: Signal<u64>,
+    built: Option<u64>,
+}
+
+impl TrackDetailView {
+    pub fn new(track_id: String, api: Arc<dyn MusicApi>, signals: &AppSignals) -> Self {
+        let track = Resource::new({
+            let api = api.clone();
+            let track_id = track_id.clone();
+            move || {
+                let api = api.clone();
+                let track_id = track_id.clone();
+                async move {
+                    if let Some(path) = track_id.strip_prefix(local::LOCAL_ID_PREFIX) {
+                        return LocalTrack::read(path)
+                            .map(|t| t.to_track())
+                            .ok_or_else(|| "File not found".to_string());
+                    }
+                    api.fetch_tracks(vec![track_id])
+                        .await
+                        .map_err(|e| e.to_string())?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| "Track not found".to_string())
+                }
+            }
+        });
+
+        let formats = if local::is_local_id(&track_id) {
+            Resource::ready(Vec::new())
+        } else {
+            Resource::new({
+                let api = api.clone();
+                let track_id = track_id.clone();
+                move || {
+                    let api = api.clone();
+                    let track_id = track_id.clone();
+                    async move {
+                        api.fetch_track_formats(track_id)
+                            .await
+                            .map_err(|e| e.to_string())
+                    }
+                }
+            })
+        };
+
+        let changed = signal(0u64);
+        create_effect({
+            let track = track.clone();
+            let formats = formats.clone();
+            let changed = changed.clone();
+            move |_| {
+                track.state.track();
+                formats.state.track();
+                Update::update(&changed, |v| *v += 1);
+            }
+        });
+
+        let similar_source = Arc::new(StaticDataSource::new(Vec::new()));
+        let renderer = TrackRenderer::new(
+            signals.library.clone(),
+            signals.audio.current_track_id.clone(),
+            signals.audio.is_playing.clone(),
+        );
+        let similar_list = DynamicList::new(similar_source.clone(), Arc::new(renderer));
+
+        Self {
+            track_id,
+            api,
+            track,
+            formats,
+            similar: None,
+            similar_source,
+            similar_list,
+            similar_filled: false,
+            tab: Tab::Details,
+            header: None,
+            rows: Vec::new(),
+            state: ListState::default().with_selected(Some(0)),
+            changed,
+            built: None,
+        }
+    }
+
+    pub fn selected_track(&self) -> Option<Track> {
+        match self.tab {
+            Tab::Details => self.track.value(),
+            Tab::Similar => self.similar_list.selected_item(),
+        }
+    }
+
+    pub fn scroll_top(&mut self) {
+        match self.tab {
+            Tab::Details => self.state.select_first(),
+            Tab::Similar => self.similar_list.select_first(),
+        }
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        match self.tab {
+            Tab::Details => self.state.select_last(),
+            Tab::Similar => self.similar_list.select_last(),
+        }
+    }
+
+    fn switch_tab(&mut self, tab: Tab) {
+        self.tab = tab;
+        if tab == Tab::Similar && self.similar.is_none() {
+            let api = self.api.clone();
+            let track_id = self.track_id.clone();
+            self.similar = Some(Resource::new(move || {
+                let api = api.clone();
+                let track_id = track_id.clone();
+                async move {
+                    api.fetch_similar_tracks(track_id)
+                        .await
+                        .map_err(|e| e.to_string())
+                }
+            }));
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        if prefix.is_none() {
+            match key {
+                Key::Char('[') => {
+                    self.switch_tab(Tab::Details);
+                    return Action::Redraw;
+                }
+                Key::Char(']') if !local::is_local_id(&self.track_id) => {
+                    self.switch_tab(Tab::Similar);
+                    return Action::Redraw;
+                }
+                _ => {}
+            }
+        }
+
+        match self.tab {
+            Tab::Details => self.handle_details_key(key, prefix),
+            Tab::Similar => self.handle_similar_key(key, prefix),
+        }
+    }
+
+    fn handle_similar_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        let list_action = self.similar_list.handle_key(key, prefix);
+        if !list_action.is_none() || prefix.is_some() {
+            return list_action;
+        }
+
+        let Some(track) = self.similar_list.selected_item() else {
+            return Action::None;
+        };
+        match key {
+            Key::Enter => Action::Navigate(Route::Track { id: track.id }),
+            Key::Char('p') => Action::PlayTrack(track),
+            _ => Action::None,
+        }
+    }
+
+    fn handle_details_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        if prefix.is_some() {
+            return Action::None;
+        }
+
+        match key {
+            Key::Up | Key::Char('k') => self.step(-1),
+            Key::Down | Key::Char('j') => self.step(1),
+            Key::Enter => {
+                return self
+                    .state
+                    .selected()
+                    .and_then(|i| self.rows.get(i))
+                    .and_then(DetailRow::target)
+                    .map_or(Action::None, Action::Navigate);
+            }
+            _ => return Action::None,
+        }
+        Action::Redraw
+    }
+
+    fn step(&mut self, delta: isize) {
+        let len = self.rows.len() as isize;
+        let mut index = self.state.selected().unwrap_or(0) as isize;
+        loop {
+            index += delta;
+            if index < 0 || index >= len {
+                return;
+            }
+            if !matches!(self.rows[index as usize], DetailRow::Section(_)) {
+                self.state.select(Some(index as usize));
+                return;
+            }
+        }
+    }
+
+    fn rebuild(&mut self, track: &Track) {
+        let version = self.changed.get();
+        if self.built == Some(version) {
+            return;
+        }
+        self.built = Some(version);
+
+        let formats = self.formats.get();
+        self.rows = build_rows(track, &formats);
+        if self.header.is_none() {
+            self.header = Some(build_header(track));
+        }
+        if self
+            .state
+            .selected()
+            .and_then(|i| self.rows.get(i))
+            .is_none_or(|row| matches!(row, DetailRow::Section(_)))
+        {
+            self.state.select(None);
+            self.step(1);
+        }
+    }
+
+    pub fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let track = match self.track.get() {
+            ResourceState::Ready(track) | ResourceState::Stale(track) => track,
+            ResourceState::Error(e) => {
+                let message = Line::from(Span::styled(
+                    format!(" Failed to load track: {e}"),
+                    Style::default().fg(colors.text.muted),
+                ));
+                frame.render_widget(message, area);
+                return;
+            }
+            _ => {
+                Spinner::new()
+                    .with_label("Loading track...")
+                    .with_style(Style::default().fg(colors.accent.primary))
+                    .view(frame, area);
+                return;
+            }
+        };
+        self.rebuild(&track);
+
+        let header_height = self.header.as_ref().map_or(0, Header::height);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header_height),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        if let Some(header) = &mut self.header {
+            match ImageCache::global_picker() {
+                Some(mut picker) => header.view_with_picker(frame, chunks[0], &mut picker),
+                None => header.view(frame, chunks[0]),
+            }
+        }
+
+        let mut titles = vec![" Details "];
+        if !local::is_local(&track) {
+            titles.push(" Similar ");
+        }
+        let tabs = Tabs::new(titles)
+            .select(match self.tab {
+                Tab::Details => 0,
+                Tab::Similar => 1,
+            })
+            .style(Style::default().fg(colors.text.muted))
+            .highlight_style(
+                Style::default()
+                    .fg(colors.accent.primary)
+                    .add_modifier(Modifier::BOLD),
+            );
+        frame.render_widget(tabs, chunks[1]);
+
+        match self.tab {
+            Tab::Details => {
+                let items: Vec<ListItem> = self.rows.iter().map(render_row).collect();
+                let list = List::new(items).highlight_style(colors.active_selected);
+                frame.render_stateful_widget(list, chunks[2], &mut self.state);
+            }
+            Tab::Similar => self.view_similar(frame, chunks[2]),
+        }
+    }
+
+    fn view_similar(&mut self, frame: &mut Frame, area: Rect) {
+        let colors = theme();
+        let state = self.similar.as_ref().map(Resource::get);
+        match state {
+            Some(ResourceState::Ready(tracks)) | Some(ResourceState::Stale(tracks)) => {
+                if !self.similar_filled {
+                    self.similar_source.set_items(tracks);
+                    self.similar_filled = true;
+                }
+                if self.similar_source.total().unwrap_or(0) == 0 {
+                    let message = Paragraph::new(" No similar tracks")
+                        .style(Style::default().fg(colors.text.muted));
+                    frame.render_widget(message, area);
+                } else {
+                    self.similar_list.view(frame, area);
+                }
+            }
+            Some(ResourceState::Error(e)) => {
+                let message = Paragraph::new(format!(" Failed to load similar tracks: {e}"))
+                    .style(Style::default().fg(colors.text.muted));
+                frame.render_widget(message, area);
+            }
+            _ => {
+                Spinner::new()
+                    .with_label("Loading similar tracks...")
+                    .with_style(Style::default().fg(colors.accent.primary))
+                    .view(frame, area);
+            }
+        }
+    }
+}
+
+fn build_header(track: &Track) -> Header {
+    let title = match &track.version {
+        Some(version) => format!(
+            "{} ({version})",
+            track.title.as_deref().unwrap_or("Unknown Track")
+        ),
+        None => track
+            .title
+            .clone()
+            .unwrap_or_else(|| "Unknown Track".into()),
+    };
+    let artists = track
+        .artists
+        .iter()
+        .filter_map(|a| a.name.as_deref())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let album = track.albums.first().and_then(|a| a.title.clone());
+
+    let mut lines = vec![HeaderLine::title(title), HeaderLine::subtitle(artists)];
+    if let Some(album) = album {
+        lines.push(HeaderLine::text(album));
+    }
+
+    let cover_url = track
+        .cover_uri
+        .as_deref()
+        .or_else(|| track.albums.first().and_then(|a| a.cover_uri.as_deref()))
+        .map(|uri| ImageCache::resolve_cover_uri(uri, CoverSize::Header));
+    Header::new(lines).with_height(5).with_cover_url(cover_url)
+}
+
+fn build_rows(track: &Track, formats: &ResourceState<Vec<(String, u32)>>) -> Vec<DetailRow> {
+    let mut rows = vec![DetailRow::Section("Artists")];
+    for artist in &track.artists {
+        let (Some(id), Some(name)) = (&artist.id, &artist.name) else {
+            continue;
+        };
+        let role = if artist.composer == Some(true) {
+            "Composer"
+        } else if artist.various == Some(true) {
+            "Various"
+        } else {
+            "Artist"
+        };
+        rows.push(DetailRow::Artist {
+            id: id.clone(),
+            name: name.clone(),
+            role,
+        });
+    }
+    for artist in track.albums.iter().flat_map(|a| a.artists.iter()) {
+        let (Some(id), Some(name)) = (&artist.id, &artist.name) else {
+            continue;
+        };
+        let known = rows
+            .iter()
+            .any(|row| matches!(row, DetailRow::Artist { id: known, .. } if known == id));
+        if !known {
+            rows.push(DetailRow::Artist {
+                id: id.clone(),
+                name: name.clone(),
+                role: "Album artist",
+            });
+        }
+    }
+
+    if !track.albums.is_empty() {
+        rows.push(DetailRow::Section("Albums"));
+        rows.extend(track.albums.iter().filter_map(|album| {
+            Some(DetailRow::Album {
+                id: album.id?,
+                title: album.title.clone()?,
+                year: album.year,
+            })
+        }));
+    }
+
+    rows.push(DetailRow::Section("Track"));
+    if let Some(duration) = track.duration {
+        let secs = duration.as_secs();
+        rows.push(DetailRow::Field {
+            label: "Duration",
+            value: format!("{}:{:02}", secs / 60, secs % 60),
+        });
+    }
+    let explicit = is_explicit(track);
+    rows.push(DetailRow::Field {
+        label: "Explicit",
+        value: if explicit { "Yes" } else { "No" }.to_string(),
+    });
+    if let Some(genre) = track.albums.first().and_then(|a| a.genre.clone()) {
+        rows.push(DetailRow::Field {
+            label: "Genre",
+            value: genre,
+        });
+    }
+    if let Some(album) = track.albums.first()
+        && let Some(position) = &album.track_position
+    {
+        rows.push(DetailRow::Field {
+            label: "Position",
+            value: format!("Disc {} • Track {}", position.volume, position.index),
+        });
+    }
+
+    rows.push(DetailRow::Section("Formats"));
+    match formats {
+        ResourceState::Ready(formats) | ResourceState::Stale(formats) if formats.is_empty() => {
+            rows.push(DetailRow::Field {
+                label: "Codecs",
+                value: if local::is_local(track) {
+                    LocalTrack::from_track(track)
+                        .map(|t| t.codec())
+                        .unwrap_or_default()
+                } else {
+                    "Unavailable".to_string()
+                },
+            });
+        }
+        ResourceState::Ready(formats) | ResourceState::Stale(formats) => {
+            rows.extend(formats.iter().map(|(codec, bitrate)| DetailRow::Field {
+                label: "Codec",
+                value: format!("{codec} • {bitrate} kbps"),
+            }));
+        }
+        ResourceState::Error(e) => rows.push(DetailRow::Field {
+            label: "Codecs",
+            value: e.clone(),
+        }),
+        ResourceState::Loading | ResourceState::Idle => rows.push(DetailRow::Field {
+            label: "Codecs",
+            value: "Loading...".to_string(),
+        }),
+    }
+
+    if let Some(derived) = &track.derived_colors {
+        rows.push(DetailRow::Section("Colors"));
+        rows.push(DetailRow::Colors(derived.clone()));
+    }
+
+    rows
+}
+
+fn render_row(row: &DetailRow) -> ListItem<'static> {
+    let colors = theme();
+    let label_style = Style::default().fg(colors.text.muted);
+    let value_style = Style::default().fg(colors.text.primary);
+    let line = match row {
+        DetailRow::Section(title) => Line::from(Span::styled(
+            format!(" {title}"),
+            Style::default()
+                .fg(colors.accent.primary)
+                .add_modifier(Modifier::BOLD),
+        )),
+        DetailRow::Field { label, value } => Line::from(vec![
+            Span::styled(format!("   {label:<14}"), label_style),
+            Span::styled(value.clone(), value_style),
+        ]),
+        DetailRow::Artist { name, role, .. } => Line::from(vec![
+            Span::styled(format!("   {role:<14}"), label_style),
+            Span::styled(name.clone(), value_style),
+        ]),
+        DetailRow::Album { title, year, .. } => {
+            let mut spans = vec![
+                Span::styled(format!("   {:<14}", "Album"), label_style),
+                Span::styled(title.clone(), value_style),
+            ];
+            if let Some(year) = year {
+                spans.push(Span::styled(format!(" • {year}"), label_style));
+            }
+            Line::from(spans)
+        }
+        DetailRow::Colors(derived) => {
+            let mut spans = Vec::new();
+            for (label, hex) in [
+                ("average", &derived.average),
+                ("accent", &derived.accent),
+                ("wave", &derived.wave_text),
+                ("player", &derived.mini_player),
+            ] {
+                let color = Color::from_str(hex).unwrap_or(colors.text.muted);
+                spans.push(Span::styled("   ██ ", Style::default().fg(color)));
+                spans.push(Span::styled(format!("{label} {hex}"), label_style));
+            }
+            Line::from(spans)
+        }
+    };
+    ListItem::new(line)
+}
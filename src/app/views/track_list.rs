@@ -1,26 +1,37 @@
 use std::sync::Arc;
 
+use im::{HashSet, Vector};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
 };
 use yandex_music::model::{playlist::Playlist, track::Track};
 
 use crate::{
     app::{
-        actions::Action,
-        components::{DynamicList, Header, HeaderBuilder, Spinner},
-        data::{DataSource, PlaylistInfo},
+        actions::{Action, Route},
+        components::{Description, DynamicList, Header, HeaderBuilder, Skeleton},
+        data::{AlbumInfo, DataSource, PlaylistInfo, RemoteChange},
         keymap::Key,
-        signals::AppSignals,
+        signals::{AppSignals, LibrarySignals},
         theme::theme,
         views::TrackRenderer,
     },
-    audio::queue::PlaybackContext,
-    cache::image::ImageCache,
-    framework::signals::Signal,
+    audio::{local::QueueItem, queue::PlaybackContext},
+    cache::image::{CoverSize, ImageCache},
+    framework::{
+        reactive::{Update, create_effect},
+        signals::Signal,
+        tasks::ScopeGuard,
+    },
+    util::state::{PlaylistPreferences, PlaylistPrefs, TrackSort},
 };
 
+const COLLAGE_TILES: usize = 4;
+const COLLAGE_SCAN_LIMIT: usize = 50;
+
 #[derive(Debug, Clone)]
 pub enum TrackListContext {
     Playlist {
@@ -30,13 +41,17 @@ pub enum TrackListContext {
         owner_uid: u64,
         track_count: usize,
         cover_url: Option<String>,
+        info: Option<PlaylistInfo>,
     },
     Album {
         id: String,
         title: String,
-        artists: String,
-        year: Option<i32>,
-        track_count: usize,
+        cover_url: Option<String>,
+        info: Option<AlbumInfo>,
+    },
+    Podcast {
+        id: String,
+        title: String,
         cover_url: Option<String>,
     },
     Artist {
@@ -51,6 +66,11 @@ pub enum TrackListContext {
         query: String,
         result_count: usize,
     },
+    Smart {
+        title: String,
+        rule: String,
+        track_count: usize,
+    },
     Queue,
     Standalone,
 }
@@ -63,22 +83,42 @@ impl TrackListContext {
                 owner,
                 track_count,
                 cover_url,
+                info,
                 ..
             } => {
-                let header = HeaderBuilder::playlist(title, owner, *track_count, None);
+                let info = info.as_ref();
+                let header = HeaderBuilder::playlist(
+                    title,
+                    owner,
+                    *track_count,
+                    info.and_then(|i| i.duration_ms)
+                        .filter(|ms| *ms > 0)
+                        .map(format_total_duration),
+                    info.and_then(|i| i.visibility.as_deref())
+                        .map(visibility_label),
+                    info.and_then(playlist_dates),
+                );
                 Some(header.with_cover_url(cover_url.clone()))
             }
             TrackListContext::Album {
                 title,
-                artists,
-                year,
-                track_count,
                 cover_url,
+                info,
                 ..
             } => {
-                let header = HeaderBuilder::album(title, artists, *year, *track_count);
+                let info = info.as_ref();
+                let header = HeaderBuilder::album(
+                    title,
+                    info.map_or("", |i| i.artists.as_str()),
+                    info.and_then(|i| i.year),
+                    info.map_or(0, |i| i.track_count),
+                    info.and_then(album_details),
+                );
                 Some(header.with_cover_url(cover_url.clone()))
             }
+            TrackListContext::Podcast {
+                title, cover_url, ..
+            } => Some(HeaderBuilder::podcast(title).with_cover_url(cover_url.clone())),
             TrackListContext::Artist {
                 name,
                 genres,
@@ -94,6 +134,11 @@ impl TrackListContext {
                 query,
                 result_count,
             } => Some(HeaderBuilder::search(query, *result_count)),
+            TrackListContext::Smart {
+                title,
+                rule,
+                track_count,
+            } => Some(HeaderBuilder::smart_playlist(title, rule, *track_count)),
             TrackListContext::Queue | TrackListContext::Standalone => None,
         }
     }
@@ -113,6 +158,7 @@ impl TrackListContext {
         match self {
             TrackListContext::Playlist { cover_url, .. }
             | TrackListContext::Album { cover_url, .. }
+            | TrackListContext::Podcast { cover_url, .. }
             | TrackListContext::Artist { cover_url, .. } => cover_url.as_deref(),
             _ => None,
         }
@@ -122,6 +168,7 @@ impl TrackListContext {
         match self {
             TrackListContext::Playlist { cover_url, .. }
             | TrackListContext::Album { cover_url, .. }
+            | TrackListContext::Podcast { cover_url, .. }
             | TrackListContext::Artist { cover_url, .. } => *cover_url = url,
             _ => {}
         }
@@ -133,8 +180,20 @@ pub struct TrackListView {
     source: Arc<dyn DataSource<Track>>,
     list: DynamicList<Track>,
     header: Option<Header>,
+    description: Option<Description>,
     playlist: Option<Playlist>,
     playlist_info_signal: Option<Signal<Option<PlaylistInfo>>>,
+    album_info_signal: Option<Signal<Option<AlbumInfo>>>,
+    artist_description: Option<Signal<Option<String>>>,
+    album_volumes: Signal<usize>,
+    collage_checked: bool,
+    library: LibrarySignals,
+    prefs_key: Option<String>,
+    prefs: PlaylistPrefs,
+    liked_changed: Option<Signal<u64>>,
+    remote_change: Option<Signal<Option<RemoteChange>>>,
+    pending_cursor: Option<(String, usize)>,
+    tasks: Option<ScopeGuard>,
 }
 
 impl TrackListView {
@@ -150,33 +209,54 @@ impl TrackListView {
         );
 
         if matches!(context, TrackListContext::Queue) {
-            renderer = renderer.with_queue_index(signals.audio.queue_index.clone());
+            renderer = renderer
+                .with_queue_index(signals.audio.queue_index.clone())
+                .with_file_info(signals.file_info.clone());
+        }
+
+        let album_volumes = Signal::new(0);
+        if let TrackListContext::Album { id, .. } = &context {
+            renderer =
+                renderer.with_album_positions(id.parse().unwrap_or(0), album_volumes.clone());
         }
 
         let renderer = Arc::new(renderer);
-        let list = DynamicList::new(source.clone(), renderer).with_fuzzy(|track| {
-            use crate::app::components::FuzzyFields;
-            let title = track.title.clone().unwrap_or_default();
-            let artists = track
-                .artists
-                .iter()
-                .filter_map(|a| a.name.as_ref())
-                .cloned()
-                .collect::<Vec<_>>()
-                .join(" ");
-            let album = track
-                .albums
-                .first()
-                .and_then(|a| a.title.clone())
-                .unwrap_or_default();
-            let full = format!("{} {} {}", title, artists, album);
-            FuzzyFields {
-                full,
-                title: Some(title),
-                artist: Some(artists),
-                album: Some(album),
-            }
-        });
+        let mut list = DynamicList::new(source.clone(), renderer)
+            .with_marking(|track: &Track| track.id.clone())
+            .with_fuzzy(|track| {
+                use crate::app::components::FuzzyFields;
+                let title = track.title.clone().unwrap_or_default();
+                let artists = track
+                    .artists
+                    .iter()
+                    .filter_map(|a| a.name.as_ref())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let album = track
+                    .albums
+                    .first()
+                    .and_then(|a| a.title.clone())
+                    .unwrap_or_default();
+                let full = format!("{} {} {}", title, artists, album);
+                FuzzyFields {
+                    full,
+                    title: Some(title),
+                    artist: Some(artists),
+                    album: Some(album),
+                }
+            });
+
+        if matches!(context, TrackListContext::Playlist { .. }) {
+            list = list.with_grouping(|track| {
+                use crate::app::components::ItemGroup;
+                let album = track.albums.first()?;
+                Some(ItemGroup {
+                    key: album.id?.to_string(),
+                    label: album.title.clone().unwrap_or_default(),
+                })
+            });
+        }
 
         if let Some(url) = context.cover_url() {
             let cache = ImageCache::global();
@@ -190,8 +270,20 @@ impl TrackListView {
             source,
             list,
             header,
+            description: None,
             playlist: None,
             playlist_info_signal: None,
+            album_info_signal: None,
+            artist_description: None,
+            album_volumes,
+            collage_checked: false,
+            library: signals.library.clone(),
+            prefs_key: None,
+            prefs: PlaylistPrefs::default(),
+            liked_changed: None,
+            remote_change: None,
+            pending_cursor: None,
+            tasks: None,
         }
     }
 
@@ -200,11 +292,56 @@ impl TrackListView {
         self
     }
 
+    pub fn with_remote_change(mut self, change: Signal<Option<RemoteChange>>) -> Self {
+        self.remote_change = Some(change);
+        self
+    }
+
+    pub fn with_album_info(mut self, info: Signal<Option<AlbumInfo>>) -> Self {
+        self.album_info_signal = Some(info);
+        self
+    }
+
+    pub fn with_artist_description(mut self, description: Signal<Option<String>>) -> Self {
+        self.artist_description = Some(description);
+        self
+    }
+
+    pub fn with_tasks(mut self, tasks: ScopeGuard) -> Self {
+        self.tasks = Some(tasks);
+        self
+    }
+
     pub fn context(&self) -> &TrackListContext {
         &self.context
     }
 
     fn maybe_update_header(&mut self) {
+        if let TrackListContext::Album {
+            cover_url,
+            info: context_info @ None,
+            ..
+        } = &mut self.context
+            && let Some(info) = self.album_info_signal.as_ref().and_then(|sig| sig.get())
+        {
+            if cover_url.is_none() {
+                *cover_url = info
+                    .cover_uri
+                    .as_deref()
+                    .map(|uri| ImageCache::resolve_cover_uri(uri, CoverSize::Header));
+            }
+            self.album_volumes.set(info.volumes);
+            *context_info = Some(info);
+            self.header = self.context.build_header();
+            return;
+        }
+
+        if let Some(text) = self.artist_description.as_ref().and_then(|sig| sig.get()) {
+            self.description = Description::new(&text);
+            self.artist_description = None;
+            return;
+        }
+
         let info = match &self.playlist_info_signal {
             Some(sig) => match sig.get() {
                 Some(info) => info,
@@ -213,16 +350,15 @@ impl TrackListView {
             None => return,
         };
 
-        let needs_update = matches!(
-            &self.context,
-            TrackListContext::Playlist { track_count, .. } if *track_count == 0
-        );
-        if needs_update && info.track_count > 0 {
+        let needs_update = matches!(&self.context, TrackListContext::Playlist { info: None, .. });
+        if needs_update {
             if let TrackListContext::Playlist {
+                kind,
                 track_count,
                 owner,
                 owner_uid,
                 cover_url,
+                info: context_info,
                 ..
             } = &mut self.context
             {
@@ -230,12 +366,140 @@ impl TrackListView {
                 *owner = info.owner.clone();
                 *owner_uid = info.owner_uid;
 
+                let key = PlaylistPreferences::key(*owner_uid, *kind);
+                self.prefs = self.library.playlist_prefs(&key);
+                self.prefs_key = Some(key);
+
                 if cover_url.is_none() {
-                    if let Some(uri) = &info.cover_uri {
-                        *cover_url = Some(ImageCache::resolve_cover_uri(uri, "200x200"));
+                    *cover_url = playlist_cover_url(&info);
+                }
+                self.description = info.description.as_deref().and_then(Description::new);
+                *context_info = Some(info);
+            }
+            self.header = self.context.build_header();
+            self.apply_prefs();
+        }
+    }
+
+    fn pending_remote_change(&self) -> Option<RemoteChange> {
+        self.remote_change.as_ref().and_then(|change| change.get())
+    }
+
+    fn refresh_remote(&mut self) {
+        if let Some(track) = self.list.selected_item() {
+            self.pending_cursor = Some((track.id, self.list.selected()));
+        }
+        if let TrackListContext::Playlist { info, .. } = &mut self.context {
+            *info = None;
+        }
+        self.source.refresh();
+    }
+
+    fn restore_cursor(&mut self) {
+        let Some((track_id, index)) = &self.pending_cursor else {
+            return;
+        };
+        let index = *index;
+        let tracks = self.source.range(0..usize::MAX);
+        match tracks.iter().position(|t| &t.id == track_id) {
+            Some(found) => {
+                self.list.select(found);
+                self.pending_cursor = None;
+            }
+            None => {
+                self.list.select(index);
+                if tracks.len() > index {
+                    self.pending_cursor = None;
+                }
+            }
+        }
+    }
+
+    fn apply_prefs(&mut self) {
+        let PlaylistPrefs {
+            sort,
+            liked_only,
+            grouped,
+        } = self.prefs;
+        self.list.set_grouped(grouped);
+        if sort == TrackSort::Playlist && !liked_only {
+            self.list.clear_arrangement();
+            return;
+        }
+        let liked_ids = liked_only.then(|| self.library.liked_track_ids.clone());
+        let dependencies = if liked_only {
+            self.liked_changed()
+        } else {
+            Signal::new(0)
+        };
+        self.list.arrange(
+            move |tracks| arrange_tracks(tracks, sort, liked_ids.as_ref()),
+            dependencies,
+        );
+    }
+
+    fn liked_changed(&mut self) -> Signal<u64> {
+        let liked_ids = &self.library.liked_track_ids;
+        self.liked_changed
+            .get_or_insert_with(|| {
+                let changed = Signal::new(0u64);
+                create_effect({
+                    let liked_ids = liked_ids.clone();
+                    let changed = changed.clone();
+                    move |_| {
+                        liked_ids.track();
+                        Update::update(&changed, |v| *v += 1);
                     }
+                });
+                changed
+            })
+            .clone()
+    }
+
+    fn update_prefs(&mut self, update: impl FnOnce(&mut PlaylistPrefs)) {
+        update(&mut self.prefs);
+        self.apply_prefs();
+        if let Some(key) = &self.prefs_key {
+            self.library.save_playlist_prefs(key, self.prefs);
+        }
+    }
+
+    fn maybe_build_track_collage(&mut self) {
+        if self.collage_checked {
+            return;
+        }
+        let TrackListContext::Playlist {
+            cover_url: None,
+            info: Some(_),
+            ..
+        } = &self.context
+        else {
+            return;
+        };
+        let loaded = self.source.total().unwrap_or(0).min(COLLAGE_SCAN_LIMIT);
+        if loaded == 0 || !self.source.is_loaded(0..loaded) {
+            return;
+        }
+        self.collage_checked = true;
+
+        let mut uris: Vec<String> = Vec::new();
+        for track in self.source.range(0..loaded) {
+            let uri = track
+                .cover_uri
+                .clone()
+                .or_else(|| track.albums.first().and_then(|a| a.cover_uri.clone()));
+            if let Some(uri) = uri.map(|u| ImageCache::resolve_cover_uri(&u, CoverSize::Thumbnail))
+                && !uris.contains(&uri)
+            {
+                uris.push(uri);
+                if uris.len() == COLLAGE_TILES {
+                    break;
                 }
             }
+        }
+
+        if let Some(url) = ImageCache::collage_url(&uris) {
+            self.context.set_cover_url(Some(url));
             self.header = self.context.build_header();
         }
     }
@@ -249,6 +513,7 @@ impl TrackListView {
     }
 
     pub fn handle_key(&mut self, key: &Key, prefix: Option<char>) -> Action {
+        self.pending_cursor = None;
         let list_action = self.list.handle_key(key, prefix);
         if !list_action.is_none() {
             return list_action;
@@ -258,37 +523,131 @@ impl TrackListView {
             return Action::None;
         }
 
+        if let Some(description) = &mut self.description {
+            match key {
+                Key::Char('i') => {
+                    description.toggle();
+                    return Action::Redraw;
+                }
+                Key::Char('J') if description.is_expanded() => {
+                    description.scroll_down();
+                    return Action::Redraw;
+                }
+                Key::Char('K') if description.is_expanded() => {
+                    description.scroll_up();
+                    return Action::Redraw;
+                }
+                _ => {}
+            }
+        }
+
+        if key == &Key::Char('b')
+            && let TrackListContext::Artist { id, name, .. } = &self.context
+        {
+            return Action::Navigate(Route::ArtistAlbums {
+                id: id.clone(),
+                name: name.clone(),
+            });
+        }
+
+        if matches!(self.context, TrackListContext::Playlist { .. }) {
+            match key {
+                Key::Char('R') if self.pending_remote_change().is_some() => {
+                    self.refresh_remote();
+                    return Action::Redraw;
+                }
+                Key::Char('Z') => {
+                    self.update_prefs(|prefs| prefs.grouped = !prefs.grouped);
+                    return Action::Redraw;
+                }
+                Key::Char('z') if self.list.toggle_group() => return Action::Redraw,
+                Key::Char('o') => {
+                    self.update_prefs(|prefs| prefs.sort = prefs.sort.next());
+                    return Action::Toast(format!("Sorted by {}", self.prefs.sort.label()));
+                }
+                Key::Char('F') => {
+                    self.update_prefs(|prefs| prefs.liked_only = !prefs.liked_only);
+                    let message = if self.prefs.liked_only {
+                        "Showing liked tracks only"
+                    } else {
+                        "Showing all tracks"
+                    };
+                    return Action::Toast(message.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        if key == &Key::Char('p')
+            && matches!(self.context, TrackListContext::Podcast { .. })
+            && let Some(track) = self.list.selected_item()
+        {
+            return Action::ToggleEpisodePlayed(track);
+        }
+
         if key == &Key::Enter
             && let Some(_track) = self.list.selected_item()
         {
-            let index = self.list.selected();
-            let tracks = self.source.range(0..self.source.total().unwrap_or(0));
-            let context = if let Some(playlist) = &self.playlist {
-                PlaybackContext::Playlist(playlist.clone())
-            } else {
-                self.context.playback_context()
-            };
-            return Action::PlayContext {
-                context,
-                tracks,
-                start_index: index,
-            };
+            return self.play_from(self.list.selected());
         }
 
         Action::None
     }
 
+    pub fn play_from(&self, index: usize) -> Action {
+        let tracks = self.source.range(0..self.source.total().unwrap_or(0));
+        let (tracks, index) = match self.list.display_order() {
+            Some(order) => {
+                let start = order.iter().position(|i| *i == index).unwrap_or(0);
+                let arranged = order.iter().filter_map(|i| tracks.get(*i).cloned());
+                (arranged.collect(), start)
+            }
+            None => (tracks, index),
+        };
+        let context = if let Some(playlist) = &self.playlist {
+            PlaybackContext::Playlist(playlist.clone())
+        } else {
+            self.context.playback_context()
+        };
+        Action::PlayContext {
+            context,
+            tracks: tracks.into_iter().map(QueueItem::from).collect(),
+            start_index: index,
+        }
+    }
+
     pub fn view(&mut self, frame: &mut Frame, area: Rect) {
         self.maybe_update_header();
+        self.maybe_build_track_collage();
+        self.restore_cursor();
 
         use crate::app::data::FetchState;
         let is_loading = matches!(self.source.fetch_state(), FetchState::Loading);
         let no_tracks = self.source.total().is_none_or(|t| t == 0);
 
+        let remote_change = self.pending_remote_change();
+        let area = match remote_change {
+            Some(change) => {
+                let [banner, rest] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+                render_remote_banner(frame, banner, change);
+                rest
+            }
+            None => area,
+        };
+
         if let Some(header) = &mut self.header {
+            let description_height = self
+                .description
+                .as_ref()
+                .map_or(0, |d| d.height(area.width));
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Length(header.height()), Constraint::Min(0)])
+                .constraints([
+                    Constraint::Length(header.height()),
+                    Constraint::Length(description_height),
+                    Constraint::Min(0),
+                ])
                 .split(area);
 
             if let Some(mut picker) = ImageCache::global_picker() {
@@ -297,19 +656,17 @@ impl TrackListView {
                 header.view(frame, chunks[0]);
             }
 
+            if let Some(description) = &mut self.description {
+                description.view(frame, chunks[1]);
+            }
+
             if is_loading && no_tracks {
-                let spinner = Spinner::new()
-                    .with_label("Loading tracks...")
-                    .with_style(ratatui::style::Style::default().fg(theme().accent.primary));
-                spinner.view(frame, chunks[1]);
+                Skeleton::new().view(frame, chunks[2]);
             } else {
-                self.list.view(frame, chunks[1]);
+                self.list.view(frame, chunks[2]);
             }
         } else if is_loading && no_tracks {
-            let spinner = Spinner::new()
-                .with_label("Loading tracks...")
-                .with_style(ratatui::style::Style::default().fg(theme().accent.primary));
-            spinner.view(frame, area);
+            Skeleton::new().view(frame, area);
         } else {
             self.list.view(frame, area);
         }
@@ -323,19 +680,24 @@ impl TrackListView {
             owner_uid,
             track_count,
             cover_url,
+            info,
             ..
         } = &mut self.context
         {
+            let playlist_info = PlaylistInfo::from(&playlist);
             *title = playlist.title.clone();
             *owner = playlist.owner.name.clone().unwrap_or_default();
             *owner_uid = playlist.owner.uid;
             *track_count = playlist.track_count as usize;
 
             if cover_url.is_none() {
-                if let Some(uri) = &playlist.cover.uri {
-                    *cover_url = Some(ImageCache::resolve_cover_uri(uri, "200x200"));
-                }
+                *cover_url = playlist_cover_url(&playlist_info);
             }
+            self.description = playlist_info
+                .description
+                .as_deref()
+                .and_then(Description::new);
+            *info = Some(playlist_info);
         }
 
         self.header = self.context.build_header();
@@ -349,12 +711,123 @@ impl TrackListView {
         self.list.selected()
     }
 
+    pub fn restore_selection(&mut self, index: usize) -> bool {
+        if self.source.total().is_none_or(|total| total <= index) {
+            return false;
+        }
+        self.list.select(index);
+        true
+    }
+
     pub fn selected_item(&self) -> Option<Track> {
         self.list.selected_item()
     }
 
+    pub fn marked_items(&self) -> Vec<Track> {
+        self.list.marked_items()
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.list.clear_marks();
+    }
+
     pub fn items(&self) -> im::Vector<Track> {
         let total = self.source.total().unwrap_or(0);
         self.source.range(0..total)
     }
 }
+
+fn render_remote_banner(frame: &mut Frame, area: Rect, change: RemoteChange) {
+    let colors = theme();
+    let line = Line::from(vec![
+        Span::styled(
+            format!(" {} — refresh? ", change.summary()),
+            colors.warning_style,
+        ),
+        Span::styled("R", colors.keyword),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn arrange_tracks(
+    tracks: &Vector<Track>,
+    sort: TrackSort,
+    liked_ids: Option<&Signal<HashSet<String>>>,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = match liked_ids {
+        Some(liked) => liked.with(|ids| {
+            (0..tracks.len())
+                .filter(|i| ids.contains(&tracks[*i].id))
+                .collect()
+        }),
+        None => (0..tracks.len()).collect(),
+    };
+
+    let text = |value: Option<&String>| value.map(|s| s.to_lowercase()).unwrap_or_default();
+    let title = |i: usize| text(tracks[i].title.as_ref());
+    let artist = |i: usize| text(tracks[i].artists.first().and_then(|a| a.name.as_ref()));
+    let album = |i: usize| text(tracks[i].albums.first().and_then(|a| a.title.as_ref()));
+    match sort {
+        TrackSort::Playlist => {}
+        TrackSort::Title => order.sort_by_cached_key(|i| title(*i)),
+        TrackSort::Artist => order.sort_by_cached_key(|i| (artist(*i), album(*i), title(*i))),
+        TrackSort::Album => order.sort_by_cached_key(|i| (album(*i), title(*i))),
+        TrackSort::Duration => order.sort_by_key(|i| tracks[*i].duration),
+    }
+    order
+}
+
+fn playlist_cover_url(info: &PlaylistInfo) -> Option<String> {
+    if let Some(uri) = &info.cover_uri {
+        return Some(ImageCache::resolve_cover_uri(uri, CoverSize::Header));
+    }
+    let uris: Vec<String> = info
+        .collage_uris
+        .iter()
+        .map(|uri| ImageCache::resolve_cover_uri(uri, CoverSize::Thumbnail))
+        .collect();
+    ImageCache::collage_url(&uris)
+}
+
+fn playlist_dates(info: &PlaylistInfo) -> Option<String> {
+    let created = info.created.map(|d| d.format("%-d %b %Y").to_string());
+    let modified = info.modified.map(|d| d.format("%-d %b %Y").to_string());
+    match (created, modified) {
+        (Some(created), Some(modified)) if created != modified => {
+            Some(format!("Created {created} • Updated {modified}"))
+        }
+        (Some(created), _) => Some(format!("Created {created}")),
+        (None, Some(modified)) => Some(format!("Updated {modified}")),
+        (None, None) => None,
+    }
+}
+
+fn album_details(info: &AlbumInfo) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(released) = info.release_date {
+        parts.push(format!("Released {}", released.format("%-d %b %Y")));
+    }
+    if let Some(label) = &info.label {
+        parts.push(label.clone());
+    }
+    if info.volumes > 1 {
+        parts.push(format!("{} discs", info.volumes));
+    }
+    (!parts.is_empty()).then(|| parts.join(" • "))
+}
+
+fn visibility_label(visibility: &str) -> &str {
+    match visibility {
+        "public" => "Public",
+        "private" => "Private",
+        other => other,
+    }
+}
+
+fn format_total_duration(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m} min"),
+        (h, m) => format!("{h} h {m} min"),
+    }
+}
@@ -23,4 +23,8 @@ impl UrlCache {
             .unwrap()
             .insert(track_id, (url, codec, bitrate));
     }
+
+    pub fn remove(&self, track_id: &str) {
+        self.cache.write().unwrap().remove(track_id);
+    }
 }
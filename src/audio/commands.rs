@@ -1,13 +1,16 @@
 use std::time::Duration;
 use yandex_music::model::track::Track;
 
+use crate::audio::local::QueueItem;
+
 #[derive(Debug, Clone)]
 pub enum AudioCommand {
-    PlayTrack(Track),
+    PlayTrack(QueueItem),
     Pause,
     Resume,
     Stop,
     Seek(Duration),
+    Reload,
     SetVolume(f32),
     LoadTrack(Track),
 }
@@ -1,7 +1,10 @@
 use flume::Sender;
 use rodio::Source;
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{
+    Arc, RwLock,
+    atomic::{AtomicU64, Ordering},
+};
 use std::time::Duration;
 use tokio::sync::Mutex;
 use yandex_music::model::track::Track;
@@ -10,24 +13,42 @@ use crate::audio::{
     commands::AudioCommand,
     fx::{
         EffectHandle, FxSource,
-        modules::{FadeEffect, MonitorEffect},
+        modules::{FadeEffect, MonitorEffect, track_gain},
         param::EffectParams,
     },
+    local::QueueItem,
+    outputs::{OutputReport, OutputTarget, RemoteMedia, RemoteOutput},
     playback::PlaybackEngine,
     progress::TrackProgress,
-    signals::AudioSignals,
+    signals::{AudioSignals, StageReporter},
     stream_manager::StreamManager,
 };
 use crate::event::events::Event;
+use crate::framework::tasks::TaskManager;
+use crate::util::state::{PodcastSpeeds, TrackGains};
+use crate::util::track::podcast_id;
+
+const TRACK_GAIN_EFFECT: &str = "track_gain";
+const VOLUME_RAMP: Duration = Duration::from_millis(120);
+const VOLUME_RAMP_STEPS: u32 = 12;
+const VOLUME_RAMP_GROUP: &str = "audio:volume_ramp";
+const MONITOR_INTERVAL: Duration = Duration::from_millis(125);
 
 pub struct AudioController {
     engine: Arc<PlaybackEngine>,
     stream_manager: Arc<StreamManager>,
     event_tx: Sender<Event>,
+    output_reports: Sender<OutputReport>,
+    track_ends: Sender<u64>,
+    play_token: Arc<AtomicU64>,
     pub track_progress: Arc<RwLock<Arc<TrackProgress>>>,
     current_playback_task: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    current_item: Arc<RwLock<Option<QueueItem>>>,
     signals: AudioSignals,
     effect_handles: Arc<RwLock<HashMap<String, EffectHandle>>>,
+    track_gains: Arc<RwLock<TrackGains>>,
+    podcast_speeds: Arc<RwLock<PodcastSpeeds>>,
+    tasks: Arc<TaskManager>,
 }
 
 impl AudioController {
@@ -37,18 +58,28 @@ impl AudioController {
         event_tx: Sender<Event>,
         signals: AudioSignals,
         track_progress: Arc<TrackProgress>,
+        tasks: Arc<TaskManager>,
     ) -> Self {
+        let (output_reports, reports_rx) = flume::unbounded();
+        let (track_ends, ends_rx) = flume::unbounded();
         let controller = Self {
             engine: Arc::new(engine),
             stream_manager,
             event_tx,
+            output_reports,
+            track_ends,
+            play_token: Arc::new(AtomicU64::new(0)),
             track_progress: Arc::new(RwLock::new(track_progress)),
             current_playback_task: Arc::new(Mutex::new(None)),
+            current_item: Arc::new(RwLock::new(None)),
             signals,
             effect_handles: Arc::new(RwLock::new(HashMap::new())),
+            track_gains: Arc::new(RwLock::new(TrackGains::load())),
+            podcast_speeds: Arc::new(RwLock::new(PodcastSpeeds::load())),
+            tasks,
         };
 
-        controller.start_monitor();
+        controller.start_monitor(reports_rx, ends_rx);
         controller
     }
 
@@ -56,36 +87,85 @@ impl AudioController {
         self.signals.clone()
     }
 
-    fn start_monitor(&self) {
+    fn start_monitor(
+        &self,
+        reports: flume::Receiver<OutputReport>,
+        track_ends: flume::Receiver<u64>,
+    ) {
         let engine = self.engine.clone();
+        let play_token = self.play_token.clone();
         let progress = self.track_progress.clone();
         let signals = self.signals.clone();
         let event_tx = self.event_tx.clone();
 
+        let end_track = {
+            let signals = signals.clone();
+            let event_tx = event_tx.clone();
+            move || {
+                signals.set_playing(false);
+                signals.is_stopped.set(true);
+                let _ = event_tx.send(Event::TrackEnded);
+            }
+        };
+
         tokio::spawn(async move {
+            let mut tick = tokio::time::interval(MONITOR_INTERVAL);
             loop {
-                tokio::time::sleep(std::time::Duration::from_millis(125)).await;
+                tokio::select! {
+                    _ = tick.tick() => {}
+                    token = track_ends.recv_async() => {
+                        let Ok(token) = token else { break };
+                        if token == play_token.load(Ordering::SeqCst) && !engine.is_remote() {
+                            end_track();
+                        }
+                        continue;
+                    }
+                    report = reports.recv_async() => {
+                        let Ok(report) = report else { break };
+                        if !engine.is_remote() {
+                            continue;
+                        }
+                        match report {
+                            OutputReport::Position(position) => {
+                                signals.update_progress(
+                                    position.as_millis() as u64,
+                                    signals.duration_ms.get(),
+                                );
+                            }
+                            OutputReport::Finished => end_track(),
+                            OutputReport::Failed(message) => {
+                                let _ = event_tx.send(Event::FetchError(message));
+                                end_track();
+                            }
+                            OutputReport::Disconnected(message) => {
+                                signals.set_playing(false);
+                                let _ = event_tx.send(Event::FetchError(message));
+                            }
+                        }
+                        continue;
+                    }
+                }
 
                 let is_playing = signals.is_playing.get();
 
                 if is_playing {
-                    if engine.is_empty() {
-                        signals.set_playing(false);
-                        signals.is_stopped.set(true);
-                        let _ = event_tx.send(Event::TrackEnded);
+                    if engine.is_remote() {
                         continue;
                     }
 
                     if signals.monitor.is_focused() {
                         let pos = engine.pos();
-                        let dur = signals.duration_ms.get();
-
-                        signals.update_progress(pos.as_millis() as u64, dur);
 
                         if let Ok(guard) = progress.read() {
+                            let dur = match signals.duration_ms.get() {
+                                0 => guard.refined_duration().map_or(0, |d| d.as_millis() as u64),
+                                dur => dur,
+                            };
+                            signals.update_progress(pos.as_millis() as u64, dur);
                             guard.set_current_position(pos);
                             let buffered = guard.get_buffered_ratio() as f32;
                             signals.update_buffered_ratio(buffered);
+                            signals.update_buffered_ranges(guard.get_buffered_ranges());
                         }
 
                         signals.amplitude.set(signals.monitor.combined_amplitude());
@@ -97,42 +177,82 @@ impl AudioController {
 
     pub async fn handle_command(&self, cmd: AudioCommand) {
         match cmd {
-            AudioCommand::PlayTrack(track) => self.play_track(track).await,
+            AudioCommand::PlayTrack(track) => self.play_track(track, Duration::ZERO).await,
             AudioCommand::Pause => self.pause().await,
             AudioCommand::Resume => self.resume().await,
             AudioCommand::Stop => self.stop().await,
             AudioCommand::SetVolume(vol) => self.set_volume(vol),
             AudioCommand::Seek(pos) => self.seek(pos).await,
+            AudioCommand::Reload => self.reload().await,
             _ => {}
         }
     }
 
-    async fn play_track(&self, track: Track) {
+    async fn play_track(&self, item: QueueItem, start: Duration) {
         self.stop().await;
 
+        let track = item.to_track();
+        if let Ok(mut current) = self.current_item.write() {
+            *current = Some(item.clone());
+        }
         self.signals.is_buffering.set(true);
         self.signals.is_stopped.set(false);
         self.signals.set_current_track(Some(track.clone()));
+        self.engine.set_metadata(
+            track.title.as_deref(),
+            self.signals.track_artists.get().as_deref(),
+        );
+
+        if self.engine.is_remote() {
+            self.play_remote(item, start).await;
+            return;
+        }
 
         let engine = self.engine.clone();
         let stream_manager = self.stream_manager.clone();
         let progress = self.track_progress.clone();
+        let token = self.play_token.fetch_add(1, Ordering::SeqCst) + 1;
+        let track_ends = self.track_ends.clone();
         let event_tx = self.event_tx.clone();
         let signals = self.signals.clone();
         let track_clone = track.clone();
         let monitor = self.signals.monitor.clone();
         let effect_handles_store = self.effect_handles.clone();
+        let gain_db = self
+            .track_gains
+            .read()
+            .map(|gains| gains.get(&track.id))
+            .unwrap_or(0.0);
+        let speed = podcast_id(&track)
+            .and_then(|id| {
+                self.podcast_speeds
+                    .read()
+                    .ok()
+                    .map(|speeds| speeds.get(&id))
+            })
+            .unwrap_or(1.0);
+        self.engine.set_speed(speed);
 
         self.apply_volume();
 
         let task = tokio::spawn(async move {
-            match stream_manager.create_stream_session(&track_clone).await {
+            let report: StageReporter = {
+                let stage = signals.load_stage.clone();
+                Arc::new(move |s| stage.set(Some(s)))
+            };
+            let result = stream_manager.create_stream_session(&item, report).await;
+            signals.load_stage.set(None);
+            match result {
                 Ok((session, new_progress)) => {
                     if let Ok(mut guard) = progress.write() {
                         *guard = new_progress;
                     }
 
-                    let mut source = FxSource::new(session.source);
+                    let mut stream = session.source;
+                    stream.on_end(move || {
+                        let _ = track_ends.send(token);
+                    });
+                    let mut source = FxSource::new(stream);
 
                     let monitor_params = Arc::new(EffectParams::new(&[]));
                     monitor_params.set_enabled(true);
@@ -161,11 +281,17 @@ impl AudioController {
                         );
                     }
 
+                    let (gain, gain_params) = track_gain(gain_db);
+                    source.add_effect(TRACK_GAIN_EFFECT, "Track Gain", gain, gain_params);
+
                     crate::audio::fx::init::init_all(&mut source);
 
                     if let Ok(old_store) = effect_handles_store.read() {
                         let new_handles = source.get_effect_handles();
                         for (name, new_handle) in new_handles.iter() {
+                            if name == TRACK_GAIN_EFFECT {
+                                continue;
+                            }
                             if let Some(old_handle) = old_store.get(name) {
                                 new_handle.set_enabled(old_handle.is_enabled());
                                 for i in 0..old_handle.param_count().min(new_handle.param_count()) {
@@ -181,10 +307,26 @@ impl AudioController {
                     }
 
                     engine.play_source(source);
+                    if !start.is_zero() {
+                        let _ = engine.try_seek(start);
+                    }
 
                     signals.is_buffering.set(false);
                     signals.set_playing(true);
 
+                    if let QueueItem::Remote(track) = item {
+                        let stream_manager = stream_manager.clone();
+                        let signals = signals.clone();
+                        tokio::spawn(async move {
+                            if stream_manager.is_downgraded(&track).await
+                                && signals.current_track_id.get().as_deref() == Some(&track.id)
+                            {
+                                tracing::info!(track_id = %track.id, "stream_quality_downgraded");
+                                signals.quality_downgraded.set(true);
+                            }
+                        });
+                    }
+
                     let _ = event_tx.send(Event::TrackStarted(track_clone, 0));
                 }
                 Err(_e) => {
@@ -200,11 +342,92 @@ impl AudioController {
         *task_guard = Some(task);
     }
 
+    async fn play_remote(&self, item: QueueItem, start: Duration) {
+        let QueueItem::Remote(track) = item else {
+            self.signals.is_buffering.set(false);
+            self.signals.is_stopped.set(true);
+            let _ = self.event_tx.send(Event::FetchError(
+                "Local files can't be played on a remote output".to_string(),
+            ));
+            let _ = self.event_tx.send(Event::TrackEnded);
+            return;
+        };
+
+        let engine = self.engine.clone();
+        let stream_manager = self.stream_manager.clone();
+        let event_tx = self.event_tx.clone();
+        let signals = self.signals.clone();
+
+        let task = tokio::spawn(async move {
+            match stream_manager.track_url(&track).await {
+                Ok((url, codec, _)) => {
+                    engine.play_remote(RemoteMedia {
+                        url,
+                        codec,
+                        title: track.title.clone(),
+                        artist: signals.track_artists.get(),
+                        album: track.albums.first().and_then(|a| a.title.clone()),
+                        cover: signals.current_cover_url.get(),
+                        duration: track.duration,
+                        position: start,
+                    });
+                    signals.update_progress(start.as_millis() as u64, signals.duration_ms.get());
+                    signals.is_buffering.set(false);
+                    signals.set_playing(true);
+                    let _ = event_tx.send(Event::TrackStarted(track, 0));
+                }
+                Err(e) => {
+                    signals.is_buffering.set(false);
+                    signals.set_playing(false);
+                    signals.is_stopped.set(true);
+                    let _ = event_tx.send(Event::FetchError(e.to_string()));
+                    let _ = event_tx.send(Event::TrackEnded);
+                }
+            }
+        });
+
+        let mut task_guard = self.current_playback_task.lock().await;
+        *task_guard = Some(task);
+    }
+
+    pub fn output_target(&self) -> Option<OutputTarget> {
+        self.engine.remote().map(|remote| remote.target().clone())
+    }
+
+    pub async fn switch_output(&self, target: Option<OutputTarget>) {
+        let item = self.current_item();
+        let position = Duration::from_millis(self.signals.position_ms.get());
+
+        self.stop().await;
+        self.engine.set_remote(
+            target.map(|target| RemoteOutput::connect(target, self.output_reports.clone())),
+        );
+        self.apply_volume();
+
+        if let Some(item) = item {
+            self.play_track(item, position).await;
+        }
+    }
+
+    async fn reload(&self) {
+        let Some(item) = self.current_item() else {
+            return;
+        };
+        let position = Duration::from_millis(self.signals.position_ms.get());
+        self.stream_manager.invalidate(&item.id());
+        self.play_track(item, position).await;
+    }
+
+    fn current_item(&self) -> Option<QueueItem> {
+        self.current_item.read().ok()?.clone()
+    }
+
     async fn stop(&self) {
         let mut task_guard = self.current_playback_task.lock().await;
         if let Some(task) = task_guard.take() {
             task.abort();
         }
+        self.signals.load_stage.set(None);
         self.engine.stop();
         if let Ok(progress) = self.track_progress.read() {
             progress.reset();
@@ -215,6 +438,7 @@ impl AudioController {
         self.signals.is_buffering.set(false);
         self.signals.update_progress(0, 0);
         self.signals.update_buffered_ratio(0.0);
+        self.signals.update_buffered_ranges(Vec::new());
     }
 
     async fn pause(&self) {
@@ -229,6 +453,9 @@ impl AudioController {
 
     async fn seek(&self, pos: std::time::Duration) {
         let _ = self.engine.try_seek(pos);
+        if self.engine.is_remote() {
+            self.update_progress(pos);
+        }
         if let Ok(progress) = self.track_progress.read() {
             progress.set_current_position(pos);
         }
@@ -263,6 +490,52 @@ impl AudioController {
         }
     }
 
+    pub fn adjust_effect_param(&self, name: &str, index: usize, delta: f32) -> Option<f32> {
+        let guard = self.effect_handles.read().ok()?;
+        let handle = guard.get(name)?;
+        if index >= handle.param_count() {
+            return None;
+        }
+        handle.set_param(index, handle.get_param(index) + delta);
+        handle.set_enabled(true);
+        Some(handle.get_param(index))
+    }
+
+    pub fn adjust_podcast_speed(&self, delta: f32) -> Option<f32> {
+        let track = self.signals.current_track.get()?;
+        let podcast_id = podcast_id(&track)?;
+        let speed = {
+            let mut speeds = self.podcast_speeds.write().ok()?;
+            let speed = speeds.adjust(&podcast_id, delta);
+            if let Err(e) = speeds.save() {
+                tracing::warn!(error = %e, "podcast_speed_save_failed");
+            }
+            speed
+        };
+
+        self.engine.set_speed(speed);
+        Some(speed)
+    }
+
+    pub fn adjust_track_gain(&self, delta_db: f32) -> Option<f32> {
+        let track_id = self.current_track_id()?;
+        let gain = {
+            let mut gains = self.track_gains.write().ok()?;
+            let gain = gains.adjust(&track_id, delta_db);
+            if let Err(e) = gains.save() {
+                tracing::warn!(error = %e, "track_gain_save_failed");
+            }
+            gain
+        };
+
+        if let Ok(guard) = self.effect_handles.read()
+            && let Some(handle) = guard.get(TRACK_GAIN_EFFECT)
+        {
+            handle.set_param(0, gain);
+        }
+        Some(gain)
+    }
+
     pub fn update_progress(&self, pos: Duration) {
         let dur = self.signals.duration_ms.get();
         self.signals.update_progress(pos.as_millis() as u64, dur);
@@ -327,6 +600,25 @@ impl AudioController {
         } else {
             self.signals.volume.get() as f32 / 100.0
         };
-        self.engine.set_volume(volume);
+        self.ramp_volume(volume);
+    }
+
+    fn ramp_volume(&self, target: f32) {
+        let start = self.engine.volume();
+        if (target - start).abs() < f32::EPSILON || self.engine.is_remote() {
+            self.tasks.abort_group(VOLUME_RAMP_GROUP);
+            self.engine.set_volume(target);
+            return;
+        }
+
+        let engine = self.engine.clone();
+        self.tasks
+            .spawn_in_group(VOLUME_RAMP_GROUP, "volume_ramp", async move {
+                for step in 1..=VOLUME_RAMP_STEPS {
+                    tokio::time::sleep(VOLUME_RAMP / VOLUME_RAMP_STEPS).await;
+                    let t = step as f32 / VOLUME_RAMP_STEPS as f32;
+                    engine.set_volume(start + (target - start) * t);
+                }
+            });
     }
 }
@@ -0,0 +1,294 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{info, warn};
+use yandex_music::model::track::Track;
+
+use crate::{
+    audio::cache::UrlCache,
+    http::MusicApi,
+    util::{config, fs::expand_path, log::get_data_dir},
+};
+
+static INDEX: LazyLock<RwLock<Option<DownloadIndex>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Downloaded track ids and their codecs, read from the download directory
+/// once and kept up to date as downloads finish.
+struct DownloadIndex {
+    dir: PathBuf,
+    codecs: HashMap<String, String>,
+}
+
+impl DownloadIndex {
+    fn scan(dir: PathBuf) -> Self {
+        let codecs = std::fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let id = path.file_stem()?.to_string_lossy().to_string();
+                let codec = path.extension()?.to_string_lossy().to_string();
+                (codec != "part").then_some((id, codec))
+            })
+            .collect();
+        Self { dir, codecs }
+    }
+}
+
+enum DownloadMessage {
+    Upcoming(Vec<Track>),
+    Request(Vec<Track>),
+}
+
+struct Job {
+    track: Track,
+    distance: Option<usize>,
+    requested: bool,
+    seq: u64,
+}
+
+impl Job {
+    /// Upcoming tracks nearest first, then requests in order. `requested`
+    /// doesn't affect the order; it keeps a job queued after its track
+    /// leaves the upcoming window.
+    fn rank(&self) -> (bool, usize, u64) {
+        (
+            self.distance.is_none(),
+            self.distance.unwrap_or(0),
+            self.seq,
+        )
+    }
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+    }
+}
+
+impl Eq for Job {}
+
+impl PartialOrd for Job {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Job {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.rank().cmp(&self.rank())
+    }
+}
+
+#[derive(Clone)]
+pub struct DownloadScheduler {
+    tx: mpsc::UnboundedSender<DownloadMessage>,
+}
+
+impl DownloadScheduler {
+    pub fn new(
+        api: Arc<dyn MusicApi>,
+        url_cache: UrlCache,
+        client: reqwest::blocking::Client,
+    ) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<DownloadMessage>();
+
+        tokio::spawn(async move {
+            let mut jobs: BinaryHeap<Job> = BinaryHeap::new();
+            let mut current: Option<(String, JoinHandle<()>)> = None;
+            let mut seq = 0u64;
+
+            loop {
+                if current.is_none()
+                    && let Some(job) = jobs.pop()
+                {
+                    let id = job.track.id.clone();
+                    let task = tokio::spawn(download(
+                        api.clone(),
+                        url_cache.clone(),
+                        client.clone(),
+                        job.track,
+                    ));
+                    current = Some((id, task));
+                }
+
+                tokio::select! {
+                    msg = rx.recv() => {
+                        let Some(msg) = msg else { break };
+                        let active = current.as_ref().map(|(id, _)| id.clone());
+                        let mut pending = jobs.into_vec();
+                        match msg {
+                            DownloadMessage::Upcoming(tracks) => {
+                                for job in &mut pending {
+                                    job.distance = tracks.iter().position(|t| t.id == job.track.id);
+                                }
+                                pending.retain(|job| job.requested || job.distance.is_some());
+                                for (distance, track) in tracks.into_iter().enumerate() {
+                                    if !pending.iter().any(|j| j.track.id == track.id)
+                                        && active.as_ref() != Some(&track.id)
+                                        && find(&track.id).is_none()
+                                    {
+                                        seq += 1;
+                                        pending.push(Job {
+                                            track,
+                                            distance: Some(distance),
+                                            requested: false,
+                                            seq,
+                                        });
+                                    }
+                                }
+                            }
+                            DownloadMessage::Request(tracks) => {
+                                for track in tracks {
+                                    if let Some(job) = pending.iter_mut().find(|j| j.track.id == track.id) {
+                                        job.requested = true;
+                                    } else if active.as_ref() != Some(&track.id) && find(&track.id).is_none() {
+                                        seq += 1;
+                                        pending.push(Job {
+                                            track,
+                                            distance: None,
+                                            requested: true,
+                                            seq,
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                        jobs = pending.into();
+                    }
+                    _ = async {
+                        if let Some((_, task)) = &mut current {
+                            let _ = task.await;
+                        } else {
+                            std::future::pending::<()>().await;
+                        }
+                    } => {
+                        current = None;
+                    }
+                }
+            }
+        });
+
+        Self { tx }
+    }
+
+    pub fn schedule_upcoming(&self, tracks: Vec<Track>) {
+        if config::get().downloads.enabled {
+            let _ = self.tx.send(DownloadMessage::Upcoming(tracks));
+        }
+    }
+
+    pub fn request(&self, tracks: Vec<Track>) {
+        if config::get().downloads.enabled {
+            let _ = self.tx.send(DownloadMessage::Request(tracks));
+        }
+    }
+}
+
+pub fn download_dir() -> PathBuf {
+    match config::get().downloads.dir.as_deref() {
+        Some(dir) if !dir.trim().is_empty() => expand_path(dir),
+        _ => get_data_dir().join("downloads"),
+    }
+}
+
+pub fn find(track_id: &str) -> Option<(PathBuf, String)> {
+    if !config::get().downloads.enabled {
+        return None;
+    }
+    let dir = download_dir();
+    let codec = {
+        let index = INDEX.read().unwrap();
+        match index.as_ref() {
+            Some(index) if index.dir == dir => index.codecs.get(track_id).cloned(),
+            _ => {
+                drop(index);
+                let index = DownloadIndex::scan(dir.clone());
+                let codec = index.codecs.get(track_id).cloned();
+                *INDEX.write().unwrap() = Some(index);
+                codec
+            }
+        }
+    }?;
+
+    let path = dir.join(format!("{track_id}.{codec}"));
+    if !path.is_file() {
+        forget(track_id);
+        return None;
+    }
+    Some((path, codec))
+}
+
+fn record(dir: &Path, track_id: String, codec: String) {
+    if let Some(index) = INDEX.write().unwrap().as_mut()
+        && index.dir == dir
+    {
+        index.codecs.insert(track_id, codec);
+    }
+}
+
+fn forget(track_id: &str) {
+    if let Some(index) = INDEX.write().unwrap().as_mut() {
+        index.codecs.remove(track_id);
+    }
+}
+
+async fn download(
+    api: Arc<dyn MusicApi>,
+    url_cache: UrlCache,
+    client: reqwest::blocking::Client,
+    track: Track,
+) {
+    let (url, codec) = match url_cache.get(&track.id) {
+        Some((url, codec, _)) => (url, codec),
+        None => match api.fetch_track_url(track.id.clone()).await {
+            Ok((url, codec, bitrate)) => {
+                url_cache.insert(track.id.clone(), url.clone(), codec.clone(), bitrate);
+                (url, codec)
+            }
+            Err(e) => {
+                warn!(error = %e, track_id = %track.id, "download_url_failed");
+                return;
+            }
+        },
+    };
+
+    let id = track.id.clone();
+    let dir = download_dir();
+    let result = tokio::task::spawn_blocking(move || {
+        save(&client, &url, &dir, &format!("{id}.{codec}")).inspect(|_| record(&dir, id, codec))
+    })
+    .await;
+    match result {
+        Ok(Ok(path)) => info!(track_id = %track.id, path = %path.display(), "track_downloaded"),
+        Ok(Err(e)) => warn!(error = %e, track_id = %track.id, "track_download_failed"),
+        Err(e) => warn!(error = %e, track_id = %track.id, "track_download_panicked"),
+    }
+}
+
+fn save(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    dir: &Path,
+    name: &str,
+) -> color_eyre::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(name);
+    let part = dir.join(format!("{name}.part"));
+
+    let mut response = client.get(url).send()?.error_for_status()?;
+    let mut file = std::fs::File::create(&part)?;
+    if let Err(e) = response.copy_to(&mut file) {
+        let _ = std::fs::remove_file(&part);
+        return Err(e.into());
+    }
+    file.sync_all()?;
+    std::fs::rename(&part, &path)?;
+    Ok(path)
+}
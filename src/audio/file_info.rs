@@ -0,0 +1,97 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+use yandex_music::model::track::Track;
+
+use crate::audio::{cache::UrlCache, local};
+use crate::framework::tasks::TaskManager;
+use crate::http::MusicApi;
+
+const BATCH_SIZE: usize = 10;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Formats = Arc<Mutex<HashMap<String, (String, u32)>>>;
+
+#[derive(Clone)]
+pub struct FileInfoResolver {
+    cache: UrlCache,
+    formats: Formats,
+    requested: Arc<Mutex<HashSet<String>>>,
+    tx: mpsc::UnboundedSender<String>,
+}
+
+impl FileInfoResolver {
+    pub fn new(api: Arc<dyn MusicApi>, cache: UrlCache, tasks: &TaskManager) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        let formats = Formats::default();
+        let requested = Arc::new(Mutex::new(HashSet::new()));
+
+        tasks.spawn("file_info_resolver", {
+            let cache = cache.clone();
+            let formats = formats.clone();
+            let requested = requested.clone();
+            async move {
+                while let Some(id) = rx.recv().await {
+                    let mut batch = vec![id];
+                    while batch.len() < BATCH_SIZE {
+                        match rx.try_recv() {
+                            Ok(id) => batch.push(id),
+                            Err(_) => break,
+                        }
+                    }
+                    batch.retain(|id| {
+                        cache.get(id).is_none() && !formats.lock().unwrap().contains_key(id)
+                    });
+                    if batch.is_empty() {
+                        continue;
+                    }
+
+                    let fetch = api.fetch_track_urls_batch(batch.clone());
+                    match tokio::time::timeout(FETCH_TIMEOUT, fetch).await {
+                        Ok(Ok(infos)) => {
+                            let mut formats = formats.lock().unwrap();
+                            for (id, _, codec, bitrate) in infos {
+                                formats.insert(id, (codec, bitrate));
+                            }
+                            continue;
+                        }
+                        Ok(Err(e)) => error!(error = %e, "file_info_fetch_failed"),
+                        Err(_) => warn!("file_info_fetch_timeout"),
+                    }
+                    let mut requested = requested.lock().unwrap();
+                    for id in &batch {
+                        requested.remove(id);
+                    }
+                }
+            }
+        });
+
+        Self {
+            cache,
+            formats,
+            requested,
+            tx,
+        }
+    }
+
+    pub fn get(&self, track: &Track) -> Option<(String, u32)> {
+        if local::is_local(track) {
+            return None;
+        }
+        if let Some(format) = self.formats.lock().unwrap().get(&track.id) {
+            return Some(format.clone());
+        }
+        if let Some((_, codec, bitrate)) = self.cache.get(&track.id) {
+            return Some((codec, bitrate));
+        }
+        if self.requested.lock().unwrap().insert(track.id.clone()) {
+            let _ = self.tx.send(track.id.clone());
+        }
+        None
+    }
+}
@@ -36,4 +36,13 @@ pub fn init_all<T: Source<Item = f32> + Send + 'static>(source: &mut FxSource<T>
 
     let fx = overdrive(sr);
     source.add_effect("overdrive", "Overdrive", fx.0, fx.1);
+
+    let fx = stereo_width();
+    source.add_effect("stereo_width", "Stereo Width", fx.0, fx.1);
+
+    let fx = night_mode(sr);
+    source.add_effect("night_mode", "Night Mode", fx.0, fx.1);
+
+    let fx = karaoke(sr);
+    source.add_effect("karaoke", "Karaoke", fx.0, fx.1);
 }
@@ -0,0 +1,42 @@
+use crate::audio::fx::Effect;
+use crate::audio::fx::param::EffectParams;
+use std::sync::Arc;
+
+pub struct GainEffect {
+    params: Arc<EffectParams>,
+    current: f32,
+}
+
+impl GainEffect {
+    pub fn new(params: Arc<EffectParams>) -> Self {
+        let current = Self::db_to_linear(params.get(0));
+        Self { params, current }
+    }
+
+    #[inline(always)]
+    fn db_to_linear(db: f32) -> f32 {
+        10.0_f32.powf(db / 20.0)
+    }
+}
+
+impl Effect for GainEffect {
+    fn process(&mut self, left: &mut [f32], right: &mut [f32]) {
+        let target = Self::db_to_linear(self.params.get(0));
+        let len = left.len().min(right.len());
+        if len == 0 {
+            return;
+        }
+
+        let step = (target - self.current) / len as f32;
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            self.current += step;
+            *l *= self.current;
+            *r *= self.current;
+        }
+        self.current = target;
+    }
+
+    fn reset(&mut self) {
+        self.current = Self::db_to_linear(self.params.get(0));
+    }
+}
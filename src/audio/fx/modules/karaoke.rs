@@ -0,0 +1,54 @@
+use crate::audio::fx::Effect;
+use crate::audio::fx::param::EffectParams;
+use std::sync::Arc;
+
+pub struct KaraokeEffect {
+    params: Arc<EffectParams>,
+    sample_rate: f32,
+    strength: f32,
+    low_mid: f32,
+}
+
+impl KaraokeEffect {
+    pub fn new(params: Arc<EffectParams>, sample_rate: f32) -> Self {
+        let strength = params.get(0);
+        Self {
+            params,
+            sample_rate,
+            strength,
+            low_mid: 0.0,
+        }
+    }
+}
+
+impl Effect for KaraokeEffect {
+    fn process(&mut self, left: &mut [f32], right: &mut [f32]) {
+        let len = left.len().min(right.len());
+        if len == 0 {
+            return;
+        }
+
+        let target = self.params.get(0);
+        let cutoff = self.params.get(1).min(self.sample_rate * 0.45);
+        let alpha = 1.0 - (-2.0 * std::f32::consts::PI * cutoff / self.sample_rate).exp();
+        let step = (target - self.strength) / len as f32;
+
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            self.strength += step;
+
+            let mid = (*l + *r) * 0.5;
+            let side = (*l - *r) * 0.5;
+            self.low_mid += alpha * (mid - self.low_mid);
+
+            let mid = mid - (mid - self.low_mid) * self.strength;
+            *l = mid + side;
+            *r = mid - side;
+        }
+        self.strength = target;
+    }
+
+    fn reset(&mut self) {
+        self.strength = self.params.get(0);
+        self.low_mid = 0.0;
+    }
+}
@@ -5,9 +5,13 @@ pub mod dc_block;
 pub mod delay;
 pub mod eq;
 pub mod fade;
+pub mod gain;
+pub mod karaoke;
 pub mod monitor;
+pub mod night;
 pub mod overdrive;
 pub mod reverb;
+pub mod width;
 
 pub use biquad::BiquadEffect;
 pub use chorus::ChorusEffect;
@@ -16,17 +20,27 @@ pub use dc_block::DcBlockEffect;
 pub use delay::StereoDelayEffect;
 pub use eq::{EQ_FREQUENCIES, Equalizer};
 pub use fade::FadeEffect;
+pub use gain::GainEffect;
+pub use karaoke::KaraokeEffect;
 pub use monitor::MonitorEffect;
+pub use night::NightModeEffect;
 pub use overdrive::OverdriveEffect;
 pub use reverb::Reverb;
+pub use width::StereoWidthEffect;
 
 use std::sync::Arc;
 
-use crate::audio::fx::Effect;
+use crate::{
+    audio::fx::Effect,
+    util::{config, state::MAX_TRACK_GAIN_DB},
+};
 
 use super::biquad::FilterType;
 use super::param::{EffectParams, ParamInfo};
 
+pub const MIN_STEREO_WIDTH: f32 = 0.5;
+pub const MAX_STEREO_WIDTH: f32 = 1.5;
+
 pub fn bass_boost(sample_rate: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
     let info = vec![
         ParamInfo {
@@ -229,6 +243,56 @@ pub fn reverb(sample_rate: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
     (effect, params)
 }
 
+pub fn track_gain(gain_db: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
+    let info = vec![ParamInfo {
+        name: "Gain",
+        min: -MAX_TRACK_GAIN_DB,
+        max: MAX_TRACK_GAIN_DB,
+        default: 0.0,
+    }];
+    let params = Arc::new(EffectParams::new(&info));
+    params.set(0, gain_db);
+    params.set_enabled(true);
+    let effect = Box::new(GainEffect::new(params.clone()));
+    (effect, params)
+}
+
+pub fn stereo_width() -> (Box<dyn Effect>, Arc<EffectParams>) {
+    let info = vec![ParamInfo {
+        name: "Width",
+        min: MIN_STEREO_WIDTH,
+        max: MAX_STEREO_WIDTH,
+        default: 1.0,
+    }];
+    let params = Arc::new(EffectParams::new(&info));
+    let width = config::get().playback.stereo_width;
+    params.set(0, width);
+    params.set_enabled(width != 1.0);
+    let effect = Box::new(StereoWidthEffect::new(params.clone()));
+    (effect, params)
+}
+
+pub fn night_mode(sample_rate: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
+    let (compressor, compressor_params) = compressor(sample_rate);
+    for (i, value) in [-30.0, 3.0, 10.0, 250.0].into_iter().enumerate() {
+        compressor_params.set(i, value);
+    }
+    let (low_shelf, low_params) = bass_boost(sample_rate);
+    low_params.set(0, 120.0);
+    low_params.set(1, 6.0);
+    let (high_shelf, high_params) = treble_boost(sample_rate);
+    high_params.set(0, 8000.0);
+    high_params.set(1, 3.0);
+    let (trim, _) = track_gain(-3.0);
+
+    let info: Vec<ParamInfo> = vec![];
+    let params = Arc::new(EffectParams::new(&info));
+    let effect = Box::new(NightModeEffect::new(vec![
+        compressor, low_shelf, high_shelf, trim,
+    ]));
+    (effect, params)
+}
+
 pub fn dc_block(sample_rate: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
     let info: Vec<ParamInfo> = vec![];
     let params = Arc::new(EffectParams::new(&info));
@@ -319,6 +383,26 @@ pub fn compressor(sample_rate: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
     (effect, params)
 }
 
+pub fn karaoke(sample_rate: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
+    let info = vec![
+        ParamInfo {
+            name: "Strength",
+            min: 0.0,
+            max: 1.0,
+            default: 1.0,
+        },
+        ParamInfo {
+            name: "Bass Keep",
+            min: 50.0,
+            max: 500.0,
+            default: 150.0,
+        },
+    ];
+    let params = Arc::new(EffectParams::new(&info));
+    let effect = Box::new(KaraokeEffect::new(params.clone(), sample_rate));
+    (effect, params)
+}
+
 pub fn overdrive(sample_rate: f32) -> (Box<dyn Effect>, Arc<EffectParams>) {
     let info = vec![
         ParamInfo {
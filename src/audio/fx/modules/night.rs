@@ -0,0 +1,25 @@
+use crate::audio::fx::Effect;
+
+pub struct NightModeEffect {
+    stages: Vec<Box<dyn Effect>>,
+}
+
+impl NightModeEffect {
+    pub fn new(stages: Vec<Box<dyn Effect>>) -> Self {
+        Self { stages }
+    }
+}
+
+impl Effect for NightModeEffect {
+    fn process(&mut self, left: &mut [f32], right: &mut [f32]) {
+        for stage in &mut self.stages {
+            stage.process(left, right);
+        }
+    }
+
+    fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+    }
+}
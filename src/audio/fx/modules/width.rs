@@ -0,0 +1,39 @@
+use crate::audio::fx::Effect;
+use crate::audio::fx::param::EffectParams;
+use std::sync::Arc;
+
+pub struct StereoWidthEffect {
+    params: Arc<EffectParams>,
+    current: f32,
+}
+
+impl StereoWidthEffect {
+    pub fn new(params: Arc<EffectParams>) -> Self {
+        let current = params.get(0);
+        Self { params, current }
+    }
+}
+
+impl Effect for StereoWidthEffect {
+    fn process(&mut self, left: &mut [f32], right: &mut [f32]) {
+        let target = self.params.get(0);
+        let len = left.len().min(right.len());
+        if len == 0 {
+            return;
+        }
+
+        let step = (target - self.current) / len as f32;
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            self.current += step;
+            let mid = (*l + *r) * 0.5;
+            let side = (*l - *r) * 0.5 * self.current;
+            *l = mid + side;
+            *r = mid - side;
+        }
+        self.current = target;
+    }
+
+    fn reset(&mut self) {
+        self.current = self.params.get(0);
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+use symphonia::core::{
+    formats::FormatOptions,
+    io::MediaSourceStream,
+    meta::{MetadataOptions, MetadataRevision, StandardTagKey},
+    probe::Hint,
+};
+use yandex_music::model::track::Track;
+
+pub const LOCAL_ID_PREFIX: &str = "local:";
+pub const SUPPORTED_EXTENSIONS: [&str; 5] = ["mp3", "flac", "m4a", "aac", "mp4"];
+const COVER_NAMES: [&str; 4] = ["cover", "folder", "front", "album"];
+const COVER_EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalTrack {
+    pub path: PathBuf,
+    pub title: String,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub cover: Option<PathBuf>,
+}
+
+impl LocalTrack {
+    pub fn is_supported(path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+    }
+
+    pub fn from_path(path: impl Into<PathBuf>) -> Option<Self> {
+        let path = path.into();
+        if !Self::is_supported(&path) {
+            return None;
+        }
+
+        let stem = path.file_stem()?.to_string_lossy().into_owned();
+        let (artist, title) = match stem.split_once(" - ") {
+            Some((artist, title)) => (Some(artist.trim().to_string()), title.trim().to_string()),
+            None => (None, stem),
+        };
+        Some(Self {
+            path,
+            title,
+            artist,
+            album: None,
+            track_number: None,
+            cover: None,
+        })
+    }
+
+    pub fn read(path: impl Into<PathBuf>) -> Option<Self> {
+        let path = path.into();
+        if !Self::is_supported(&path) {
+            return None;
+        }
+        let cover = path.parent().and_then(find_cover);
+        Self::read_with_cover(path, cover)
+    }
+
+    /// Like [`LocalTrack::read`], but takes the folder's cover from the
+    /// caller so a whole directory only looks for it once.
+    pub fn read_with_cover(path: impl Into<PathBuf>, cover: Option<PathBuf>) -> Option<Self> {
+        let mut track = Self::from_path(path)?;
+        if let Some(tags) = read_tags(&track.path) {
+            let tag = |key: StandardTagKey| {
+                tags.tags()
+                    .iter()
+                    .find(|t| t.std_key == Some(key))
+                    .map(|t| t.value.to_string().trim().to_string())
+                    .filter(|v| !v.is_empty())
+            };
+            if let Some(title) = tag(StandardTagKey::TrackTitle) {
+                track.title = title;
+            }
+            track.artist = tag(StandardTagKey::Artist)
+                .or_else(|| tag(StandardTagKey::AlbumArtist))
+                .or(track.artist);
+            track.album = tag(StandardTagKey::Album);
+            track.track_number = tag(StandardTagKey::TrackNumber)
+                .and_then(|n| n.split('/').next()?.trim().parse().ok());
+        }
+        track.cover = cover;
+        Some(track)
+    }
+
+    pub fn from_track(track: &Track) -> Option<Self> {
+        let path = PathBuf::from(track.id.strip_prefix(LOCAL_ID_PREFIX)?);
+        let album = track.albums.first();
+        Some(Self {
+            title: track.title.clone().unwrap_or_default(),
+            artist: track.artists.first().and_then(|a| a.name.clone()),
+            album: album.and_then(|a| a.title.clone()),
+            track_number: None,
+            cover: track
+                .cover_uri
+                .as_deref()
+                .and_then(|uri| uri.strip_prefix("file://"))
+                .map(PathBuf::from),
+            path,
+        })
+    }
+
+    pub fn codec(&self) -> String {
+        self.path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    }
+
+    pub fn id(&self) -> String {
+        format!("{LOCAL_ID_PREFIX}{}", self.path.display())
+    }
+
+    pub fn to_track(&self) -> Track {
+        let id = self.id();
+        let artists: Vec<_> = self
+            .artist
+            .iter()
+            .map(|name| json!({ "name": name }))
+            .collect();
+        let cover_uri = self
+            .cover
+            .as_ref()
+            .map(|cover| format!("file://{}", cover.display()));
+        let albums: Vec<_> = self
+            .album
+            .iter()
+            .map(|title| json!({ "title": title, "coverUri": cover_uri }))
+            .collect();
+        serde_json::from_value(json!({
+            "id": id,
+            "realId": id,
+            "title": self.title,
+            "artists": artists,
+            "albums": albums,
+            "coverUri": cover_uri,
+            "trackSource": "local",
+        }))
+        .expect("local track metadata is always a valid track")
+    }
+}
+
+pub fn find_cover(dir: &Path) -> Option<PathBuf> {
+    let mut images: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| COVER_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        })
+        .collect();
+    images.sort();
+
+    let stem = |p: &PathBuf| {
+        p.file_stem()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+    COVER_NAMES
+        .iter()
+        .find_map(|name| images.iter().find(|p| stem(p) == *name))
+        .or_else(|| images.first())
+        .cloned()
+}
+
+fn read_tags(path: &Path) -> Option<MetadataRevision> {
+    let file = std::fs::File::open(path).ok()?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let mut probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .ok()?;
+    if let Some(revision) = probed.format.metadata().current() {
+        return Some(revision.clone());
+    }
+    probed.metadata.get()?.current().cloned()
+}
+
+pub fn is_local(track: &Track) -> bool {
+    is_local_id(&track.id)
+}
+
+pub fn is_local_id(id: &str) -> bool {
+    id.starts_with(LOCAL_ID_PREFIX)
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum QueueItem {
+    Remote(Track),
+    Local(LocalTrack),
+}
+
+impl QueueItem {
+    pub fn id(&self) -> String {
+        match self {
+            QueueItem::Remote(track) => track.id.clone(),
+            QueueItem::Local(local) => local.id(),
+        }
+    }
+
+    pub fn remote(&self) -> Option<&Track> {
+        match self {
+            QueueItem::Remote(track) => Some(track),
+            QueueItem::Local(_) => None,
+        }
+    }
+
+    pub fn to_track(&self) -> Track {
+        match self {
+            QueueItem::Remote(track) => track.clone(),
+            QueueItem::Local(local) => local.to_track(),
+        }
+    }
+}
+
+/// Tracks handed over from views keep local files behind a `local:` id, so
+/// the conversion sorts them back into the right variant.
+impl From<Track> for QueueItem {
+    fn from(track: Track) -> Self {
+        match LocalTrack::from_track(&track) {
+            Some(local) => QueueItem::Local(local),
+            None => QueueItem::Remote(track),
+        }
+    }
+}
+
+impl From<LocalTrack> for QueueItem {
+    fn from(track: LocalTrack) -> Self {
+        QueueItem::Local(track)
+    }
+}
@@ -1,12 +1,17 @@
 pub mod cache;
 pub mod commands;
 pub mod controller;
+pub mod downloads;
 pub mod enums;
 pub mod error;
+pub mod file_info;
 pub mod fx;
 pub mod liked;
+pub mod local;
 pub mod monitor;
+pub mod outputs;
 pub mod playback;
+pub mod player;
 pub mod progress;
 pub mod queue;
 pub mod signals;
@@ -0,0 +1,193 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use mdns_sd::{ServiceDaemon, ServiceEvent};
+use rust_cast::{
+    CastDevice,
+    channels::{
+        media::{
+            IdleReason, Image, Media, Metadata, MusicTrackMediaMetadata, PlayerState, StreamType,
+        },
+        receiver::CastDeviceApp,
+    },
+};
+
+use super::{Command, OutputKind, OutputTarget, Playback, Transport};
+
+const SERVICE_TYPE: &str = "_googlecast._tcp.local.";
+const RECEIVER_ID: &str = "receiver-0";
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+
+pub fn discover(timeout: Duration) -> color_eyre::Result<Vec<OutputTarget>> {
+    let daemon = ServiceDaemon::new()?;
+    let events = daemon.browse(SERVICE_TYPE)?;
+    let deadline = Instant::now() + timeout;
+    let mut found = BTreeMap::new();
+
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        let Ok(event) = events.recv_timeout(left) else {
+            break;
+        };
+        if let ServiceEvent::ServiceResolved(service) = event {
+            let Some(address) = service.get_addresses_v4().into_iter().next() else {
+                continue;
+            };
+            let name = service
+                .get_property_val_str("fn")
+                .map(str::to_string)
+                .unwrap_or_else(|| service.get_hostname().trim_end_matches('.').to_string());
+            found.insert(
+                service.get_fullname().to_string(),
+                OutputTarget {
+                    name,
+                    kind: OutputKind::Cast {
+                        host: address.to_string(),
+                        port: service.get_port(),
+                    },
+                },
+            );
+        }
+    }
+    let _ = daemon.shutdown();
+
+    Ok(found.into_values().collect())
+}
+
+pub(super) struct Connection {
+    device: CastDevice<'static>,
+    transport_id: String,
+    session_id: String,
+    media_session_id: Option<i32>,
+    last_ping: Instant,
+}
+
+impl Connection {
+    pub(super) fn open(host: &str, port: u16) -> color_eyre::Result<Self> {
+        let device = CastDevice::connect_without_host_verification(host.to_string(), port)?;
+        device.connection.connect(RECEIVER_ID)?;
+        let app = device
+            .receiver
+            .launch_app(&CastDeviceApp::DefaultMediaReceiver)?;
+        device.connection.connect(app.transport_id.clone())?;
+
+        Ok(Self {
+            device,
+            transport_id: app.transport_id,
+            session_id: app.session_id,
+            media_session_id: None,
+            last_ping: Instant::now(),
+        })
+    }
+}
+
+impl Transport for Connection {
+    fn handle(&mut self, command: Command) -> color_eyre::Result<()> {
+        let transport = self.transport_id.as_str();
+        match command {
+            Command::Load(media) => {
+                let content_type = media.content_type().to_string();
+                let status = self.device.media.load(
+                    transport,
+                    self.session_id.as_str(),
+                    &Media {
+                        content_id: media.url,
+                        stream_type: StreamType::Buffered,
+                        content_type,
+                        metadata: Some(Metadata::MusicTrack(MusicTrackMediaMetadata {
+                            album_name: media.album,
+                            title: media.title,
+                            album_artist: None,
+                            artist: media.artist,
+                            composer: None,
+                            track_number: None,
+                            disc_number: None,
+                            images: media
+                                .cover
+                                .map(|url| Image {
+                                    url,
+                                    dimensions: None,
+                                })
+                                .into_iter()
+                                .collect(),
+                            release_date: None,
+                        })),
+                        duration: media.duration.map(|d| d.as_secs_f32()),
+                    },
+                )?;
+                self.media_session_id = status.entries.first().map(|e| e.media_session_id);
+                if let Some(id) = self.media_session_id
+                    && !media.position.is_zero()
+                {
+                    self.device.media.seek(
+                        transport,
+                        id,
+                        Some(media.position.as_secs_f32()),
+                        None,
+                    )?;
+                }
+            }
+            Command::Play => {
+                if let Some(id) = self.media_session_id {
+                    self.device.media.play(transport, id)?;
+                }
+            }
+            Command::Pause => {
+                if let Some(id) = self.media_session_id {
+                    self.device.media.pause(transport, id)?;
+                }
+            }
+            Command::Seek(position) => {
+                if let Some(id) = self.media_session_id {
+                    self.device
+                        .media
+                        .seek(transport, id, Some(position.as_secs_f32()), None)?;
+                }
+            }
+            Command::Volume(volume) => {
+                self.device.receiver.set_volume(volume.clamp(0.0, 1.0))?;
+            }
+            Command::Stop => {
+                if let Some(id) = self.media_session_id.take() {
+                    self.device.media.stop(transport, id)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self) -> color_eyre::Result<Option<Playback>> {
+        if self.last_ping.elapsed() >= PING_INTERVAL {
+            self.device.heartbeat.ping()?;
+            self.last_ping = Instant::now();
+        }
+
+        let Some(id) = self.media_session_id else {
+            return Ok(None);
+        };
+        let status = self
+            .device
+            .media
+            .get_status(self.transport_id.as_str(), Some(id))?;
+        let Some(entry) = status.entries.first() else {
+            return Ok(None);
+        };
+
+        let playback = match (&entry.player_state, &entry.idle_reason) {
+            (PlayerState::Idle, Some(IdleReason::Finished)) => Some(Playback::Finished),
+            (PlayerState::Idle, Some(IdleReason::Error)) => Some(Playback::Failed),
+            _ => entry
+                .current_time
+                .map(|time| Playback::Position(Duration::from_secs_f32(time.max(0.0)))),
+        };
+        if matches!(playback, Some(Playback::Finished | Playback::Failed)) {
+            self.media_session_id = None;
+        }
+        Ok(playback)
+    }
+
+    fn close(&mut self) {
+        let _ = self.device.receiver.stop_app(self.session_id.as_str());
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::BTreeSet,
+    net::UdpSocket,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::bail;
+use reqwest::{Url, blocking::Client};
+
+use super::{Command, OutputKind, OutputTarget, Playback, RemoteMedia, Transport};
+
+const SSDP_ADDRESS: &str = "239.255.255.250:1900";
+const RENDERER_TYPE: &str = "urn:schemas-upnp-org:device:MediaRenderer:1";
+const AV_TRANSPORT: &str = "urn:schemas-upnp-org:service:AVTransport";
+const RENDERING_CONTROL: &str = "urn:schemas-upnp-org:service:RenderingControl";
+const READ_TIMEOUT: Duration = Duration::from_millis(250);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Service {
+    service_type: String,
+    control_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    av_transport: Service,
+    rendering_control: Option<Service>,
+}
+
+pub fn discover(timeout: Duration) -> color_eyre::Result<Vec<OutputTarget>> {
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.set_read_timeout(Some(READ_TIMEOUT))?;
+    let search = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {SSDP_ADDRESS}\r\nMAN: \"ssdp:discover\"\r\nMX: {}\r\nST: {RENDERER_TYPE}\r\n\r\n",
+        timeout.as_secs().max(1)
+    );
+    socket.send_to(search.as_bytes(), SSDP_ADDRESS)?;
+
+    let deadline = Instant::now() + timeout;
+    let mut locations = BTreeSet::new();
+    let mut buffer = [0u8; 2048];
+    while Instant::now() < deadline {
+        let Ok((len, _)) = socket.recv_from(&mut buffer) else {
+            continue;
+        };
+        let response = String::from_utf8_lossy(&buffer[..len]);
+        if let Some(location) = response.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("location")
+                .then(|| value.trim().to_string())
+        }) {
+            locations.insert(location);
+        }
+    }
+
+    let client = client()?;
+    Ok(locations
+        .into_iter()
+        .filter_map(|location| match describe(&client, &location) {
+            Ok(target) => target,
+            Err(e) => {
+                tracing::debug!(error = %e, location = %location, "dlna_describe_failed");
+                None
+            }
+        })
+        .collect())
+}
+
+fn client() -> color_eyre::Result<Client> {
+    Ok(Client::builder().timeout(REQUEST_TIMEOUT).build()?)
+}
+
+fn describe(client: &Client, location: &str) -> color_eyre::Result<Option<OutputTarget>> {
+    let xml = client.get(location).send()?.error_for_status()?.text()?;
+    let document = roxmltree::Document::parse(&xml)?;
+    let text = |node: roxmltree::Node, tag: &str| {
+        node.children()
+            .find(|n| n.has_tag_name(tag))
+            .and_then(|n| n.text())
+            .map(|t| t.trim().to_string())
+    };
+
+    let root = document.root_element();
+    let base = text(root, "URLBase").unwrap_or_else(|| location.to_string());
+    let base = Url::parse(&base)?;
+    let Some(device) = root.descendants().find(|n| {
+        n.has_tag_name("device")
+            && text(*n, "deviceType")
+                .is_some_and(|t| t.starts_with("urn:schemas-upnp-org:device:MediaRenderer"))
+    }) else {
+        return Ok(None);
+    };
+
+    let service = |prefix: &str| {
+        device
+            .descendants()
+            .filter(|n| n.has_tag_name("service"))
+            .find_map(|n| {
+                let service_type = text(n, "serviceType").filter(|t| t.starts_with(prefix))?;
+                let control_url = base.join(&text(n, "controlURL")?).ok()?.to_string();
+                Some(Service {
+                    service_type,
+                    control_url,
+                })
+            })
+    };
+    let Some(av_transport) = service(AV_TRANSPORT) else {
+        return Ok(None);
+    };
+
+    Ok(Some(OutputTarget {
+        name: text(device, "friendlyName").unwrap_or_else(|| base.to_string()),
+        kind: OutputKind::Dlna(Device {
+            av_transport,
+            rendering_control: service(RENDERING_CONTROL),
+        }),
+    }))
+}
+
+pub(super) struct Connection {
+    client: Client,
+    device: Device,
+    loaded: bool,
+    started: bool,
+}
+
+impl Connection {
+    pub(super) fn open(device: &Device) -> color_eyre::Result<Self> {
+        let connection = Self {
+            client: client()?,
+            device: device.clone(),
+            loaded: false,
+            started: false,
+        };
+        connection.transport("GetTransportInfo", &[])?;
+        Ok(connection)
+    }
+
+    fn transport(&self, action: &str, args: &[(&str, &str)]) -> color_eyre::Result<String> {
+        self.call(&self.device.av_transport, action, args)
+    }
+
+    fn call(
+        &self,
+        service: &Service,
+        action: &str,
+        args: &[(&str, &str)],
+    ) -> color_eyre::Result<String> {
+        let mut arguments = String::from("<InstanceID>0</InstanceID>");
+        for (name, value) in args {
+            arguments.push_str(&format!("<{name}>{}</{name}>", escape(value)));
+        }
+        let body = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{}\">{arguments}</u:{action}></s:Body></s:Envelope>",
+            service.service_type
+        );
+
+        let response = self
+            .client
+            .post(&service.control_url)
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header(
+                "SOAPACTION",
+                format!("\"{}#{action}\"", service.service_type),
+            )
+            .body(body)
+            .send()?;
+        let status = response.status();
+        let text = response.text()?;
+        if !status.is_success() {
+            bail!("{action} failed with {status}");
+        }
+        Ok(text)
+    }
+}
+
+impl Transport for Connection {
+    fn handle(&mut self, command: Command) -> color_eyre::Result<()> {
+        match command {
+            Command::Load(media) => {
+                if self.loaded {
+                    let _ = self.transport("Stop", &[]);
+                }
+                let metadata = didl(&media);
+                self.transport(
+                    "SetAVTransportURI",
+                    &[
+                        ("CurrentURI", media.url.as_str()),
+                        ("CurrentURIMetaData", metadata.as_str()),
+                    ],
+                )?;
+                self.transport("Play", &[("Speed", "1")])?;
+                self.loaded = true;
+                self.started = false;
+                if !media.position.is_zero() {
+                    let _ = self.transport(
+                        "Seek",
+                        &[("Unit", "REL_TIME"), ("Target", &timestamp(media.position))],
+                    );
+                }
+            }
+            Command::Play if self.loaded => {
+                self.transport("Play", &[("Speed", "1")])?;
+            }
+            Command::Pause if self.loaded => {
+                self.transport("Pause", &[])?;
+            }
+            Command::Seek(position) if self.loaded => {
+                self.transport(
+                    "Seek",
+                    &[("Unit", "REL_TIME"), ("Target", &timestamp(position))],
+                )?;
+            }
+            Command::Volume(volume) => {
+                if let Some(service) = &self.device.rendering_control {
+                    let level = ((volume.clamp(0.0, 1.0) * 100.0).round() as u8).to_string();
+                    self.call(
+                        service,
+                        "SetVolume",
+                        &[("Channel", "Master"), ("DesiredVolume", &level)],
+                    )?;
+                }
+            }
+            Command::Stop if self.loaded => {
+                self.loaded = false;
+                self.transport("Stop", &[])?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self) -> color_eyre::Result<Option<Playback>> {
+        if !self.loaded {
+            return Ok(None);
+        }
+
+        let info = self.transport("GetTransportInfo", &[])?;
+        match value(&info, "CurrentTransportState").as_deref() {
+            Some("PLAYING") => self.started = true,
+            Some("STOPPED" | "NO_MEDIA_PRESENT") if self.started => {
+                self.loaded = false;
+                return Ok(Some(Playback::Finished));
+            }
+            _ => {}
+        }
+        if value(&info, "CurrentTransportStatus").is_some_and(|s| s == "ERROR_OCCURRED") {
+            self.loaded = false;
+            return Ok(Some(Playback::Failed));
+        }
+
+        let position = self.transport("GetPositionInfo", &[])?;
+        Ok(value(&position, "RelTime")
+            .and_then(|t| parse_timestamp(&t))
+            .map(Playback::Position))
+    }
+
+    fn close(&mut self) {
+        if self.loaded {
+            let _ = self.transport("Stop", &[]);
+        }
+    }
+}
+
+fn value(xml: &str, tag: &str) -> Option<String> {
+    let document = roxmltree::Document::parse(xml).ok()?;
+    document
+        .descendants()
+        .find(|n| n.tag_name().name() == tag)
+        .and_then(|n| n.text())
+        .map(|t| t.trim().to_string())
+}
+
+fn didl(media: &RemoteMedia) -> String {
+    let mut item = String::new();
+    let mut field = |tag: &str, value: Option<&str>| {
+        if let Some(value) = value {
+            item.push_str(&format!("<{tag}>{}</{tag}>", escape(value)));
+        }
+    };
+    field("dc:title", media.title.as_deref());
+    field("upnp:artist", media.artist.as_deref());
+    field("upnp:album", media.album.as_deref());
+    field("upnp:albumArtURI", media.cover.as_deref());
+    field("upnp:class", Some("object.item.audioItem.musicTrack"));
+
+    let duration = media
+        .duration
+        .map(|d| format!(" duration=\"{}\"", timestamp(d)))
+        .unwrap_or_default();
+    format!(
+        "<DIDL-Lite xmlns=\"urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/\" \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\" \
+         xmlns:upnp=\"urn:schemas-upnp-org:metadata-1-0/upnp/\">\
+         <item id=\"0\" parentID=\"-1\" restricted=\"1\">{item}\
+         <res protocolInfo=\"http-get:*:{}:*\"{duration}>{}</res></item></DIDL-Lite>",
+        media.content_type(),
+        escape(&media.url)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn timestamp(position: Duration) -> String {
+    let secs = position.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn parse_timestamp(text: &str) -> Option<Duration> {
+    let (clock, fraction) = text.split_once('.').unwrap_or((text, "0"));
+    let mut secs = 0u64;
+    for part in clock.split(':') {
+        secs = secs * 60 + part.parse::<u64>().ok()?;
+    }
+    let fraction: f64 = format!("0.{fraction}").parse().ok()?;
+    Some(Duration::from_secs(secs) + Duration::from_secs_f64(fraction))
+}
@@ -0,0 +1,214 @@
+pub mod cast;
+pub mod dlna;
+pub mod network;
+#[cfg(all(target_os = "linux", feature = "pipewire"))]
+pub mod pipewire;
+
+use std::time::Duration;
+
+use flume::{Receiver, RecvTimeoutError, Sender};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputKind {
+    Cast { host: String, port: u16 },
+    Dlna(dlna::Device),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTarget {
+    pub name: String,
+    pub kind: OutputKind,
+}
+
+impl OutputTarget {
+    pub fn label(&self) -> String {
+        let kind = match self.kind {
+            OutputKind::Cast { .. } => "Cast",
+            OutputKind::Dlna(_) => "DLNA",
+        };
+        format!("{} ({kind})", self.name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteMedia {
+    pub url: String,
+    pub codec: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub cover: Option<String>,
+    pub duration: Option<Duration>,
+    pub position: Duration,
+}
+
+impl RemoteMedia {
+    fn content_type(&self) -> &'static str {
+        match self.codec.as_str() {
+            c if c.contains("mp4") => "audio/mp4",
+            c if c.contains("flac") => "audio/flac",
+            c if c.contains("aac") => "audio/aac",
+            _ => "audio/mpeg",
+        }
+    }
+}
+
+enum Command {
+    Load(Box<RemoteMedia>),
+    Play,
+    Pause,
+    Seek(Duration),
+    Volume(f32),
+    Stop,
+}
+
+enum Playback {
+    Position(Duration),
+    Finished,
+    Failed,
+}
+
+pub enum OutputReport {
+    Position(Duration),
+    Finished,
+    Failed(String),
+    Disconnected(String),
+}
+
+trait Transport {
+    fn handle(&mut self, command: Command) -> color_eyre::Result<()>;
+
+    fn poll(&mut self) -> color_eyre::Result<Option<Playback>>;
+
+    fn close(&mut self);
+}
+
+pub fn discover(timeout: Duration) -> color_eyre::Result<Vec<OutputTarget>> {
+    let (cast, dlna) = std::thread::scope(|scope| {
+        let cast = scope.spawn(|| cast::discover(timeout));
+        let dlna = scope.spawn(|| dlna::discover(timeout));
+        (cast.join(), dlna.join())
+    });
+    let results = [
+        cast.unwrap_or_else(|_| Err(color_eyre::eyre::eyre!("cast discovery panicked"))),
+        dlna.unwrap_or_else(|_| Err(color_eyre::eyre::eyre!("DLNA discovery panicked"))),
+    ];
+
+    let mut targets = Vec::new();
+    let mut error = None;
+    for result in results {
+        match result {
+            Ok(found) => targets.extend(found),
+            Err(e) => {
+                tracing::warn!(error = %e, "output_discovery_failed");
+                error.get_or_insert(e);
+            }
+        }
+    }
+    match error {
+        Some(e) if targets.is_empty() => Err(e),
+        _ => {
+            targets.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(targets)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RemoteOutput {
+    target: OutputTarget,
+    tx: Sender<Command>,
+}
+
+impl RemoteOutput {
+    pub fn connect(target: OutputTarget, reports: Sender<OutputReport>) -> Self {
+        let (tx, rx) = flume::unbounded();
+        let worker = target.clone();
+        let _ = std::thread::Builder::new()
+            .name("yamusic-output".into())
+            .spawn(move || {
+                let result = match &worker.kind {
+                    OutputKind::Cast { host, port } => {
+                        run(cast::Connection::open(host, *port), rx, &worker, &reports)
+                    }
+                    OutputKind::Dlna(device) => {
+                        run(dlna::Connection::open(device), rx, &worker, &reports)
+                    }
+                };
+                if let Err(e) = result {
+                    tracing::warn!(error = %e, output = %worker.name, "output_failed");
+                    let _ = reports.send(OutputReport::Disconnected(format!(
+                        "Playback on {} stopped: {e}",
+                        worker.name
+                    )));
+                }
+            });
+        Self { target, tx }
+    }
+
+    pub fn target(&self) -> &OutputTarget {
+        &self.target
+    }
+
+    pub fn is_alive(&self) -> bool {
+        !self.tx.is_disconnected()
+    }
+
+    pub fn load(&self, media: RemoteMedia) {
+        let _ = self.tx.send(Command::Load(Box::new(media)));
+    }
+
+    pub fn play(&self) {
+        let _ = self.tx.send(Command::Play);
+    }
+
+    pub fn pause(&self) {
+        let _ = self.tx.send(Command::Pause);
+    }
+
+    pub fn seek(&self, position: Duration) {
+        let _ = self.tx.send(Command::Seek(position));
+    }
+
+    pub fn set_volume(&self, volume: f32) {
+        let _ = self.tx.send(Command::Volume(volume));
+    }
+
+    pub fn stop(&self) {
+        let _ = self.tx.send(Command::Stop);
+    }
+}
+
+fn run<T: Transport>(
+    transport: color_eyre::Result<T>,
+    rx: Receiver<Command>,
+    target: &OutputTarget,
+    reports: &Sender<OutputReport>,
+) -> color_eyre::Result<()> {
+    let mut transport = transport?;
+    tracing::info!(output = %target.name, "output_connected");
+
+    loop {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(command) => transport.handle(command)?,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let report = match transport.poll()? {
+            Some(Playback::Position(position)) => OutputReport::Position(position),
+            Some(Playback::Finished) => OutputReport::Finished,
+            Some(Playback::Failed) => {
+                OutputReport::Failed(format!("{} couldn't play the track", target.name))
+            }
+            None => continue,
+        };
+        let _ = reports.send(report);
+    }
+
+    transport.close();
+    tracing::info!(output = %target.name, "output_disconnected");
+    Ok(())
+}
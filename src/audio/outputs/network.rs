@@ -0,0 +1,137 @@
+use std::{
+    io::Write,
+    net::{TcpStream, ToSocketAddrs, UdpSocket},
+    num::NonZero,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use rodio::{Player, Source, mixer::MixerSource};
+
+use crate::util::config::{OutputConfig, OutputMode};
+
+const CHANNELS: u16 = 2;
+const CHUNK: Duration = Duration::from_millis(20);
+const LEAD: Duration = Duration::from_millis(100);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub struct NetworkSink {
+    running: Arc<AtomicBool>,
+}
+
+impl NetworkSink {
+    pub fn open(config: &OutputConfig) -> color_eyre::Result<(Self, Player)> {
+        let sample_rate = NonZero::new(config.sample_rate)
+            .ok_or_else(|| color_eyre::eyre::eyre!("output sample_rate must be positive"))?;
+        let (mixer, source) =
+            rodio::mixer::mixer(NonZero::new(CHANNELS).expect("non-zero"), sample_rate);
+        let player = Player::connect_new(&mixer);
+
+        let writer = match config.mode {
+            OutputMode::Udp => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.connect(&config.address)?;
+                Writer::Udp(socket)
+            }
+            OutputMode::Local | OutputMode::PipeWire | OutputMode::Tcp => Writer::Tcp {
+                address: config.address.clone(),
+                stream: None,
+                retry_at: Instant::now(),
+            },
+        };
+
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = running.clone();
+        std::thread::Builder::new()
+            .name("yamusic-network-output".into())
+            .spawn(move || stream(source, writer, &flag))?;
+        tracing::info!(mode = ?config.mode, address = %config.address, "network_output_started");
+
+        Ok((Self { running }, player))
+    }
+}
+
+impl Drop for NetworkSink {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+enum Writer {
+    Tcp {
+        address: String,
+        stream: Option<TcpStream>,
+        retry_at: Instant,
+    },
+    Udp(UdpSocket),
+}
+
+impl Writer {
+    fn write(&mut self, buffer: &[u8]) {
+        match self {
+            Writer::Udp(socket) => {
+                let _ = socket.send(buffer);
+            }
+            Writer::Tcp {
+                address,
+                stream,
+                retry_at,
+            } => {
+                if stream.is_none() && Instant::now() >= *retry_at {
+                    match connect(address) {
+                        Ok(connected) => {
+                            let _ = connected.set_nodelay(true);
+                            tracing::info!(address = %address, "network_output_connected");
+                            *stream = Some(connected);
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, address = %address, "network_output_connect_failed");
+                            *retry_at = Instant::now() + RECONNECT_INTERVAL;
+                        }
+                    }
+                }
+                if let Some(connected) = stream
+                    && let Err(e) = connected.write_all(buffer)
+                {
+                    tracing::warn!(error = %e, address = %address, "network_output_disconnected");
+                    *stream = None;
+                    *retry_at = Instant::now() + RECONNECT_INTERVAL;
+                }
+            }
+        }
+    }
+}
+
+fn connect(address: &str) -> std::io::Result<TcpStream> {
+    let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "address did not resolve")
+    })?;
+    TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+}
+
+fn stream(mut source: MixerSource, mut writer: Writer, running: &AtomicBool) {
+    let samples_per_sec = source.sample_rate().get() as u64 * CHANNELS as u64;
+    let chunk = (samples_per_sec * CHUNK.as_millis() as u64 / 1000) as usize;
+    let mut buffer = Vec::with_capacity(chunk * 2);
+    let started = Instant::now();
+    let mut sent = 0u64;
+
+    while running.load(Ordering::Relaxed) {
+        buffer.clear();
+        for _ in 0..chunk {
+            let sample = source.next().unwrap_or(0.0).clamp(-1.0, 1.0);
+            buffer.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+        }
+        writer.write(&buffer);
+
+        sent += chunk as u64;
+        let due = Duration::from_secs_f64(sent as f64 / samples_per_sec as f64);
+        if let Some(wait) = due.checked_sub(started.elapsed() + LEAD) {
+            std::thread::sleep(wait);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::{num::NonZero, sync::mpsc};
+
+use pipewire as pw;
+use pw::{properties::properties, spa};
+use rodio::{Player, mixer::MixerSource};
+
+const CHANNELS: u16 = 2;
+const SAMPLE_SIZE: usize = std::mem::size_of::<f32>();
+
+enum Message {
+    Metadata {
+        title: Option<String>,
+        artist: Option<String>,
+    },
+    Quit,
+}
+
+pub struct PipeWireSink {
+    tx: pw::channel::Sender<Message>,
+}
+
+impl PipeWireSink {
+    pub fn open(sample_rate: u32) -> color_eyre::Result<(Self, Player)> {
+        let rate = NonZero::new(sample_rate)
+            .ok_or_else(|| color_eyre::eyre::eyre!("output sample_rate must be positive"))?;
+        let (mixer, source) = rodio::mixer::mixer(NonZero::new(CHANNELS).expect("non-zero"), rate);
+        let player = Player::connect_new(&mixer);
+
+        let (tx, rx) = pw::channel::channel();
+        let (ready_tx, ready_rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("yamusic-pipewire".into())
+            .spawn(move || {
+                if let Err(e) = run(source, sample_rate, rx, &ready_tx) {
+                    tracing::warn!(error = %e, "pipewire_output_failed");
+                    let _ = ready_tx.send(Err(e));
+                }
+            })?;
+        ready_rx
+            .recv()
+            .map_err(|_| color_eyre::eyre::eyre!("pipewire thread exited"))??;
+        tracing::info!(sample_rate, "pipewire_output_started");
+
+        Ok((Self { tx }, player))
+    }
+
+    pub fn set_metadata(&self, title: Option<&str>, artist: Option<&str>) {
+        let _ = self.tx.send(Message::Metadata {
+            title: title.map(str::to_string),
+            artist: artist.map(str::to_string),
+        });
+    }
+}
+
+impl Drop for PipeWireSink {
+    fn drop(&mut self) {
+        let _ = self.tx.send(Message::Quit);
+    }
+}
+
+fn run(
+    source: MixerSource,
+    sample_rate: u32,
+    rx: pw::channel::Receiver<Message>,
+    ready: &mpsc::Sender<color_eyre::Result<()>>,
+) -> color_eyre::Result<()> {
+    pw::init();
+    let mainloop = pw::main_loop::MainLoopRc::new(None)?;
+    let context = pw::context::ContextRc::new(&mainloop, None)?;
+    let core = context.connect_rc(None)?;
+
+    let stream = pw::stream::StreamRc::new(
+        core,
+        "yamusic",
+        properties! {
+            *pw::keys::MEDIA_TYPE => "Audio",
+            *pw::keys::MEDIA_ROLE => "Music",
+            *pw::keys::MEDIA_CATEGORY => "Playback",
+            *pw::keys::AUDIO_CHANNELS => "2",
+            *pw::keys::APP_NAME => "yamusic",
+            *pw::keys::APP_ID => "yamusic",
+            *pw::keys::APP_ICON_NAME => "audio-x-generic",
+            *pw::keys::NODE_NAME => "yamusic",
+        },
+    )?;
+
+    let _listener = stream
+        .add_local_listener_with_user_data(source)
+        .process(|stream, source| {
+            let Some(mut buffer) = stream.dequeue_buffer() else {
+                return;
+            };
+            let stride = SAMPLE_SIZE * CHANNELS as usize;
+            let data = &mut buffer.datas_mut()[0];
+            let frames = match data.data() {
+                Some(slice) => {
+                    let frames = slice.len() / stride;
+                    for sample in slice[..frames * stride].chunks_exact_mut(SAMPLE_SIZE) {
+                        let value = source.next().unwrap_or(0.0);
+                        sample.copy_from_slice(&value.to_le_bytes());
+                    }
+                    frames
+                }
+                None => 0,
+            };
+            let chunk = data.chunk_mut();
+            *chunk.offset_mut() = 0;
+            *chunk.stride_mut() = stride as _;
+            *chunk.size_mut() = (stride * frames) as _;
+        })
+        .register()?;
+
+    let mut info = spa::param::audio::AudioInfoRaw::new();
+    info.set_format(spa::param::audio::AudioFormat::F32LE);
+    info.set_rate(sample_rate);
+    info.set_channels(CHANNELS as u32);
+    let mut position = [0; spa::param::audio::MAX_CHANNELS];
+    position[0] = spa::sys::SPA_AUDIO_CHANNEL_FL;
+    position[1] = spa::sys::SPA_AUDIO_CHANNEL_FR;
+    info.set_position(position);
+
+    let format: Vec<u8> = spa::pod::serialize::PodSerializer::serialize(
+        std::io::Cursor::new(Vec::new()),
+        &spa::pod::Value::Object(spa::pod::Object {
+            type_: spa::sys::SPA_TYPE_OBJECT_Format,
+            id: spa::sys::SPA_PARAM_EnumFormat,
+            properties: info.into(),
+        }),
+    )
+    .map_err(|e| color_eyre::eyre::eyre!("failed to build stream format: {e:?}"))?
+    .0
+    .into_inner();
+    let mut params = [spa::pod::Pod::from_bytes(&format)
+        .ok_or_else(|| color_eyre::eyre::eyre!("invalid stream format"))?];
+
+    stream.connect(
+        spa::utils::Direction::Output,
+        None,
+        pw::stream::StreamFlags::AUTOCONNECT
+            | pw::stream::StreamFlags::MAP_BUFFERS
+            | pw::stream::StreamFlags::RT_PROCESS,
+        &mut params,
+    )?;
+
+    let _receiver = rx.attach(mainloop.loop_(), {
+        let mainloop = mainloop.clone();
+        let stream = stream.clone();
+        move |message| match message {
+            Message::Metadata { title, artist } => {
+                let mut props = pw::properties::PropertiesBox::new();
+                props.insert(*pw::keys::MEDIA_TITLE, title.unwrap_or_default());
+                props.insert(*pw::keys::MEDIA_ARTIST, artist.unwrap_or_default());
+                unsafe {
+                    pw::sys::pw_stream_update_properties(
+                        stream.as_raw_ptr(),
+                        props.dict().as_raw_ptr(),
+                    );
+                }
+            }
+            Message::Quit => mainloop.quit(),
+        }
+    });
+
+    let _ = ready.send(Ok(()));
+    mainloop.run();
+    tracing::info!("pipewire_output_stopped");
+    Ok(())
+}
@@ -1,23 +1,109 @@
-use crate::audio::util::{construct_sink, setup_device_config};
+use crate::{
+    audio::{
+        outputs::{RemoteMedia, RemoteOutput, network::NetworkSink},
+        util::{construct_sink, setup_device_config},
+    },
+    util::config::{self, OutputMode},
+};
 use rodio::{MixerDeviceSink, Player, Source};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+
+#[allow(dead_code)]
+enum Output {
+    Device(MixerDeviceSink),
+    Network(NetworkSink),
+    #[cfg(all(target_os = "linux", feature = "pipewire"))]
+    PipeWire(super::outputs::pipewire::PipeWireSink),
+}
 
 pub struct PlaybackEngine {
-    _stream: MixerDeviceSink,
+    _output: Output,
     sink: Arc<Player>,
+    remote: RwLock<Option<RemoteOutput>>,
 }
 
 impl PlaybackEngine {
     pub fn new() -> color_eyre::Result<Self> {
-        let (device, stream_config, sample_format) = setup_device_config();
-        let (stream, sink) = construct_sink(device, &stream_config, sample_format)?;
+        let config = config::get();
+        let local = || -> color_eyre::Result<(Output, Player)> {
+            let (device, stream_config, sample_format) = setup_device_config();
+            let (stream, sink) = construct_sink(device, &stream_config, sample_format)?;
+            Ok((Output::Device(stream), sink))
+        };
+        let (output, sink) = match config.output.mode {
+            OutputMode::Local => local()?,
+            #[cfg(all(target_os = "linux", feature = "pipewire"))]
+            OutputMode::PipeWire => {
+                match super::outputs::pipewire::PipeWireSink::open(config.output.sample_rate) {
+                    Ok((pipewire, sink)) => (Output::PipeWire(pipewire), sink),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "pipewire_output_unavailable");
+                        local()?
+                    }
+                }
+            }
+            #[cfg(not(all(target_os = "linux", feature = "pipewire")))]
+            OutputMode::PipeWire => {
+                tracing::warn!("pipewire_output_unavailable");
+                local()?
+            }
+            OutputMode::Tcp | OutputMode::Udp => {
+                let (network, sink) = NetworkSink::open(&config.output)?;
+                (Output::Network(network), sink)
+            }
+        };
 
         Ok(Self {
-            _stream: stream,
+            _output: output,
             sink: Arc::new(sink),
+            remote: RwLock::new(None),
         })
     }
 
+    pub fn remote(&self) -> Option<RemoteOutput> {
+        self.remote
+            .read()
+            .ok()?
+            .as_ref()
+            .filter(|remote| remote.is_alive())
+            .cloned()
+    }
+
+    pub fn is_remote(&self) -> bool {
+        self.remote().is_some()
+    }
+
+    pub fn set_remote(&self, remote: Option<RemoteOutput>) {
+        let Ok(mut current) = self.remote.write() else {
+            return;
+        };
+        if let Some(previous) = current.take() {
+            previous.stop();
+        }
+        if let Some(remote) = &remote {
+            self.sink.stop();
+            remote.set_volume(self.sink.volume());
+        }
+        *current = remote;
+    }
+
+    pub fn play_remote(&self, media: RemoteMedia) {
+        if let Some(remote) = self.remote() {
+            remote.load(media);
+        }
+    }
+
+    #[cfg_attr(
+        not(all(target_os = "linux", feature = "pipewire")),
+        allow(unused_variables)
+    )]
+    pub fn set_metadata(&self, title: Option<&str>, artist: Option<&str>) {
+        #[cfg(all(target_os = "linux", feature = "pipewire"))]
+        if let Output::PipeWire(pipewire) = &self._output {
+            pipewire.set_metadata(title, artist);
+        }
+    }
+
     pub fn play_source<S>(&self, source: S)
     where
         S: Source<Item = f32> + Send + 'static,
@@ -26,18 +112,38 @@ impl PlaybackEngine {
     }
 
     pub fn set_volume(&self, volume: f32) {
+        if let Some(remote) = self.remote() {
+            remote.set_volume(volume);
+        }
         self.sink.set_volume(volume);
     }
 
+    pub fn set_speed(&self, speed: f32) {
+        self.sink.set_speed(speed);
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.sink.volume()
+    }
+
     pub fn pause(&self) {
-        self.sink.pause();
+        match self.remote() {
+            Some(remote) => remote.pause(),
+            None => self.sink.pause(),
+        }
     }
 
     pub fn play(&self) {
-        self.sink.play();
+        match self.remote() {
+            Some(remote) => remote.play(),
+            None => self.sink.play(),
+        }
     }
 
     pub fn stop(&self) {
+        if let Some(remote) = self.remote() {
+            remote.stop();
+        }
         self.sink.stop();
     }
 
@@ -46,7 +152,7 @@ impl PlaybackEngine {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.sink.empty()
+        !self.is_remote() && self.sink.empty()
     }
 
     pub fn pos(&self) -> std::time::Duration {
@@ -54,6 +160,12 @@ impl PlaybackEngine {
     }
 
     pub fn try_seek(&self, pos: std::time::Duration) -> Result<(), rodio::source::SeekError> {
-        self.sink.try_seek(pos)
+        match self.remote() {
+            Some(remote) => {
+                remote.seek(pos);
+                Ok(())
+            }
+            None => self.sink.try_seek(pos),
+        }
     }
 }
@@ -0,0 +1,124 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use im::Vector;
+use tokio::sync::RwLock;
+use yandex_music::model::track::Track;
+
+use crate::{
+    audio::{
+        file_info::FileInfoResolver, fx::EffectHandle, local::QueueItem, outputs::OutputTarget,
+        queue::PlaybackContext, signals::AudioSignals, state::SystemState,
+    },
+    framework::tasks::TaskManager,
+};
+
+pub type EffectHandles = Arc<std::sync::RwLock<HashMap<String, EffectHandle>>>;
+
+#[async_trait]
+pub trait Player: Send + Sync {
+    fn signals(&self) -> &AudioSignals;
+
+    fn state_handle(&self) -> Arc<RwLock<SystemState>>;
+
+    fn get_effect_handles(&self) -> EffectHandles;
+
+    fn file_info(&self) -> FileInfoResolver;
+
+    fn tasks(&self) -> Arc<TaskManager>;
+
+    fn toggle_effect(&self, name: &str) -> bool;
+
+    fn is_effect_enabled(&self, name: &str) -> Option<bool>;
+
+    fn adjust_effect_param(&self, name: &str, index: usize, delta: f32) -> Option<f32>;
+
+    fn adjust_track_gain(&self, delta_db: f32) -> Option<f32>;
+
+    fn adjust_podcast_speed(&self, delta: f32) -> Option<f32>;
+
+    fn output_target(&self) -> Option<OutputTarget>;
+
+    async fn switch_output(&mut self, target: Option<OutputTarget>);
+
+    async fn load_context(
+        &mut self,
+        context: PlaybackContext,
+        tracks: Vector<QueueItem>,
+        index: usize,
+    ) -> Option<QueueItem>;
+
+    async fn play_single_track(&mut self, track: Track);
+
+    async fn play_track_at_index(&mut self, index: usize);
+
+    async fn on_track_ended(&mut self);
+
+    async fn play_next(&mut self);
+
+    async fn play_previous(&mut self);
+
+    async fn play_pause(&mut self);
+
+    async fn stop(&mut self);
+
+    async fn seek_forwards(&mut self, seconds: u64);
+
+    async fn seek_backwards(&mut self, seconds: u64);
+
+    async fn seek_to(&mut self, position: std::time::Duration);
+
+    async fn reload_track(&mut self);
+
+    fn set_volume(&mut self, volume: u8);
+
+    fn volume_up(&mut self, step: u8);
+
+    fn volume_down(&mut self, step: u8);
+
+    fn toggle_mute(&mut self);
+
+    fn toggle_shuffle(&mut self);
+
+    fn toggle_repeat_mode(&mut self);
+
+    fn queue_track(&mut self, item: QueueItem);
+
+    fn play_track_next(&mut self, item: QueueItem);
+
+    fn queue_albums(&mut self, album_ids: Vec<u32>);
+
+    fn download_tracks(&mut self, tracks: Vec<Track>);
+
+    fn remove_from_queue(&mut self, index: usize);
+
+    fn remove_track_from_queue(&mut self, track_id: &str);
+
+    fn clear_queue(&mut self);
+
+    async fn sync_queue(&mut self);
+
+    fn maybe_trigger_fetch(&mut self, cursor_index: usize);
+
+    fn wave_update_buffer(&mut self, tracks: Vec<Track>);
+
+    fn send_wave_like(&mut self, track: &Track);
+
+    fn send_wave_unlike(&mut self, track: &Track);
+
+    fn send_wave_dislike(&mut self, track: &Track);
+
+    async fn send_wave_dislike_skip(&mut self, track: &Track);
+
+    fn send_wave_undislike(&mut self, track: &Track);
+
+    async fn sync_liked_collection(&mut self);
+
+    async fn is_album_liked(&self, album_id: u32) -> bool;
+
+    async fn is_artist_liked(&self, artist_id: &str) -> bool;
+
+    async fn is_artist_disliked(&self, artist_id: &str) -> bool;
+
+    async fn is_playlist_liked(&self, uid: u64, kind: u32) -> bool;
+}
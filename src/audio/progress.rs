@@ -1,5 +1,5 @@
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[derive(Default, Debug)]
@@ -8,8 +8,10 @@ pub struct TrackProgress {
     total_duration_millis: Arc<AtomicU64>,
     total_bytes: Arc<AtomicU64>,
     buffered_bytes: Arc<AtomicU64>,
+    buffered_ranges: Arc<Mutex<Vec<(u64, u64)>>>,
     bitrate: Arc<AtomicU64>,
     generation: Arc<AtomicU64>,
+    duration_refined: Arc<AtomicBool>,
 }
 
 impl TrackProgress {
@@ -19,8 +21,10 @@ impl TrackProgress {
             total_duration_millis: Arc::new(AtomicU64::new(0)),
             total_bytes: Arc::new(AtomicU64::new(0)),
             buffered_bytes: Arc::new(AtomicU64::new(0)),
+            buffered_ranges: Arc::new(Mutex::new(Vec::new())),
             bitrate: Arc::new(AtomicU64::new(0)),
             generation: Arc::new(AtomicU64::new(0)),
+            duration_refined: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -38,17 +42,32 @@ impl TrackProgress {
         self.total_bytes.store(bytes, Ordering::Relaxed);
 
         let bitrate = self.bitrate.load(Ordering::Relaxed);
-        if bitrate > 0 {
+        if bitrate > 0 && !self.duration_refined.load(Ordering::Relaxed) {
             self.set_total_duration(Duration::from_secs_f64(
                 (bytes * 8) as f64 / (bitrate * 1000) as f64,
             ));
         }
     }
 
+    pub fn mark_duration_refined(&self) {
+        self.duration_refined.store(true, Ordering::Relaxed);
+    }
+
+    pub fn refined_duration(&self) -> Option<Duration> {
+        self.duration_refined
+            .load(Ordering::Relaxed)
+            .then(|| self.total_duration())
+            .filter(|d| !d.is_zero())
+    }
+
     pub fn set_buffered_bytes(&self, bytes: u64) {
         self.buffered_bytes.store(bytes, Ordering::Relaxed);
     }
 
+    pub fn set_buffered_ranges(&self, ranges: Vec<(u64, u64)>) {
+        *self.buffered_ranges.lock().unwrap() = ranges;
+    }
+
     pub fn set_bitrate(&self, bitrate: u64) {
         self.bitrate.store(bitrate, Ordering::Relaxed);
     }
@@ -74,6 +93,19 @@ impl TrackProgress {
         }
     }
 
+    pub fn get_buffered_ranges(&self) -> Vec<(f32, f32)> {
+        let total = self.total_bytes.load(Ordering::Relaxed);
+        if total == 0 {
+            return Vec::new();
+        }
+        self.buffered_ranges
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|&(start, end)| (start as f32 / total as f32, end as f32 / total as f32))
+            .collect()
+    }
+
     pub fn get_total_bytes(&self) -> u64 {
         self.total_bytes.load(Ordering::Relaxed)
     }
@@ -97,7 +129,9 @@ impl TrackProgress {
     pub fn reset(&self) {
         self.generation.fetch_add(1, Ordering::SeqCst);
         self.set_buffered_bytes(0);
+        self.set_buffered_ranges(Vec::new());
         self.set_current_position(Duration::ZERO);
+        self.duration_refined.store(false, Ordering::Relaxed);
         self.set_total_duration(Duration::ZERO);
         self.set_total_bytes(0);
         self.set_bitrate(0);
@@ -1,21 +1,23 @@
 use super::enums::RepeatMode;
 use super::signals::AudioSignals;
 use crate::audio::cache::UrlCache;
+use crate::audio::local::QueueItem;
 use crate::audio::progress::TrackProgress;
 use crate::audio::stream_manager::StreamManager;
 use crate::event::events::Event;
 use crate::framework::signals::Signal;
-use crate::http::ApiService;
-use crate::util::track::extract_ids;
+use crate::http::MusicApi;
+use crate::util::{config, track::extract_ids};
 use chrono::Utc;
 use flume::Sender;
 use im::Vector;
 use rand::{rng, seq::SliceRandom};
 use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::{sync::mpsc, task::JoinHandle};
-use tracing::{error, warn};
+use tracing::{debug, error, warn};
 use yandex_music::model::rotor::feedback::{StationFeedback, StationFeedbackEvent};
 
 use yandex_music::model::{
@@ -27,6 +29,8 @@ const FETCH_THRESHOLD: usize = 2;
 const URL_PREFETCH_WINDOW: usize = 5;
 const URL_PREFETCH_BATCH_SIZE: usize = 3;
 const WAVE_VISIBLE_TRACKS: usize = 1;
+const WAVE_SKIPPED_LIMIT: usize = 100;
+const SEAMLESS_EDGE_SECS: f32 = 1.0;
 
 #[derive(Debug)]
 enum PrefetchMessage {
@@ -42,7 +46,7 @@ struct UrlPrefetcher {
 }
 
 impl UrlPrefetcher {
-    fn new(api: Arc<ApiService>, url_cache: UrlCache) -> Self {
+    fn new(api: Arc<dyn MusicApi>, url_cache: UrlCache) -> Self {
         let (tx, mut rx) = mpsc::unbounded_channel::<PrefetchMessage>();
 
         tokio::spawn(async move {
@@ -167,6 +171,7 @@ pub enum PlaybackContext {
     Album(Album),
     Track(Track),
     Wave(Session),
+    Folder(PathBuf),
     Standalone,
 }
 #[derive(Debug, Clone)]
@@ -184,7 +189,7 @@ pub struct WaveTrackEvent {
 }
 
 struct ShuffleState {
-    original_queue: Option<Vector<Track>>,
+    original_queue: Option<Vector<QueueItem>>,
     index_map: Vec<Option<usize>>,
     is_active: bool,
 }
@@ -204,25 +209,29 @@ impl ShuffleState {
         self.is_active = false;
     }
 
-    fn enable(&mut self, queue: Vector<Track>, current_index: usize) -> (Vector<Track>, usize) {
+    fn enable(
+        &mut self,
+        queue: Vector<QueueItem>,
+        current_index: usize,
+    ) -> (Vector<QueueItem>, usize) {
         debug_assert!(!self.is_active, "enable called while already shuffled");
 
         self.original_queue = Some(queue.clone());
 
         let mut indices: Vec<Option<usize>> = (0..queue.len()).map(Some).collect();
-        let mut queue_vec: Vec<Track> = queue.into_iter().collect();
+        let mut queue_vec: Vec<QueueItem> = queue.into_iter().collect();
 
         if !queue_vec.is_empty() && current_index < queue_vec.len() {
-            let current_track = queue_vec.remove(current_index);
+            let current_item = queue_vec.remove(current_index);
             let current_index_val = indices.remove(current_index);
 
-            let mut rest: Vec<(Track, Option<usize>)> =
+            let mut rest: Vec<(QueueItem, Option<usize>)> =
                 queue_vec.into_iter().zip(indices).collect();
             rest.shuffle(&mut rng());
 
             let mut new_queue_vec = Vec::with_capacity(rest.len() + 1);
             let mut new_indices = Vec::with_capacity(rest.len() + 1);
-            new_queue_vec.push(current_track);
+            new_queue_vec.push(current_item);
             new_indices.push(current_index_val);
             for (t, i) in rest {
                 new_queue_vec.push(t);
@@ -233,7 +242,7 @@ impl ShuffleState {
             self.is_active = true;
             (Vector::from(new_queue_vec), 0)
         } else {
-            let mut combined: Vec<(Track, Option<usize>)> =
+            let mut combined: Vec<(QueueItem, Option<usize>)> =
                 queue_vec.into_iter().zip(indices).collect();
             combined.shuffle(&mut rng());
 
@@ -244,7 +253,7 @@ impl ShuffleState {
         }
     }
 
-    fn disable(&mut self, current_shuffled_index: usize) -> Option<(Vector<Track>, usize)> {
+    fn disable(&mut self, current_shuffled_index: usize) -> Option<(Vector<QueueItem>, usize)> {
         debug_assert!(self.is_active, "disable called while not shuffled");
 
         let original_queue = self.original_queue.take()?;
@@ -267,7 +276,7 @@ impl ShuffleState {
 }
 
 struct HistoryState {
-    entries: Vector<Track>,
+    entries: Vector<QueueItem>,
     cursor: usize,
 }
 
@@ -284,15 +293,15 @@ impl HistoryState {
         self.cursor = 0;
     }
 
-    fn push(&mut self, track: Track) {
+    fn push(&mut self, item: QueueItem) {
         if !self.entries.is_empty() && self.cursor < self.entries.len() {
             self.entries.truncate(self.cursor + 1);
         }
-        self.entries.push_back(track);
+        self.entries.push_back(item);
         self.cursor = self.entries.len().saturating_sub(1);
     }
 
-    fn as_vector(&self) -> Vector<Track> {
+    fn as_vector(&self) -> Vector<QueueItem> {
         self.entries.clone()
     }
 }
@@ -300,6 +309,7 @@ impl HistoryState {
 struct FetchState {
     task: Option<JoinHandle<(Vec<Track>, Option<Session>)>>,
     pending_track_ids: Vec<String>,
+    pending_album_ids: VecDeque<u32>,
     wave_session: Arc<Mutex<Option<Session>>>,
 }
 
@@ -308,6 +318,7 @@ impl FetchState {
         Self {
             task: None,
             pending_track_ids: Vec::new(),
+            pending_album_ids: VecDeque::new(),
             wave_session: Arc::new(Mutex::new(None)),
         }
     }
@@ -317,6 +328,7 @@ impl FetchState {
             task.abort();
         }
         self.pending_track_ids.clear();
+        self.pending_album_ids.clear();
         *self.wave_session.lock().unwrap() = None;
     }
 
@@ -354,7 +366,7 @@ impl FetchState {
         self.wave_session.clone()
     }
 
-    fn trigger_playlist_batch(&mut self, api: Arc<ApiService>, event_tx: Option<Sender<Event>>) {
+    fn trigger_playlist_batch(&mut self, api: Arc<dyn MusicApi>, event_tx: Option<Sender<Event>>) {
         debug_assert!(!self.is_fetching());
         let count = FETCH_BATCH_SIZE.min(self.pending_track_ids.len());
         let ids: Vec<String> = self.pending_track_ids.drain(0..count).collect();
@@ -381,12 +393,43 @@ impl FetchState {
         }));
     }
 
+    fn trigger_album_batch(&mut self, api: Arc<dyn MusicApi>, event_tx: Option<Sender<Event>>) {
+        debug_assert!(!self.is_fetching());
+        let Some(album_id) = self.pending_album_ids.pop_front() else {
+            return;
+        };
+
+        self.task = Some(tokio::spawn(async move {
+            match api.fetch_album_with_tracks(album_id).await {
+                Ok(album) => {
+                    let valid: Vec<Track> = album
+                        .volumes
+                        .into_iter()
+                        .flatten()
+                        .filter(|t| t.available.unwrap_or(false))
+                        .collect();
+                    if !valid.is_empty()
+                        && let Some(tx) = event_tx
+                    {
+                        let _ = tx.send(Event::QueueUpdated);
+                    }
+                    (valid, None)
+                }
+                Err(e) => {
+                    error!(error = %e, album_id, "album_fetch_failed");
+                    (vec![], None)
+                }
+            }
+        }));
+    }
+
     fn trigger_wave_batch(
         &mut self,
-        api: Arc<ApiService>,
+        api: Arc<dyn MusicApi>,
         event_tx: Option<Sender<Event>>,
         wave_seeds: Vec<String>,
         pending_feedback: Vec<WaveTrackEvent>,
+        skipped: HashSet<String>,
     ) {
         debug_assert!(!self.is_fetching());
         let session = match self.wave_session_clone() {
@@ -433,12 +476,21 @@ impl FetchState {
                 .await
             {
                 Ok(response) => {
-                    let new_tracks: Vec<Track> = response
+                    let mut new_tracks: Vec<Track> = response
                         .sequence
                         .iter()
                         // .take(1)
                         .map(|item| item.track.clone())
                         .collect();
+                    if new_tracks.iter().any(|t| !skipped.contains(&t.id)) {
+                        new_tracks.retain(|t| {
+                            let keep = !skipped.contains(&t.id);
+                            if !keep {
+                                debug!(track_id = %t.id, "wave_track_filtered");
+                            }
+                            keep
+                        });
+                    }
                     if !new_tracks.is_empty()
                         && let Some(tx) = event_tx
                     {
@@ -461,7 +513,7 @@ impl FetchState {
 }
 
 struct WaveExtensionHandles {
-    queue: Signal<Vector<Track>>,
+    queue: Signal<Vector<QueueItem>>,
     queue_length: Signal<usize>,
     wave_session: Arc<Mutex<Option<Session>>>,
     playback_context: Arc<Mutex<PlaybackContext>>,
@@ -473,14 +525,15 @@ impl WaveExtensionHandles {
         *self.wave_session.lock().unwrap() = Some(session.clone());
         *self.playback_context.lock().unwrap() = PlaybackContext::Wave(session);
 
-        let visible: Vector<Track> = additional
+        let visible: Vector<QueueItem> = additional
             .iter()
             .take(WAVE_VISIBLE_TRACKS)
             .cloned()
+            .map(QueueItem::Remote)
             .collect();
         self.queue.update(|q| q.extend(visible));
         self.queue_length
-            .set(self.queue.with(|q: &Vector<Track>| q.len()));
+            .set(self.queue.with(|q: &Vector<QueueItem>| q.len()));
 
         if let Some(tx) = self.event_tx.clone() {
             let _ = tx.send(Event::QueueUpdated);
@@ -533,7 +586,7 @@ impl QueueSignals {
         Self { inner }
     }
 
-    fn queue(&self) -> Vector<Track> {
+    fn queue(&self) -> Vector<QueueItem> {
         self.inner.queue.with(|q| q.clone())
     }
 
@@ -549,13 +602,13 @@ impl QueueSignals {
         self.inner.is_shuffled.get()
     }
 
-    fn write_queue(&self, queue: Vector<Track>) {
+    fn write_queue(&self, queue: Vector<QueueItem>) {
         let len = queue.len();
         self.inner.queue.set(queue);
         self.inner.queue_length.set(len);
     }
 
-    fn write_history(&self, history: Vector<Track>) {
+    fn write_history(&self, history: Vector<QueueItem>) {
         self.inner.history.set(history);
     }
 
@@ -571,7 +624,7 @@ impl QueueSignals {
         self.inner.is_shuffled.set(shuffled);
     }
 
-    fn raw_queue_handle(&self) -> Signal<Vector<Track>> {
+    fn raw_queue_handle(&self) -> Signal<Vector<QueueItem>> {
         self.inner.queue.clone()
     }
 
@@ -581,7 +634,7 @@ impl QueueSignals {
 }
 
 pub struct QueueManager {
-    pub api: Arc<ApiService>,
+    pub api: Arc<dyn MusicApi>,
     pub url_cache: UrlCache,
     pub stream_manager: Arc<StreamManager>,
     url_prefetcher: UrlPrefetcher,
@@ -590,16 +643,17 @@ pub struct QueueManager {
     shuffle: ShuffleState,
     history: HistoryState,
     fetch: FetchState,
-    wave_buffer: VecDeque<Track>,
+    wave_buffer: VecDeque<QueueItem>,
     wave_feedbacks: Vec<WaveTrackEvent>,
     wave_feedback_sent: bool,
+    wave_skipped: VecDeque<String>,
     track_progress: Arc<TrackProgress>,
     pub event_tx: Option<Sender<Event>>,
 }
 
 impl QueueManager {
     pub fn new(
-        api: Arc<ApiService>,
+        api: Arc<dyn MusicApi>,
         url_cache: UrlCache,
         stream_manager: Arc<StreamManager>,
         signals: AudioSignals,
@@ -620,6 +674,7 @@ impl QueueManager {
             wave_buffer: VecDeque::new(),
             wave_feedbacks: Vec::new(),
             wave_feedback_sent: false,
+            wave_skipped: VecDeque::new(),
             track_progress,
             event_tx: None,
         }
@@ -645,20 +700,21 @@ impl QueueManager {
     }
 
     pub fn wave_update_buffer(&mut self, tracks: Vec<Track>) {
-        for t in tracks {
-            self.wave_buffer.push_back(t);
-        }
+        self.wave_buffer
+            .extend(tracks.into_iter().map(QueueItem::Remote));
     }
 
     pub async fn load(
         &mut self,
         context: PlaybackContext,
-        mut tracks: Vector<Track>,
+        mut tracks: Vector<QueueItem>,
         mut start_index: usize,
-    ) -> Option<Track> {
+    ) -> Option<QueueItem> {
         self.fetch.reset();
         self.url_prefetcher.reset();
 
+        let seamless = matches!(context, PlaybackContext::Album(_)) && is_seamless(&tracks);
+        self.stream_manager.set_seamless(seamless);
         *self.playback_context.lock().unwrap() = context;
         self.shuffle.reset();
         self.history.reset();
@@ -689,6 +745,7 @@ impl QueueManager {
                 }
                 PlaybackContext::Artist(_)
                 | PlaybackContext::Album(_)
+                | PlaybackContext::Folder(_)
                 | PlaybackContext::Standalone => {
                     if start_index >= tracks.len() {
                         start_index = 0;
@@ -703,25 +760,24 @@ impl QueueManager {
                     }
                     tracks = slice_from(tracks, start_index);
                     let visible_count = 1 + WAVE_VISIBLE_TRACKS;
-                    let visible: Vector<Track> =
+                    let visible: Vector<QueueItem> =
                         tracks.iter().take(visible_count).cloned().collect();
-                    let hidden: Vec<Track> = tracks.into_iter().skip(visible_count).collect();
                     self.signals.write_queue(visible);
-                    for t in hidden {
-                        self.wave_buffer.push_back(t);
-                    }
+                    self.wave_buffer
+                        .extend(tracks.into_iter().skip(visible_count));
                     self.fetch.set_wave_session(session.clone());
                     None
                 }
                 PlaybackContext::Track(seed_track) => {
-                    let mut initial_queue = Vector::new();
-                    initial_queue.push_back(seed_track.clone());
-                    self.signals.write_queue(initial_queue);
-                    let needs_init = seed_track.track_source.as_ref().is_none_or(|s| s != "UGC");
-                    if needs_init {
-                        Some(seed_track.clone())
-                    } else {
-                        None
+                    let item = QueueItem::from(seed_track.clone());
+                    self.signals.write_queue(Vector::unit(item.clone()));
+                    match item {
+                        QueueItem::Remote(track)
+                            if track.track_source.as_ref().is_none_or(|s| s != "UGC") =>
+                        {
+                            Some(track)
+                        }
+                        _ => None,
                     }
                 }
             }
@@ -733,12 +789,12 @@ impl QueueManager {
 
         self.signals.write_index(0);
 
-        let track = self.signals.queue().get(0).cloned();
-        if let Some(t) = &track {
-            self.commit_track_to_history(t.clone());
+        let item = self.signals.queue().get(0).cloned();
+        if let Some(item) = &item {
+            self.commit_track_to_history(item.clone());
             self.update_prefetch_interest();
         }
-        track
+        item
     }
 
     fn wave_by_seed(&self, seed_track: &Track) {
@@ -767,7 +823,7 @@ impl QueueManager {
         });
     }
 
-    pub async fn get_next_track(&mut self) -> Option<Track> {
+    pub async fn get_next_track(&mut self) -> Option<QueueItem> {
         if self.signals.queue().is_empty() {
             return None;
         }
@@ -811,7 +867,7 @@ impl QueueManager {
         None
     }
 
-    pub fn get_previous_track(&mut self) -> Option<Track> {
+    pub fn get_previous_track(&mut self) -> Option<QueueItem> {
         let prev = PlaybackPolicy::prev_index(
             self.signals.index(),
             self.signals.queue().len(),
@@ -820,7 +876,7 @@ impl QueueManager {
         self.advance_to(prev)
     }
 
-    pub async fn play_track_at_index(&mut self, index: usize) -> Option<Track> {
+    pub async fn play_track_at_index(&mut self, index: usize) -> Option<QueueItem> {
         self.poll_fetch().await;
         if index >= self.signals.queue().len() {
             return None;
@@ -828,16 +884,20 @@ impl QueueManager {
         self.advance_to(index)
     }
 
-    pub async fn skip_wave_track(&mut self) -> Option<Track> {
+    pub async fn skip_wave_track(&mut self) -> Option<QueueItem> {
         if self.in_wave() && !self.wave_feedback_sent {
-            if let Some(track) = self.signals.queue().get(self.signals.index()).cloned() {
+            if let Some(QueueItem::Remote(track)) =
+                self.signals.queue().get(self.signals.index()).cloned()
+            {
+                let total_played = self.track_progress.current_position();
                 self.wave_feedbacks.push(WaveTrackEvent {
                     track_id: as_wave_seed(&track),
                     outcome: WaveTrackOutcome::Skipped,
-                    total_played: self.track_progress.current_position(),
+                    total_played,
                     track_length: None,
                 });
                 self.wave_feedback_sent = true;
+                self.record_wave_skip(&track, total_played);
             }
             self.wave_buffer.clear();
             if !self.fetch.is_fetching() {
@@ -864,11 +924,36 @@ impl QueueManager {
         None
     }
 
+    fn record_wave_skip(&mut self, track: &Track, total_played: Duration) {
+        let threshold = Duration::from_secs(config::get().playback.wave_quick_skip_secs);
+        if total_played < threshold {
+            self.set_wave_filtered(&track.id, true);
+        }
+    }
+
+    pub fn set_wave_filtered(&mut self, track_id: &str, filtered: bool) {
+        let known = self.wave_skipped.iter().position(|id| id == track_id);
+        match (filtered, known) {
+            (true, None) => {
+                if self.wave_skipped.len() >= WAVE_SKIPPED_LIMIT {
+                    self.wave_skipped.pop_front();
+                }
+                self.wave_skipped.push_back(track_id.to_string());
+            }
+            (false, Some(index)) => {
+                self.wave_skipped.remove(index);
+            }
+            _ => {}
+        }
+    }
+
     pub fn wave_finish_track(&mut self) {
         if !self.in_wave() || self.wave_feedback_sent {
             return;
         }
-        if let Some(track) = self.signals.queue().get(self.signals.index()).cloned() {
+        if let Some(QueueItem::Remote(track)) =
+            self.signals.queue().get(self.signals.index()).cloned()
+        {
             let id = as_wave_seed(&track);
             if self.wave_feedbacks.iter().any(|e| e.track_id == id) {
                 self.wave_feedback_sent = true;
@@ -901,11 +986,11 @@ impl QueueManager {
         }
     }
 
-    fn advance_to(&mut self, index: usize) -> Option<Track> {
+    fn advance_to(&mut self, index: usize) -> Option<QueueItem> {
         self.signals.write_index(index);
         self.wave_feedback_sent = false;
-        let track = self.signals.queue().get(index).cloned()?;
-        self.commit_track_to_history(track.clone());
+        let item = self.signals.queue().get(index).cloned()?;
+        self.commit_track_to_history(item.clone());
 
         if self.in_wave() {
             let queue_len = self.signals.queue().len();
@@ -926,10 +1011,10 @@ impl QueueManager {
         }
 
         self.update_prefetch_interest();
-        Some(track)
+        Some(item)
     }
 
-    pub fn queue_track(&mut self, track: Track) {
+    pub fn queue_track(&mut self, item: QueueItem) {
         let mut queue = self.signals.queue();
         let current_index = self.signals.index();
 
@@ -940,15 +1025,15 @@ impl QueueManager {
         };
 
         if insert_at <= queue.len() {
-            queue.insert(insert_at, track);
+            queue.insert(insert_at, item);
             self.signals.write_queue(queue);
             self.shuffle.record_inserted(insert_at);
         }
         self.update_prefetch_interest();
     }
 
-    pub fn play_next(&mut self, track: Track) {
-        self.queue_track(track);
+    pub fn play_next(&mut self, item: QueueItem) {
+        self.queue_track(item);
     }
 
     pub fn remove_track(&mut self, index: usize) {
@@ -966,6 +1051,27 @@ impl QueueManager {
         }
     }
 
+    pub fn remove_track_id(&mut self, track_id: &str) {
+        let queue = self.signals.queue();
+        let current_index = self.signals.index();
+        let mut kept = Vector::new();
+        let mut index = current_index;
+        for (i, item) in queue.iter().enumerate() {
+            if item.id() == track_id && i != current_index {
+                if i < current_index {
+                    index -= 1;
+                }
+                continue;
+            }
+            kept.push_back(item.clone());
+        }
+        if kept.len() != queue.len() {
+            self.signals.write_queue(kept);
+            self.signals.write_index(index);
+            self.update_prefetch_interest();
+        }
+    }
+
     pub fn clear(&mut self) {
         self.signals.write_queue(Vector::new());
         self.signals.write_index(0);
@@ -975,6 +1081,10 @@ impl QueueManager {
         self.update_prefetch_interest();
     }
 
+    pub fn queue_albums(&mut self, album_ids: Vec<u32>) {
+        self.fetch.pending_album_ids.extend(album_ids);
+    }
+
     pub fn trigger_fetch_if_needed(&mut self) {
         if self.in_wave() {
             return;
@@ -993,14 +1103,26 @@ impl QueueManager {
             return;
         }
 
+        if !self.fetch.pending_album_ids.is_empty() {
+            self.fetch
+                .trigger_album_batch(self.api.clone(), self.event_tx.clone());
+            return;
+        }
+
         if self.fetch.wave_session_clone().is_some() {
             let history_seeds = self.build_wave_history_seeds();
             let pending_feedback = std::mem::take(&mut self.wave_feedbacks);
+            let skipped = if config::get().playback.wave_filter_skipped {
+                self.wave_skipped.iter().cloned().collect()
+            } else {
+                HashSet::new()
+            };
             self.fetch.trigger_wave_batch(
                 self.api.clone(),
                 self.event_tx.clone(),
                 history_seeds,
                 pending_feedback,
+                skipped,
             );
         }
     }
@@ -1010,6 +1132,7 @@ impl QueueManager {
             .entries
             .iter()
             .rev()
+            .filter_map(QueueItem::remote)
             .take(20)
             .map(as_wave_seed)
             .collect()
@@ -1035,23 +1158,24 @@ impl QueueManager {
     }
 
     fn wave_append(&mut self, tracks: Vec<Track>) {
+        let items = tracks.into_iter().map(QueueItem::Remote);
         if self.in_wave() {
-            for track in tracks {
+            for item in items {
                 let current_index = self.signals.index();
                 let queue_len = self.signals.queue().len();
                 let visible_ahead = queue_len.saturating_sub(current_index + 1);
 
                 if visible_ahead < WAVE_VISIBLE_TRACKS {
                     let mut q = self.signals.queue();
-                    q.push_back(track);
+                    q.push_back(item);
                     self.signals.write_queue(q);
                 } else {
-                    self.wave_buffer.push_back(track);
+                    self.wave_buffer.push_back(item);
                 }
             }
         } else {
             let mut queue = self.signals.queue();
-            queue.extend(tracks);
+            queue.extend(items);
             self.signals.write_queue(queue);
         }
 
@@ -1065,22 +1189,28 @@ impl QueueManager {
         }
 
         let current_index = self.signals.index();
-        let current_id = queue.get(current_index).map(|t| t.id.clone());
+        let current_id = queue.get(current_index).map(QueueItem::id);
 
         let needed: Vec<String> = (0..URL_PREFETCH_WINDOW)
-            .filter_map(|i| queue.get(current_index + i))
+            .filter_map(|i| queue.get(current_index + i)?.remote())
             .map(|t| t.id.clone())
             .collect();
 
-        if let Some(next_track) = queue.get(current_index + 1) {
+        if let Some(QueueItem::Remote(next_track)) = queue.get(current_index + 1) {
             self.stream_manager.prewarm(next_track.clone());
         }
 
+        let upcoming: Vec<Track> = (1..=config::get().downloads.ahead)
+            .filter_map(|i| queue.get(current_index + i)?.remote())
+            .cloned()
+            .collect();
+        self.stream_manager.schedule_downloads(upcoming);
+
         self.url_prefetcher.update(needed, current_id);
     }
 
-    fn commit_track_to_history(&mut self, track: Track) {
-        self.history.push(track);
+    fn commit_track_to_history(&mut self, item: QueueItem) {
+        self.history.push(item);
         self.signals.write_history(self.history.as_vector());
     }
 
@@ -1113,7 +1243,25 @@ impl QueueManager {
     }
 }
 
-fn slice_from(mut v: Vector<Track>, start: usize) -> Vector<Track> {
+fn is_seamless(tracks: &Vector<QueueItem>) -> bool {
+    let edges = |item: &QueueItem| {
+        let track = item.remote()?;
+        let fade = track.fade.as_ref()?;
+        let duration = track.duration?.as_secs_f32();
+        Some((
+            fade.in_stop <= SEAMLESS_EDGE_SECS,
+            fade.out_start >= duration - SEAMLESS_EDGE_SECS,
+        ))
+    };
+    let boundaries: Vec<bool> = tracks
+        .iter()
+        .zip(tracks.iter().skip(1))
+        .filter_map(|(a, b)| Some(edges(a)?.1 && edges(b)?.0))
+        .collect();
+    !boundaries.is_empty() && boundaries.iter().filter(|&&b| b).count() * 4 >= boundaries.len() * 3
+}
+
+fn slice_from(mut v: Vector<QueueItem>, start: usize) -> Vector<QueueItem> {
     if start == 0 {
         v
     } else if start < v.len() {
@@ -4,8 +4,30 @@ use im::Vector;
 use yandex_music::model::track::Track;
 
 use crate::audio::enums::RepeatMode;
+use crate::audio::local::QueueItem;
 use crate::audio::monitor::Monitor;
+use crate::cache::image::{CoverSize, ImageCache};
 use crate::framework::signals::Signal;
+use crate::util::chapters::{self, Chapter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStage {
+    Resolving,
+    Connecting,
+    Buffering,
+}
+
+impl LoadStage {
+    pub fn label(self) -> &'static str {
+        match self {
+            LoadStage::Resolving => "Resolving stream",
+            LoadStage::Connecting => "Connecting",
+            LoadStage::Buffering => "Buffering",
+        }
+    }
+}
+
+pub type StageReporter = Arc<dyn Fn(LoadStage) + Send + Sync>;
 
 #[derive(Clone)]
 pub struct AudioSignals {
@@ -13,6 +35,7 @@ pub struct AudioSignals {
     pub is_paused: Signal<bool>,
     pub is_stopped: Signal<bool>,
     pub is_buffering: Signal<bool>,
+    pub load_stage: Signal<Option<LoadStage>>,
     pub current_track: Signal<Option<Track>>,
     pub current_track_id: Signal<Option<String>>,
     pub current_cover_url: Signal<Option<String>>,
@@ -20,12 +43,15 @@ pub struct AudioSignals {
     pub track_artists: Signal<Option<String>>,
     pub position_ms: Signal<u64>,
     pub duration_ms: Signal<u64>,
+    pub chapters: Signal<Vec<Chapter>>,
     pub progress_ratio: Signal<f32>,
     pub buffered_ratio: Signal<f32>,
+    pub buffered_ranges: Signal<Vec<(f32, f32)>>,
+    pub quality_downgraded: Signal<bool>,
     pub volume: Signal<u8>,
     pub is_muted: Signal<bool>,
-    pub queue: Signal<Vector<Track>>,
-    pub history: Signal<Vector<Track>>,
+    pub queue: Signal<Vector<QueueItem>>,
+    pub history: Signal<Vector<QueueItem>>,
     pub queue_index: Signal<usize>,
     pub queue_length: Signal<usize>,
     pub repeat_mode: Signal<RepeatMode>,
@@ -41,6 +67,7 @@ impl AudioSignals {
             is_paused: Signal::new(false),
             is_stopped: Signal::new(true),
             is_buffering: Signal::new(false),
+            load_stage: Signal::new(None),
             current_track: Signal::new(None),
             current_track_id: Signal::new(None),
             current_cover_url: Signal::new(None),
@@ -48,8 +75,11 @@ impl AudioSignals {
             track_artists: Signal::new(None),
             position_ms: Signal::new(0),
             duration_ms: Signal::new(0),
+            chapters: Signal::new(Vec::new()),
             progress_ratio: Signal::new(0.0),
             buffered_ratio: Signal::new(0.0),
+            buffered_ranges: Signal::new(Vec::new()),
+            quality_downgraded: Signal::new(false),
             volume: Signal::new(100),
             is_muted: Signal::new(false),
             queue: Signal::new(Vector::new()),
@@ -75,18 +105,13 @@ impl AudioSignals {
                     .join(", "),
             ));
             self.current_track_id.set(Some(t.id.clone()));
+            self.quality_downgraded.set(false);
+            self.chapters.set(chapters::for_track(t));
             self.current_cover_url.set(
                 t.cover_uri
                     .as_ref()
                     .or_else(|| t.albums.first().and_then(|a| a.cover_uri.as_ref()))
-                    .map(|uri| {
-                        let uri = uri.replace("%%", "400x400");
-                        if uri.starts_with("http") {
-                            uri
-                        } else {
-                            format!("https://{}", uri)
-                        }
-                    }),
+                    .map(|uri| ImageCache::resolve_cover_uri(uri, CoverSize::Full)),
             );
 
             if let Some(duration) = t.duration {
@@ -97,7 +122,9 @@ impl AudioSignals {
             self.track_title.set(None);
             self.track_artists.set(None);
             self.current_track_id.set(None);
+            self.quality_downgraded.set(false);
             self.current_cover_url.set(None);
+            self.chapters.set(Vec::new());
             self.duration_ms.set(0);
             self.is_stopped.set(true);
         }
@@ -125,14 +152,20 @@ impl AudioSignals {
         self.buffered_ratio.set(ratio.clamp(0.0, 1.0));
     }
 
-    pub fn update_queue(&self, queue: Vector<Track>, index: usize) {
+    pub fn update_buffered_ranges(&self, ranges: Vec<(f32, f32)>) {
+        if self.buffered_ranges.with(|current| *current != ranges) {
+            self.buffered_ranges.set(ranges);
+        }
+    }
+
+    pub fn update_queue(&self, queue: Vector<QueueItem>, index: usize) {
         let len = queue.len();
         self.queue.set(queue);
         self.queue_index.set(index);
         self.queue_length.set(len);
     }
 
-    pub fn set_queue(&self, queue: Vector<Track>, history: Vector<Track>, index: usize) {
+    pub fn set_queue(&self, queue: Vector<QueueItem>, history: Vector<QueueItem>, index: usize) {
         self.queue.set(queue.clone());
         self.history.set(history);
         self.queue_index.set(index);
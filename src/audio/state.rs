@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
 use yandex_music::model::track::Track;
 
-use crate::audio::liked::LikedCache;
+use crate::{audio::liked::LikedCache, http::MusicApi};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum PlaybackState {
@@ -15,3 +18,15 @@ pub enum PlaybackState {
 pub struct SystemState {
     pub liked: LikedCache,
 }
+
+pub async fn sync_liked_collection_with(api: Arc<dyn MusicApi>, state: Arc<RwLock<SystemState>>) {
+    let revision = {
+        let state = state.read().await;
+        state.liked.revision
+    };
+
+    if let Ok(liked_collection) = api.fetch_liked_collection(revision).await {
+        let mut state = state.write().await;
+        state.liked.apply_collection(liked_collection);
+    }
+}
@@ -1,32 +1,63 @@
 use crate::audio::cache::UrlCache;
+use crate::audio::downloads::{self, DownloadScheduler};
+use crate::audio::local::QueueItem;
 use crate::audio::progress::TrackProgress;
-use crate::http::ApiService;
+use crate::audio::signals::{LoadStage, StageReporter};
+use crate::http::MusicApi;
 use crate::stream;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use yandex_music::model::track::Track;
 
 pub struct StreamManager {
-    api: Arc<ApiService>,
+    api: Arc<dyn MusicApi>,
     url_cache: UrlCache,
     prewarm_cache: Arc<Mutex<HashMap<String, (stream::StreamingSession, Arc<TrackProgress>)>>>,
     http_client: reqwest::blocking::Client,
+    seamless: Arc<AtomicBool>,
+    downloads: DownloadScheduler,
 }
 
 impl StreamManager {
-    pub fn new(api: Arc<ApiService>, url_cache: UrlCache) -> Self {
+    pub fn new(api: Arc<dyn MusicApi>, url_cache: UrlCache) -> Self {
         let http_client = reqwest::blocking::Client::builder()
-            .pool_max_idle_per_host(4)
-            .pool_idle_timeout(std::time::Duration::from_secs(60))
+            .pool_max_idle_per_host(8)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .connect_timeout(Duration::from_secs(10))
+            .tcp_nodelay(true)
+            .tcp_keepalive(Duration::from_secs(30))
+            .http2_adaptive_window(true)
             .build()
             .expect("failed to create streaming http client");
 
+        let downloads = DownloadScheduler::new(api.clone(), url_cache.clone(), http_client.clone());
+
         Self {
             api,
             url_cache,
             prewarm_cache: Arc::new(Mutex::new(HashMap::new())),
             http_client,
+            seamless: Arc::new(AtomicBool::new(false)),
+            downloads,
+        }
+    }
+
+    pub fn schedule_downloads(&self, upcoming: Vec<Track>) {
+        self.downloads.schedule_upcoming(upcoming);
+    }
+
+    pub fn download(&self, tracks: Vec<Track>) {
+        self.downloads.request(tracks);
+    }
+
+    pub fn set_seamless(&self, seamless: bool) {
+        if self.seamless.swap(seamless, Ordering::Relaxed) != seamless {
+            tracing::info!(seamless, "seamless_context_changed");
+            self.prewarm_cache.lock().unwrap().clear();
         }
     }
 
@@ -38,15 +69,42 @@ impl StreamManager {
 
         let this = self.clone();
         tokio::spawn(async move {
-            if let Ok(result) = this.create_stream_session(&track).await {
+            if let Ok(result) = this.remote_session(&track, Arc::new(|_| {})).await {
                 this.prewarm_cache.lock().unwrap().insert(track.id, result);
             }
         });
     }
 
     pub async fn create_stream_session(
+        &self,
+        item: &QueueItem,
+        report: StageReporter,
+    ) -> color_eyre::Result<(stream::StreamingSession, Arc<TrackProgress>)> {
+        match item {
+            QueueItem::Remote(track) => self.remote_session(track, report).await,
+            QueueItem::Local(local) => self.file_session(local.path.clone(), local.codec()).await,
+        }
+    }
+
+    async fn file_session(
+        &self,
+        path: PathBuf,
+        codec: String,
+    ) -> color_eyre::Result<(stream::StreamingSession, Arc<TrackProgress>)> {
+        let skip_silence = !self.seamless.load(Ordering::Relaxed);
+        let progress = Arc::new(TrackProgress::new());
+        let progress_clone = progress.clone();
+        let session = tokio::task::spawn_blocking(move || {
+            stream::create_file_session(&path, codec, progress_clone, skip_silence)
+        })
+        .await??;
+        Ok((session, progress))
+    }
+
+    async fn remote_session(
         &self,
         track: &Track,
+        report: StageReporter,
     ) -> color_eyre::Result<(stream::StreamingSession, Arc<TrackProgress>)> {
         {
             let mut cache = self.prewarm_cache.lock().unwrap();
@@ -55,16 +113,16 @@ impl StreamManager {
             }
         }
 
-        let _start = std::time::Instant::now();
-        let (url, codec, bitrate) =
-            if let Some((url, codec, bitrate)) = self.url_cache.get(&track.id) {
-                (url, codec, bitrate)
-            } else {
-                let (url, codec, bitrate) = self.api.fetch_track_url(track.id.clone()).await?;
-                self.url_cache
-                    .insert(track.id.clone(), url.clone(), codec.clone(), bitrate);
-                (url, codec, bitrate)
-            };
+        if let Some((path, codec)) = downloads::find(&track.id) {
+            return self.file_session(path, codec).await;
+        }
+
+        let skip_silence = !self.seamless.load(Ordering::Relaxed);
+
+        if self.url_cache.get(&track.id).is_none() {
+            report(LoadStage::Resolving);
+        }
+        let (url, codec, bitrate) = self.track_url(track).await?;
 
         let progress = Arc::new(TrackProgress::new());
         progress.set_bitrate(bitrate.into());
@@ -73,12 +131,53 @@ impl StreamManager {
 
         let client = self.http_client.clone();
         let session = tokio::task::spawn_blocking(move || {
-            stream::create_streaming_session(client, url, codec, bitrate, progress_clone)
+            stream::create_streaming_session(
+                client,
+                url,
+                codec,
+                bitrate,
+                progress_clone,
+                skip_silence,
+                report,
+            )
         })
         .await??;
 
         Ok((session, progress))
     }
+
+    pub async fn track_url(&self, track: &Track) -> color_eyre::Result<(String, String, u32)> {
+        if let Some(cached) = self.url_cache.get(&track.id) {
+            return Ok(cached);
+        }
+        let (url, codec, bitrate) = self.api.fetch_track_url(track.id.clone()).await?;
+        self.url_cache
+            .insert(track.id.clone(), url.clone(), codec.clone(), bitrate);
+        Ok((url, codec, bitrate))
+    }
+
+    pub fn invalidate(&self, track_id: &str) {
+        self.url_cache.remove(track_id);
+        self.prewarm_cache.lock().unwrap().remove(track_id);
+    }
+
+    pub async fn is_downgraded(&self, track: &Track) -> bool {
+        let Some((_, codec, bitrate)) = self.url_cache.get(&track.id) else {
+            return false;
+        };
+        if codec.contains("flac") {
+            return false;
+        }
+        match self.api.fetch_track_formats(track.id.clone()).await {
+            Ok(formats) => formats
+                .iter()
+                .any(|(best, best_bitrate)| best.contains("flac") || *best_bitrate > bitrate),
+            Err(e) => {
+                tracing::debug!(error = %e, track_id = %track.id, "track_formats_failed");
+                false
+            }
+        }
+    }
 }
 
 impl Clone for StreamManager {
@@ -88,6 +187,8 @@ impl Clone for StreamManager {
             url_cache: self.url_cache.clone(),
             prewarm_cache: self.prewarm_cache.clone(),
             http_client: self.http_client.clone(),
+            seamless: self.seamless.clone(),
+            downloads: self.downloads.clone(),
         }
     }
 }
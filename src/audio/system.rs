@@ -3,36 +3,48 @@ use crate::{
         commands::AudioCommand,
         controller::AudioController,
         enums::RepeatMode,
+        local::QueueItem,
+        outputs::OutputTarget,
         playback::PlaybackEngine,
+        player::{EffectHandles, Player},
         progress::TrackProgress,
         queue::{PlaybackContext, QueueManager, as_wave_seed},
         signals::AudioSignals,
-        state::SystemState,
+        state::{SystemState, sync_liked_collection_with},
         stream_manager::StreamManager,
     },
     event::events::Event,
-    http::ApiService,
+    framework::tasks::TaskManager,
+    http::MusicApi,
+    util::{
+        config::{self, QueueEnd},
+        track::extract_ids,
+    },
 };
+use async_trait::async_trait;
 use flume::Sender;
 use im::Vector;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::RwLock;
 use yandex_music::model::track::Track;
 
+const QUEUE_END_PAGE: usize = 50;
+
 pub struct AudioSystem {
     controller: AudioController,
     queue: QueueManager,
     event_tx: Sender<Event>,
-    #[allow(dead_code)]
-    api: Arc<ApiService>,
+    api: Arc<dyn MusicApi>,
     state: Arc<RwLock<SystemState>>,
     signals: AudioSignals,
+    file_info: FileInfoResolver,
+    tasks: Arc<TaskManager>,
 }
 
-use crate::audio::cache::UrlCache;
+use crate::audio::{cache::UrlCache, file_info::FileInfoResolver};
 
 impl AudioSystem {
-    pub async fn new(event_tx: Sender<Event>, api: Arc<ApiService>) -> color_eyre::Result<Self> {
+    pub async fn new(event_tx: Sender<Event>, api: Arc<dyn MusicApi>) -> color_eyre::Result<Self> {
         let engine = PlaybackEngine::new()?;
         let url_cache = UrlCache::new();
         let stream_manager = Arc::new(
@@ -45,6 +57,8 @@ impl AudioSystem {
         );
 
         let signals = AudioSignals::new();
+        let tasks = Arc::new(TaskManager::new());
+        let file_info = FileInfoResolver::new(api.clone(), url_cache.clone(), &tasks);
 
         let track_progress = Arc::new(TrackProgress::default());
 
@@ -54,6 +68,7 @@ impl AudioSystem {
             event_tx.clone(),
             signals.clone(),
             track_progress.clone(),
+            tasks.clone(),
         );
 
         let mut queue = QueueManager::new(
@@ -73,118 +88,27 @@ impl AudioSystem {
             api,
             state,
             signals,
+            file_info,
+            tasks,
         })
     }
 
-    pub fn signals(&self) -> &AudioSignals {
-        &self.signals
-    }
-
-    pub fn toggle_effect(&self, name: &str) -> bool {
-        self.controller.toggle_effect(name)
-    }
-
-    pub fn is_effect_enabled(&self, name: &str) -> Option<bool> {
-        self.controller.is_effect_enabled(name)
-    }
-
-    pub fn get_effect_handles(
-        &self,
-    ) -> Arc<std::sync::RwLock<std::collections::HashMap<String, crate::audio::fx::EffectHandle>>>
-    {
-        self.controller.get_effect_handles()
-    }
-
-    pub async fn load_context(
-        &mut self,
-        context: PlaybackContext,
-        tracks: Vector<Track>,
-        index: usize,
-    ) -> Option<Track> {
-        let in_wave = matches!(&context, PlaybackContext::Wave(_));
-        let track = self.queue.load(context, tracks, index).await;
-        if in_wave {
-            self.send_wave_started();
-        }
-        if let Some(t) = &track {
-            self.controller
-                .handle_command(AudioCommand::PlayTrack(t.clone()))
-                .await;
-            if in_wave {
-                self.send_wave_track_started(t);
-            }
-        }
-        track
-    }
-
     pub async fn load_tracks(&mut self, tracks: Vec<Track>) {
         if let Some(track) = self
-            .queue
-            .load(PlaybackContext::Standalone, Vector::from(tracks), 0)
-            .await
-        {
-            self.controller
-                .handle_command(AudioCommand::PlayTrack(track))
-                .await;
-        }
-    }
-
-    pub async fn play_single_track(&mut self, track: Track) {
-        if let Some(playing_track) = self
             .queue
             .load(
-                PlaybackContext::Track(track.clone()),
-                Vector::from(vec![track]),
+                PlaybackContext::Standalone,
+                tracks.into_iter().map(QueueItem::from).collect(),
                 0,
             )
             .await
         {
-            self.controller
-                .handle_command(AudioCommand::PlayTrack(playing_track))
-                .await;
-        }
-    }
-
-    pub async fn play_track_at_index(&mut self, index: usize) {
-        if let Some(track) = self.queue.play_track_at_index(index).await {
             self.controller
                 .handle_command(AudioCommand::PlayTrack(track))
                 .await;
         }
     }
 
-    pub async fn on_track_ended(&mut self) {
-        self.queue.wave_finish_track();
-
-        if let Some(next_track) = self.queue.get_next_track().await {
-            if self.queue.in_wave() {
-                self.send_wave_track_started(&next_track);
-            }
-            self.controller
-                .handle_command(AudioCommand::PlayTrack(next_track))
-                .await;
-        } else {
-            let _ = self.event_tx.send(Event::QueueEnded);
-        }
-    }
-
-    pub async fn play_next(&mut self) {
-        let next = if self.queue.in_wave() {
-            self.queue.skip_wave_track().await
-        } else {
-            self.queue.get_next_track().await
-        };
-
-        if let Some(next_track) = next {
-            if self.queue.in_wave() {
-                self.send_wave_track_started(&next_track);
-            }
-            self.controller
-                .handle_command(AudioCommand::PlayTrack(next_track))
-                .await;
-        }
-    }
-
     fn send_wave_feedback(
         &self,
         feedback_type: &'static str,
@@ -231,34 +155,253 @@ impl AudioSystem {
         self.send_wave_feedback("radioStarted", None, None, false);
     }
 
-    pub fn send_wave_track_started(&self, track: &Track) {
+    pub fn send_wave_track_started(&self, item: &QueueItem) {
+        let Some(track) = item.remote() else {
+            return;
+        };
         let track_id = as_wave_seed(track);
         self.send_wave_feedback("trackStarted", Some(track_id), None, true);
     }
 
-    pub fn send_wave_like(&mut self, track: &Track) {
-        let track_id = as_wave_seed(track);
-        self.send_wave_feedback("like", Some(track_id), None, true);
-        self.queue.refresh_wave_queue();
+    pub fn current_track(&self) -> Option<Track> {
+        self.signals.current_track.get()
     }
 
-    pub fn send_wave_unlike(&mut self, track: &Track) {
-        let track_id = as_wave_seed(track);
-        self.send_wave_feedback("unlike", Some(track_id), None, true);
-        self.queue.refresh_wave_queue();
+    pub fn current_track_id(&self) -> Option<String> {
+        self.signals.current_track_id.get()
     }
 
-    pub fn send_wave_dislike(&mut self, track: &Track) {
-        let track_id = as_wave_seed(track);
-        self.send_wave_feedback("dislike", Some(track_id), None, true);
-        self.queue.refresh_wave_queue();
+    pub fn is_playing(&self) -> bool {
+        self.signals.is_playing.get()
     }
 
-    pub async fn send_wave_dislike_skip(&mut self, track: &Track) {
-        let track_id = as_wave_seed(track);
-        self.send_wave_feedback("dislike", Some(track_id), None, true);
-        self.queue.refresh_wave_queue();
+    pub fn repeat_mode(&self) -> RepeatMode {
+        self.signals.repeat_mode.get()
+    }
+
+    pub fn is_shuffled(&self) -> bool {
+        self.signals.is_shuffled.get()
+    }
+
+    pub fn volume(&self) -> u8 {
+        self.signals.volume.get()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.signals.is_muted.get()
+    }
+
+    pub fn track_progress(&self) -> Arc<TrackProgress> {
+        self.controller.track_progress.read().unwrap().clone()
+    }
+
+    pub fn queue(&self) -> Vector<QueueItem> {
+        self.signals.queue.with(|q| q.clone())
+    }
+
+    pub fn history(&self) -> Vector<QueueItem> {
+        self.signals.history.with(|h| h.clone())
+    }
+
+    pub fn current_track_index(&self) -> usize {
+        self.signals.queue_index.get()
+    }
+
+    pub fn current_amplitude(&self) -> f32 {
+        self.signals.amplitude.get()
+    }
+
+    pub async fn is_liked(&self, track_id: &str) -> bool {
+        let state = self.state.read().await;
+        state.liked.is_liked(track_id)
+    }
+
+    pub async fn is_disliked(&self, track_id: &str) -> bool {
+        let state = self.state.read().await;
+        state.liked.is_disliked(track_id)
+    }
+
+    async fn continue_after_queue_end(&mut self) {
+        let playback = config::get().playback.clone();
+        let result = match playback.queue_end {
+            QueueEnd::Stop => return,
+            QueueEnd::Repeat => {
+                if let Some(track) = self.queue.play_track_at_index(0).await {
+                    self.controller
+                        .handle_command(AudioCommand::PlayTrack(track))
+                        .await;
+                }
+                return;
+            }
+            QueueEnd::Radio => self.start_queue_end_radio().await,
+            QueueEnd::Playlist => match playback.queue_end_playlist {
+                Some(kind) => self.start_queue_end_playlist(kind).await,
+                None => Err(color_eyre::eyre::eyre!("queue_end_playlist is not set")),
+            },
+        };
+        if let Err(e) = result {
+            tracing::warn!(error = %e, strategy = ?playback.queue_end, "queue_end_failed");
+            let _ = self.event_tx.send(Event::FetchError(format!(
+                "Couldn't continue playback: {e}"
+            )));
+        }
+    }
+
+    async fn start_queue_end_radio(&mut self) -> color_eyre::Result<()> {
+        let Some(seed) = self.signals.current_track.get() else {
+            return Ok(());
+        };
+        let session = self
+            .api
+            .create_session(vec![format!("track:{}", as_wave_seed(&seed))])
+            .await?;
+        let tracks: Vector<QueueItem> = session
+            .sequence
+            .iter()
+            .map(|s| s.track.clone())
+            .filter(|t| t.id != seed.id)
+            .map(QueueItem::Remote)
+            .collect();
+        self.load_context(PlaybackContext::Wave(session), tracks, 0)
+            .await;
+        Ok(())
+    }
+
+    async fn start_queue_end_playlist(&mut self, kind: u32) -> color_eyre::Result<()> {
+        let playlist = self.api.fetch_playlist(kind).await?;
+        let ids = playlist
+            .tracks
+            .as_ref()
+            .map(extract_ids)
+            .unwrap_or_default();
+        let first_page = ids.into_iter().take(QUEUE_END_PAGE).collect();
+        let tracks: Vector<QueueItem> = self
+            .api
+            .fetch_tracks_by_ids(first_page)
+            .await?
+            .into_iter()
+            .filter(|t| t.available.unwrap_or(false))
+            .map(QueueItem::Remote)
+            .collect();
+        self.load_context(PlaybackContext::Playlist(playlist), tracks, 0)
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Player for AudioSystem {
+    fn signals(&self) -> &AudioSignals {
+        &self.signals
+    }
+
+    fn state_handle(&self) -> Arc<RwLock<SystemState>> {
+        self.state.clone()
+    }
+
+    fn get_effect_handles(&self) -> EffectHandles {
+        self.controller.get_effect_handles()
+    }
+
+    fn file_info(&self) -> FileInfoResolver {
+        self.file_info.clone()
+    }
+
+    fn tasks(&self) -> Arc<TaskManager> {
+        self.tasks.clone()
+    }
+
+    fn toggle_effect(&self, name: &str) -> bool {
+        self.controller.toggle_effect(name)
+    }
+
+    fn is_effect_enabled(&self, name: &str) -> Option<bool> {
+        self.controller.is_effect_enabled(name)
+    }
+
+    fn adjust_effect_param(&self, name: &str, index: usize, delta: f32) -> Option<f32> {
+        self.controller.adjust_effect_param(name, index, delta)
+    }
 
+    fn adjust_track_gain(&self, delta_db: f32) -> Option<f32> {
+        self.controller.adjust_track_gain(delta_db)
+    }
+
+    fn adjust_podcast_speed(&self, delta: f32) -> Option<f32> {
+        self.controller.adjust_podcast_speed(delta)
+    }
+
+    fn output_target(&self) -> Option<OutputTarget> {
+        self.controller.output_target()
+    }
+
+    async fn switch_output(&mut self, target: Option<OutputTarget>) {
+        self.controller.switch_output(target).await;
+    }
+
+    async fn load_context(
+        &mut self,
+        context: PlaybackContext,
+        tracks: Vector<QueueItem>,
+        index: usize,
+    ) -> Option<QueueItem> {
+        let in_wave = matches!(&context, PlaybackContext::Wave(_));
+        let track = self.queue.load(context, tracks, index).await;
+        if in_wave {
+            self.send_wave_started();
+        }
+        if let Some(t) = &track {
+            self.controller
+                .handle_command(AudioCommand::PlayTrack(t.clone()))
+                .await;
+            if in_wave {
+                self.send_wave_track_started(t);
+            }
+        }
+        track
+    }
+
+    async fn play_single_track(&mut self, track: Track) {
+        if let Some(playing_track) = self
+            .queue
+            .load(
+                PlaybackContext::Track(track.clone()),
+                Vector::unit(QueueItem::from(track)),
+                0,
+            )
+            .await
+        {
+            self.controller
+                .handle_command(AudioCommand::PlayTrack(playing_track))
+                .await;
+        }
+    }
+
+    async fn play_track_at_index(&mut self, index: usize) {
+        if let Some(track) = self.queue.play_track_at_index(index).await {
+            self.controller
+                .handle_command(AudioCommand::PlayTrack(track))
+                .await;
+        }
+    }
+
+    async fn on_track_ended(&mut self) {
+        self.queue.wave_finish_track();
+
+        if let Some(next_track) = self.queue.get_next_track().await {
+            if self.queue.in_wave() {
+                self.send_wave_track_started(&next_track);
+            }
+            self.controller
+                .handle_command(AudioCommand::PlayTrack(next_track))
+                .await;
+        } else {
+            let _ = self.event_tx.send(Event::QueueEnded);
+            self.continue_after_queue_end().await;
+        }
+    }
+
+    async fn play_next(&mut self) {
         let next = if self.queue.in_wave() {
             self.queue.skip_wave_track().await
         } else {
@@ -275,13 +418,7 @@ impl AudioSystem {
         }
     }
 
-    pub fn send_wave_undislike(&mut self, track: &Track) {
-        let track_id = as_wave_seed(track);
-        self.send_wave_feedback("undislike", Some(track_id), None, true);
-        self.queue.refresh_wave_queue();
-    }
-
-    pub async fn play_previous(&mut self) {
+    async fn play_previous(&mut self) {
         if let Some(prev_track) = self.queue.get_previous_track() {
             self.controller
                 .handle_command(AudioCommand::PlayTrack(prev_track))
@@ -289,23 +426,7 @@ impl AudioSystem {
         }
     }
 
-    pub fn queue_track(&mut self, track: Track) {
-        self.queue.queue_track(track);
-    }
-
-    pub fn play_track_next(&mut self, track: Track) {
-        self.queue.play_next(track);
-    }
-
-    pub fn remove_from_queue(&mut self, index: usize) {
-        self.queue.remove_track(index);
-    }
-
-    pub fn clear_queue(&mut self) {
-        self.queue.clear();
-    }
-
-    pub async fn play_pause(&mut self) {
+    async fn play_pause(&mut self) {
         if self.signals.is_playing.get() {
             self.controller.handle_command(AudioCommand::Pause).await;
         } else {
@@ -313,26 +434,18 @@ impl AudioSystem {
         }
     }
 
-    pub async fn stop(&mut self) {
+    async fn stop(&mut self) {
         self.controller.handle_command(AudioCommand::Stop).await;
     }
 
-    pub fn set_volume(&mut self, volume: u8) {
-        self.controller.set_volume_u8(volume);
-    }
-
-    pub fn volume_up(&mut self, volume: u8) {
-        self.controller.volume_up(volume);
-    }
-
-    pub fn volume_down(&mut self, volume: u8) {
-        self.controller.volume_down(volume);
-    }
-
-    pub async fn seek_backwards(&mut self, seconds: u64) {
+    async fn seek_forwards(&mut self, seconds: u64) {
         let current_ms = self.signals.position_ms.get();
+        let total_ms = self.signals.duration_ms.get();
         let delta_ms = seconds * 1000;
-        let new_pos_ms = current_ms.saturating_sub(delta_ms);
+        let mut new_pos_ms = current_ms.saturating_add(delta_ms);
+        if total_ms > 0 {
+            new_pos_ms = new_pos_ms.min(total_ms);
+        }
         self.controller
             .handle_command(AudioCommand::Seek(std::time::Duration::from_millis(
                 new_pos_ms,
@@ -340,14 +453,10 @@ impl AudioSystem {
             .await;
     }
 
-    pub async fn seek_forwards(&mut self, seconds: u64) {
+    async fn seek_backwards(&mut self, seconds: u64) {
         let current_ms = self.signals.position_ms.get();
-        let total_ms = self.signals.duration_ms.get();
         let delta_ms = seconds * 1000;
-        let mut new_pos_ms = current_ms.saturating_add(delta_ms);
-        if total_ms > 0 {
-            new_pos_ms = new_pos_ms.min(total_ms);
-        }
+        let new_pos_ms = current_ms.saturating_sub(delta_ms);
         self.controller
             .handle_command(AudioCommand::Seek(std::time::Duration::from_millis(
                 new_pos_ms,
@@ -355,128 +464,152 @@ impl AudioSystem {
             .await;
     }
 
-    pub fn toggle_mute(&mut self) {
-        self.controller.toggle_mute();
+    async fn seek_to(&mut self, position: std::time::Duration) {
+        self.controller
+            .handle_command(AudioCommand::Seek(position))
+            .await;
     }
 
-    pub fn toggle_repeat_mode(&mut self) {
-        self.queue.toggle_repeat_mode();
+    async fn reload_track(&mut self) {
+        self.controller.handle_command(AudioCommand::Reload).await;
     }
 
-    pub fn toggle_shuffle(&mut self) {
-        self.queue.toggle_shuffle();
+    fn set_volume(&mut self, volume: u8) {
+        self.controller.set_volume_u8(volume);
     }
 
-    pub fn current_track(&self) -> Option<Track> {
-        self.signals.current_track.get()
+    fn volume_up(&mut self, step: u8) {
+        self.controller.volume_up(step);
     }
 
-    pub fn current_track_id(&self) -> Option<String> {
-        self.signals.current_track_id.get()
+    fn volume_down(&mut self, step: u8) {
+        self.controller.volume_down(step);
     }
 
-    pub fn is_playing(&self) -> bool {
-        self.signals.is_playing.get()
+    fn toggle_mute(&mut self) {
+        self.controller.toggle_mute();
     }
 
-    pub fn repeat_mode(&self) -> RepeatMode {
-        self.signals.repeat_mode.get()
+    fn toggle_shuffle(&mut self) {
+        self.queue.toggle_shuffle();
     }
 
-    pub fn is_shuffled(&self) -> bool {
-        self.signals.is_shuffled.get()
+    fn toggle_repeat_mode(&mut self) {
+        self.queue.toggle_repeat_mode();
     }
 
-    pub fn volume(&self) -> u8 {
-        self.signals.volume.get()
+    fn queue_track(&mut self, item: QueueItem) {
+        self.queue.queue_track(item);
     }
 
-    pub fn is_muted(&self) -> bool {
-        self.signals.is_muted.get()
+    fn play_track_next(&mut self, item: QueueItem) {
+        self.queue.play_next(item);
     }
 
-    pub fn track_progress(&self) -> Arc<TrackProgress> {
-        self.controller.track_progress.read().unwrap().clone()
+    fn queue_albums(&mut self, album_ids: Vec<u32>) {
+        self.queue.queue_albums(album_ids);
     }
 
-    pub fn queue(&self) -> Vector<Track> {
-        self.signals.queue.with(|q| q.clone())
+    fn download_tracks(&mut self, tracks: Vec<Track>) {
+        self.queue.stream_manager.download(tracks);
     }
 
-    pub fn history(&self) -> Vector<Track> {
-        self.signals.history.with(|h| h.clone())
+    fn remove_from_queue(&mut self, index: usize) {
+        self.queue.remove_track(index);
     }
 
-    pub fn current_track_index(&self) -> usize {
-        self.signals.queue_index.get()
+    fn remove_track_from_queue(&mut self, track_id: &str) {
+        self.queue.remove_track_id(track_id);
     }
 
-    pub fn current_amplitude(&self) -> f32 {
-        self.signals.amplitude.get()
+    fn clear_queue(&mut self) {
+        self.queue.clear();
     }
 
-    pub async fn sync_queue(&mut self) {
+    async fn sync_queue(&mut self) {
         self.queue.poll_fetch().await;
     }
 
-    pub fn maybe_trigger_fetch(&mut self, cursor_index: usize) {
-        let queue_len = self.signals.queue.with(|q: &Vector<Track>| q.len());
+    fn maybe_trigger_fetch(&mut self, cursor_index: usize) {
+        let queue_len = self.signals.queue.with(|q: &Vector<QueueItem>| q.len());
         if queue_len > 0 && cursor_index + 2 >= queue_len {
             self.queue.trigger_fetch_if_needed();
         }
     }
 
-    pub fn state_handle(&self) -> Arc<RwLock<SystemState>> {
-        self.state.clone()
+    fn wave_update_buffer(&mut self, tracks: Vec<Track>) {
+        self.queue.wave_update_buffer(tracks);
     }
 
-    pub async fn sync_liked_collection_with(api: Arc<ApiService>, state: Arc<RwLock<SystemState>>) {
-        let revision = {
-            let state = state.read().await;
-            state.liked.revision
-        };
+    fn send_wave_like(&mut self, track: &Track) {
+        let track_id = as_wave_seed(track);
+        self.send_wave_feedback("like", Some(track_id), None, true);
+        self.queue.refresh_wave_queue();
+    }
 
-        if let Ok(liked_collection) = api.fetch_liked_collection(revision).await {
-            let mut state = state.write().await;
-            state.liked.apply_collection(liked_collection);
-        }
+    fn send_wave_unlike(&mut self, track: &Track) {
+        let track_id = as_wave_seed(track);
+        self.send_wave_feedback("unlike", Some(track_id), None, true);
+        self.queue.refresh_wave_queue();
     }
 
-    pub async fn sync_liked_collection(&mut self) {
-        Self::sync_liked_collection_with(self.api.clone(), self.state.clone()).await;
+    fn send_wave_dislike(&mut self, track: &Track) {
+        let track_id = as_wave_seed(track);
+        self.send_wave_feedback("dislike", Some(track_id), None, true);
+        self.queue.set_wave_filtered(&track.id, true);
+        self.queue.refresh_wave_queue();
     }
 
-    pub async fn is_liked(&self, track_id: &str) -> bool {
-        let state = self.state.read().await;
-        state.liked.is_liked(track_id)
+    async fn send_wave_dislike_skip(&mut self, track: &Track) {
+        let track_id = as_wave_seed(track);
+        self.send_wave_feedback("dislike", Some(track_id), None, true);
+        self.queue.set_wave_filtered(&track.id, true);
+        self.queue.refresh_wave_queue();
+
+        let next = if self.queue.in_wave() {
+            self.queue.skip_wave_track().await
+        } else {
+            self.queue.get_next_track().await
+        };
+
+        if let Some(next_track) = next {
+            if self.queue.in_wave() {
+                self.send_wave_track_started(&next_track);
+            }
+            self.controller
+                .handle_command(AudioCommand::PlayTrack(next_track))
+                .await;
+        }
     }
 
-    pub async fn is_disliked(&self, track_id: &str) -> bool {
-        let state = self.state.read().await;
-        state.liked.is_disliked(track_id)
+    fn send_wave_undislike(&mut self, track: &Track) {
+        let track_id = as_wave_seed(track);
+        self.send_wave_feedback("undislike", Some(track_id), None, true);
+        self.queue.set_wave_filtered(&track.id, false);
+        self.queue.refresh_wave_queue();
     }
 
-    pub async fn is_album_liked(&self, album_id: u32) -> bool {
+    async fn sync_liked_collection(&mut self) {
+        sync_liked_collection_with(self.api.clone(), self.state.clone()).await;
+    }
+
+    async fn is_album_liked(&self, album_id: u32) -> bool {
         let state = self.state.read().await;
         state.liked.is_album_liked(album_id)
     }
 
-    pub async fn is_artist_liked(&self, artist_id: &str) -> bool {
+    async fn is_artist_liked(&self, artist_id: &str) -> bool {
         let state = self.state.read().await;
         state.liked.is_artist_liked(artist_id)
     }
 
-    pub async fn is_artist_disliked(&self, artist_id: &str) -> bool {
+    async fn is_artist_disliked(&self, artist_id: &str) -> bool {
         let state = self.state.read().await;
         state.liked.is_artist_disliked(artist_id)
     }
 
-    pub async fn is_playlist_liked(&self, uid: u64, kind: u32) -> bool {
+    async fn is_playlist_liked(&self, uid: u64, kind: u32) -> bool {
         let state = self.state.read().await;
         state.liked.is_playlist_liked(uid, kind)
     }
-
-    pub fn wave_update_buffer(&mut self, tracks: Vec<Track>) {
-        self.queue.wave_update_buffer(tracks);
-    }
 }
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use image::{DynamicImage, RgbImage};
+use ratatui::{Frame, layout::Rect, style::Color};
+use ratatui_image::{
+    StatefulImage,
+    picker::Picker,
+    protocol::StatefulProtocol,
+    thread::{ResizeRequest, ResizeResponse, ThreadProtocol},
+};
+use tokio::sync::mpsc;
+
+use super::image::ImageCache;
+
+const PREVIEW_SIZE: u32 = 6;
+
+enum ArtUpdate {
+    Preview(RgbImage),
+    Protocol(StatefulProtocol),
+    Resized(ResizeResponse),
+}
+
+pub struct CoverArt {
+    protocol: ThreadProtocol,
+    updates: flume::Receiver<ArtUpdate>,
+    preview: Option<RgbImage>,
+}
+
+impl CoverArt {
+    pub fn new(image: Arc<DynamicImage>, picker: Picker) -> Self {
+        let (request_tx, mut requests) = mpsc::unbounded_channel::<ResizeRequest>();
+        let (tx, updates) = flume::unbounded();
+
+        tokio::spawn(async move {
+            let preview_tx = tx.clone();
+            let protocol = tokio::task::spawn_blocking(move || {
+                let preview = image.thumbnail(PREVIEW_SIZE, PREVIEW_SIZE).to_rgb8();
+                let _ = preview_tx.send(ArtUpdate::Preview(preview));
+                notify();
+                picker.new_resize_protocol((*image).clone())
+            })
+            .await;
+            let Ok(protocol) = protocol else { return };
+            if tx.send(ArtUpdate::Protocol(protocol)).is_err() {
+                return;
+            }
+            notify();
+
+            while let Some(request) = requests.recv().await {
+                let resized = tokio::task::spawn_blocking(move || request.resize_encode()).await;
+                let Ok(Ok(response)) = resized else { continue };
+                if tx.send(ArtUpdate::Resized(response)).is_err() {
+                    break;
+                }
+                notify();
+            }
+        });
+
+        Self {
+            protocol: ThreadProtocol::new(request_tx, None),
+            updates,
+            preview: None,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        for update in self.updates.try_iter() {
+            match update {
+                ArtUpdate::Preview(preview) => self.preview = Some(preview),
+                ArtUpdate::Protocol(protocol) => self.protocol.replace_protocol(protocol),
+                ArtUpdate::Resized(response) => {
+                    self.protocol.update_resized_protocol(response);
+                }
+            }
+        }
+
+        frame.render_stateful_widget(StatefulImage::default(), area, &mut self.protocol);
+        if self.protocol.protocol_type().is_none()
+            && let Some(preview) = &self.preview
+        {
+            render_preview(frame, area, preview);
+        }
+    }
+}
+
+fn notify() {
+    ImageCache::global().version().update(|v| *v += 1);
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, preview: &RgbImage) {
+    if area.is_empty() {
+        return;
+    }
+    let (width, height) = preview.dimensions();
+    let rows = area.height as f32 * 2.0;
+    let sample = |x: f32, y: f32, channel: usize| {
+        let fx = (x * width as f32 - 0.5).clamp(0.0, (width - 1) as f32);
+        let fy = (y * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+        let (x0, y0) = (fx.floor() as u32, fy.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (tx, ty) = (fx.fract(), fy.fract());
+        let at = |px: u32, py: u32| preview.get_pixel(px, py).0[channel] as f32;
+        let top = at(x0, y0) + (at(x1, y0) - at(x0, y0)) * tx;
+        let bottom = at(x0, y1) + (at(x1, y1) - at(x0, y1)) * tx;
+        (top + (bottom - top) * ty).round() as u8
+    };
+    let pixel = |x: u16, row: u32| {
+        let x = (x as f32 + 0.5) / area.width as f32;
+        let y = (row as f32 + 0.5) / rows;
+        Color::Rgb(sample(x, y, 0), sample(x, y, 1), sample(x, y, 2))
+    };
+
+    let buf = frame.buffer_mut();
+    for y in 0..area.height {
+        for x in 0..area.width {
+            if let Some(cell) = buf.cell_mut((area.x + x, area.y + y)) {
+                cell.set_symbol("▀")
+                    .set_fg(pixel(x, y as u32 * 2))
+                    .set_bg(pixel(x, y as u32 * 2 + 1));
+            }
+        }
+    }
+}
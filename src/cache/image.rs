@@ -1,10 +1,36 @@
+use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
 
 use dashmap::DashMap;
-use image::DynamicImage;
+use image::{
+    DynamicImage, RgbaImage,
+    imageops::{self, FilterType},
+};
 use ratatui_image::{picker::Picker, protocol::StatefulProtocol};
 
-use crate::framework::reactive::Signal;
+use crate::{framework::reactive::Signal, util::log::get_data_dir};
+
+const COLLAGE_PREFIX: &str = "collage:";
+const COLLAGE_SEPARATOR: char = '|';
+const COLLAGE_TILE: u32 = 100;
+const MAX_FILE_NAME: usize = 180;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverSize {
+    Thumbnail,
+    Header,
+    Full,
+}
+
+impl CoverSize {
+    pub fn spec(self) -> &'static str {
+        match self {
+            CoverSize::Thumbnail => "100x100",
+            CoverSize::Header => "200x200",
+            CoverSize::Full => "400x400",
+        }
+    }
+}
 
 #[derive(Clone)]
 enum CacheEntry {
@@ -78,26 +104,113 @@ impl ImageCache {
             .map(|img| picker.new_resize_protocol((*img).clone()))
     }
 
-    pub fn resolve_cover_uri(uri: &str, size: &str) -> String {
-        let uri = uri.replace("%%", size);
-        if uri.starts_with("http") {
+    pub fn resolve_cover_uri(uri: &str, size: CoverSize) -> String {
+        let uri = uri.replace("%%", size.spec());
+        if uri.starts_with("http") || uri.starts_with("file://") {
             uri
         } else {
             format!("https://{uri}")
         }
     }
 
-    pub fn get_cover(&self, uri: &str, size: &str) -> Option<Arc<DynamicImage>> {
+    pub fn collage_url(urls: &[String]) -> Option<String> {
+        match urls {
+            [] => None,
+            [url] => Some(url.clone()),
+            _ => Some(format!(
+                "{COLLAGE_PREFIX}{}",
+                urls.join(&COLLAGE_SEPARATOR.to_string())
+            )),
+        }
+    }
+
+    pub fn get_cover(&self, uri: &str, size: CoverSize) -> Option<Arc<DynamicImage>> {
         let url = Self::resolve_cover_uri(uri, size);
         self.get_or_fetch(&url)
     }
 }
 
 async fn fetch_image(url: &str) -> Option<DynamicImage> {
+    if let Some(urls) = url.strip_prefix(COLLAGE_PREFIX) {
+        return Box::pin(fetch_collage(urls.split(COLLAGE_SEPARATOR).collect())).await;
+    }
+    if let Some(path) = url.strip_prefix("file://") {
+        let bytes = tokio::fs::read(path).await.ok()?;
+        return decode(bytes).await;
+    }
+    let path = disk_path(url);
+    if let Ok(bytes) = tokio::fs::read(&path).await
+        && let Some(image) = decode(bytes).await
+    {
+        return Some(image);
+    }
+
     let response = reqwest::get(url).await.ok()?;
     if !response.status().is_success() {
         return None;
     }
     let bytes = response.bytes().await.ok()?;
-    image::load_from_memory(&bytes).ok()
+    let image = decode(bytes.to_vec()).await?;
+    if let Some(dir) = path.parent()
+        && tokio::fs::create_dir_all(dir).await.is_ok()
+    {
+        let _ = tokio::fs::write(&path, &bytes).await;
+    }
+    Some(image)
+}
+
+async fn decode(bytes: Vec<u8>) -> Option<DynamicImage> {
+    tokio::task::spawn_blocking(move || {
+        image::load_from_memory(&bytes)
+            .ok()
+            .map(|image| image.into_rgba8().into())
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+fn disk_path(url: &str) -> PathBuf {
+    let key = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let mut name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.len() > MAX_FILE_NAME {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        name = format!("{}_{:016x}", &name[..MAX_FILE_NAME / 2], hasher.finish());
+    }
+    get_data_dir().join("covers").join(name)
+}
+
+async fn fetch_collage(urls: Vec<&str>) -> Option<DynamicImage> {
+    let tiles: Vec<DynamicImage> = futures::future::join_all(urls.into_iter().map(fetch_image))
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    if tiles.is_empty() {
+        return None;
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut canvas = RgbaImage::new(COLLAGE_TILE * 2, COLLAGE_TILE * 2);
+        for (i, tile) in tiles.iter().cycle().take(4).enumerate() {
+            let tile = tile.resize_to_fill(COLLAGE_TILE, COLLAGE_TILE, FilterType::Triangle);
+            let x = (i as u32 % 2) * COLLAGE_TILE;
+            let y = (i as u32 / 2) * COLLAGE_TILE;
+            imageops::overlay(&mut canvas, &tile, x as i64, y as i64);
+        }
+        DynamicImage::ImageRgba8(canvas)
+    })
+    .await
+    .ok()
 }
@@ -1 +1,2 @@
+pub mod art;
 pub mod image;
@@ -0,0 +1,127 @@
+use dashmap::DashMap;
+use flume::{Receiver, Sender};
+
+use crate::event::events::Event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventCategory {
+    System,
+    Playback,
+    Library,
+    Search,
+    Errors,
+}
+
+impl EventCategory {
+    pub const ALL: [EventCategory; 5] = [
+        EventCategory::System,
+        EventCategory::Playback,
+        EventCategory::Library,
+        EventCategory::Search,
+        EventCategory::Errors,
+    ];
+}
+
+impl Event {
+    pub fn category(&self) -> EventCategory {
+        match self {
+            Event::Initialize
+            | Event::Shutdown
+            | Event::Reload
+            | Event::Forwarded(_)
+            | Event::LinkResolved(..)
+            | Event::Script(_)
+            | Event::CommandFinished(..)
+            | Event::OutputTargetsFound(_)
+            | Event::ListenAlong(_) => EventCategory::System,
+
+            Event::TrackStarted(..)
+            | Event::TrackEnded
+            | Event::QueueEnded
+            | Event::PlaybackProgress(_)
+            | Event::QueueUpdated
+            | Event::WaveReady(..)
+            | Event::WaveTimedOut
+            | Event::WaveFailed(_)
+            | Event::WaveBuffer(_)
+            | Event::LocalTracksRead(..)
+            | Event::Play(_)
+            | Event::Resume
+            | Event::Pause
+            | Event::Volume(_)
+            | Event::VolumeUp(_)
+            | Event::VolumeDown(_)
+            | Event::Next
+            | Event::Previous
+            | Event::Seek(_)
+            | Event::SeekForward(_)
+            | Event::SeekBackward(_)
+            | Event::ToggleMute => EventCategory::Playback,
+
+            Event::TracksFetched(_)
+            | Event::TrackFetched(_)
+            | Event::PlaylistFetched(_)
+            | Event::PlaylistTracksFetched(..)
+            | Event::PlaylistTracksPageFetched(..)
+            | Event::AlbumTracksFetched(_)
+            | Event::ArtistTracksFetched(..)
+            | Event::ArtistTracksPageFetched(..)
+            | Event::PlaylistsFetched(_)
+            | Event::PlaylistSelected(_)
+            | Event::PlaylistKindSelected(_)
+            | Event::AlbumSelected(_)
+            | Event::ArtistSelected(_)
+            | Event::TrackSelected(_)
+            | Event::LyricsFetched(_)
+            | Event::LikedStatusUpdated(_)
+            | Event::ImportResolved(_)
+            | Event::PlaylistCreated(_)
+            | Event::FileSaved(..)
+            | Event::SmartLibraryFetched(..)
+            | Event::PlaylistAppended(..)
+            | Event::DiscographyResolved(..)
+            | Event::LikeProgress(..)
+            | Event::TracksLiked { .. }
+            | Event::LocalLibraryChanged(_) => EventCategory::Library,
+
+            Event::SearchResults(..)
+            | Event::SearchPageFetched(..)
+            | Event::PodcastPageFetched(..)
+            | Event::Search(_) => EventCategory::Search,
+
+            Event::FetchError(_) => EventCategory::Errors,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: DashMap<EventCategory, Vec<Sender<Event>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self, category: EventCategory) -> Receiver<Event> {
+        self.subscribe_many(&[category])
+    }
+
+    pub fn subscribe_many(&self, categories: &[EventCategory]) -> Receiver<Event> {
+        let (tx, rx) = flume::unbounded();
+        for category in categories {
+            self.subscribers
+                .entry(*category)
+                .or_default()
+                .push(tx.clone());
+        }
+        rx
+    }
+
+    pub fn publish(&self, event: &Event) {
+        if let Some(mut subscribers) = self.subscribers.get_mut(&event.category()) {
+            subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+        }
+    }
+}
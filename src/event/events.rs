@@ -1,4 +1,17 @@
-use crate::audio::liked::LikedSnapshot;
+use std::path::PathBuf;
+
+use im::HashSet;
+
+use crate::{
+    app::state::SearchTab,
+    audio::{liked::LikedSnapshot, local::LocalTrack, outputs::OutputTarget},
+    event::watcher::LibraryChanges,
+    http::PodcastSearch,
+    util::{
+        import::ImportSession, link::ResolvedLink, listen_along::SessionEvent,
+        script::ScriptCommand,
+    },
+};
 use yandex_music::model::{
     album::Album, artist::Artist, info::pager::Pager, playlist::Playlist, rotor::session::Session,
     search::Search, track::Track,
@@ -8,6 +21,10 @@ use yandex_music::model::{
 #[allow(clippy::large_enum_variant)]
 pub enum Event {
     Initialize,
+    Shutdown,
+    Reload,
+    Forwarded(Vec<String>),
+    LinkResolved(Result<ResolvedLink, String>, bool),
     TrackStarted(Track, usize),
     TrackEnded,
     QueueEnded,
@@ -20,7 +37,8 @@ pub enum Event {
     AlbumTracksFetched(Vec<Track>),
     ArtistTracksFetched(Vec<Track>, Pager),
     ArtistTracksPageFetched(String, Vec<Track>, Pager),
-    SearchPageFetched(Search, u32),
+    SearchPageFetched(Search, SearchTab, u32),
+    PodcastPageFetched(PodcastSearch, SearchTab, u32),
     PlaylistsFetched(Vec<Playlist>),
     PlaylistSelected(Playlist),
     PlaylistKindSelected(u32),
@@ -28,12 +46,31 @@ pub enum Event {
     ArtistSelected(Artist),
     TrackSelected(Track),
     LyricsFetched(Option<String>),
-    SearchResults(Search),
+    SearchResults(String, Search, Option<PodcastSearch>),
     FetchError(String),
     WaveReady(Session, Vec<Track>),
+    WaveTimedOut,
+    WaveFailed(String),
     WaveBuffer(Vec<Track>),
+    LocalTracksRead(PathBuf, bool, Result<Vec<LocalTrack>, String>),
     QueueUpdated,
     LikedStatusUpdated(LikedSnapshot),
+    ImportResolved(Result<ImportSession, String>),
+    PlaylistCreated(Result<Playlist, String>),
+    FileSaved(String, Result<PathBuf, String>),
+    SmartLibraryFetched(HashSet<String>, Result<Vec<Track>, String>),
+    PlaylistAppended(u32, String, usize, Result<u32, String>),
+    DiscographyResolved(String, Result<(Vec<Track>, Vec<u32>), String>),
+    LikeProgress(usize, usize),
+    TracksLiked {
+        liked: Vec<String>,
+        failed: Vec<(String, String)>,
+    },
+    LocalLibraryChanged(LibraryChanges),
+    Script(ScriptCommand),
+    CommandFinished(String, Result<(bool, String), String>),
+    OutputTargetsFound(Result<Vec<OutputTarget>, String>),
+    ListenAlong(SessionEvent),
 
     Play(i32),
     Search(String),
@@ -1 +1,4 @@
+pub mod bus;
 pub mod events;
+pub mod signals;
+pub mod watcher;
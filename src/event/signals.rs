@@ -0,0 +1,40 @@
+use flume::Sender;
+
+use crate::event::events::Event;
+
+#[cfg(unix)]
+pub async fn listen(tx: Sender<Event>) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let (Ok(mut term), Ok(mut int), Ok(mut hup), Ok(mut usr1)) = (
+        signal(SignalKind::terminate()),
+        signal(SignalKind::interrupt()),
+        signal(SignalKind::hangup()),
+        signal(SignalKind::user_defined1()),
+    ) else {
+        tracing::warn!("signal_handlers_unavailable");
+        return;
+    };
+
+    loop {
+        let event = tokio::select! {
+            Some(_) = term.recv() => Event::Shutdown,
+            Some(_) = int.recv() => Event::Shutdown,
+            Some(_) = hup.recv() => Event::Reload,
+            Some(_) = usr1.recv() => Event::Reload,
+            else => break,
+        };
+        if tx.send(event).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn listen(tx: Sender<Event>) {
+    while tokio::signal::ctrl_c().await.is_ok() {
+        if tx.send(Event::Shutdown).is_err() {
+            break;
+        }
+    }
+}
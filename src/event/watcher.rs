@@ -0,0 +1,104 @@
+use std::{collections::BTreeSet, path::PathBuf, time::Duration};
+
+use flume::Sender;
+use notify::{
+    EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+    event::{ModifyKind, RenameMode},
+};
+
+use crate::{audio::local::LocalTrack, event::events::Event, framework::tasks::TaskManager};
+
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Default)]
+pub struct LibraryChanges {
+    pub added: BTreeSet<PathBuf>,
+    pub removed: BTreeSet<PathBuf>,
+}
+
+impl LibraryChanges {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    fn add(&mut self, path: PathBuf) {
+        if path.is_dir() || LocalTrack::is_supported(&path) {
+            self.removed.remove(&path);
+            self.added.insert(path);
+        }
+    }
+
+    fn remove(&mut self, path: PathBuf) {
+        self.added.remove(&path);
+        self.removed.insert(path);
+    }
+
+    fn record(&mut self, event: notify::Event) {
+        let mut paths = event.paths.into_iter();
+        match event.kind {
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                if let Some(from) = paths.next() {
+                    self.remove(from);
+                }
+                paths.for_each(|to| self.add(to));
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) | EventKind::Remove(_) => {
+                paths.for_each(|path| self.remove(path));
+            }
+            EventKind::Create(_) | EventKind::Modify(_) => paths.for_each(|path| self.add(path)),
+            _ => {}
+        }
+    }
+}
+
+fn register(root: PathBuf, raw_tx: Sender<notify::Event>) -> notify::Result<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = raw_tx.send(event);
+        }
+    })?;
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+    Ok(watcher)
+}
+
+/// Watches `root` recursively and sends settled batches of changes as
+/// `Event::LocalLibraryChanged`. Registering adds a watch per subdirectory,
+/// so it runs on a blocking thread; the watcher lives as long as the task.
+pub fn watch(root: PathBuf, tx: Sender<Event>, tasks: &TaskManager) {
+    tasks.spawn_in_group("library_watcher", "library_watcher", async move {
+        let (raw_tx, raw_rx) = flume::unbounded();
+        let _watcher = match tokio::task::spawn_blocking(move || register(root, raw_tx)).await {
+            Ok(Ok(watcher)) => watcher,
+            Ok(Err(e)) => {
+                tracing::warn!(error = %e, "library_watch_failed");
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "library_watch_panicked");
+                return;
+            }
+        };
+
+        let mut pending = LibraryChanges::default();
+        loop {
+            let next = if pending.is_empty() {
+                raw_rx.recv_async().await.ok()
+            } else {
+                match tokio::time::timeout(SETTLE_DELAY, raw_rx.recv_async()).await {
+                    Ok(event) => event.ok(),
+                    Err(_) => {
+                        let changes = std::mem::take(&mut pending);
+                        if tx.send(Event::LocalLibraryChanged(changes)).is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+            };
+            match next {
+                Some(event) => pending.record(event),
+                None => break,
+            }
+        }
+    });
+}
@@ -15,7 +15,9 @@ pub use model::Model;
 pub use reactive::{ReadSignal, Signal};
 pub use resources::{PaginatedResource, Resource, ResourceBuilder, ResourceState};
 pub use runtime::{RegistryHandle, Runtime, RuntimeBuilder, RuntimeConfig, RuntimeMessage};
-pub use tasks::{DebouncedTask, GroupId, TaskId, TaskManager, TaskScope, ThrottledTask};
+pub use tasks::{
+    DebouncedTask, GroupId, ScopeGuard, TaskId, TaskManager, TaskScope, ThrottledTask,
+};
 
 pub mod prelude {
     pub use super::{
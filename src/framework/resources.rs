@@ -1,5 +1,6 @@
 use std::{future::Future, sync::Arc};
 
+use futures::StreamExt;
 use tokio::sync::Mutex;
 
 use crate::framework::reactive::{SharedSignal, Signal, shared, signal};
@@ -85,6 +86,52 @@ where
         });
     }
 
+    pub fn load_ahead<F, Fut>(&self, pages: usize, concurrency: usize, fetcher: F)
+    where
+        F: Fn(usize) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(Vec<T>, bool), String>> + Send + 'static,
+    {
+        if pages == 0 || !self.has_more.get() || self.state.with(|s| s.is_loading()) {
+            return;
+        }
+
+        let items = self.items.clone();
+        let state = self.state.clone();
+        let page = self.page.clone();
+        let has_more = self.has_more.clone();
+        let start = page.get();
+        let abort = Arc::clone(&self.abort);
+
+        state.set(ResourceState::Loading);
+
+        let handle = tokio::spawn(async move {
+            let mut results = futures::stream::iter(start..start + pages)
+                .map(&fetcher)
+                .buffered(concurrency.max(1));
+            while let Some(result) = results.next().await {
+                match result {
+                    Ok((new_items, more)) => {
+                        items.update(|list| list.extend(new_items));
+                        page.set(page.get() + 1);
+                        has_more.set(more);
+                        if !more {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        state.set(ResourceState::Error(e));
+                        return;
+                    }
+                }
+            }
+            state.set(ResourceState::Ready(()));
+        });
+
+        tokio::spawn(async move {
+            *abort.lock().await = Some(handle.abort_handle());
+        });
+    }
+
     pub fn reset(&self) {
         self.items.set(Vec::<T>::new());
         self.state.set(ResourceState::<(), String>::Idle);
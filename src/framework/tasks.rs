@@ -3,13 +3,15 @@ use std::{
     future::Future,
     sync::{
         Arc, RwLock,
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
     },
     time::{Duration, Instant},
 };
 
 use tokio::task::JoinHandle;
 
+use crate::framework::reactive::Signal;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TaskId(u64);
 
@@ -82,6 +84,7 @@ pub struct TaskManager {
     tasks: RwLock<HashMap<TaskId, TaskInfo>>,
     groups: RwLock<HashMap<GroupId, TaskId>>,
     stats: RwLock<TaskStats>,
+    running: Signal<usize>,
 }
 
 impl Default for TaskManager {
@@ -96,9 +99,18 @@ impl TaskManager {
             tasks: RwLock::new(HashMap::new()),
             groups: RwLock::new(HashMap::new()),
             stats: RwLock::new(TaskStats::default()),
+            running: Signal::new(0),
         }
     }
 
+    pub fn running_signal(&self) -> Signal<usize> {
+        self.running.clone()
+    }
+
+    fn publish_running(&self) {
+        self.running.set_neq(self.running_count());
+    }
+
     pub fn spawn<F>(&self, description: impl Into<String>, future: F) -> TaskId
     where
         F: Future<Output = ()> + Send + 'static,
@@ -119,6 +131,7 @@ impl TaskManager {
             stats.running += 1;
         }
 
+        self.publish_running();
         id
     }
 
@@ -155,6 +168,7 @@ impl TaskManager {
             stats.running += 1;
         }
 
+        self.publish_running();
         task_id
     }
 
@@ -182,6 +196,7 @@ impl TaskManager {
             stats.running += 1;
         }
 
+        self.publish_running();
         task_id
     }
 
@@ -201,10 +216,13 @@ impl TaskManager {
                 }
             }
 
-            let mut stats = self.stats.write().unwrap();
-            stats.running = stats.running.saturating_sub(1);
-            stats.aborted += 1;
+            {
+                let mut stats = self.stats.write().unwrap();
+                stats.running = stats.running.saturating_sub(1);
+                stats.aborted += 1;
+            }
 
+            self.publish_running();
             true
         } else {
             false
@@ -249,6 +267,8 @@ impl TaskManager {
             stats.running = 0;
             stats.aborted += aborted as u64;
         }
+
+        self.publish_running();
     }
 
     pub fn is_group_active(&self, group: impl Into<GroupId>) -> bool {
@@ -275,6 +295,10 @@ impl TaskManager {
         }
     }
 
+    pub fn scope(self: &Arc<Self>, prefix: impl Into<String>) -> TaskScope {
+        TaskScope::new(self.clone(), prefix)
+    }
+
     pub fn running_tasks(&self) -> Vec<(TaskId, String, Duration)> {
         let tasks = self.tasks.read().unwrap();
         tasks
@@ -318,6 +342,8 @@ impl TaskManager {
             stats.running = stats.running.saturating_sub(stats_update.0 as usize);
             stats.completed += stats_update.0;
             stats.failed += stats_update.1;
+            drop(stats);
+            self.publish_running();
         }
     }
 }
@@ -331,29 +357,96 @@ impl Drop for TaskManager {
     }
 }
 
+#[derive(Clone)]
 pub struct TaskScope {
     manager: Arc<TaskManager>,
-    group_prefix: String,
+    group_prefix: Arc<str>,
+    tasks: Arc<RwLock<Vec<TaskId>>>,
+    closed: Arc<AtomicBool>,
 }
 
 impl TaskScope {
     pub fn new(manager: Arc<TaskManager>, prefix: impl Into<String>) -> Self {
         Self {
             manager,
-            group_prefix: prefix.into(),
+            group_prefix: prefix.into().into(),
+            tasks: Arc::new(RwLock::new(Vec::new())),
+            closed: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    pub fn spawn<F>(&self, name: &str, future: F) -> TaskId
+    pub fn prefix(&self) -> &str {
+        &self.group_prefix
+    }
+
+    pub fn spawn<F>(&self, name: &str, future: F) -> Option<TaskId>
     where
         F: Future<Output = ()> + Send + 'static,
     {
+        if self.is_closed() {
+            return None;
+        }
         let group = format!("{}:{}", self.group_prefix, name);
-        self.manager.spawn_in_group(group, name, future)
+        let id = self.manager.spawn_in_group(group.clone(), group, future);
+        self.register(id);
+        Some(id)
+    }
+
+    pub fn spawn_concurrent<F>(&self, name: &str, future: F) -> Option<TaskId>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if self.is_closed() {
+            return None;
+        }
+        let id = self
+            .manager
+            .spawn(format!("{}:{}", self.group_prefix, name), future);
+        self.register(id);
+        Some(id)
+    }
+
+    fn register(&self, id: TaskId) {
+        let mut tasks = self.tasks.write().unwrap();
+        tasks.retain(|task| self.manager.is_running(*task));
+        tasks.push(id);
     }
 
     pub fn abort_all(&self) {
-        tracing::warn!("TaskScope::abort_all is not fully implemented");
+        let tasks: Vec<_> = {
+            let mut tasks = self.tasks.write().unwrap();
+            tasks.drain(..).collect()
+        };
+        for id in tasks {
+            self.manager.abort(id);
+        }
+    }
+
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        self.abort_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    pub fn guard(&self) -> ScopeGuard {
+        ScopeGuard(self.clone())
+    }
+}
+
+pub struct ScopeGuard(TaskScope);
+
+impl ScopeGuard {
+    pub fn scope(&self) -> &TaskScope {
+        &self.0
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        self.0.close();
     }
 }
 
@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use yandex_music::model::{
+    album::Album,
+    collection::Collection,
+    info::{lyrics::LyricsFormat, pager::Pager},
+    playlist::Playlist,
+    rotor::{Rotor, feedback::StationFeedback, session::Session},
+    search::{Search, SearchType, search_result::SearchResult},
+    track::{PartialTrack, Track},
+};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastSearch {
+    pub podcasts: Option<SearchResult<Album>>,
+    #[serde(alias = "podcast_episodes")]
+    pub podcast_episodes: Option<SearchResult<Track>>,
+}
+
+#[async_trait]
+pub trait MusicApi: Send + Sync {
+    fn current_user_id(&self) -> u64;
+
+    async fn search(&self, query: &str) -> color_eyre::Result<Search>;
+
+    async fn search_paginated(
+        &self,
+        query: &str,
+        page: u32,
+        item_type: SearchType,
+    ) -> color_eyre::Result<Search>;
+
+    async fn search_podcasts(&self, query: &str, page: u32) -> color_eyre::Result<PodcastSearch>;
+
+    async fn fetch_liked_tracks(&self) -> color_eyre::Result<Playlist>;
+
+    async fn fetch_disliked_tracks(&self) -> color_eyre::Result<Vec<PartialTrack>>;
+
+    async fn fetch_all_playlists(&self) -> color_eyre::Result<Vec<Playlist>>;
+
+    async fn fetch_playlist(&self, kind: u32) -> color_eyre::Result<Playlist>;
+
+    async fn fetch_playlist_bare(&self, kind: u32) -> color_eyre::Result<Playlist>;
+
+    async fn fetch_user_playlist(&self, owner: &str, kind: u32) -> color_eyre::Result<Playlist>;
+
+    async fn create_playlist(&self, title: &str, tracks: &[Track]) -> color_eyre::Result<Playlist>;
+
+    async fn append_to_playlist(&self, kind: u32, tracks: &[Track])
+    -> color_eyre::Result<Playlist>;
+
+    async fn fetch_tracks_by_ids(
+        &self,
+        track_album_ids: Vec<String>,
+    ) -> color_eyre::Result<Vec<Track>>;
+
+    async fn fetch_playlists(&self, kinds: Vec<u32>) -> color_eyre::Result<Playlist>;
+
+    async fn fetch_tracks(&self, track_ids: Vec<String>) -> color_eyre::Result<Vec<Track>>;
+
+    async fn fetch_similar_tracks(&self, track_id: String) -> color_eyre::Result<Vec<Track>>;
+
+    async fn fetch_track_url(&self, track_id: String) -> color_eyre::Result<(String, String, u32)>;
+
+    async fn fetch_track_formats(&self, track_id: String)
+    -> color_eyre::Result<Vec<(String, u32)>>;
+
+    async fn fetch_track_urls_batch(
+        &self,
+        track_ids: Vec<String>,
+    ) -> color_eyre::Result<Vec<(String, String, String, u32)>>;
+
+    async fn fetch_lyrics(
+        &self,
+        track_id: String,
+        format: LyricsFormat,
+    ) -> color_eyre::Result<Option<String>>;
+
+    async fn fetch_album_with_tracks(&self, album_id: u32) -> color_eyre::Result<Album>;
+
+    async fn fetch_artist_tracks(&self, artist_id: String) -> color_eyre::Result<Vec<Track>>;
+
+    async fn fetch_artist_tracks_paginated(
+        &self,
+        artist_id: String,
+        page: u32,
+        page_size: u32,
+    ) -> color_eyre::Result<(Vec<Track>, Pager)>;
+
+    async fn fetch_artist_albums(
+        &self,
+        artist_id: String,
+        page: u32,
+        page_size: u32,
+    ) -> color_eyre::Result<(Vec<Album>, Pager)>;
+
+    async fn fetch_artist_appearances(&self, artist_id: String) -> color_eyre::Result<Vec<Album>>;
+
+    async fn fetch_artist_description(
+        &self,
+        artist_id: String,
+    ) -> color_eyre::Result<Option<String>>;
+
+    async fn fetch_stations(&self) -> color_eyre::Result<Vec<Rotor>>;
+
+    async fn create_session(&self, seeds: Vec<String>) -> color_eyre::Result<Session>;
+
+    async fn get_session_tracks(
+        &self,
+        session_id: String,
+        queue: Vec<String>,
+        feedbacks: Vec<StationFeedback>,
+    ) -> color_eyre::Result<Session>;
+
+    async fn send_rotor_feedback(
+        &self,
+        station_id: String,
+        batch_id: Option<String>,
+        feedback_type: &str,
+        track_id: Option<String>,
+        from: Option<String>,
+        total_played: Option<Duration>,
+    ) -> color_eyre::Result<()>;
+
+    async fn report_play_progress(
+        &self,
+        track_id: String,
+        album_id: Option<String>,
+        position: Duration,
+        length: Duration,
+    ) -> color_eyre::Result<()>;
+
+    async fn toggle_like_track(&self, track_id: String, is_liked: bool) -> color_eyre::Result<()>;
+
+    async fn add_like_track(&self, track_id: String) -> color_eyre::Result<()>;
+
+    async fn remove_like_track(&self, track_id: String) -> color_eyre::Result<()>;
+
+    async fn toggle_dislike_track(
+        &self,
+        track_id: String,
+        is_disliked: bool,
+    ) -> color_eyre::Result<()>;
+
+    async fn add_dislike_track(&self, track_id: String) -> color_eyre::Result<()>;
+
+    async fn remove_dislike_track(&self, track_id: String) -> color_eyre::Result<()>;
+
+    async fn add_like_album(&self, album_id: u32) -> color_eyre::Result<()>;
+
+    async fn remove_like_album(&self, album_id: u32) -> color_eyre::Result<()>;
+
+    async fn add_like_playlist(&self, owner_uid: u64, kind: u32) -> color_eyre::Result<()>;
+
+    async fn remove_like_playlist(&self, owner_uid: u64, kind: u32) -> color_eyre::Result<()>;
+
+    async fn add_like_artist(&self, artist_id: String) -> color_eyre::Result<()>;
+
+    async fn remove_like_artist(&self, artist_id: String) -> color_eyre::Result<()>;
+
+    async fn add_dislike_artist(&self, artist_id: String) -> color_eyre::Result<()>;
+
+    async fn remove_dislike_artist(&self, artist_id: String) -> color_eyre::Result<()>;
+
+    async fn fetch_liked_collection(&self, revision: Option<u64>)
+    -> color_eyre::Result<Collection>;
+}
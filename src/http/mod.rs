@@ -1,24 +1,33 @@
-use std::{sync::Arc, time::Duration};
+mod api;
+pub mod throttle;
 
+use std::{future::Future, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
 use chrono::Utc;
 use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use serde::Deserialize;
 use yandex_music::{
-    DEFAULT_CLIENT_ID, YandexMusicClient,
+    API_PATH, DEFAULT_CLIENT_ID, YandexMusicClient,
     api::{
+        Endpoint,
         album::{
             add_liked_album::AddLikedAlbumOptions, get_album::GetAlbumOptions,
             remove_liked_album::RemoveLikedAlbumOptions,
         },
         artist::{
             add_disliked_artist::AddDislikedArtistOptions, add_liked_artist::AddLikedArtistOptions,
+            get_artist::GetArtistOptions, get_artist_albums::GetArtistAlbumsOptions,
             get_artist_tracks::ArtistTracksOptions,
             remove_disliked_artist::RemoveDislikedArtistOptions,
             remove_liked_artist::RemoveLikedArtistOptions,
         },
         collection::sync::{CollectionSyncOption, CollectionSyncOptions},
         playlist::{
-            add_liked_playlist::AddLikedPlaylistOptions, get_all_playlists::GetAllPlaylistsOptions,
-            get_playlists::GetPlaylistsOptions, remove_liked_playlist::RemoveLikedPlaylistOptions,
+            add_liked_playlist::AddLikedPlaylistOptions, create_playlist::CreatePlaylistOptions,
+            get_all_playlists::GetAllPlaylistsOptions, get_playlists::GetPlaylistsOptions,
+            modify_playlist::ModifyPlaylistOptions,
+            remove_liked_playlist::RemoveLikedPlaylistOptions,
         },
         rotor::{
             create_session::CreateSessionOptions, get_session_tracks::GetSessionTracksOptions,
@@ -27,27 +36,42 @@ use yandex_music::{
         search::get_search::SearchOptions,
         track::{
             add_disliked_tracks::AddDislikedTracksOptions, add_liked_tracks::AddLikedTracksOptions,
-            get_file_info::GetFileInfoOptions, get_file_info_batch::GetFileInfoBatchOptions,
-            get_lyrics::GetLyricsOptions, get_similar_tracks::GetSimilarTracksOptions,
-            get_tracks::GetTracksOptions, remove_disliked_tracks::RemoveDislikedTracksOptions,
+            get_disliked_tracks::GetDislikedTracksOptions, get_file_info::GetFileInfoOptions,
+            get_file_info_batch::GetFileInfoBatchOptions, get_lyrics::GetLyricsOptions,
+            get_similar_tracks::GetSimilarTracksOptions, get_tracks::GetTracksOptions,
+            play_audio::PlayAudioOptions, remove_disliked_tracks::RemoveDislikedTracksOptions,
             remove_liked_tracks::RemoveLikedTracksOptions,
         },
     },
+    error::ClientError,
     model::{
         album::Album,
+        artist::SortBy,
         collection::Collection,
-        info::{lyrics::LyricsFormat, pager::Pager},
-        playlist::Playlist,
+        info::{file_info::Quality, lyrics::LyricsFormat, pager::Pager},
+        playlist::{
+            Playlist,
+            modify::{Diff, DiffOp},
+        },
         rotor::{
             Rotor,
             feedback::{StationFeedback, StationFeedbackEvent},
             session::Session,
         },
-        search::Search,
-        track::Track,
+        search::{Search, SearchType},
+        track::{PartialTrack, Track, TrackShort},
     },
 };
 
+pub use api::{MusicApi, PodcastSearch};
+
+const PLAY_AUDIO_FROM: &str = "yamusic";
+
+#[derive(Deserialize)]
+struct ApiEnvelope<T> {
+    result: T,
+}
+
 pub struct ApiService {
     pub client: Arc<YandexMusicClient>,
     user_id: u64,
@@ -102,25 +126,85 @@ impl ApiService {
         Ok(Self { client, user_id })
     }
 
-    pub fn current_user_id(&self) -> u64 {
+    async fn call<T, F, Fut>(&self, request: F) -> color_eyre::Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            throttle::pace().await;
+            match request().await {
+                Ok(value) => {
+                    throttle::record_success();
+                    return Ok(value);
+                }
+                Err(e) if throttle::is_rate_limited(&e) => {
+                    throttle::record_limited();
+                    if attempt == throttle::MAX_RETRIES {
+                        return Err(color_eyre::eyre::eyre!(
+                            "Yandex Music is throttling requests, try again in a minute"
+                        ));
+                    }
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MusicApi for ApiService {
+    fn current_user_id(&self) -> u64 {
         self.user_id
     }
 
-    pub async fn search(&self, query: &str) -> color_eyre::Result<Search> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn search(&self, query: &str) -> color_eyre::Result<Search> {
         let opts = SearchOptions::new(query);
-        Ok(self.client.search(&opts).await?)
+        Ok(self.call(|| self.client.search(&opts)).await?)
     }
 
-    pub async fn search_paginated(&self, query: &str, page: u32) -> color_eyre::Result<Search> {
-        let opts = SearchOptions::new(query).page(page);
-        Ok(self.client.search(&opts).await?)
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn search_paginated(
+        &self,
+        query: &str,
+        page: u32,
+        item_type: SearchType,
+    ) -> color_eyre::Result<Search> {
+        let opts = SearchOptions::new(query).page(page).item_type(item_type);
+        Ok(self.call(|| self.client.search(&opts)).await?)
     }
 
-    pub async fn fetch_liked_tracks(&self) -> color_eyre::Result<Playlist> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn search_podcasts(&self, query: &str, page: u32) -> color_eyre::Result<PodcastSearch> {
+        let opts = SearchOptions::new(query)
+            .page(page)
+            .item_type(SearchType::Podcasts);
+        let url = format!("{API_PATH}{}", opts.path());
+        let envelope: ApiEnvelope<PodcastSearch> = self
+            .call(|| async {
+                Ok(self
+                    .client
+                    .inner
+                    .get(&url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?)
+            })
+            .await?;
+        Ok(envelope.result)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_liked_tracks(&self) -> color_eyre::Result<Playlist> {
         let opts = GetPlaylistsOptions::new(self.user_id)
             .kinds([3u32])
             .with_tracks(true);
-        let playlist = self.client.get_playlists(&opts).await?;
+        let playlist = self.call(|| self.client.get_playlists(&opts)).await?;
 
         playlist
             .into_iter()
@@ -128,12 +212,25 @@ impl ApiService {
             .ok_or(color_eyre::eyre::eyre!("Playlist not found"))
     }
 
-    pub async fn fetch_all_playlists(&self) -> color_eyre::Result<Vec<Playlist>> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_disliked_tracks(&self) -> color_eyre::Result<Vec<PartialTrack>> {
+        let opts = GetDislikedTracksOptions::new(self.user_id);
+        let mut tracks = self
+            .call(|| self.client.get_disliked_tracks(&opts))
+            .await?
+            .tracks;
+        tracks.sort_by_key(|t| std::cmp::Reverse(t.timestamp));
+        Ok(tracks)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_all_playlists(&self) -> color_eyre::Result<Vec<Playlist>> {
         let opts = GetAllPlaylistsOptions::new(self.user_id);
-        Ok(self.client.get_all_playlists(&opts).await?)
+        Ok(self.call(|| self.client.get_all_playlists(&opts)).await?)
     }
 
-    pub async fn fetch_playlist(&self, kind: u32) -> color_eyre::Result<Playlist> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_playlist(&self, kind: u32) -> color_eyre::Result<Playlist> {
         self.client
             .get_playlists(
                 &GetPlaylistsOptions::new(self.user_id)
@@ -146,13 +243,13 @@ impl ApiService {
             .ok_or_else(|| color_eyre::eyre::eyre!("Playlist not found"))
     }
 
-    pub async fn fetch_playlist_bare(&self, kind: u32) -> color_eyre::Result<Playlist> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_playlist_bare(&self, kind: u32) -> color_eyre::Result<Playlist> {
         self.client
             .get_playlists(
                 &GetPlaylistsOptions::new(self.user_id)
                     .kinds([kind])
-                    .with_tracks(true)
-                    .rich_tracks(false),
+                    .with_tracks(false),
             )
             .await?
             .into_iter()
@@ -160,15 +257,66 @@ impl ApiService {
             .ok_or_else(|| color_eyre::eyre::eyre!("Playlist not found"))
     }
 
-    pub async fn fetch_tracks_by_ids(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_user_playlist(&self, owner: &str, kind: u32) -> color_eyre::Result<Playlist> {
+        // Unlike the list endpoint, this one also accepts the owner's login,
+        // which is what share links carry.
+        let url = format!("{API_PATH}users/{owner}/playlists/{kind}");
+        let envelope: ApiEnvelope<Playlist> = self
+            .call(|| async {
+                Ok(self
+                    .client
+                    .inner
+                    .get(&url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?)
+            })
+            .await?;
+        Ok(envelope.result)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn create_playlist(&self, title: &str, tracks: &[Track]) -> color_eyre::Result<Playlist> {
+        let opts = CreatePlaylistOptions::new(self.user_id, title, "private");
+        let playlist = self.call(|| self.client.create_playlist(&opts)).await?;
+        if tracks.is_empty() {
+            return Ok(playlist);
+        }
+
+        let diff = Diff::new(DiffOp::insert(0), track_shorts(tracks));
+        let opts = ModifyPlaylistOptions::new(self.user_id, playlist.kind, diff, playlist.revision);
+        Ok(self.call(|| self.client.modify_playlist(&opts)).await?)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn append_to_playlist(
+        &self,
+        kind: u32,
+        tracks: &[Track],
+    ) -> color_eyre::Result<Playlist> {
+        let playlist = self.fetch_playlist_bare(kind).await?;
+        let diff = Diff::new(
+            DiffOp::insert(playlist.track_count as usize),
+            track_shorts(tracks),
+        );
+        let opts = ModifyPlaylistOptions::new(self.user_id, kind, diff, playlist.revision);
+        Ok(self.call(|| self.client.modify_playlist(&opts)).await?)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_tracks_by_ids(
         &self,
         track_album_ids: Vec<String>,
     ) -> color_eyre::Result<Vec<Track>> {
         let opts = GetTracksOptions::new(track_album_ids);
-        Ok(self.client.get_tracks(&opts).await?)
+        Ok(self.call(|| self.client.get_tracks(&opts)).await?)
     }
 
-    pub async fn fetch_playlists(&self, kinds: Vec<u32>) -> color_eyre::Result<Playlist> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_playlists(&self, kinds: Vec<u32>) -> color_eyre::Result<Playlist> {
         self.client
             .get_playlists(
                 &GetPlaylistsOptions::new(self.user_id)
@@ -181,32 +329,62 @@ impl ApiService {
             .ok_or_else(|| color_eyre::eyre::eyre!("Playlist not found"))
     }
 
-    pub async fn fetch_tracks(&self, track_ids: Vec<String>) -> color_eyre::Result<Vec<Track>> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_tracks(&self, track_ids: Vec<String>) -> color_eyre::Result<Vec<Track>> {
         let opts = GetTracksOptions::new(track_ids);
-        Ok(self.client.get_tracks(&opts).await?)
+        Ok(self.call(|| self.client.get_tracks(&opts)).await?)
     }
 
-    pub async fn fetch_similar_tracks(&self, track_id: String) -> color_eyre::Result<Vec<Track>> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_similar_tracks(&self, track_id: String) -> color_eyre::Result<Vec<Track>> {
         let opts = GetSimilarTracksOptions::new(track_id);
-        Ok(self.client.get_similar_tracks(&opts).await?.similar_tracks)
+        Ok(self
+            .call(|| self.client.get_similar_tracks(&opts))
+            .await?
+            .similar_tracks)
     }
 
-    pub async fn fetch_track_url(
-        &self,
-        track_id: String,
-    ) -> color_eyre::Result<(String, String, u32)> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_track_url(&self, track_id: String) -> color_eyre::Result<(String, String, u32)> {
         let opts = GetFileInfoOptions::new(track_id);
-        let info = self.client.get_file_info(&opts).await?;
+        let info = self.call(|| self.client.get_file_info(&opts)).await?;
 
         Ok((info.url, info.codec, info.bitrate))
     }
 
-    pub async fn fetch_track_urls_batch(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_track_formats(
+        &self,
+        track_id: String,
+    ) -> color_eyre::Result<Vec<(String, u32)>> {
+        let requests = [Quality::Lossless, Quality::Normal, Quality::Low].map(|quality| {
+            let opts = GetFileInfoOptions::new(track_id.clone()).quality(quality);
+            async move { self.call(|| self.client.get_file_info(&opts)).await }
+        });
+        let results = futures::future::join_all(requests).await;
+        if results.iter().all(Result::is_err) {
+            return Err(color_eyre::eyre::eyre!(
+                "No formats available for {track_id}"
+            ));
+        }
+
+        let mut formats: Vec<(String, u32)> = results
+            .into_iter()
+            .flatten()
+            .map(|info| (info.codec, info.bitrate))
+            .collect();
+        formats.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        formats.dedup();
+        Ok(formats)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_track_urls_batch(
         &self,
         track_ids: Vec<String>,
     ) -> color_eyre::Result<Vec<(String, String, String, u32)>> {
         let opts = GetFileInfoBatchOptions::new(track_ids.clone());
-        let results = self.client.get_file_info_batch(&opts).await?;
+        let results = self.call(|| self.client.get_file_info_batch(&opts)).await?;
 
         let mut mapped = Vec::new();
         for (i, info) in results.into_iter().enumerate() {
@@ -217,13 +395,14 @@ impl ApiService {
         Ok(mapped)
     }
 
-    pub async fn fetch_lyrics(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_lyrics(
         &self,
         track_id: String,
         format: LyricsFormat,
     ) -> color_eyre::Result<Option<String>> {
         let opts = GetLyricsOptions::new(track_id, format);
-        match self.client.get_lyrics(&opts).await {
+        match self.call(|| self.client.get_lyrics(&opts)).await {
             Ok(lyrics) => {
                 let url = lyrics.download_url;
                 let text = self.client.inner.get(url).send().await?.text().await?;
@@ -233,17 +412,23 @@ impl ApiService {
         }
     }
 
-    pub async fn fetch_album_with_tracks(&self, album_id: u32) -> color_eyre::Result<Album> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_album_with_tracks(&self, album_id: u32) -> color_eyre::Result<Album> {
         let opts = GetAlbumOptions::new(album_id).with_tracks();
-        Ok(self.client.get_album(&opts).await?)
+        Ok(self.call(|| self.client.get_album(&opts)).await?)
     }
 
-    pub async fn fetch_artist_tracks(&self, artist_id: String) -> color_eyre::Result<Vec<Track>> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_artist_tracks(&self, artist_id: String) -> color_eyre::Result<Vec<Track>> {
         let opts = ArtistTracksOptions::new(artist_id);
-        Ok(self.client.get_artist_tracks(&opts).await?.tracks)
+        Ok(self
+            .call(|| self.client.get_artist_tracks(&opts))
+            .await?
+            .tracks)
     }
 
-    pub async fn fetch_artist_tracks_paginated(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_artist_tracks_paginated(
         &self,
         artist_id: String,
         page: u32,
@@ -252,34 +437,83 @@ impl ApiService {
         let opts = ArtistTracksOptions::new(artist_id)
             .page(page)
             .page_size(page_size);
-        let result = self.client.get_artist_tracks(&opts).await?;
+        let result = self.call(|| self.client.get_artist_tracks(&opts)).await?;
         Ok((result.tracks, result.pager))
     }
 
-    pub async fn fetch_stations(&self) -> color_eyre::Result<Vec<Rotor>> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_artist_albums(
+        &self,
+        artist_id: String,
+        page: u32,
+        page_size: u32,
+    ) -> color_eyre::Result<(Vec<Album>, Pager)> {
+        let opts = GetArtistAlbumsOptions::new(artist_id)
+            .page(page)
+            .page_size(page_size)
+            .sort_by(SortBy::Year);
+        let result = self.call(|| self.client.get_artist_albums(&opts)).await?;
+        Ok((result.albums, result.pager))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_artist_appearances(&self, artist_id: String) -> color_eyre::Result<Vec<Album>> {
+        let opts = GetArtistOptions::new(artist_id);
+        Ok(self
+            .call(|| self.client.get_artist(&opts))
+            .await?
+            .also_albums)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_artist_description(
+        &self,
+        artist_id: String,
+    ) -> color_eyre::Result<Option<String>> {
+        let opts = GetArtistOptions::new(artist_id);
+        Ok(self
+            .call(|| self.client.get_artist(&opts))
+            .await?
+            .artist
+            .description
+            .map(|d| d.text))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_stations(&self) -> color_eyre::Result<Vec<Rotor>> {
         let opts = yandex_music::api::rotor::get_all_stations::GetAllStationsOptions::default();
-        Ok(self.client.get_all_stations(&opts).await?)
+        Ok(self.call(|| self.client.get_all_stations(&opts)).await?)
     }
 
-    pub async fn create_session(&self, seeds: Vec<String>) -> color_eyre::Result<Session> {
-        let opts = CreateSessionOptions::new(seeds)
-            .include_tracks_in_response(true)
-            .include_wave_model(true)
-            .interactive(true);
-        Ok(self.client.create_session(opts).await?)
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn create_session(&self, seeds: Vec<String>) -> color_eyre::Result<Session> {
+        self.call(|| {
+            let opts = CreateSessionOptions::new(seeds.clone())
+                .include_tracks_in_response(true)
+                .include_wave_model(true)
+                .interactive(true);
+            self.client.create_session(opts)
+        })
+        .await
     }
 
-    pub async fn get_session_tracks(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn get_session_tracks(
         &self,
         session_id: String,
         queue: Vec<String>,
         feedbacks: Vec<StationFeedback>,
     ) -> color_eyre::Result<Session> {
-        let opts = GetSessionTracksOptions::new(session_id, queue).feedbacks(feedbacks);
-        Ok(self.client.get_session_tracks(opts).await?)
+        self.call(|| {
+            let opts = GetSessionTracksOptions::new(session_id.clone(), queue.clone())
+                .feedbacks(feedbacks.clone());
+            self.client.get_session_tracks(opts)
+        })
+        .await
     }
 
-    pub async fn send_rotor_feedback(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn send_rotor_feedback(
         &self,
         station_id: String,
         batch_id: Option<String>,
@@ -303,116 +537,156 @@ impl ApiService {
         };
 
         let opts = SendStationFeedbackOptions::new(station_id, feedback);
-        self.client.send_station_feedback(&opts).await?;
+        self.call(|| self.client.send_station_feedback(&opts))
+            .await?;
 
         Ok(())
     }
 
-    pub async fn toggle_like_track(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn report_play_progress(
         &self,
         track_id: String,
-        is_liked: bool,
+        album_id: Option<String>,
+        position: Duration,
+        length: Duration,
     ) -> color_eyre::Result<()> {
+        let now = Utc::now();
+        let position = position.as_secs() as u32;
+        let mut opts = PlayAudioOptions::new(PLAY_AUDIO_FROM)
+            .track_id(track_id)
+            .uid(self.user_id)
+            .track_length_seconds(length.as_secs() as u32)
+            .total_played_seconds(position)
+            .end_position_seconds(position)
+            .timestamp(now)
+            .client_now(now);
+        if let Some(album_id) = album_id {
+            opts = opts.album_id(album_id);
+        }
+        self.call(|| self.client.play_audio(&opts)).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn toggle_like_track(&self, track_id: String, is_liked: bool) -> color_eyre::Result<()> {
         if is_liked {
             let opts = RemoveLikedTracksOptions::new(self.user_id, vec![track_id]);
-            self.client.remove_liked_tracks(&opts).await?;
+            self.call(|| self.client.remove_liked_tracks(&opts)).await?;
         } else {
             let opts = AddLikedTracksOptions::new(self.user_id, vec![track_id]);
-            self.client.add_liked_tracks(&opts).await?;
+            self.call(|| self.client.add_liked_tracks(&opts)).await?;
         }
 
         Ok(())
     }
 
-    pub async fn add_like_track(&self, track_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn add_like_track(&self, track_id: String) -> color_eyre::Result<()> {
         let opts = AddLikedTracksOptions::new(self.user_id, vec![track_id]);
-        self.client.add_liked_tracks(&opts).await?;
+        self.call(|| self.client.add_liked_tracks(&opts)).await?;
         Ok(())
     }
 
-    pub async fn remove_like_track(&self, track_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn remove_like_track(&self, track_id: String) -> color_eyre::Result<()> {
         let opts = RemoveLikedTracksOptions::new(self.user_id, vec![track_id]);
-        self.client.remove_liked_tracks(&opts).await?;
+        self.call(|| self.client.remove_liked_tracks(&opts)).await?;
         Ok(())
     }
 
-    pub async fn toggle_dislike_track(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn toggle_dislike_track(
         &self,
         track_id: String,
         is_disliked: bool,
     ) -> color_eyre::Result<()> {
         if is_disliked {
             let opts = RemoveDislikedTracksOptions::new(self.user_id, vec![track_id]);
-            self.client.remove_disliked_tracks(&opts).await?;
+            self.call(|| self.client.remove_disliked_tracks(&opts))
+                .await?;
         } else {
             let opts = AddDislikedTracksOptions::new(self.user_id, vec![track_id]);
-            self.client.add_disliked_tracks(&opts).await?;
+            self.call(|| self.client.add_disliked_tracks(&opts)).await?;
         }
 
         Ok(())
     }
 
-    pub async fn add_dislike_track(&self, track_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn add_dislike_track(&self, track_id: String) -> color_eyre::Result<()> {
         let opts = AddDislikedTracksOptions::new(self.user_id, vec![track_id]);
-        self.client.add_disliked_tracks(&opts).await?;
+        self.call(|| self.client.add_disliked_tracks(&opts)).await?;
         Ok(())
     }
 
-    pub async fn remove_dislike_track(&self, track_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn remove_dislike_track(&self, track_id: String) -> color_eyre::Result<()> {
         let opts = RemoveDislikedTracksOptions::new(self.user_id, vec![track_id]);
-        self.client.remove_disliked_tracks(&opts).await?;
+        self.call(|| self.client.remove_disliked_tracks(&opts))
+            .await?;
         Ok(())
     }
 
-    pub async fn add_like_album(&self, album_id: u32) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn add_like_album(&self, album_id: u32) -> color_eyre::Result<()> {
         let opts = AddLikedAlbumOptions::new(self.user_id, album_id);
-        self.client.add_liked_album(&opts).await?;
+        self.call(|| self.client.add_liked_album(&opts)).await?;
         Ok(())
     }
 
-    pub async fn remove_like_album(&self, album_id: u32) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn remove_like_album(&self, album_id: u32) -> color_eyre::Result<()> {
         let opts = RemoveLikedAlbumOptions::new(self.user_id, album_id);
-        self.client.remove_liked_album(&opts).await?;
+        self.call(|| self.client.remove_liked_album(&opts)).await?;
         Ok(())
     }
 
-    pub async fn add_like_playlist(&self, owner_uid: u64, kind: u32) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn add_like_playlist(&self, owner_uid: u64, kind: u32) -> color_eyre::Result<()> {
         let opts = AddLikedPlaylistOptions::new(self.user_id, owner_uid, kind);
-        self.client.add_liked_playlist(&opts).await?;
+        self.call(|| self.client.add_liked_playlist(&opts)).await?;
         Ok(())
     }
 
-    pub async fn remove_like_playlist(&self, owner_uid: u64, kind: u32) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn remove_like_playlist(&self, owner_uid: u64, kind: u32) -> color_eyre::Result<()> {
         let opts = RemoveLikedPlaylistOptions::new(self.user_id, owner_uid, kind);
-        self.client.remove_liked_playlist(&opts).await?;
+        self.call(|| self.client.remove_liked_playlist(&opts))
+            .await?;
         Ok(())
     }
 
-    pub async fn add_like_artist(&self, artist_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn add_like_artist(&self, artist_id: String) -> color_eyre::Result<()> {
         let opts = AddLikedArtistOptions::new(self.user_id, artist_id);
-        self.client.add_liked_artist(&opts).await?;
+        self.call(|| self.client.add_liked_artist(&opts)).await?;
         Ok(())
     }
 
-    pub async fn remove_like_artist(&self, artist_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn remove_like_artist(&self, artist_id: String) -> color_eyre::Result<()> {
         let opts = RemoveLikedArtistOptions::new(self.user_id, artist_id);
-        self.client.remove_liked_artist(&opts).await?;
+        self.call(|| self.client.remove_liked_artist(&opts)).await?;
         Ok(())
     }
 
-    pub async fn add_dislike_artist(&self, artist_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn add_dislike_artist(&self, artist_id: String) -> color_eyre::Result<()> {
         let opts = AddDislikedArtistOptions::new(self.user_id, artist_id);
-        self.client.add_disliked_artist(&opts).await?;
+        self.call(|| self.client.add_disliked_artist(&opts)).await?;
         Ok(())
     }
 
-    pub async fn remove_dislike_artist(&self, artist_id: String) -> color_eyre::Result<()> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn remove_dislike_artist(&self, artist_id: String) -> color_eyre::Result<()> {
         let opts = RemoveDislikedArtistOptions::new(self.user_id, artist_id);
-        self.client.remove_disliked_artist(&opts).await?;
+        self.call(|| self.client.remove_disliked_artist(&opts))
+            .await?;
         Ok(())
     }
 
-    pub async fn fetch_liked_collection(
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_liked_collection(
         &self,
         revision: Option<u64>,
     ) -> color_eyre::Result<Collection> {
@@ -429,6 +703,16 @@ impl ApiService {
             .liked_albums(get_opt())
             .liked_artists(get_opt())
             .liked_playlists(get_opt());
-        Ok(self.client.collection_sync(&opts).await?)
+        Ok(self.call(|| self.client.collection_sync(&opts)).await?)
     }
 }
+
+fn track_shorts(tracks: &[Track]) -> Vec<TrackShort> {
+    tracks
+        .iter()
+        .map(|t| {
+            let album_id = t.albums.first().and_then(|a| a.id).map(|id| id.to_string());
+            TrackShort::new(t.id.clone(), album_id)
+        })
+        .collect()
+}
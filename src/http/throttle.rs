@@ -0,0 +1,62 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use reqwest::StatusCode;
+use yandex_music::error::ClientError;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+pub(super) const MAX_RETRIES: usize = 2;
+
+struct State {
+    until: Option<Instant>,
+    backoff: Duration,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    until: None,
+    backoff: INITIAL_BACKOFF,
+});
+
+pub fn remaining() -> Option<Duration> {
+    let until = STATE.lock().unwrap().until?;
+    until
+        .checked_duration_since(Instant::now())
+        .filter(|d| !d.is_zero())
+}
+
+pub(super) async fn pace() {
+    if let Some(wait) = remaining() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+pub(super) fn record_limited() {
+    let mut state = STATE.lock().unwrap();
+    let wait = state.backoff;
+    let until = Instant::now() + wait;
+    if state.until.is_none_or(|current| current < until) {
+        state.until = Some(until);
+    }
+    state.backoff = (wait * 2).min(MAX_BACKOFF);
+    tracing::warn!(wait_secs = wait.as_secs(), "api_rate_limited");
+}
+
+pub(super) fn record_success() {
+    STATE.lock().unwrap().backoff = INITIAL_BACKOFF;
+}
+
+pub(super) fn is_rate_limited(error: &ClientError) -> bool {
+    match error {
+        ClientError::RequestError { error } => {
+            error.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+        }
+        ClientError::YandexMusicError { error } => {
+            error.name.contains("too-many-requests")
+                || error.message.as_deref().is_some_and(|m| m.contains("429"))
+        }
+        _ => false,
+    }
+}
@@ -3,21 +3,39 @@ use yamusic::{
     app::App,
     audio::system::AudioSystem,
     auth::{LoginScreen, TokenProvider},
-    http::ApiService,
-    util::{hook::set_panic_hook, log::initialize_logging},
+    http::{ApiService, MusicApi},
+    util::{
+        hook::set_panic_hook,
+        instance::Instance,
+        log::{LogGuard, initialize_logging},
+    },
 };
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> color_eyre::Result<()> {
-    setup()?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let listener = match Instance::acquire(&args).await? {
+        Instance::Primary(listener) => listener,
+        Instance::Forwarded => return Ok(()),
+    };
+
+    let _log_guard = setup()?;
 
     let (client, user_id) = resolve_and_verify_token().await?;
 
     let (event_tx, event_rx) = flume::unbounded();
-    let api = Arc::new(ApiService::new("".to_string(), Some(client), Some(user_id)).await?);
+    let api: Arc<dyn MusicApi> =
+        Arc::new(ApiService::new("".to_string(), Some(client), Some(user_id)).await?);
 
     let audio = AudioSystem::new(event_tx.clone(), api.clone()).await?;
     let mut app = App::new(audio, api, event_tx, event_rx).await?;
+    match listener {
+        Ok(listener) => app.serve_instance(listener),
+        Err(e) => tracing::warn!(error = %e, "instance_socket_bind_failed"),
+    }
+    if !args.is_empty() {
+        app.invoke(args);
+    }
     app.run().await
 }
 
@@ -41,7 +59,7 @@ async fn resolve_and_verify_token()
     }
 }
 
-fn setup() -> color_eyre::Result<()> {
+fn setup() -> color_eyre::Result<LogGuard> {
     color_eyre::install()?;
     dotenv::dotenv().ok();
     set_panic_hook();
@@ -143,6 +143,14 @@ impl BufferState {
         self.start_pos + self.data.len() as u64
     }
 
+    pub fn ranges(&self) -> Vec<(u64, u64)> {
+        if self.data.is_empty() {
+            Vec::new()
+        } else {
+            vec![(self.start_pos, self.end_pos())]
+        }
+    }
+
     pub fn max_buffered_from_start(&self) -> u64 {
         self.max_buffered_from_start
     }
@@ -1,4 +1,7 @@
-use crate::audio::progress::TrackProgress;
+use crate::audio::{
+    progress::TrackProgress,
+    signals::{LoadStage, StageReporter},
+};
 use color_eyre::{Result, eyre::eyre};
 use flume::{Receiver, Sender};
 use reqwest::blocking::Client;
@@ -36,11 +39,18 @@ pub struct StreamingDataSource {
 }
 
 impl StreamingDataSource {
-    pub fn new(client: Client, url: String, progress: Arc<TrackProgress>) -> Result<Self> {
+    pub fn new(
+        client: Client,
+        url: String,
+        progress: Arc<TrackProgress>,
+        report: StageReporter,
+    ) -> Result<Self> {
         let progress_generation = progress.get_generation();
 
-        let range_header = format!("bytes=0-{}", PREFETCH_SIZE - 1);
+        report(LoadStage::Connecting);
+        let range_header = format!("bytes=0-{}", MIN_INITIAL_DATA - 1);
         let resp = client.get(&url).header("Range", range_header).send()?;
+        report(LoadStage::Buffering);
 
         let total = if let Some(range) = resp.headers().get("content-range") {
             let s = range
@@ -68,10 +78,11 @@ impl StreamingDataSource {
             b.append(&initial_data, 0);
         }
 
-        progress.set_buffered_bytes({
+        {
             let b = buffer.lock().unwrap();
-            b.max_buffered_from_start()
-        });
+            progress.set_buffered_bytes(b.max_buffered_from_start());
+            progress.set_buffered_ranges(b.ranges());
+        }
 
         let position = Arc::new(AtomicU64::new(0));
         let (tx_cmd, rx_cmd) = flume::unbounded();
@@ -144,7 +155,7 @@ impl StreamingDataSource {
                             let maybe_buffered = {
                                 let mut buf = buffer.lock().unwrap();
                                 if buf.append(&data, start) {
-                                    Some(buf.max_buffered_from_start())
+                                    Some((buf.max_buffered_from_start(), buf.ranges()))
                                 } else {
                                     None
                                 }
@@ -152,9 +163,10 @@ impl StreamingDataSource {
 
                             if request_generation == generation.load(Ordering::SeqCst)
                                 && progress_generation == prog.get_generation()
-                                && let Some(buffered_pos) = maybe_buffered
+                                && let Some((buffered_pos, ranges)) = maybe_buffered
                             {
                                 prog.set_buffered_bytes(buffered_pos);
+                                prog.set_buffered_ranges(ranges);
                             }
                             let _ = tx_res.send(());
                         }
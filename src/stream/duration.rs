@@ -0,0 +1,110 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::Duration;
+
+use crate::audio::progress::TrackProgress;
+
+const MIN_SAMPLE_WINDOW: Duration = Duration::from_secs(5);
+const MIN_CHANGE_RATIO: f64 = 0.005;
+
+pub struct TrackedReader<R> {
+    inner: R,
+    position: Arc<AtomicU64>,
+}
+
+impl<R> TrackedReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            position: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn position(&self) -> Arc<AtomicU64> {
+        self.position.clone()
+    }
+}
+
+impl<R: Read> Read for TrackedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.position.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+impl<R: Seek> Seek for TrackedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = self.inner.seek(pos)?;
+        self.position.store(position, Ordering::Relaxed);
+        Ok(position)
+    }
+}
+
+pub struct DurationRefiner {
+    read_position: Arc<AtomicU64>,
+    total_bytes: u64,
+    samples_per_sec: u64,
+    origin: Option<(u64, Duration)>,
+    decoded_samples: u64,
+    estimate: Option<Duration>,
+}
+
+impl DurationRefiner {
+    pub fn new(
+        read_position: Arc<AtomicU64>,
+        total_bytes: u64,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Self {
+        Self {
+            read_position,
+            total_bytes,
+            samples_per_sec: sample_rate as u64 * channels.max(1) as u64,
+            origin: None,
+            decoded_samples: 0,
+            estimate: None,
+        }
+    }
+
+    pub fn reset(&mut self, position: Duration) {
+        self.origin = Some((self.read_position.load(Ordering::Relaxed), position));
+        self.decoded_samples = 0;
+    }
+
+    pub fn observe(&mut self, samples: usize, progress: &TrackProgress) {
+        let (origin_bytes, origin_time) = *self
+            .origin
+            .get_or_insert_with(|| (self.read_position.load(Ordering::Relaxed), Duration::ZERO));
+        self.decoded_samples += samples as u64;
+        if self.samples_per_sec == 0 || self.total_bytes == 0 {
+            return;
+        }
+
+        let decoded =
+            Duration::from_secs_f64(self.decoded_samples as f64 / self.samples_per_sec as f64);
+        let consumed = self
+            .read_position
+            .load(Ordering::Relaxed)
+            .saturating_sub(origin_bytes);
+        if decoded < MIN_SAMPLE_WINDOW || consumed == 0 {
+            return;
+        }
+
+        let bytes_per_sec = consumed as f64 / decoded.as_secs_f64();
+        let remaining = self.total_bytes.saturating_sub(origin_bytes) as f64 / bytes_per_sec;
+        let total = origin_time + Duration::from_secs_f64(remaining);
+        let changed = self.estimate.is_none_or(|previous| {
+            (total.as_secs_f64() - previous.as_secs_f64()).abs()
+                > previous.as_secs_f64() * MIN_CHANGE_RATIO
+        });
+        if changed {
+            self.estimate = Some(total);
+            progress.set_total_duration(total);
+            progress.mark_duration_refined();
+        }
+    }
+}
@@ -1,6 +1,11 @@
 mod buffer;
 mod data_source;
+mod duration;
 mod pcm;
+mod silence;
 
 pub use self::data_source::StreamingDataSource;
-pub use self::pcm::{StreamController, StreamingSession, create_streaming_session};
+pub use self::pcm::{
+    StreamController, StreamStats, StreamingSession, create_file_session, create_streaming_session,
+    stream_stats,
+};
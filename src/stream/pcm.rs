@@ -1,25 +1,56 @@
-use crate::audio::progress::TrackProgress;
+use crate::audio::{progress::TrackProgress, signals::StageReporter};
 use color_eyre::{Result, eyre::eyre};
 use crossbeam_channel::{
     Receiver as CbReceiver, Sender as CbSender, TryRecvError, bounded as cb_bounded, select,
 };
 use rodio::{Decoder, Source};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
 use std::num::NonZero;
+use std::path::Path;
 use std::sync::{
     Arc,
-    atomic::{AtomicU64, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
 };
 use std::thread;
 use std::time::Duration;
 
 use reqwest::blocking::Client;
 
-use super::data_source::StreamingDataSource;
+use super::{
+    data_source::StreamingDataSource,
+    duration::{DurationRefiner, TrackedReader},
+    silence::SilenceGate,
+};
+use crate::util::{config, process::raise_thread_priority};
 
 const PCM_CHUNK_SAMPLES: usize = 16384;
 
 const SAMPLE_CHANNEL_CAPACITY: usize = 64;
 
+const RECYCLE_CHANNEL_CAPACITY: usize = SAMPLE_CHANNEL_CAPACITY + 4;
+
+const SEEK_SETTLE: Duration = Duration::from_millis(40);
+
+static UNDERRUNS: AtomicU64 = AtomicU64::new(0);
+static UNDERRUN_MILLIS: AtomicU64 = AtomicU64::new(0);
+static DECODER_ELEVATED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamStats {
+    pub underruns: u64,
+    pub underrun_time: Duration,
+    pub decoder_elevated: bool,
+}
+
+pub fn stream_stats() -> StreamStats {
+    StreamStats {
+        underruns: UNDERRUNS.load(Ordering::Relaxed),
+        underrun_time: Duration::from_millis(UNDERRUN_MILLIS.load(Ordering::Relaxed)),
+        decoder_elevated: DECODER_ELEVATED.load(Ordering::Relaxed),
+    }
+}
+
 enum SampleMessage {
     Samples(Vec<f32>, u64),
     Finished(u64),
@@ -52,6 +83,7 @@ impl StreamController {
 
 pub struct BufferedStreamingSource {
     rx: CbReceiver<SampleMessage>,
+    recycle_tx: CbSender<Vec<f32>>,
     pending_samples: Vec<f32>,
     sample_pos: usize,
     pending_generation: u64,
@@ -61,11 +93,15 @@ pub struct BufferedStreamingSource {
     total_duration: Option<Duration>,
     finished_generation: Option<u64>,
     controller: StreamController,
+    primed: bool,
+    starved_samples: u64,
+    on_end: Option<Box<dyn FnOnce() + Send>>,
 }
 
 impl BufferedStreamingSource {
     fn new(
         rx: CbReceiver<SampleMessage>,
+        recycle_tx: CbSender<Vec<f32>>,
         generation: Arc<AtomicU64>,
         sample_rate: u32,
         channels: u16,
@@ -75,6 +111,7 @@ impl BufferedStreamingSource {
         let pending_generation = generation.load(Ordering::SeqCst);
         Self {
             rx,
+            recycle_tx,
             pending_samples: Vec::new(),
             sample_pos: 0,
             pending_generation,
@@ -84,7 +121,71 @@ impl BufferedStreamingSource {
             total_duration,
             finished_generation: None,
             controller,
+            primed: false,
+            starved_samples: 0,
+            on_end: None,
+        }
+    }
+
+    pub fn on_end(&mut self, notify: impl FnOnce() + Send + 'static) {
+        self.on_end = Some(Box::new(notify));
+    }
+
+    fn end(&mut self) -> Option<f32> {
+        if let Some(notify) = self.on_end.take() {
+            notify();
+        }
+        None
+    }
+
+    fn note_starved(&mut self) {
+        if !self.primed {
+            return;
         }
+        if self.starved_samples == 0 {
+            UNDERRUNS.fetch_add(1, Ordering::Relaxed);
+            tracing::debug!("stream_underrun");
+        }
+        self.starved_samples += 1;
+    }
+
+    fn note_fed(&mut self) {
+        if self.starved_samples > 0 {
+            let frames = self.starved_samples / self.channels.max(1) as u64;
+            UNDERRUN_MILLIS.fetch_add(
+                frames * 1000 / self.sample_rate.max(1) as u64,
+                Ordering::Relaxed,
+            );
+            self.starved_samples = 0;
+        }
+        self.primed = true;
+    }
+
+    fn recycle(&self, buffer: Vec<f32>) {
+        if buffer.capacity() > 0 {
+            let _ = self.recycle_tx.try_send(buffer);
+        }
+    }
+}
+
+struct ChunkPool {
+    recycled: CbReceiver<Vec<f32>>,
+    spare: Option<Vec<f32>>,
+}
+
+impl ChunkPool {
+    fn take(&mut self) -> Vec<f32> {
+        let mut buffer = self
+            .spare
+            .take()
+            .or_else(|| self.recycled.try_recv().ok())
+            .unwrap_or_else(|| Vec::with_capacity(PCM_CHUNK_SAMPLES));
+        buffer.clear();
+        buffer
+    }
+
+    fn put(&mut self, buffer: Vec<f32>) {
+        self.spare = Some(buffer);
     }
 }
 
@@ -98,6 +199,8 @@ impl Iterator for BufferedStreamingSource {
             self.pending_samples.clear();
             self.sample_pos = 0;
             self.finished_generation = None;
+            self.primed = false;
+            self.starved_samples = 0;
         }
 
         if self.sample_pos < self.pending_samples.len() {
@@ -110,9 +213,12 @@ impl Iterator for BufferedStreamingSource {
             match self.rx.try_recv() {
                 Ok(SampleMessage::Samples(chunk, packet_generation)) => {
                     if packet_generation != current_generation {
+                        self.recycle(chunk);
                         continue;
                     }
-                    self.pending_samples = chunk;
+                    let used = std::mem::replace(&mut self.pending_samples, chunk);
+                    self.recycle(used);
+                    self.note_fed();
                     self.sample_pos = 0;
                     if self.sample_pos < self.pending_samples.len() {
                         let sample = self.pending_samples[self.sample_pos];
@@ -124,17 +230,18 @@ impl Iterator for BufferedStreamingSource {
                     if packet_generation == current_generation {
                         self.finished_generation = Some(packet_generation);
                         if self.sample_pos >= self.pending_samples.len() {
-                            return None;
+                            return self.end();
                         }
                     }
                 }
                 Err(TryRecvError::Empty) => {
                     if self.finished_generation == Some(current_generation) {
-                        return None;
+                        return self.end();
                     }
+                    self.note_starved();
                     return Some(0.0);
                 }
-                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Disconnected) => return self.end(),
             }
         }
     }
@@ -177,12 +284,48 @@ pub fn create_streaming_session(
     codec: String,
     _bitrate: u32,
     progress: Arc<TrackProgress>,
+    skip_silence: bool,
+    report: StageReporter,
 ) -> Result<StreamingSession> {
-    let data_source = StreamingDataSource::new(client, url, Arc::clone(&progress))?;
+    let data_source = StreamingDataSource::new(client, url, Arc::clone(&progress), report)?;
     let total_bytes = data_source.total_bytes();
+    create_session(data_source, total_bytes, codec, progress, skip_silence)
+}
 
+pub fn create_file_session(
+    path: &Path,
+    codec: String,
+    progress: Arc<TrackProgress>,
+    skip_silence: bool,
+) -> Result<StreamingSession> {
+    let file = File::open(path)?;
+    let total_bytes = file.metadata()?.len();
+    progress.set_total_bytes(total_bytes);
+    progress.set_buffered_bytes(total_bytes);
+    progress.set_buffered_ranges(vec![(0, total_bytes)]);
+    create_session(
+        BufReader::new(file),
+        total_bytes,
+        codec,
+        progress,
+        skip_silence,
+    )
+}
+
+fn create_session<R>(
+    data: R,
+    total_bytes: u64,
+    codec: String,
+    progress: Arc<TrackProgress>,
+    skip_silence: bool,
+) -> Result<StreamingSession>
+where
+    R: Read + Seek + Send + Sync + 'static,
+{
+    let data = TrackedReader::new(data);
+    let read_position = data.position();
     let decoder = Decoder::builder()
-        .with_data(data_source)
+        .with_data(data)
         .with_hint(codec.as_str())
         .with_byte_len(total_bytes)
         .with_coarse_seek(true)
@@ -195,9 +338,19 @@ pub fn create_streaming_session(
     let total_duration = decoder.total_duration();
     if let Some(total) = total_duration {
         progress.set_total_duration(total);
+        progress.mark_duration_refined();
     }
+    let refiner = total_duration.is_none().then(|| {
+        DurationRefiner::new(
+            read_position,
+            total_bytes,
+            sample_rate.get(),
+            channels.get(),
+        )
+    });
 
     let (sample_tx, sample_rx) = cb_bounded::<SampleMessage>(SAMPLE_CHANNEL_CAPACITY);
+    let (recycle_tx, recycle_rx) = cb_bounded::<Vec<f32>>(RECYCLE_CHANNEL_CAPACITY);
     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded::<DecoderCommand>();
     let generation = Arc::new(AtomicU64::new(0));
     let controller = StreamController {
@@ -205,15 +358,29 @@ pub fn create_streaming_session(
         generation: generation.clone(),
     };
 
+    let gate = skip_silence
+        .then(|| SilenceGate::new(&config::get().playback, sample_rate.get(), channels.get()))
+        .flatten();
     let decoder_generation = generation.clone();
     let progress_clone = Arc::clone(&progress);
     let progress_generation = progress.get_generation();
     thread::Builder::new()
         .name("yamusic-stream".into())
         .spawn(move || {
+            let elevated = raise_thread_priority();
+            DECODER_ELEVATED.store(elevated, Ordering::Relaxed);
+            if !elevated {
+                tracing::debug!("decoder_priority_unchanged");
+            }
             run_decode_loop(
                 decoder,
+                gate,
+                refiner,
                 sample_tx,
+                ChunkPool {
+                    recycled: recycle_rx,
+                    spare: None,
+                },
                 cmd_rx,
                 decoder_generation,
                 progress_clone,
@@ -224,6 +391,7 @@ pub fn create_streaming_session(
 
     let source = BufferedStreamingSource::new(
         sample_rx,
+        recycle_tx,
         generation,
         sample_rate.get(),
         channels.get(),
@@ -234,14 +402,43 @@ pub fn create_streaming_session(
     Ok(StreamingSession { source, controller })
 }
 
-fn run_decode_loop(
-    mut decoder: Decoder<StreamingDataSource>,
+fn settle_seek(
+    cmd_rx: &CbReceiver<DecoderCommand>,
+    mut position: Duration,
+    mut generation: u64,
+) -> Option<(Duration, u64)> {
+    loop {
+        match cmd_rx.recv_timeout(SEEK_SETTLE) {
+            Ok(DecoderCommand::Seek {
+                position: next,
+                generation: next_generation,
+            }) => {
+                position = next;
+                generation = next_generation;
+            }
+            Ok(DecoderCommand::Stop) => return None,
+            Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                return Some((position, generation));
+            }
+            Err(crossbeam_channel::RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_decode_loop<R>(
+    mut decoder: Decoder<R>,
+    mut gate: Option<SilenceGate>,
+    mut refiner: Option<DurationRefiner>,
     sample_tx: CbSender<SampleMessage>,
+    mut pool: ChunkPool,
     cmd_rx: CbReceiver<DecoderCommand>,
     generation: Arc<AtomicU64>,
     progress: Arc<TrackProgress>,
     progress_generation: u64,
-) {
+) where
+    R: Read + Seek + Send + Sync + 'static,
+{
     let mut active_generation = generation.load(Ordering::Acquire);
     let mut chunk = Vec::with_capacity(PCM_CHUNK_SAMPLES);
     let mut pending_chunk: Option<Vec<f32>> = None;
@@ -253,12 +450,23 @@ fn run_decode_loop(
                     position,
                     generation: new_gen,
                 }) => {
+                    let Some((position, new_gen)) = settle_seek(&cmd_rx, position, new_gen) else {
+                        return;
+                    };
                     let _ = decoder.try_seek(position);
+                    if let Some(refiner) = &mut refiner {
+                        refiner.reset(position);
+                    }
                     if progress_generation == progress.get_generation() {
                         progress.set_current_position(position);
                     }
                     active_generation = new_gen;
-                    pending_chunk = None;
+                    if let Some(stale) = pending_chunk.take() {
+                        pool.put(stale);
+                    }
+                    if let Some(gate) = &mut gate {
+                        gate.reset();
+                    }
                 }
                 Ok(DecoderCommand::Stop) => return,
                 Err(crossbeam_channel::TryRecvError::Empty) => break,
@@ -276,11 +484,22 @@ fn run_decode_loop(
                 recv(cmd_rx) -> msg => {
                     match msg {
                         Ok(DecoderCommand::Seek { position, generation: new_gen }) => {
+                            let Some((position, new_gen)) =
+                                settle_seek(&cmd_rx, position, new_gen)
+                            else {
+                                return;
+                            };
                             let _ = decoder.try_seek(position);
+                            if let Some(refiner) = &mut refiner {
+                                refiner.reset(position);
+                            }
                             if progress_generation == progress.get_generation() {
                                 progress.set_current_position(position);
                             }
                             active_generation = new_gen;
+                            if let Some(gate) = &mut gate {
+                                gate.reset();
+                            }
                             continue;
                         }
                         Ok(DecoderCommand::Stop) => return,
@@ -291,10 +510,13 @@ fn run_decode_loop(
         }
 
         chunk.clear();
-        for _ in 0..PCM_CHUNK_SAMPLES {
-            match decoder.next() {
-                Some(sample) => chunk.push(sample),
-                None => break,
+        {
+            let _span = tracing::trace_span!("decode_chunk").entered();
+            for _ in 0..PCM_CHUNK_SAMPLES {
+                match decoder.next() {
+                    Some(sample) => chunk.push(sample),
+                    None => break,
+                }
             }
         }
 
@@ -302,8 +524,24 @@ fn run_decode_loop(
             let _ = sample_tx.send(SampleMessage::Finished(active_generation));
             return;
         }
+        if let Some(refiner) = &mut refiner
+            && progress_generation == progress.get_generation()
+        {
+            refiner.observe(chunk.len(), &progress);
+        }
 
-        let send_chunk = std::mem::replace(&mut chunk, Vec::with_capacity(PCM_CHUNK_SAMPLES));
+        let send_chunk = match &mut gate {
+            Some(gate) => {
+                let mut out = pool.take();
+                gate.process(&chunk, &mut out);
+                if out.is_empty() {
+                    pool.put(out);
+                    continue;
+                }
+                out
+            }
+            None => std::mem::replace(&mut chunk, pool.take()),
+        };
 
         match sample_tx.try_send(SampleMessage::Samples(send_chunk, active_generation)) {
             Ok(()) => {}
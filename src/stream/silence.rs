@@ -0,0 +1,119 @@
+use crate::util::config::PlaybackConfig;
+
+pub struct SilenceGate {
+    threshold: f32,
+    channels: usize,
+    max_held: usize,
+    leading: bool,
+    held: Vec<f32>,
+}
+
+impl SilenceGate {
+    pub fn new(config: &PlaybackConfig, sample_rate: u32, channels: u16) -> Option<Self> {
+        if !config.skip_silence {
+            return None;
+        }
+        let channels = channels.max(1) as usize;
+        Some(Self {
+            threshold: 10.0_f32.powf(config.silence_threshold_db / 20.0),
+            channels,
+            max_held: (config.max_silence_secs.max(0.0) * sample_rate as f32) as usize * channels,
+            leading: true,
+            held: Vec::new(),
+        })
+    }
+
+    pub fn process(&mut self, chunk: &[f32], out: &mut Vec<f32>) {
+        let threshold = self.threshold;
+        let audible = |s: &f32| s.abs() > threshold;
+        let (Some(first), Some(last)) = (
+            chunk.iter().position(audible),
+            chunk.iter().rposition(audible),
+        ) else {
+            if !self.leading {
+                self.held.extend_from_slice(chunk);
+                if self.held.len() > self.max_held {
+                    out.append(&mut self.held);
+                }
+            }
+            return;
+        };
+
+        let start = if std::mem::take(&mut self.leading) {
+            first - first % self.channels
+        } else {
+            0
+        };
+        let end = ((last / self.channels + 1) * self.channels).min(chunk.len());
+
+        out.append(&mut self.held);
+        out.extend_from_slice(&chunk[start..end]);
+        self.held.extend_from_slice(&chunk[end..]);
+    }
+
+    pub fn reset(&mut self) {
+        self.leading = false;
+        self.held.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate() -> SilenceGate {
+        let config = PlaybackConfig {
+            skip_silence: true,
+            max_silence_secs: 0.5,
+            ..PlaybackConfig::default()
+        };
+        SilenceGate::new(&config, 10, 2).unwrap()
+    }
+
+    #[test]
+    fn disabled_without_skip_silence() {
+        assert!(SilenceGate::new(&PlaybackConfig::default(), 44_100, 2).is_none());
+    }
+
+    #[test]
+    fn trims_leading_silence_on_frame_boundaries() {
+        let mut gate = gate();
+        let mut out = Vec::new();
+        gate.process(&[0.0; 4], &mut out);
+        assert!(out.is_empty());
+        gate.process(&[0.0, 0.0, 0.0, 0.5, 0.5, 0.5, 0.0, 0.0], &mut out);
+        assert_eq!(out, [0.0, 0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn keeps_short_gaps_and_holds_trailing_silence() {
+        let mut gate = gate();
+        let mut out = Vec::new();
+        gate.process(&[0.5, 0.5, 0.0, 0.0], &mut out);
+        gate.process(&[0.0; 4], &mut out);
+        assert_eq!(out, [0.5, 0.5]);
+        gate.process(&[0.2, 0.2], &mut out);
+        assert_eq!(out, [0.5, 0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.2, 0.2]);
+    }
+
+    #[test]
+    fn flushes_silence_longer_than_the_limit() {
+        let mut gate = gate();
+        let mut out = Vec::new();
+        gate.process(&[0.5, 0.5], &mut out);
+        gate.process(&[0.0; 8], &mut out);
+        assert_eq!(out.len(), 2);
+        gate.process(&[0.0; 4], &mut out);
+        assert_eq!(out.len(), 14);
+    }
+
+    #[test]
+    fn reset_drops_held_silence() {
+        let mut gate = gate();
+        let mut out = Vec::new();
+        gate.process(&[0.5, 0.5, 0.0, 0.0], &mut out);
+        gate.reset();
+        gate.process(&[0.0, 0.0, 0.2, 0.2], &mut out);
+        assert_eq!(out, [0.5, 0.5, 0.0, 0.0, 0.2, 0.2]);
+    }
+}
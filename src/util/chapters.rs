@@ -0,0 +1,99 @@
+use yandex_music::model::track::Track;
+
+const SEPARATORS: &[char] = &['-', '–', '—', ':', '|', '.', ')', ' '];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    pub start_ms: u64,
+    pub title: String,
+}
+
+pub fn for_track(track: &Track) -> Vec<Chapter> {
+    track
+        .short_description
+        .as_deref()
+        .map(parse)
+        .unwrap_or_default()
+}
+
+pub fn parse(text: &str) -> Vec<Chapter> {
+    let chapters: Vec<Chapter> = text.lines().filter_map(parse_line).collect();
+    let ascending = chapters.windows(2).all(|w| w[0].start_ms < w[1].start_ms);
+    if chapters.len() < 2 || !ascending {
+        return Vec::new();
+    }
+    chapters
+}
+
+pub fn current(chapters: &[Chapter], position_ms: u64) -> Option<&Chapter> {
+    chapters.iter().rev().find(|c| c.start_ms <= position_ms)
+}
+
+fn parse_line(line: &str) -> Option<Chapter> {
+    let line = line.trim().trim_start_matches(['-', '•', '*', ' ']);
+    let (stamp, rest) = line.split_once(char::is_whitespace)?;
+    let start_ms = parse_timestamp(stamp.trim_matches(['(', ')', '[', ']']))?;
+    let title = rest.trim_start_matches(SEPARATORS).trim();
+    (!title.is_empty()).then(|| Chapter {
+        start_ms,
+        title: title.to_string(),
+    })
+}
+
+fn parse_timestamp(text: &str) -> Option<u64> {
+    let parts: Vec<&str> = text.split(':').collect();
+    if !(2..=3).contains(&parts.len()) || parts[1..].iter().any(|p| p.len() != 2) {
+        return None;
+    }
+    let mut secs = 0u64;
+    for (i, part) in parts.iter().enumerate() {
+        let value = part.parse::<u64>().ok()?;
+        if i > 0 && value >= 60 {
+            return None;
+        }
+        secs = secs * 60 + value;
+    }
+    Some(secs * 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(start_ms: u64, title: &str) -> Chapter {
+        Chapter {
+            start_ms,
+            title: title.into(),
+        }
+    }
+
+    #[test]
+    fn parses_timestamped_lines() {
+        let text = "In this episode:\n00:00 Intro\n- 05:30 - Guest interview\n[1:02:03] Wrap-up";
+        assert_eq!(
+            parse(text),
+            [
+                chapter(0, "Intro"),
+                chapter(330_000, "Guest interview"),
+                chapter(3_723_000, "Wrap-up"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lists() {
+        assert!(parse("00:00 Only one chapter").is_empty());
+        assert!(parse("05:00 Second\n00:00 First").is_empty());
+        assert!(parse("00:00 Intro\n00:75 Bad seconds").is_empty());
+        assert!(parse("0:1 Intro\n0:2 Outro").is_empty());
+        assert!(parse("00:00\n01:00 Outro").is_empty());
+    }
+
+    #[test]
+    fn finds_current_chapter() {
+        let chapters = [chapter(0, "Intro"), chapter(60_000, "Main")];
+        assert_eq!(current(&chapters, 59_999).unwrap().title, "Intro");
+        assert_eq!(current(&chapters, 60_000).unwrap().title, "Main");
+        assert!(current(&chapters[1..], 0).is_none());
+    }
+}
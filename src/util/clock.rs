@@ -0,0 +1,31 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+pub fn now() -> DateTime<Utc> {
+    Utc::now()
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn relative(ts: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - ts).num_seconds().max(0);
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", secs / 60),
+        3600..86400 => format!("{} h ago", secs / 3600),
+        86400..172800 => "1 day ago".to_string(),
+        _ => format!("{} days ago", secs / 86400),
+    }
+}
+
+pub fn day_label(ts: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let day = ts.with_timezone(&Local).date_naive();
+    let today = now.with_timezone(&Local).date_naive();
+    match (today - day).num_days() {
+        0 => "Today".to_string(),
+        1 => "Yesterday".to_string(),
+        2..7 => day.format("%A").to_string(),
+        _ => day.format("%-d %B %Y").to_string(),
+    }
+}
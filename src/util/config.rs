@@ -0,0 +1,335 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, LazyLock},
+};
+
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::keymap::Step,
+    util::{format::RowFormat, log::get_config_dir},
+};
+
+const CONFIG_FILE: &str = "config.toml";
+
+static CONFIG: LazyLock<ArcSwap<Config>> = LazyLock::new(|| ArcSwap::from_pointee(load()));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub playback: PlaybackConfig,
+    pub controls: ControlsConfig,
+    pub track_list: TrackListConfig,
+    pub smart_playlists: Vec<SmartPlaylistConfig>,
+    pub screensaver: ScreensaverConfig,
+    pub startup: StartupConfig,
+    pub now_playing: NowPlayingConfig,
+    pub output: OutputConfig,
+    pub lyrics: LyricsConfig,
+    pub downloads: DownloadsConfig,
+    pub library: LibraryConfig,
+    pub keybind: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaybackConfig {
+    pub skip_silence: bool,
+    pub silence_threshold_db: f32,
+    pub max_silence_secs: f32,
+    pub pause_on_focus_loss: bool,
+    pub focus_loss_pause_secs: u64,
+    pub stereo_width: f32,
+    pub wave_filter_skipped: bool,
+    pub wave_quick_skip_secs: u64,
+    pub queue_end: QueueEnd,
+    pub queue_end_playlist: Option<u32>,
+    pub resume_context_minutes: u64,
+}
+
+impl Default for PlaybackConfig {
+    fn default() -> Self {
+        Self {
+            skip_silence: false,
+            silence_threshold_db: -60.0,
+            max_silence_secs: 30.0,
+            pause_on_focus_loss: false,
+            focus_loss_pause_secs: 30,
+            stereo_width: 1.0,
+            wave_filter_skipped: false,
+            wave_quick_skip_secs: 30,
+            queue_end: QueueEnd::Stop,
+            queue_end_playlist: None,
+            resume_context_minutes: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueEnd {
+    #[default]
+    Stop,
+    Repeat,
+    Radio,
+    Playlist,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlsConfig {
+    pub seek_step_secs: u64,
+    pub fine_seek_step_secs: u64,
+    pub volume_step: u8,
+    pub fine_volume_step: u8,
+}
+
+impl Default for ControlsConfig {
+    fn default() -> Self {
+        Self {
+            seek_step_secs: 10,
+            fine_seek_step_secs: 2,
+            volume_step: 5,
+            fine_volume_step: 1,
+        }
+    }
+}
+
+impl ControlsConfig {
+    pub fn seek_step(&self, step: Step) -> u64 {
+        match step {
+            Step::Coarse => self.seek_step_secs,
+            Step::Fine => self.fine_seek_step_secs,
+        }
+    }
+
+    pub fn volume_step(&self, step: Step) -> u8 {
+        match step {
+            Step::Coarse => self.volume_step,
+            Step::Fine => self.fine_volume_step,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackLayout {
+    #[default]
+    Rich,
+    Columns,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackColumn {
+    Index,
+    Liked,
+    Title,
+    Artist,
+    Album,
+    Duration,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrackListConfig {
+    pub layout: TrackLayout,
+    pub columns: Vec<TrackColumn>,
+    pub format: Option<String>,
+    #[serde(skip)]
+    pub row_format: Option<RowFormat>,
+    pub prefetch_pages: usize,
+}
+
+impl Default for TrackListConfig {
+    fn default() -> Self {
+        Self {
+            layout: TrackLayout::Rich,
+            columns: vec![
+                TrackColumn::Index,
+                TrackColumn::Liked,
+                TrackColumn::Title,
+                TrackColumn::Artist,
+                TrackColumn::Album,
+                TrackColumn::Duration,
+            ],
+            format: None,
+            row_format: None,
+            prefetch_pages: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupView {
+    #[default]
+    Home,
+    Liked,
+    Last,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupConfig {
+    pub view: StartupView,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NowPlayingFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NowPlayingConfig {
+    pub path: Option<String>,
+    pub format: NowPlayingFormat,
+    pub template: String,
+}
+
+impl Default for NowPlayingConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            format: NowPlayingFormat::Text,
+            template: "{artist} - {title}".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    #[default]
+    Local,
+    Tcp,
+    Udp,
+    PipeWire,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputConfig {
+    pub mode: OutputMode,
+    pub address: String,
+    pub sample_rate: u32,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            mode: OutputMode::Local,
+            address: "127.0.0.1:4953".to_string(),
+            sample_rate: 48000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LyricsLayout {
+    #[default]
+    Animated,
+    Static,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LyricsConfig {
+    pub layout: LyricsLayout,
+    pub translate_command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadsConfig {
+    pub enabled: bool,
+    pub dir: Option<String>,
+    pub ahead: usize,
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            ahead: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LibraryConfig {
+    pub watch: bool,
+}
+
+impl Default for LibraryConfig {
+    fn default() -> Self {
+        Self { watch: true }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreensaverConfig {
+    pub enabled: bool,
+    pub idle_minutes: u64,
+}
+
+impl Default for ScreensaverConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_minutes: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartPlaylistConfig {
+    pub name: String,
+    pub rule: String,
+    pub limit: Option<usize>,
+}
+
+impl Config {
+    pub fn smart_playlist(&self, name: &str) -> Option<&SmartPlaylistConfig> {
+        self.smart_playlists.iter().find(|p| p.name == name)
+    }
+}
+
+pub fn path() -> PathBuf {
+    get_config_dir().join(CONFIG_FILE)
+}
+
+fn load() -> Config {
+    let Ok(contents) = std::fs::read_to_string(path()) else {
+        return Config::default();
+    };
+    let mut config: Config = toml::from_str(&contents).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "config_parse_failed");
+        Config::default()
+    });
+    config.track_list.row_format = config
+        .track_list
+        .format
+        .as_deref()
+        .filter(|f| !f.trim().is_empty())
+        .map(RowFormat::parse);
+    config
+}
+
+pub fn get() -> Arc<Config> {
+    CONFIG.load_full()
+}
+
+pub fn reload() {
+    CONFIG.store(Arc::new(load()));
+}
@@ -0,0 +1,83 @@
+use std::{backtrace::Backtrace, fmt::Write, panic::PanicHookInfo, path::PathBuf};
+
+use crate::util::{
+    clock, config,
+    log::{LOG_FILE, get_data_dir},
+};
+
+const CRASH_DIR: &str = "crashes";
+const LOG_TAIL_LINES: usize = 200;
+const TERMINAL_VARS: [&str; 5] = [
+    "TERM",
+    "TERM_PROGRAM",
+    "TERM_PROGRAM_VERSION",
+    "COLORTERM",
+    "TMUX",
+];
+
+pub fn write_report(info: &PanicHookInfo) -> std::io::Result<PathBuf> {
+    let backtrace = Backtrace::force_capture();
+    let mut report = String::new();
+
+    let _ = writeln!(report, "# {} crash report\n", env!("CARGO_PKG_NAME"));
+    let _ = writeln!(report, "version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "time: {}", clock::now().to_rfc3339());
+    let _ = writeln!(
+        report,
+        "os: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    for var in TERMINAL_VARS {
+        if let Ok(value) = std::env::var(var) {
+            let _ = writeln!(report, "{}: {value}", var.to_lowercase());
+        }
+    }
+    if let Ok((width, height)) = crossterm::terminal::size() {
+        let _ = writeln!(report, "terminal size: {width}x{height}");
+    }
+
+    let _ = writeln!(report, "\n## Panic\n\n{info}");
+    let _ = writeln!(report, "\n## Backtrace\n\n{backtrace}");
+
+    let _ = writeln!(report, "## Config\n");
+    match toml::to_string_pretty(config::get().as_ref()) {
+        Ok(config) => report.push_str(&config),
+        Err(e) => {
+            let _ = writeln!(report, "unavailable: {e}");
+        }
+    }
+
+    let _ = writeln!(report, "\n## Log (last {LOG_TAIL_LINES} lines)\n");
+    match std::fs::read_to_string(get_data_dir().join(LOG_FILE.as_str())) {
+        Ok(log) => {
+            let lines: Vec<&str> = log.lines().collect();
+            for line in &lines[lines.len().saturating_sub(LOG_TAIL_LINES)..] {
+                let _ = writeln!(report, "{line}");
+            }
+        }
+        Err(e) => {
+            let _ = writeln!(report, "unavailable: {e}");
+        }
+    }
+
+    let directory = get_data_dir().join(CRASH_DIR);
+    std::fs::create_dir_all(&directory)?;
+    let path = directory.join(format!("crash-{}.md", clock::now().format("%Y%m%d-%H%M%S")));
+    std::fs::write(&path, sanitize(report))?;
+    Ok(path)
+}
+
+fn sanitize(report: String) -> String {
+    match directories::BaseDirs::new() {
+        Some(dirs) => {
+            let home = dirs.home_dir().to_string_lossy().into_owned();
+            if home.len() > 1 {
+                report.replace(&home, "~")
+            } else {
+                report
+            }
+        }
+        None => report,
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use yandex_music::model::track::Track;
+
+use crate::{
+    audio::{downloads, local::LocalTrack},
+    util::{csv, link, log::get_data_dir},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    M3u8,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::M3u8, ExportFormat::Csv, ExportFormat::Json];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::M3u8 => "M3U8 playlist",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::M3u8 => "m3u8",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExportedTrack {
+    id: String,
+    title: String,
+    artists: Vec<String>,
+    album: Option<String>,
+    album_id: Option<u32>,
+    duration_ms: Option<u128>,
+    url: String,
+}
+
+impl From<&Track> for ExportedTrack {
+    fn from(track: &Track) -> Self {
+        let album = track.albums.first();
+        Self {
+            id: track.id.clone(),
+            title: track.title.clone().unwrap_or_default(),
+            artists: track
+                .artists
+                .iter()
+                .filter_map(|a| a.name.clone())
+                .collect(),
+            album: album.and_then(|a| a.title.clone()),
+            album_id: album.and_then(|a| a.id),
+            duration_ms: track.duration.map(|d| d.as_millis()),
+            url: downloads::find(&track.id).map_or_else(
+                || link::track_url(track),
+                |(path, _)| format!("file://{}", path.display()),
+            ),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExportedPlaylist<'a> {
+    title: &'a str,
+    tracks: Vec<ExportedTrack>,
+}
+
+pub fn export_dir() -> PathBuf {
+    directories::UserDirs::new()
+        .and_then(|dirs| dirs.download_dir().map(Path::to_path_buf))
+        .unwrap_or_else(|| get_data_dir().join("exports"))
+}
+
+pub fn render(title: &str, tracks: &[Track], format: ExportFormat) -> color_eyre::Result<String> {
+    let tracks: Vec<ExportedTrack> = tracks.iter().map(ExportedTrack::from).collect();
+
+    let mut out = String::new();
+    match format {
+        ExportFormat::M3u8 => {
+            writeln!(out, "#EXTM3U")?;
+            writeln!(out, "#PLAYLIST:{title}")?;
+            for track in &tracks {
+                let seconds = track.duration_ms.map_or(-1, |ms| (ms / 1000) as i64);
+                writeln!(
+                    out,
+                    "#EXTINF:{seconds},{} - {}",
+                    track.artists.join(", "),
+                    track.title
+                )?;
+                writeln!(out, "{}", track.url)?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(
+                out,
+                "{}",
+                csv::join_line(&[
+                    "Track Name",
+                    "Artist Name(s)",
+                    "Album Name",
+                    "Duration (ms)",
+                    "URL"
+                ])
+            )?;
+            for track in &tracks {
+                writeln!(
+                    out,
+                    "{}",
+                    csv::join_line(&[
+                        track.title.clone(),
+                        track.artists.join(", "),
+                        track.album.clone().unwrap_or_default(),
+                        track
+                            .duration_ms
+                            .map(|ms| ms.to_string())
+                            .unwrap_or_default(),
+                        track.url.clone(),
+                    ])
+                )?;
+            }
+        }
+        ExportFormat::Json => {
+            out = serde_json::to_string_pretty(&ExportedPlaylist { title, tracks })?;
+        }
+    }
+    Ok(out)
+}
+
+pub async fn write(
+    title: String,
+    tracks: Vec<Track>,
+    format: ExportFormat,
+) -> color_eyre::Result<PathBuf> {
+    let contents = render(&title, &tracks, format)?;
+    let path = export_dir().join(format!(
+        "{}.{}",
+        file_name(&title, "playlist"),
+        format.extension()
+    ));
+    save(path, contents).await
+}
+
+pub async fn write_lyrics(track: Track, lrc: String) -> color_eyre::Result<PathBuf> {
+    let path = match LocalTrack::from_track(&track) {
+        Some(local) => local.path.with_extension("lrc"),
+        None => {
+            let artists: Vec<&str> = track
+                .artists
+                .iter()
+                .filter_map(|a| a.name.as_deref())
+                .collect();
+            let title = format!(
+                "{} - {}",
+                artists.join(", "),
+                track.title.as_deref().unwrap_or_default()
+            );
+            export_dir().join(format!("{}.lrc", file_name(&title, "lyrics")))
+        }
+    };
+    save(path, lrc).await
+}
+
+async fn save(path: PathBuf, contents: String) -> color_eyre::Result<PathBuf> {
+    tokio::task::spawn_blocking(move || {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let path = available_path(&path);
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        std::io::Write::write_all(&mut file, contents.as_bytes())?;
+        Ok(path)
+    })
+    .await?
+}
+
+fn available_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{stem} ({n}){extension}")))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn file_name(title: &str, fallback: &str) -> String {
+    let name: String = title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == ' ' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.trim().is_empty() {
+        fallback.to_string()
+    } else {
+        name.trim().to_string()
+    }
+}
@@ -0,0 +1,209 @@
+use yandex_music::model::track::Track;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowField {
+    Index,
+    Title,
+    Artists,
+    Album,
+    Duration,
+    Year,
+    Liked,
+    State,
+}
+
+impl RowField {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "index" => Some(Self::Index),
+            "title" => Some(Self::Title),
+            "artist" | "artists" => Some(Self::Artists),
+            "album" => Some(Self::Album),
+            "duration" => Some(Self::Duration),
+            "year" => Some(Self::Year),
+            "liked" => Some(Self::Liked),
+            "state" => Some(Self::State),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Index => "index",
+            Self::Title => "title",
+            Self::Artists => "artist",
+            Self::Album => "album",
+            Self::Duration => "duration",
+            Self::Year => "year",
+            Self::Liked => "liked",
+            Self::State => "state",
+        }
+    }
+
+    pub fn env_var(&self) -> String {
+        format!("YAMUSIC_{}", self.name().to_ascii_uppercase())
+    }
+
+    pub fn value(&self, ctx: &RowContext) -> String {
+        field_value(*self, ctx)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(RowField),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowFormat {
+    segments: Vec<Segment>,
+}
+
+pub struct RowContext<'a> {
+    pub track: &'a Track,
+    pub index: usize,
+    pub liked: &'a str,
+    pub state: &'a str,
+}
+
+impl RowFormat {
+    pub fn parse(template: &str) -> Self {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+
+        while let Some(pos) = rest.find(['{', '}']) {
+            literal.push_str(&rest[..pos]);
+            let tail = &rest[pos..];
+            if tail.starts_with("{{") || tail.starts_with("}}") {
+                literal.push_str(&tail[..1]);
+                rest = &tail[2..];
+                continue;
+            }
+            if let Some(end) = tail.find('}')
+                && tail.starts_with('{')
+                && let Some(field) = RowField::parse(tail[1..end].trim())
+            {
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(field));
+                rest = &tail[end + 1..];
+                continue;
+            }
+            literal.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Self { segments }
+    }
+
+    pub fn render(&self, ctx: &RowContext) -> String {
+        self.render_with(|field| field_value(field, ctx))
+    }
+
+    pub fn render_with(&self, mut value: impl FnMut(RowField) -> String) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Field(field) => out.push_str(&value(*field)),
+            }
+        }
+        out
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = RowField> + '_ {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Field(field) => Some(*field),
+            Segment::Literal(_) => None,
+        })
+    }
+}
+
+fn field_value(field: RowField, ctx: &RowContext) -> String {
+    let track = ctx.track;
+    match field {
+        RowField::Index => (ctx.index + 1).to_string(),
+        RowField::Title => track.title.clone().unwrap_or_else(|| "Unknown".to_string()),
+        RowField::Artists => track
+            .artists
+            .iter()
+            .filter_map(|a| a.name.as_deref())
+            .collect::<Vec<_>>()
+            .join(", "),
+        RowField::Album => track
+            .albums
+            .first()
+            .and_then(|a| a.title.clone())
+            .unwrap_or_default(),
+        RowField::Duration => track
+            .duration
+            .map(|d| format!("{}:{:02}", d.as_secs() / 60, d.as_secs() % 60))
+            .unwrap_or_default(),
+        RowField::Year => track
+            .albums
+            .first()
+            .and_then(|a| a.year)
+            .map(|y| y.to_string())
+            .unwrap_or_default(),
+        RowField::Liked => ctx.liked.to_string(),
+        RowField::State => ctx.state.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(text: &str) -> Segment {
+        Segment::Literal(text.into())
+    }
+
+    #[test]
+    fn parses_fields_and_literals() {
+        assert_eq!(
+            RowFormat::parse("{index}. {artist} - { title } ({duration})").segments,
+            vec![
+                Segment::Field(RowField::Index),
+                literal(". "),
+                Segment::Field(RowField::Artists),
+                literal(" - "),
+                Segment::Field(RowField::Title),
+                literal(" ("),
+                Segment::Field(RowField::Duration),
+                literal(")"),
+            ]
+        );
+        assert_eq!(
+            RowFormat::parse("{artists}{album}")
+                .fields()
+                .collect::<Vec<_>>(),
+            [RowField::Artists, RowField::Album]
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_and_escaped_braces() {
+        assert_eq!(
+            RowFormat::parse("{{title}} {mood} }{").segments,
+            vec![literal("{title} {mood} }{")]
+        );
+        assert_eq!(RowFormat::parse("{title").segments, vec![literal("{title")]);
+        assert_eq!(RowFormat::parse("").segments, vec![]);
+    }
+
+    #[test]
+    fn renders_with_custom_values() {
+        let format = RowFormat::parse("{title} by {artist}");
+        assert_eq!(
+            format.render_with(|field| format!("${}", field.env_var())),
+            "$YAMUSIC_TITLE by $YAMUSIC_ARTIST"
+        );
+    }
+}
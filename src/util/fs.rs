@@ -0,0 +1,117 @@
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+};
+
+use crate::audio::local::{self, LocalTrack};
+
+pub fn expand_path(path: &str) -> PathBuf {
+    let path = path.trim();
+    match path.strip_prefix("~/") {
+        Some(rest) => directories::BaseDirs::new()
+            .map(|dirs| dirs.home_dir().join(rest))
+            .unwrap_or_else(|| rest.into()),
+        None => PathBuf::from(path),
+    }
+}
+
+pub fn user_music_dir() -> Option<PathBuf> {
+    directories::UserDirs::new()
+        .and_then(|dirs| {
+            dirs.audio_dir()
+                .map(Path::to_path_buf)
+                .or_else(|| Some(dirs.home_dir().join("Music")))
+        })
+        .filter(|dir| dir.is_dir())
+}
+
+pub fn music_dir() -> PathBuf {
+    user_music_dir()
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf()))
+        .unwrap_or_else(|| PathBuf::from("/"))
+}
+
+#[derive(Debug, Clone)]
+pub enum FileEntry {
+    Dir { path: PathBuf, name: String },
+    Track(LocalTrack),
+}
+
+impl FileEntry {
+    pub fn name(&self) -> String {
+        match self {
+            FileEntry::Dir { name, .. } => name.clone(),
+            FileEntry::Track(track) => track.title.clone(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            FileEntry::Dir { path, .. } => path,
+            FileEntry::Track(track) => &track.path,
+        }
+    }
+
+    pub fn scan(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            None
+        } else if path.is_dir() {
+            Some(FileEntry::Dir { path, name })
+        } else {
+            LocalTrack::from_path(path).map(FileEntry::Track)
+        }
+    }
+}
+
+pub fn sort_entries(entries: &mut [FileEntry]) {
+    entries.sort_by(|a, b| match (a, b) {
+        (FileEntry::Dir { name: a, .. }, FileEntry::Dir { name: b, .. }) => {
+            a.to_lowercase().cmp(&b.to_lowercase())
+        }
+        (FileEntry::Dir { .. }, FileEntry::Track(_)) => Ordering::Less,
+        (FileEntry::Track(_), FileEntry::Dir { .. }) => Ordering::Greater,
+        (FileEntry::Track(a), FileEntry::Track(b)) => compare_tracks(a, b),
+    });
+}
+
+pub fn list_dir(path: &Path) -> std::io::Result<Vec<FileEntry>> {
+    let mut entries: Vec<FileEntry> = std::fs::read_dir(path)?
+        .filter_map(|entry| FileEntry::scan(entry.ok()?.path()))
+        .collect();
+    sort_entries(&mut entries);
+    Ok(entries)
+}
+
+pub fn local_tracks(path: &Path) -> std::io::Result<Vec<LocalTrack>> {
+    if !path.is_dir() {
+        return Ok(LocalTrack::read(path).into_iter().collect());
+    }
+
+    let files: Vec<PathBuf> = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    Ok(read_tracks(path, files))
+}
+
+fn read_tracks(dir: &Path, files: Vec<PathBuf>) -> Vec<LocalTrack> {
+    let cover = local::find_cover(dir);
+    let mut tracks: Vec<LocalTrack> = files
+        .into_iter()
+        .filter_map(|file| LocalTrack::read_with_cover(file, cover.clone()))
+        .collect();
+    tracks.sort_by(compare_tracks);
+    tracks
+}
+
+fn compare_tracks(a: &LocalTrack, b: &LocalTrack) -> Ordering {
+    a.album
+        .cmp(&b.album)
+        .then_with(|| {
+            a.track_number
+                .unwrap_or(u32::MAX)
+                .cmp(&b.track_number.unwrap_or(u32::MAX))
+        })
+        .then_with(|| a.path.cmp(&b.path))
+}
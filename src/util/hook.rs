@@ -1,9 +1,17 @@
-use crate::app::terminal::Terminal;
+use crate::{app::terminal::Terminal, util::crash};
 
 pub fn set_panic_hook() {
     let hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
+        let report = crash::write_report(panic_info);
         let _ = Terminal::restore();
         hook(panic_info);
+        match report {
+            Ok(path) => eprintln!(
+                "\nA crash report was written to {}\nPlease attach it when opening an issue.",
+                path.display()
+            ),
+            Err(e) => eprintln!("\nFailed to write a crash report: {e}"),
+        }
     }));
 }
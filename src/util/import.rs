@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use color_eyre::eyre::eyre;
+use futures::{StreamExt, stream};
+use yandex_music::model::track::Track;
+
+use crate::{
+    http::MusicApi,
+    util::{csv, fs},
+};
+
+const SEARCH_CONCURRENCY: usize = 4;
+const MAX_CANDIDATES: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct ImportRow {
+    pub artist: String,
+    pub title: String,
+}
+
+impl ImportRow {
+    pub fn label(&self) -> String {
+        if self.artist.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} - {}", self.artist, self.title)
+        }
+    }
+
+    fn matches(&self, track: &Track) -> bool {
+        let title = normalize(track.title.as_deref().unwrap_or_default());
+        if title != normalize(&self.title) {
+            return false;
+        }
+        let artist = normalize(&self.artist);
+        artist.is_empty()
+            || track.artists.iter().any(|a| {
+                let name = normalize(a.name.as_deref().unwrap_or_default());
+                !name.is_empty() && artist.contains(&name)
+            })
+    }
+}
+
+pub fn candidate_label(track: &Track) -> String {
+    let artists = track
+        .artists
+        .iter()
+        .filter_map(|a| a.name.as_deref())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let title = track.title.as_deref().unwrap_or("Unknown Track");
+    match track.albums.first().and_then(|a| a.title.as_deref()) {
+        Some(album) => format!("{artists} - {title} ({album})"),
+        None => format!("{artists} - {title}"),
+    }
+}
+
+pub fn parse_rows(contents: &str) -> Vec<ImportRow> {
+    let mut lines = contents
+        .lines()
+        .map(|l| l.trim_start_matches('\u{feff}').trim())
+        .filter(|l| !l.is_empty())
+        .peekable();
+
+    let columns = lines.peek().and_then(|header| {
+        let fields: Vec<String> = csv::split_line(header)
+            .iter()
+            .map(|f| f.trim().to_lowercase())
+            .collect();
+        let title = fields
+            .iter()
+            .position(|f| matches!(f.as_str(), "track name" | "title" | "track" | "name"))?;
+        let artist = fields
+            .iter()
+            .position(|f| f.starts_with("artist name"))
+            .or_else(|| fields.iter().position(|f| f == "artist" || f == "artists"))?;
+        Some((artist, title))
+    });
+    if columns.is_some() {
+        lines.next();
+    }
+
+    lines
+        .filter_map(|line| match columns {
+            Some((artist, title)) => {
+                let fields = csv::split_line(line);
+                Some(ImportRow {
+                    artist: fields.get(artist)?.trim().to_string(),
+                    title: fields.get(title)?.trim().to_string(),
+                })
+            }
+            None => {
+                let (artist, title) = line.split_once(" - ").unwrap_or(("", line));
+                Some(ImportRow {
+                    artist: artist.trim().to_string(),
+                    title: title.trim().to_string(),
+                })
+            }
+        })
+        .filter(|row| !row.title.is_empty())
+        .collect()
+}
+
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum ImportMatch {
+    Resolved(Track),
+    Ambiguous(Vec<Track>),
+    Missing,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportEntry {
+    pub row: ImportRow,
+    pub status: ImportMatch,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportSession {
+    pub title: String,
+    pub entries: Vec<ImportEntry>,
+}
+
+impl ImportSession {
+    pub async fn from_file(api: Arc<dyn MusicApi>, path: &str) -> color_eyre::Result<Self> {
+        let path = fs::expand_path(path);
+        let contents = tokio::fs::read_to_string(&path).await?;
+        let rows = parse_rows(&contents);
+        if rows.is_empty() {
+            return Err(eyre!("No tracks found in {}", path.display()));
+        }
+
+        let title = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Imported".to_string());
+        Ok(Self::resolve(api, title, rows).await)
+    }
+
+    pub async fn resolve(api: Arc<dyn MusicApi>, title: String, rows: Vec<ImportRow>) -> Self {
+        let entries = stream::iter(rows)
+            .map(|row| {
+                let api = api.clone();
+                async move {
+                    let query = format!("{} {}", row.artist, row.title);
+                    let candidates: Vec<Track> = api
+                        .search(query.trim())
+                        .await
+                        .ok()
+                        .and_then(|s| s.tracks)
+                        .map(|t| t.results.into_iter().take(MAX_CANDIDATES).collect())
+                        .unwrap_or_default();
+
+                    let status = match candidates.iter().find(|t| row.matches(t)) {
+                        Some(track) => ImportMatch::Resolved(track.clone()),
+                        None if candidates.is_empty() => ImportMatch::Missing,
+                        None => ImportMatch::Ambiguous(candidates),
+                    };
+                    ImportEntry { row, status }
+                }
+            })
+            .buffered(SEARCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        Self { title, entries }
+    }
+
+    pub fn next_ambiguous(&self) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|e| matches!(e.status, ImportMatch::Ambiguous(_)))
+    }
+
+    pub fn choose(&mut self, index: usize, candidate: Option<usize>) {
+        let Some(entry) = self.entries.get_mut(index) else {
+            return;
+        };
+        if let ImportMatch::Ambiguous(candidates) = &entry.status {
+            entry.status = candidate
+                .and_then(|i| candidates.get(i).cloned())
+                .map_or(ImportMatch::Missing, ImportMatch::Resolved);
+        }
+    }
+
+    pub fn tracks(&self) -> Vec<Track> {
+        self.entries
+            .iter()
+            .filter_map(|e| match &e.status {
+                ImportMatch::Resolved(track) => Some(track.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn missing(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, ImportMatch::Missing))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(rows: Vec<ImportRow>) -> Vec<(String, String)> {
+        rows.into_iter().map(|r| (r.artist, r.title)).collect()
+    }
+
+    #[test]
+    fn parses_csv_with_header() {
+        let contents =
+            "\u{feff}Track Name,Artist Name(s),Album\n\"Hello, World\",Foo,Bar\n,Nobody,Empty\n";
+        assert_eq!(
+            pairs(parse_rows(contents)),
+            vec![("Foo".to_string(), "Hello, World".to_string())]
+        );
+    }
+
+    #[test]
+    fn parses_plain_text_lines() {
+        let contents = "Foo - Bar\n\nJust A Title\n";
+        assert_eq!(
+            pairs(parse_rows(contents)),
+            vec![
+                ("Foo".to_string(), "Bar".to_string()),
+                (String::new(), "Just A Title".to_string()),
+            ]
+        );
+    }
+}
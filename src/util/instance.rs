@@ -0,0 +1,101 @@
+use std::{path::PathBuf, time::Duration};
+
+use flume::Sender;
+
+use crate::{event::events::Event, util::log::get_data_dir};
+
+const SOCKET_FILE: &str = "yamusic.sock";
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(250);
+
+pub fn socket_path() -> PathBuf {
+    get_data_dir().join(SOCKET_FILE)
+}
+
+#[cfg(unix)]
+pub type InstanceListener = tokio::net::UnixListener;
+
+#[cfg(not(unix))]
+pub type InstanceListener = ();
+
+pub enum Instance {
+    Primary(std::io::Result<InstanceListener>),
+    Forwarded,
+}
+
+impl Instance {
+    #[cfg(unix)]
+    pub async fn acquire(args: &[String]) -> color_eyre::Result<Self> {
+        use tokio::{io::AsyncWriteExt, net::UnixStream};
+
+        let path = socket_path();
+        if let Ok(mut stream) = UnixStream::connect(&path).await {
+            let mut payload = args.join("\n");
+            payload.push('\n');
+            stream.write_all(payload.as_bytes()).await?;
+            stream.shutdown().await?;
+            return Ok(Instance::Forwarded);
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let _ = std::fs::remove_file(&path);
+
+        Ok(Instance::Primary(tokio::net::UnixListener::bind(&path)))
+    }
+
+    #[cfg(not(unix))]
+    pub async fn acquire(_args: &[String]) -> color_eyre::Result<Self> {
+        Ok(Instance::Primary(Ok(())))
+    }
+}
+
+#[cfg(unix)]
+struct SocketGuard;
+
+#[cfg(unix)]
+impl Drop for SocketGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(socket_path());
+    }
+}
+
+#[cfg(unix)]
+pub async fn serve(listener: InstanceListener, tx: Sender<Event>) {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let _guard = SocketGuard;
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                tracing::warn!(error = %e, "instance_accept_failed");
+                tokio::time::sleep(ACCEPT_BACKOFF).await;
+                continue;
+            }
+        };
+
+        let read = async {
+            let mut lines = BufReader::new(stream).lines();
+            let mut args = Vec::new();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if !line.is_empty() {
+                    args.push(line);
+                }
+            }
+            args
+        };
+        let Ok(args) = tokio::time::timeout(READ_TIMEOUT, read).await else {
+            tracing::warn!("instance_client_timed_out");
+            continue;
+        };
+
+        if tx.send(Event::Forwarded(args)).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn serve(_listener: InstanceListener, _tx: Sender<Event>) {}
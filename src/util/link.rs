@@ -0,0 +1,310 @@
+use color_eyre::eyre::eyre;
+use yandex_music::model::{album::Album, playlist::Playlist, track::Track};
+
+use crate::{
+    audio::local,
+    http::MusicApi,
+    util::{
+        listen_along::{self, SessionRequest},
+        process,
+        track::extract_ids,
+    },
+};
+
+const BASE_URL: &str = "https://music.yandex.ru";
+
+pub fn track_url(track: &Track) -> String {
+    if let Some(path) = track.id.strip_prefix(local::LOCAL_ID_PREFIX) {
+        return format!("file://{path}");
+    }
+    match track.albums.first().and_then(|a| a.id) {
+        Some(album_id) => format!("{BASE_URL}/album/{album_id}/track/{}", track.id),
+        None => format!("{BASE_URL}/track/{}", track.id),
+    }
+}
+
+pub fn album_url(album_id: impl std::fmt::Display) -> String {
+    format!("{BASE_URL}/album/{album_id}")
+}
+
+pub fn artist_url(artist_id: &str) -> String {
+    format!("{BASE_URL}/artist/{artist_id}")
+}
+
+pub fn playlist_url(owner: impl std::fmt::Display, kind: u32) -> String {
+    format!("{BASE_URL}/users/{owner}/playlists/{kind}")
+}
+
+pub fn open_in_browser(url: &str) -> std::io::Result<()> {
+    use std::process::{Command, Stdio};
+
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(process::reap)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MusicLink {
+    Track(String),
+    Album(u32),
+    Artist(String),
+    Playlist { owner: Option<String>, kind: u32 },
+}
+
+impl MusicLink {
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+
+        if let Some((scheme, id)) = input.split_once(':')
+            && !id.starts_with("//")
+        {
+            return match scheme {
+                "track" => Some(MusicLink::Track(id.to_string())),
+                "album" => id.parse().ok().map(MusicLink::Album),
+                "artist" => Some(MusicLink::Artist(id.to_string())),
+                "playlist" => match id.rsplit_once('_') {
+                    Some((owner, kind)) => Some(MusicLink::Playlist {
+                        owner: Some(owner.to_string()),
+                        kind: kind.parse().ok()?,
+                    }),
+                    None => id
+                        .parse()
+                        .ok()
+                        .map(|kind| MusicLink::Playlist { owner: None, kind }),
+                },
+                _ => None,
+            };
+        }
+
+        let path = input
+            .split_once("://")
+            .map_or(input, |(_, rest)| rest)
+            .split(['?', '#'])
+            .next()?;
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        let host = segments.next()?;
+        if !host.starts_with("music.yandex.") {
+            return None;
+        }
+
+        let segments: Vec<&str> = segments.collect();
+        match segments.as_slice() {
+            ["album", _, "track", id, ..] | ["track", id, ..] => {
+                Some(MusicLink::Track(id.to_string()))
+            }
+            ["album", id, ..] => id.parse().ok().map(MusicLink::Album),
+            ["artist", id, ..] => Some(MusicLink::Artist(id.to_string())),
+            ["users", owner, "playlists", kind, ..] => Some(MusicLink::Playlist {
+                owner: Some(owner.to_string()),
+                kind: kind.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    pub async fn resolve(&self, api: &dyn MusicApi) -> color_eyre::Result<ResolvedLink> {
+        match self {
+            MusicLink::Track(id) => api
+                .fetch_tracks(vec![id.clone()])
+                .await?
+                .into_iter()
+                .next()
+                .map(ResolvedLink::Track)
+                .ok_or_else(|| eyre!("Track not found")),
+            MusicLink::Album(id) => {
+                Ok(ResolvedLink::Album(api.fetch_album_with_tracks(*id).await?))
+            }
+            MusicLink::Artist(id) => {
+                let tracks = api.fetch_artist_tracks(id.clone()).await?;
+                let name = tracks
+                    .iter()
+                    .flat_map(|t| t.artists.iter())
+                    .find(|a| a.id.as_deref() == Some(id.as_str()))
+                    .and_then(|a| a.name.clone())
+                    .unwrap_or_else(|| "Artist".to_string());
+                Ok(ResolvedLink::Artist {
+                    id: id.clone(),
+                    name,
+                    tracks,
+                })
+            }
+            MusicLink::Playlist { owner, kind } => {
+                let playlist = match owner {
+                    Some(owner) if *owner != api.current_user_id().to_string() => {
+                        api.fetch_user_playlist(owner, *kind).await?
+                    }
+                    _ => api.fetch_playlist(*kind).await?,
+                };
+                let ids = playlist
+                    .tracks
+                    .as_ref()
+                    .map(extract_ids)
+                    .unwrap_or_default();
+                let tracks = if ids.is_empty() {
+                    Vec::new()
+                } else {
+                    api.fetch_tracks(ids).await?
+                };
+                Ok(ResolvedLink::Playlist(playlist, tracks))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum ResolvedLink {
+    Track(Track),
+    Album(Album),
+    Artist {
+        id: String,
+        name: String,
+        tracks: Vec<Track>,
+    },
+    Playlist(Playlist, Vec<Track>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Invocation {
+    pub link: Option<MusicLink>,
+    pub play: bool,
+    pub session: Option<SessionRequest>,
+}
+
+impl Invocation {
+    pub fn parse(args: &[String]) -> color_eyre::Result<Self> {
+        let mut invocation = Invocation::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--play" | "-p" => invocation.play = true,
+                "--host" | "--join" => {
+                    let addr = args
+                        .next()
+                        .ok_or_else(|| eyre!("{arg} needs an address, e.g. 127.0.0.1:7878"))?
+                        .clone();
+                    invocation.session = Some(if arg == "--host" {
+                        SessionRequest::Host(listen_along::host_address(&addr))
+                    } else {
+                        SessionRequest::Join(addr)
+                    });
+                }
+                "--leave" => invocation.session = Some(SessionRequest::Leave),
+                _ if invocation.link.is_none() => {
+                    invocation.link = Some(
+                        MusicLink::parse(arg).ok_or_else(|| eyre!("Unrecognized link: {arg}"))?,
+                    );
+                }
+                _ => return Err(eyre!("Unexpected argument: {arg}")),
+            }
+        }
+        Ok(invocation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_web_links() {
+        assert_eq!(
+            MusicLink::parse("https://music.yandex.ru/album/123/track/456?utm=x"),
+            Some(MusicLink::Track("456".into()))
+        );
+        assert_eq!(
+            MusicLink::parse("music.yandex.com/album/123"),
+            Some(MusicLink::Album(123))
+        );
+        assert_eq!(
+            MusicLink::parse("https://music.yandex.ru/artist/789/tracks"),
+            Some(MusicLink::Artist("789".into()))
+        );
+        assert_eq!(MusicLink::parse("https://example.com/album/123"), None);
+    }
+
+    #[test]
+    fn keeps_playlist_owner() {
+        assert_eq!(
+            MusicLink::parse("https://music.yandex.ru/users/42/playlists/1005"),
+            Some(MusicLink::Playlist {
+                owner: Some("42".into()),
+                kind: 1005
+            })
+        );
+        assert_eq!(
+            MusicLink::parse(&playlist_url(42, 7)),
+            Some(MusicLink::Playlist {
+                owner: Some("42".into()),
+                kind: 7
+            })
+        );
+        assert_eq!(
+            MusicLink::parse("https://music.yandex.ru/users/some-login/playlists/3"),
+            Some(MusicLink::Playlist {
+                owner: Some("some-login".into()),
+                kind: 3
+            })
+        );
+    }
+
+    #[test]
+    fn parses_short_forms() {
+        assert_eq!(
+            MusicLink::parse("track:456"),
+            Some(MusicLink::Track("456".into()))
+        );
+        assert_eq!(
+            MusicLink::parse("playlist:3"),
+            Some(MusicLink::Playlist {
+                owner: None,
+                kind: 3
+            })
+        );
+        assert_eq!(
+            MusicLink::parse("playlist:42_3"),
+            Some(MusicLink::Playlist {
+                owner: Some("42".into()),
+                kind: 3
+            })
+        );
+        assert_eq!(MusicLink::parse("album:abc"), None);
+    }
+
+    #[test]
+    fn parses_invocation() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let invocation = Invocation::parse(&args(&["--play", "album:5"])).unwrap();
+        assert!(invocation.play);
+        assert_eq!(invocation.link, Some(MusicLink::Album(5)));
+        assert!(Invocation::parse(&args(&["--join"])).is_err());
+        assert_eq!(
+            Invocation::parse(&args(&["--host", "7878"]))
+                .unwrap()
+                .session,
+            Some(SessionRequest::Host("127.0.0.1:7878".into()))
+        );
+        assert_eq!(
+            Invocation::parse(&args(&["--host", "192.168.1.10:7878"]))
+                .unwrap()
+                .session,
+            Some(SessionRequest::Host("192.168.1.10:7878".into()))
+        );
+        assert!(Invocation::parse(&args(&["album:5", "album:6"])).is_err());
+    }
+}
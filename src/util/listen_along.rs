@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use flume::Sender;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+
+use crate::{event::events::Event, framework::tasks::TaskScope};
+
+const BROADCAST_INTERVAL: Duration = Duration::from_secs(1);
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(5);
+const DEFAULT_HOST: &str = "127.0.0.1";
+const MAX_FRAME_BYTES: u64 = 1024 * 1024;
+pub const MAX_DRIFT_MS: u64 = 1_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionRequest {
+    Host(String),
+    Join(String),
+    Leave,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionFrame {
+    pub queue: Vec<String>,
+    pub index: usize,
+    pub position_ms: u64,
+    pub playing: bool,
+}
+
+impl SessionFrame {
+    pub fn current(&self) -> Option<&str> {
+        self.queue.get(self.index).map(String::as_str)
+    }
+
+    fn same_playback(&self, other: &SessionFrame) -> bool {
+        self.queue == other.queue && self.index == other.index && self.playing == other.playing
+    }
+}
+
+pub enum SessionRole {
+    Host(watch::Sender<SessionFrame>),
+    Follower { syncing: Option<String> },
+}
+
+#[derive(Debug, Clone)]
+pub enum SessionEvent {
+    Hosting(String),
+    Joined(String),
+    Following(String),
+    Frame(SessionFrame),
+    SyncFailed(String),
+    Ended(String),
+}
+
+pub fn host_address(addr: &str) -> String {
+    match addr.parse::<u16>() {
+        Ok(port) => format!("{DEFAULT_HOST}:{port}"),
+        Err(_) => addr.to_string(),
+    }
+}
+
+pub async fn host(
+    addr: String,
+    frames: watch::Receiver<SessionFrame>,
+    tx: Sender<Event>,
+    scope: TaskScope,
+) {
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            let _ = tx.send(Event::ListenAlong(SessionEvent::Ended(format!(
+                "Couldn't host on {addr}: {e}"
+            ))));
+            return;
+        }
+    };
+    let local = listener
+        .local_addr()
+        .map_or(addr, |local| local.to_string());
+    let _ = tx.send(Event::ListenAlong(SessionEvent::Hosting(local)));
+
+    let mut backoff = ACCEPT_BACKOFF;
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => {
+                backoff = ACCEPT_BACKOFF;
+                accepted
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "listen_along_accept_failed");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_ACCEPT_BACKOFF);
+                continue;
+            }
+        };
+        let _ = stream.set_nodelay(true);
+        if tx
+            .send(Event::ListenAlong(SessionEvent::Joined(peer.to_string())))
+            .is_err()
+        {
+            break;
+        }
+        scope.spawn_concurrent(&format!("peer {peer}"), broadcast(stream, frames.clone()));
+    }
+}
+
+async fn broadcast(mut stream: TcpStream, mut frames: watch::Receiver<SessionFrame>) {
+    let mut ticker = tokio::time::interval(BROADCAST_INTERVAL);
+    let mut last: Option<SessionFrame> = None;
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            changed = frames.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let moved = last.as_ref().is_none_or(|l| !l.same_playback(&frames.borrow()));
+                if !moved {
+                    continue;
+                }
+            }
+        }
+
+        let frame = frames.borrow_and_update().clone();
+        let Ok(mut line) = serde_json::to_vec(&frame) else {
+            continue;
+        };
+        line.push(b'\n');
+        if stream.write_all(&line).await.is_err() {
+            break;
+        }
+        last = Some(frame);
+    }
+}
+
+pub async fn follow(addr: String, tx: Sender<Event>) {
+    let stream = match TcpStream::connect(&addr).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            let _ = tx.send(Event::ListenAlong(SessionEvent::Ended(format!(
+                "Couldn't join {addr}: {e}"
+            ))));
+            return;
+        }
+    };
+    let _ = stream.set_nodelay(true);
+    let _ = tx.send(Event::ListenAlong(SessionEvent::Following(addr.clone())));
+
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = (&mut reader)
+            .take(MAX_FRAME_BYTES)
+            .read_until(b'\n', &mut line)
+            .await;
+        if !matches!(read, Ok(n) if n > 0) {
+            break;
+        }
+        if line.len() as u64 == MAX_FRAME_BYTES && line.last() != Some(&b'\n') {
+            tracing::warn!(addr = %addr, "listen_along_frame_too_long");
+            let _ = tx.send(Event::ListenAlong(SessionEvent::Ended(format!(
+                "{addr} sent an oversized frame"
+            ))));
+            return;
+        }
+        match serde_json::from_slice::<SessionFrame>(&line) {
+            Ok(frame) => {
+                if tx
+                    .send(Event::ListenAlong(SessionEvent::Frame(frame)))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "listen_along_frame_invalid"),
+        }
+    }
+
+    let _ = tx.send(Event::ListenAlong(SessionEvent::Ended(format!(
+        "{addr} ended the session"
+    ))));
+}
@@ -3,7 +3,10 @@ use std::path::PathBuf;
 use directories::ProjectDirs;
 use lazy_static::lazy_static;
 use tracing_error::ErrorLayer;
-use tracing_subscriber::{self, Layer, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    self, EnvFilter, Layer, Registry, fmt::format::FmtSpan, layer::SubscriberExt,
+    util::SubscriberInitExt,
+};
 
 lazy_static! {
     pub static ref PROJECT_NAME: String = env!("CARGO_CRATE_NAME").to_uppercase().to_string();
@@ -13,6 +16,23 @@ lazy_static! {
             .map(PathBuf::from);
     pub static ref LOG_ENV: String = format!("{}_LOGLEVEL", PROJECT_NAME.clone());
     pub static ref LOG_FILE: String = format!("{}.log", env!("CARGO_PKG_NAME"));
+    pub static ref OTLP_ENV: String = format!("{}_OTLP_ENDPOINT", PROJECT_NAME.clone());
+    pub static ref TRACE_ENV: String = format!("{}_TRACE", PROJECT_NAME.clone());
+}
+
+#[derive(Default)]
+pub struct LogGuard {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take() {
+            let _ = provider.shutdown();
+        }
+    }
 }
 
 fn project_directory() -> Option<ProjectDirs> {
@@ -29,7 +49,17 @@ pub fn get_data_dir() -> PathBuf {
     }
 }
 
-pub fn initialize_logging() -> color_eyre::Result<()> {
+pub fn get_config_dir() -> PathBuf {
+    if let Some(s) = DATA_FOLDER.clone() {
+        s
+    } else if let Some(proj_dirs) = project_directory() {
+        proj_dirs.config_local_dir().to_path_buf()
+    } else {
+        PathBuf::from(".").join(".config")
+    }
+}
+
+pub fn initialize_logging() -> color_eyre::Result<LogGuard> {
     let directory = get_data_dir();
     std::fs::create_dir_all(directory.clone())?;
     let log_path = directory.join(LOG_FILE.clone());
@@ -48,10 +78,67 @@ pub fn initialize_logging() -> color_eyre::Result<()> {
         .with_writer(log_file)
         .with_target(false)
         .with_ansi(false)
-        .with_filter(tracing_subscriber::filter::EnvFilter::from_default_env());
+        .with_span_events(FmtSpan::CLOSE)
+        .with_filter(EnvFilter::from_default_env());
+
+    let endpoint = std::env::var(OTLP_ENV.clone())
+        .ok()
+        .filter(|e| !e.is_empty());
+    let (otlp_layer, guard) = otlp_layer(endpoint.as_deref())?;
+
     tracing_subscriber::registry()
+        .with(otlp_layer)
         .with(file_subscriber)
         .with(ErrorLayer::default())
         .init();
-    Ok(())
+
+    if endpoint.is_some() && cfg!(not(feature = "otlp")) {
+        tracing::warn!("{} is set but this build lacks the otlp feature", *OTLP_ENV);
+    }
+    Ok(guard)
+}
+
+#[cfg(feature = "otlp")]
+fn trace_filter() -> EnvFilter {
+    EnvFilter::try_from_env(TRACE_ENV.clone())
+        .unwrap_or_else(|_| EnvFilter::new(format!("{}=debug", env!("CARGO_CRATE_NAME"))))
+}
+
+type OtlpLayer = Option<Box<dyn Layer<Registry> + Send + Sync>>;
+
+#[cfg(feature = "otlp")]
+fn otlp_layer(endpoint: Option<&str>) -> color_eyre::Result<(OtlpLayer, LogGuard)> {
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::{Resource, trace::SdkTracerProvider};
+
+    let Some(endpoint) = endpoint else {
+        return Ok((None, LogGuard::default()));
+    };
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(env!("CARGO_PKG_NAME"))
+                .build(),
+        )
+        .build();
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+        .with_filter(trace_filter());
+    Ok((
+        Some(layer.boxed()),
+        LogGuard {
+            provider: Some(provider),
+        },
+    ))
+}
+
+#[cfg(not(feature = "otlp"))]
+fn otlp_layer(_endpoint: Option<&str>) -> color_eyre::Result<(OtlpLayer, LogGuard)> {
+    Ok((None, LogGuard::default()))
 }
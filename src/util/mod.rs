@@ -1,6 +1,25 @@
 pub mod animation;
+pub mod chapters;
+pub mod clock;
 pub mod colors;
+pub mod config;
+pub mod crash;
+pub mod csv;
+pub mod export;
+pub mod format;
+pub mod fs;
 pub mod hook;
+pub mod import;
+pub mod instance;
+pub mod link;
+pub mod listen_along;
 pub mod log;
+pub mod now_playing;
+pub mod palette;
+pub mod process;
+pub mod script;
+pub mod smart;
+pub mod state;
 pub mod task;
+pub mod text;
 pub mod track;
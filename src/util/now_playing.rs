@@ -0,0 +1,128 @@
+use std::{io::Write, path::Path};
+
+use flume::{Receiver, Sender};
+use serde_json::json;
+use yandex_music::model::track::Track;
+
+use crate::util::{
+    config::{self, NowPlayingFormat},
+    format::{RowContext, RowFormat},
+    fs, link,
+};
+
+const LIKED_MARK: &str = "♥";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snapshot {
+    track_id: Option<String>,
+    state: &'static str,
+    liked: bool,
+}
+
+pub struct NowPlaying {
+    tx: Sender<(String, String)>,
+    last: Option<(String, Snapshot)>,
+}
+
+impl NowPlaying {
+    pub fn spawn() -> Self {
+        let (tx, rx) = flume::unbounded();
+        let _ = std::thread::Builder::new()
+            .name("yamusic-now-playing".into())
+            .spawn(move || run(rx));
+        Self { tx, last: None }
+    }
+
+    pub fn update(&mut self, track: Option<&Track>, playing: bool, liked: bool) {
+        let config = config::get();
+        let Some(path) = config.now_playing.path.as_deref().filter(|p| !p.is_empty()) else {
+            return;
+        };
+        let state = match (track, playing) {
+            (None, _) => "stopped",
+            (Some(_), true) => "playing",
+            (Some(_), false) => "paused",
+        };
+        let snapshot = Snapshot {
+            track_id: track.map(|t| t.id.clone()),
+            state,
+            liked,
+        };
+        if self
+            .last
+            .as_ref()
+            .is_some_and(|(p, s)| p == path && *s == snapshot)
+        {
+            return;
+        }
+
+        let contents = match config.now_playing.format {
+            NowPlayingFormat::Text => track
+                .map(|track| {
+                    RowFormat::parse(&config.now_playing.template).render(&RowContext {
+                        track,
+                        index: 0,
+                        liked: if liked { LIKED_MARK } else { "" },
+                        state,
+                    })
+                })
+                .unwrap_or_default(),
+            NowPlayingFormat::Json => to_json(track, state, liked),
+        };
+        let _ = self.tx.send((path.to_string(), contents));
+        self.last = Some((path.to_string(), snapshot));
+    }
+}
+
+fn to_json(track: Option<&Track>, state: &str, liked: bool) -> String {
+    let value = match track {
+        Some(track) => json!({
+            "state": state,
+            "id": track.id,
+            "title": track.title,
+            "artists": track
+                .artists
+                .iter()
+                .filter_map(|a| a.name.as_deref())
+                .collect::<Vec<_>>(),
+            "album": track.albums.first().and_then(|a| a.title.as_deref()),
+            "duration_ms": track.duration.map(|d| d.as_millis() as u64),
+            "liked": liked,
+            "url": link::track_url(track),
+        }),
+        None => json!({ "state": state }),
+    };
+    value.to_string()
+}
+
+fn run(rx: Receiver<(String, String)>) {
+    while let Ok(mut latest) = rx.recv() {
+        while let Ok(newer) = rx.try_recv() {
+            latest = newer;
+        }
+        let (path, contents) = latest;
+        if let Err(e) = write(&fs::expand_path(&path), &contents) {
+            tracing::warn!(error = %e, "now_playing_write_failed");
+        }
+    }
+}
+
+fn write(path: &Path, contents: &str) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        if std::fs::metadata(path).is_ok_and(|m| m.file_type().is_fifo()) {
+            let mut pipe = std::fs::OpenOptions::new().write(true).open(path)?;
+            return writeln!(pipe, "{contents}");
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("tmp");
+    let mut file = std::fs::File::create(&temp)?;
+    writeln!(file, "{contents}")?;
+    std::fs::rename(temp, path)
+}
@@ -0,0 +1,130 @@
+use std::sync::OnceLock;
+
+use dashmap::DashMap;
+use image::DynamicImage;
+use yandex_music::model::track::{Track, TrackDerivedColors};
+
+use crate::cache::image::{CoverSize, ImageCache};
+
+const SAMPLE_SIZE: u32 = 32;
+const BUCKET_BITS: u8 = 4;
+
+static DERIVED: OnceLock<DashMap<String, Option<TrackDerivedColors>>> = OnceLock::new();
+
+pub fn track_colors(track: &Track) -> Option<TrackDerivedColors> {
+    if let Some(colors) = &track.derived_colors {
+        return Some(colors.clone());
+    }
+
+    let uri = track
+        .cover_uri
+        .as_ref()
+        .or_else(|| track.albums.first().and_then(|a| a.cover_uri.as_ref()))?;
+    let url = ImageCache::resolve_cover_uri(uri, CoverSize::Full);
+
+    let derived = DERIVED.get_or_init(DashMap::new);
+    if let Some(entry) = derived.get(&url) {
+        return entry.value().clone();
+    }
+
+    let image = ImageCache::global().get_or_fetch(&url)?;
+    derived.insert(url.clone(), None);
+    tokio::task::spawn_blocking(move || {
+        let colors = from_image(&image);
+        DERIVED.get_or_init(DashMap::new).insert(url, colors);
+    });
+    None
+}
+
+pub fn from_image(image: &DynamicImage) -> Option<TrackDerivedColors> {
+    let sample = image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgb8();
+    let shift = 8 - BUCKET_BITS;
+
+    let mut buckets: Vec<(u32, [u64; 3])> = vec![(0, [0; 3]); 1 << (BUCKET_BITS * 3)];
+    let mut total = [0u64; 3];
+    let mut count = 0u64;
+    for pixel in sample.pixels() {
+        let [r, g, b] = pixel.0;
+        let index = ((r >> shift) as usize) << (BUCKET_BITS * 2)
+            | ((g >> shift) as usize) << BUCKET_BITS
+            | (b >> shift) as usize;
+        let bucket = &mut buckets[index];
+        bucket.0 += 1;
+        for (sum, value) in bucket.1.iter_mut().zip([r, g, b]) {
+            *sum += value as u64;
+        }
+        for (sum, value) in total.iter_mut().zip([r, g, b]) {
+            *sum += value as u64;
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return None;
+    }
+
+    let mut dominant: Vec<((u8, u8, u8), f32)> = buckets
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, sum)| {
+            let n64 = *n as u64;
+            let rgb = (
+                (sum[0] / n64) as u8,
+                (sum[1] / n64) as u8,
+                (sum[2] / n64) as u8,
+            );
+            let (saturation, lightness) = saturation_lightness(rgb);
+            let vivid = saturation * (1.0 - (lightness - 0.5).abs() * 1.4).max(0.05);
+            (rgb, *n as f32 * (0.15 + vivid))
+        })
+        .collect();
+    dominant.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let average = (
+        (total[0] / count) as u8,
+        (total[1] / count) as u8,
+        (total[2] / count) as u8,
+    );
+    let accent = dominant.first().map_or(average, |(rgb, _)| *rgb);
+    let secondary = dominant
+        .iter()
+        .map(|(rgb, _)| *rgb)
+        .find(|rgb| distance(*rgb, accent) > 60.0)
+        .unwrap_or(average);
+
+    Some(TrackDerivedColors {
+        average: hex(average),
+        wave_text: hex(mix(accent, (255, 255, 255), 0.35)),
+        mini_player: hex(mix(secondary, (0, 0, 0), 0.25)),
+        accent: hex(accent),
+    })
+}
+
+fn saturation_lightness((r, g, b): (u8, u8, u8)) -> (f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    let saturation = if delta == 0.0 {
+        0.0
+    } else {
+        delta / (1.0 - (2.0 * lightness - 1.0).abs())
+    };
+    (saturation.clamp(0.0, 1.0), lightness)
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> f32 {
+    let dr = a.0 as f32 - b.0 as f32;
+    let dg = a.1 as f32 - b.1 as f32;
+    let db = a.2 as f32 - b.2 as f32;
+    (dr * dr + dg * dg + db * db).sqrt()
+}
+
+fn mix(a: (u8, u8, u8), b: (u8, u8, u8), amount: f32) -> (u8, u8, u8) {
+    let blend = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * amount).round() as u8;
+    (blend(a.0, b.0), blend(a.1, b.1), blend(a.2, b.2))
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02X}{g:02X}{b:02X}")
+}
@@ -0,0 +1,101 @@
+use std::{
+    io::Write,
+    process::{Child, Command, Stdio},
+};
+
+pub fn shell(command: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    let mut process = {
+        let mut process = Command::new("cmd");
+        process.arg("/C");
+        process
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut process = {
+        let mut process = Command::new("sh");
+        process.arg("-c");
+        process
+    };
+    process.arg(command).stdin(Stdio::null());
+    process
+}
+
+pub fn spawn_detached(command: &str) -> bool {
+    shell(command)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(reap)
+        .is_ok()
+}
+
+pub fn reap(mut child: Child) {
+    let _ = std::thread::Builder::new()
+        .name("yamusic-reap".into())
+        .spawn(move || child.wait());
+}
+
+pub fn run_with_input(command: &str, input: &str) -> Result<String, String> {
+    let mut child = shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!("exited with {}", output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+pub fn env_reference(name: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("\"%{name}%\"")
+    } else {
+        format!("\"${{{name}}}\"")
+    }
+}
+
+pub fn run_captured(command: &str, env: &[(String, String)]) -> Result<(bool, String), String> {
+    let output = shell(command)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .output()
+        .map_err(|e| e.to_string())?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok((output.status.success(), text.trim_end().to_string()))
+}
+
+/// Lowers the calling thread's nice value to -10. Unprivileged processes need
+/// `CAP_SYS_NICE` or a `nice` limit of -10 (`RLIMIT_NICE`, e.g. set in
+/// `/etc/security/limits.conf`); without either the thread keeps its priority.
+#[cfg(target_os = "linux")]
+pub fn raise_thread_priority() -> bool {
+    unsafe { libc::setpriority(libc::PRIO_PROCESS as _, libc::gettid() as libc::id_t, -10) == 0 }
+}
+
+#[cfg(target_os = "macos")]
+pub fn raise_thread_priority() -> bool {
+    unsafe {
+        libc::pthread_set_qos_class_self_np(libc::qos_class_t::QOS_CLASS_USER_INTERACTIVE, 0) == 0
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn raise_thread_priority() -> bool {
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_HIGHEST,
+    };
+    unsafe { SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_HIGHEST) != 0 }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn raise_thread_priority() -> bool {
+    false
+}
@@ -0,0 +1,309 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use flume::{Receiver, Sender};
+use rhai::{AST, Array, Dynamic, Engine, Map, Scope};
+use yandex_music::model::track::Track;
+
+use crate::{
+    event::{
+        bus::{EventBus, EventCategory},
+        events::Event,
+    },
+    util::{fs, log::get_config_dir, process, state::HistoryContext},
+};
+
+const SCRIPTS_DIR: &str = "scripts";
+const SCRIPT_EXTENSION: &str = "rhai";
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    TrackStart,
+    TrackEnd,
+    Like,
+    QueueEnd,
+}
+
+impl Hook {
+    fn function(&self) -> &'static str {
+        match self {
+            Hook::TrackStart => "on_track_start",
+            Hook::TrackEnd => "on_track_end",
+            Hook::Like => "on_like",
+            Hook::QueueEnd => "on_queue_end",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ScriptCommand {
+    Notify(String),
+    Queue { id: String, next: bool },
+    ClearQueue,
+    Error(String),
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Request {
+    Hook(Hook, Option<Track>),
+    Reload,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Input {
+    Request(Request),
+    Event(Event),
+}
+
+type Context = Arc<Mutex<Option<(&'static str, String)>>>;
+
+#[derive(Clone)]
+pub struct Scripts {
+    tx: Sender<Request>,
+    context: Context,
+}
+
+pub fn scripts_dir() -> PathBuf {
+    get_config_dir().join(SCRIPTS_DIR)
+}
+
+impl Scripts {
+    pub fn spawn(event_tx: Sender<Event>, bus: &EventBus) -> Self {
+        let (tx, rx) = flume::unbounded();
+        let events = bus.subscribe(EventCategory::Playback);
+        let context = Context::default();
+        let shared = context.clone();
+        let _ = std::thread::Builder::new()
+            .name("yamusic-scripts".into())
+            .spawn(move || run(rx, events, event_tx, shared));
+        Self { tx, context }
+    }
+
+    pub fn set_context(&self, context: Option<&HistoryContext>) {
+        if let Ok(mut current) = self.context.lock() {
+            *current = context.map(|c| (c.kind(), c.name().to_string()));
+        }
+    }
+
+    pub fn fire(&self, hook: Hook, track: Option<&Track>) {
+        let _ = self.tx.send(Request::Hook(hook, track.cloned()));
+    }
+
+    pub fn reload(&self) {
+        let _ = self.tx.send(Request::Reload);
+    }
+}
+
+struct Host {
+    engine: Engine,
+    scripts: Vec<(String, AST)>,
+    commands: Arc<Mutex<Vec<ScriptCommand>>>,
+    context: Context,
+}
+
+impl Host {
+    fn new(context: Context) -> Self {
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_print(|text| tracing::info!(script = %text, "script_print"));
+
+        let push = |commands: &Arc<Mutex<Vec<ScriptCommand>>>| {
+            let commands = commands.clone();
+            move |command: ScriptCommand| {
+                if let Ok(mut commands) = commands.lock() {
+                    commands.push(command);
+                }
+            }
+        };
+        let send = push(&commands);
+        engine.register_fn("notify", move |text: &str| {
+            send(ScriptCommand::Notify(text.to_string()))
+        });
+        let send = push(&commands);
+        engine.register_fn("queue_add", move |id: &str| {
+            send(ScriptCommand::Queue {
+                id: id.to_string(),
+                next: false,
+            })
+        });
+        let send = push(&commands);
+        engine.register_fn("play_next", move |id: &str| {
+            send(ScriptCommand::Queue {
+                id: id.to_string(),
+                next: true,
+            })
+        });
+        let send = push(&commands);
+        engine.register_fn("queue_clear", move || send(ScriptCommand::ClearQueue));
+        engine.register_fn("run", process::spawn_detached);
+        engine.register_fn("write_file", |path: &str, contents: &str| {
+            std::fs::write(fs::expand_path(path), contents).is_ok()
+        });
+
+        Self {
+            engine,
+            scripts: Vec::new(),
+            commands,
+            context,
+        }
+    }
+
+    fn load(&mut self) -> Vec<String> {
+        self.scripts.clear();
+        let Ok(entries) = std::fs::read_dir(scripts_dir()) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == SCRIPT_EXTENSION))
+            .collect();
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            let name = script_name(&path);
+            match self.engine.compile_file(path) {
+                Ok(ast) => self.scripts.push((name, ast)),
+                Err(e) => errors.push(format!("{name}: {e}")),
+            }
+        }
+        errors
+    }
+
+    fn call(&mut self, hook: Hook, track: Option<Track>) -> (Vec<ScriptCommand>, Vec<String>) {
+        let function = hook.function();
+        let context = self.context.lock().ok().and_then(|c| c.clone());
+        let argument = track
+            .as_ref()
+            .map_or(Dynamic::UNIT, |t| track_map(t, context.as_ref()));
+        let mut errors = Vec::new();
+
+        for (name, ast) in &self.scripts {
+            let Some(params) = ast
+                .iter_functions()
+                .find(|f| f.name == function)
+                .map(|f| f.params.len())
+            else {
+                continue;
+            };
+            let mut scope = Scope::new();
+            let result = if params == 0 {
+                self.engine
+                    .call_fn::<Dynamic>(&mut scope, ast, function, ())
+            } else {
+                self.engine
+                    .call_fn::<Dynamic>(&mut scope, ast, function, (argument.clone(),))
+            };
+            if let Err(e) = result {
+                errors.push(format!("{name}: {function}: {e}"));
+            }
+        }
+
+        let commands = self
+            .commands
+            .lock()
+            .map(|mut c| std::mem::take(&mut *c))
+            .unwrap_or_default();
+        (commands, errors)
+    }
+}
+
+fn run(rx: Receiver<Request>, events: Receiver<Event>, event_tx: Sender<Event>, context: Context) {
+    let mut host = Host::new(context);
+    let mut current: Option<Track> = None;
+    let report = |errors: Vec<String>| {
+        for error in errors {
+            tracing::warn!(error = %error, "script_failed");
+            let _ = event_tx.send(Event::Script(ScriptCommand::Error(error)));
+        }
+    };
+    report(host.load());
+
+    loop {
+        let input = flume::Selector::new()
+            .recv(&rx, |r| r.map(Input::Request))
+            .recv(&events, |e| e.map(Input::Event))
+            .wait();
+        let (hook, track) = match input {
+            Ok(Input::Request(Request::Hook(hook, track))) => (hook, track),
+            Ok(Input::Request(Request::Reload)) => {
+                report(host.load());
+                continue;
+            }
+            Ok(Input::Event(Event::TrackStarted(track, _))) => {
+                current = Some(track.clone());
+                (Hook::TrackStart, Some(track))
+            }
+            Ok(Input::Event(Event::TrackEnded)) => (Hook::TrackEnd, current.clone()),
+            Ok(Input::Event(Event::QueueEnded)) => (Hook::QueueEnd, None),
+            Ok(Input::Event(_)) => continue,
+            Err(_) => break,
+        };
+        if host.scripts.is_empty() {
+            continue;
+        }
+        let (commands, errors) = host.call(hook, track);
+        for command in commands {
+            let _ = event_tx.send(Event::Script(command));
+        }
+        report(errors);
+    }
+}
+
+fn script_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn track_map(track: &Track, context: Option<&(&'static str, String)>) -> Dynamic {
+    let artists: Array = track
+        .artists
+        .iter()
+        .filter_map(|a| a.name.clone())
+        .map(Dynamic::from)
+        .collect();
+    let mut map = Map::new();
+    map.insert("id".into(), track.id.clone().into());
+    map.insert(
+        "title".into(),
+        track.title.clone().unwrap_or_default().into(),
+    );
+    map.insert(
+        "artist".into(),
+        artists
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+            .into(),
+    );
+    map.insert("artists".into(), artists.into());
+    map.insert(
+        "album".into(),
+        track
+            .albums
+            .first()
+            .and_then(|a| a.title.clone())
+            .unwrap_or_default()
+            .into(),
+    );
+    map.insert(
+        "duration_ms".into(),
+        (track.duration.map_or(0, |d| d.as_millis()) as i64).into(),
+    );
+    map.insert(
+        "context".into(),
+        context.map_or(Dynamic::UNIT, |(kind, name)| {
+            let mut context = Map::new();
+            context.insert("kind".into(), (*kind).into());
+            context.insert("name".into(), name.clone().into());
+            context.into()
+        }),
+    );
+    map.into()
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use color_eyre::eyre::eyre;
+use im::HashSet;
+use yandex_music::model::track::Track;
+
+use crate::util::state::{HistoryEntry, PlayCounts, PlayHistory, PlayStat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, value: u32, target: u32) -> bool {
+        match self {
+            Comparison::Less => value < target,
+            Comparison::LessOrEqual => value <= target,
+            Comparison::Equal => value == target,
+            Comparison::GreaterOrEqual => value >= target,
+            Comparison::Greater => value > target,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Condition {
+    Liked,
+    Genre(String),
+    Artist(String),
+    Album(String),
+    PlayedWithin(i64),
+    Plays(Comparison, u32),
+}
+
+impl Condition {
+    fn parse(clause: &str) -> color_eyre::Result<Self> {
+        let lower = clause.to_lowercase();
+        if lower == "liked" {
+            return Ok(Condition::Liked);
+        }
+
+        if let Some(rest) = lower.strip_prefix("played in ") {
+            let days = rest
+                .trim_end_matches("days")
+                .trim_end_matches("day")
+                .trim_end_matches('d')
+                .trim();
+            return days
+                .parse()
+                .map(Condition::PlayedWithin)
+                .map_err(|_| eyre!("Invalid day count in \"{clause}\""));
+        }
+
+        if let Some(rest) = lower.strip_prefix("plays") {
+            let rest = rest.trim();
+            let (comparison, value) = [
+                ("<=", Comparison::LessOrEqual),
+                (">=", Comparison::GreaterOrEqual),
+                ("<", Comparison::Less),
+                (">", Comparison::Greater),
+                ("=", Comparison::Equal),
+            ]
+            .into_iter()
+            .find_map(|(op, cmp)| rest.strip_prefix(op).map(|v| (cmp, v.trim())))
+            .ok_or_else(|| eyre!("Missing comparison in \"{clause}\""))?;
+            return value
+                .parse()
+                .map(|n| Condition::Plays(comparison, n))
+                .map_err(|_| eyre!("Invalid play count in \"{clause}\""));
+        }
+
+        let (field, value) = clause
+            .split_once('=')
+            .ok_or_else(|| eyre!("Unknown condition \"{clause}\""))?;
+        let value = value.trim().trim_matches('"').to_lowercase();
+        match field.trim().to_lowercase().as_str() {
+            "genre" => Ok(Condition::Genre(value)),
+            "artist" => Ok(Condition::Artist(value)),
+            "album" => Ok(Condition::Album(value)),
+            other => Err(eyre!("Unknown field \"{other}\"")),
+        }
+    }
+
+    fn matches(&self, stat: &PlayStat, liked: &HashSet<String>, now: DateTime<Utc>) -> bool {
+        let entry = &stat.track;
+        match self {
+            Condition::Liked => liked.contains(&entry.track_id),
+            Condition::Genre(genre) => entry
+                .genre
+                .as_deref()
+                .is_some_and(|g| g.to_lowercase() == *genre),
+            Condition::Artist(artist) => entry.artists.iter().any(|a| a.to_lowercase() == *artist),
+            Condition::Album(album) => entry
+                .album
+                .as_deref()
+                .is_some_and(|a| a.to_lowercase() == *album),
+            Condition::PlayedWithin(days) => stat.last_played() >= now - Duration::days(*days),
+            Condition::Plays(comparison, count) => comparison.holds(stat.count, *count),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Term {
+    negated: bool,
+    condition: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule(Vec<Term>);
+
+impl Rule {
+    pub fn parse(input: &str) -> color_eyre::Result<Self> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let terms = words
+            .split(|w| w.eq_ignore_ascii_case("and"))
+            .map(|clause| {
+                let (negated, clause) = match clause.split_first() {
+                    Some((first, rest)) if first.eq_ignore_ascii_case("not") => (true, rest),
+                    _ => (false, clause),
+                };
+                if clause.is_empty() {
+                    return Err(eyre!("Empty condition in \"{input}\""));
+                }
+                Ok(Term {
+                    negated,
+                    condition: Condition::parse(&clause.join(" "))?,
+                })
+            })
+            .collect::<color_eyre::Result<Vec<_>>>()?;
+        Ok(Self(terms))
+    }
+
+    pub fn matches(&self, stat: &PlayStat, liked: &HashSet<String>, now: DateTime<Utc>) -> bool {
+        self.0
+            .iter()
+            .all(|term| term.condition.matches(stat, liked, now) != term.negated)
+    }
+
+    pub fn evaluate(
+        &self,
+        library: &Library,
+        liked: &HashSet<String>,
+        now: DateTime<Utc>,
+        limit: Option<usize>,
+    ) -> Vec<Track> {
+        let candidates = library.candidates();
+        let mut stats: Vec<&PlayStat> = candidates
+            .iter()
+            .filter(|stat| self.matches(stat, liked, now))
+            .collect();
+        stats.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| b.last_played().cmp(&a.last_played()))
+        });
+        stats
+            .into_iter()
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|stat| stat.track.to_track())
+            .collect()
+    }
+}
+
+pub struct Library<'a> {
+    pub counts: &'a PlayCounts,
+    pub history: &'a PlayHistory,
+    pub liked: &'a [Track],
+}
+
+impl Library<'_> {
+    fn candidates(&self) -> Vec<PlayStat> {
+        let mut stats: HashMap<&str, PlayStat> = self
+            .counts
+            .stats()
+            .map(|stat| (stat.track.track_id.as_str(), stat.clone()))
+            .collect();
+
+        let mut played: HashMap<&str, PlayStat> = HashMap::new();
+        for entry in self.history.entries() {
+            if stats.contains_key(entry.track_id.as_str()) {
+                continue;
+            }
+            let stat = played
+                .entry(entry.track_id.as_str())
+                .or_insert_with(|| PlayStat {
+                    track: entry.clone(),
+                    count: 0,
+                    recent: Vec::new(),
+                });
+            if entry.played_at > stat.track.played_at {
+                stat.track = entry.clone();
+            }
+            stat.count += 1;
+        }
+        stats.extend(played);
+
+        for track in self.liked {
+            stats.entry(track.id.as_str()).or_insert_with(|| PlayStat {
+                track: HistoryEntry::new(track, DateTime::UNIX_EPOCH, None),
+                count: 0,
+                recent: Vec::new(),
+            });
+        }
+        stats.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn track(id: &str, artist: &str, genre: &str) -> Track {
+        serde_json::from_value(json!({
+            "id": id,
+            "realId": id,
+            "title": format!("Track {id}"),
+            "artists": [{ "name": artist }],
+            "albums": [{ "id": 1, "title": "Album", "genre": genre }],
+        }))
+        .unwrap()
+    }
+
+    fn ids(tracks: Vec<Track>) -> Vec<String> {
+        let mut ids: Vec<String> = tracks.into_iter().map(|t| t.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn parses_terms() {
+        assert_eq!(
+            Rule::parse("liked AND not genre = Rock and plays >= 3 and played in 7 days").unwrap(),
+            Rule(vec![
+                Term {
+                    negated: false,
+                    condition: Condition::Liked,
+                },
+                Term {
+                    negated: true,
+                    condition: Condition::Genre("rock".into()),
+                },
+                Term {
+                    negated: false,
+                    condition: Condition::Plays(Comparison::GreaterOrEqual, 3),
+                },
+                Term {
+                    negated: false,
+                    condition: Condition::PlayedWithin(7),
+                },
+            ])
+        );
+        assert_eq!(
+            Rule::parse(r#"artist = "Daft Punk""#).unwrap(),
+            Rule(vec![Term {
+                negated: false,
+                condition: Condition::Artist("daft punk".into()),
+            }])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        assert!(Rule::parse("plays 3").is_err());
+        assert!(Rule::parse("played in many days").is_err());
+        assert!(Rule::parse("liked and").is_err());
+        assert!(Rule::parse("mood = calm").is_err());
+    }
+
+    #[test]
+    fn evaluates_liked_and_history() {
+        let now = DateTime::UNIX_EPOCH + Duration::days(100);
+        let mut counts = PlayCounts::default();
+        counts.record(&track("1", "A", "rock"), now);
+        let mut history = PlayHistory::default();
+        history.record(HistoryEntry::new(&track("2", "B", "rock"), now, None));
+        let liked_tracks = vec![track("1", "A", "rock"), track("3", "C", "jazz")];
+        let liked: HashSet<String> = ["1".to_string(), "3".to_string()].into_iter().collect();
+        let library = Library {
+            counts: &counts,
+            history: &history,
+            liked: &liked_tracks,
+        };
+
+        let evaluate = |rule: &str| {
+            ids(Rule::parse(rule)
+                .unwrap()
+                .evaluate(&library, &liked, now, None))
+        };
+        assert_eq!(evaluate("liked"), ["1", "3"]);
+        assert_eq!(evaluate("genre = rock"), ["1", "2"]);
+        assert_eq!(evaluate("plays >= 1"), ["1", "2"]);
+        assert_eq!(evaluate("not liked and played in 1 day"), ["2"]);
+    }
+}
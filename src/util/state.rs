@@ -0,0 +1,620 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    path::Path,
+};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::json;
+use yandex_music::model::track::Track;
+
+use crate::{app::actions::Route, audio::enums::RepeatMode, util::log::get_data_dir};
+
+const SESSION_FILE: &str = "session.json";
+const TRACK_GAIN_FILE: &str = "track_gain.json";
+const PODCAST_SPEED_FILE: &str = "podcast_speed.json";
+const EPISODE_PROGRESS_FILE: &str = "episode_progress.json";
+const CONTEXT_POSITIONS_FILE: &str = "context_positions.json";
+const PLAYLIST_PREFS_FILE: &str = "playlist_prefs.json";
+const HISTORY_FILE: &str = "history.json";
+const PLAY_COUNTS_FILE: &str = "play_counts.json";
+const DAILY_MIX_FILE: &str = "daily_mix.json";
+pub const MAX_TRACK_GAIN_DB: f32 = 12.0;
+pub const MIN_PLAYBACK_SPEED: f32 = 0.5;
+pub const MAX_PLAYBACK_SPEED: f32 = 3.0;
+const MAX_HISTORY_ENTRIES: usize = 1000;
+const RECENT_PLAYS_DAYS: i64 = 31;
+const EPISODE_FINISHED_MARGIN_MS: u64 = 30_000;
+const EPISODE_MIN_RESUME_MS: u64 = 5_000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub queue: Vec<String>,
+    pub queue_index: usize,
+    pub position_ms: u64,
+    pub volume: u8,
+    pub is_shuffled: bool,
+    pub repeat_mode: RepeatMode,
+    pub routes: Vec<Route>,
+    pub selections: Vec<Option<usize>>,
+}
+
+impl SessionState {
+    pub fn load() -> Option<Self> {
+        read_json(SESSION_FILE, "session")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(SESSION_FILE, self)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TrackGains(HashMap<String, f32>);
+
+impl TrackGains {
+    pub fn load() -> Self {
+        load_json(TRACK_GAIN_FILE, "track_gain")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(TRACK_GAIN_FILE, self)
+    }
+
+    pub fn get(&self, track_id: &str) -> f32 {
+        self.0.get(track_id).copied().unwrap_or(0.0)
+    }
+
+    pub fn adjust(&mut self, track_id: &str, delta_db: f32) -> f32 {
+        let gain = (self.get(track_id) + delta_db).clamp(-MAX_TRACK_GAIN_DB, MAX_TRACK_GAIN_DB);
+        if gain.abs() < f32::EPSILON {
+            self.0.remove(track_id);
+            0.0
+        } else {
+            self.0.insert(track_id.to_string(), gain);
+            gain
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PodcastSpeeds(HashMap<String, f32>);
+
+impl PodcastSpeeds {
+    pub fn load() -> Self {
+        load_json(PODCAST_SPEED_FILE, "podcast_speed")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(PODCAST_SPEED_FILE, self)
+    }
+
+    pub fn get(&self, podcast_id: &str) -> f32 {
+        self.0.get(podcast_id).copied().unwrap_or(1.0)
+    }
+
+    pub fn adjust(&mut self, podcast_id: &str, delta: f32) -> f32 {
+        let speed = ((self.get(podcast_id) + delta) * 100.0).round() / 100.0;
+        let speed = speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED);
+        if (speed - 1.0).abs() < f32::EPSILON {
+            self.0.remove(podcast_id);
+        } else {
+            self.0.insert(podcast_id.to_string(), speed);
+        }
+        speed
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EpisodeState {
+    pub position_ms: u64,
+    pub duration_ms: u64,
+    pub played: bool,
+}
+
+impl EpisodeState {
+    pub fn ratio(&self) -> f64 {
+        if self.played {
+            return 1.0;
+        }
+        if self.duration_ms == 0 {
+            return 0.0;
+        }
+        (self.position_ms as f64 / self.duration_ms as f64).clamp(0.0, 1.0)
+    }
+
+    pub fn resume_point(&self) -> Option<u64> {
+        (!self.played && self.position_ms >= EPISODE_MIN_RESUME_MS).then_some(self.position_ms)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EpisodeProgress(HashMap<String, EpisodeState>);
+
+impl EpisodeProgress {
+    pub fn load() -> Self {
+        load_json(EPISODE_PROGRESS_FILE, "episode_progress")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(EPISODE_PROGRESS_FILE, self)
+    }
+
+    pub fn get(&self, track_id: &str) -> Option<EpisodeState> {
+        self.0.get(track_id).copied()
+    }
+
+    pub fn update(&mut self, track_id: &str, position_ms: u64, duration_ms: u64) {
+        let played = duration_ms > 0
+            && position_ms + EPISODE_FINISHED_MARGIN_MS.min(duration_ms / 10) >= duration_ms;
+        self.0.insert(
+            track_id.to_string(),
+            EpisodeState {
+                position_ms: if played { 0 } else { position_ms },
+                duration_ms,
+                played,
+            },
+        );
+    }
+
+    pub fn set_played(&mut self, track_id: &str, duration_ms: u64, played: bool) {
+        if played {
+            self.0.insert(
+                track_id.to_string(),
+                EpisodeState {
+                    position_ms: 0,
+                    duration_ms,
+                    played,
+                },
+            );
+        } else {
+            self.0.remove(track_id);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextPosition {
+    pub track_id: String,
+    pub title: String,
+    pub position_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ContextPositions(HashMap<String, ContextPosition>);
+
+impl ContextPositions {
+    pub fn load() -> Self {
+        load_json(CONTEXT_POSITIONS_FILE, "context_positions")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(CONTEXT_POSITIONS_FILE, self)
+    }
+
+    pub fn get(&self, key: &str) -> Option<ContextPosition> {
+        self.0.get(key).cloned()
+    }
+
+    pub fn set(&mut self, key: &str, position: ContextPosition) {
+        self.0.insert(key.to_string(), position);
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.0.remove(key).is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackSort {
+    #[default]
+    Playlist,
+    Title,
+    Artist,
+    Album,
+    Duration,
+}
+
+impl TrackSort {
+    pub fn next(self) -> Self {
+        match self {
+            TrackSort::Playlist => TrackSort::Title,
+            TrackSort::Title => TrackSort::Artist,
+            TrackSort::Artist => TrackSort::Album,
+            TrackSort::Album => TrackSort::Duration,
+            TrackSort::Duration => TrackSort::Playlist,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TrackSort::Playlist => "Playlist order",
+            TrackSort::Title => "Title",
+            TrackSort::Artist => "Artist",
+            TrackSort::Album => "Album",
+            TrackSort::Duration => "Duration",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaylistPrefs {
+    pub sort: TrackSort,
+    pub liked_only: bool,
+    pub grouped: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlaylistPreferences(HashMap<String, PlaylistPrefs>);
+
+impl PlaylistPreferences {
+    pub fn load() -> Self {
+        load_json(PLAYLIST_PREFS_FILE, "playlist_prefs")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(PLAYLIST_PREFS_FILE, self)
+    }
+
+    pub fn key(owner_uid: u64, kind: u32) -> String {
+        format!("{owner_uid}:{kind}")
+    }
+
+    pub fn get(&self, key: &str) -> PlaylistPrefs {
+        self.0.get(key).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, key: &str, prefs: PlaylistPrefs) {
+        if prefs == PlaylistPrefs::default() {
+            self.0.remove(key);
+        } else {
+            self.0.insert(key.to_string(), prefs);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HistoryContext {
+    Playlist {
+        kind: u32,
+        #[serde(default)]
+        owner: u64,
+        title: String,
+    },
+    Album {
+        id: String,
+        title: String,
+    },
+    Artist {
+        id: String,
+        name: String,
+    },
+    Podcast {
+        id: String,
+        title: String,
+    },
+    Wave {
+        seeds: Vec<String>,
+        title: Option<String>,
+    },
+    Folder {
+        path: String,
+        title: String,
+    },
+}
+
+impl HistoryContext {
+    pub fn folder(path: &Path) -> Self {
+        HistoryContext::Folder {
+            path: path.display().to_string(),
+            title: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HistoryContext::Playlist { .. } => "Playlist",
+            HistoryContext::Album { .. } => "Album",
+            HistoryContext::Artist { .. } => "Artist",
+            HistoryContext::Podcast { .. } => "Podcast",
+            HistoryContext::Wave { .. } => "Wave",
+            HistoryContext::Folder { .. } => "Folder",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            HistoryContext::Playlist { title, .. }
+            | HistoryContext::Album { title, .. }
+            | HistoryContext::Podcast { title, .. }
+            | HistoryContext::Folder { title, .. } => title,
+            HistoryContext::Artist { name, .. } => name,
+            HistoryContext::Wave { title, .. } => title.as_deref().unwrap_or("My Wave"),
+        }
+    }
+
+    pub fn resume_key(&self) -> Option<String> {
+        match self {
+            HistoryContext::Playlist { kind, owner, .. } => {
+                Some(format!("playlist:{owner}:{kind}"))
+            }
+            HistoryContext::Album { id, .. } => Some(format!("album:{id}")),
+            HistoryContext::Folder { path, .. } => Some(format!("folder:{path}")),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub track_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub artists: Vec<String>,
+    #[serde(default)]
+    pub artist_ids: Vec<String>,
+    pub album: Option<String>,
+    pub album_id: Option<u32>,
+    #[serde(default)]
+    pub genre: Option<String>,
+    pub cover_uri: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub played_at: DateTime<Utc>,
+    #[serde(default)]
+    pub context: Option<HistoryContext>,
+}
+
+impl HistoryEntry {
+    pub fn new(track: &Track, played_at: DateTime<Utc>, context: Option<HistoryContext>) -> Self {
+        let album = track.albums.first();
+        Self {
+            track_id: track.id.clone(),
+            title: track.title.clone().unwrap_or_default(),
+            artists: track
+                .artists
+                .iter()
+                .filter_map(|a| a.name.clone())
+                .collect(),
+            artist_ids: track.artists.iter().filter_map(|a| a.id.clone()).collect(),
+            album: album.and_then(|a| a.title.clone()),
+            album_id: album.and_then(|a| a.id),
+            genre: album.and_then(|a| a.genre.clone()),
+            cover_uri: track
+                .cover_uri
+                .clone()
+                .or_else(|| album.and_then(|a| a.cover_uri.clone())),
+            duration_ms: track.duration.map(|d| d.as_millis() as u64),
+            played_at,
+            context,
+        }
+    }
+
+    pub fn to_track(&self) -> Track {
+        let artists: Vec<_> = self
+            .artists
+            .iter()
+            .map(|name| json!({ "name": name }))
+            .collect();
+        let albums: Vec<_> = self
+            .album
+            .iter()
+            .map(|title| {
+                json!({
+                    "id": self.album_id,
+                    "title": title,
+                    "genre": self.genre,
+                    "coverUri": self.cover_uri,
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "id": self.track_id,
+            "realId": self.track_id,
+            "title": self.title,
+            "artists": artists,
+            "albums": albums,
+            "coverUri": self.cover_uri,
+            "durationMs": self.duration_ms,
+        }))
+        .expect("history entry is always a valid track")
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlayHistory(VecDeque<HistoryEntry>);
+
+impl PlayHistory {
+    pub fn load() -> Self {
+        load_json(HISTORY_FILE, "history")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(HISTORY_FILE, self)
+    }
+
+    pub fn record(&mut self, entry: HistoryEntry) {
+        if self
+            .0
+            .front()
+            .is_some_and(|last| last.track_id == entry.track_id)
+        {
+            self.0.pop_front();
+        }
+        self.0.push_front(entry);
+        self.0.truncate(MAX_HISTORY_ENTRIES);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.0.iter()
+    }
+
+    pub fn recent_contexts(&self, limit: usize) -> Vec<(HistoryContext, DateTime<Utc>)> {
+        let mut contexts: Vec<(HistoryContext, DateTime<Utc>)> = Vec::new();
+        for entry in &self.0 {
+            let Some(context) = &entry.context else {
+                continue;
+            };
+            if contexts.iter().any(|(c, _)| c == context) {
+                continue;
+            }
+            contexts.push((context.clone(), entry.played_at));
+            if contexts.len() == limit {
+                break;
+            }
+        }
+        contexts
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayRange {
+    Week,
+    Month,
+    #[default]
+    AllTime,
+}
+
+impl PlayRange {
+    pub fn next(self) -> Self {
+        match self {
+            PlayRange::Week => PlayRange::Month,
+            PlayRange::Month => PlayRange::AllTime,
+            PlayRange::AllTime => PlayRange::Week,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlayRange::Week => "This week",
+            PlayRange::Month => "This month",
+            PlayRange::AllTime => "All time",
+        }
+    }
+
+    fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            PlayRange::Week => Some(now - Duration::days(7)),
+            PlayRange::Month => Some(now - Duration::days(30)),
+            PlayRange::AllTime => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayStat {
+    pub track: HistoryEntry,
+    pub count: u32,
+    #[serde(default)]
+    pub recent: Vec<DateTime<Utc>>,
+}
+
+impl PlayStat {
+    pub fn last_played(&self) -> DateTime<Utc> {
+        self.track.played_at
+    }
+
+    pub fn count_in(&self, range: PlayRange, now: DateTime<Utc>) -> u32 {
+        match range.since(now) {
+            Some(since) => self.recent.iter().filter(|t| **t >= since).count() as u32,
+            None => self.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlayCounts(HashMap<String, PlayStat>);
+
+impl PlayCounts {
+    pub fn load() -> Self {
+        load_json(PLAY_COUNTS_FILE, "play_counts")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(PLAY_COUNTS_FILE, self)
+    }
+
+    pub fn record(&mut self, track: &Track, played_at: DateTime<Utc>) {
+        let entry = HistoryEntry::new(track, played_at, None);
+        let stat = self.0.entry(track.id.clone()).or_insert_with(|| PlayStat {
+            track: entry.clone(),
+            count: 0,
+            recent: Vec::new(),
+        });
+        stat.track = entry;
+        stat.count += 1;
+        stat.recent.push(played_at);
+        let cutoff = played_at - Duration::days(RECENT_PLAYS_DAYS);
+        stat.recent.retain(|t| *t >= cutoff);
+    }
+
+    pub fn stats(&self) -> impl Iterator<Item = &PlayStat> {
+        self.0.values()
+    }
+
+    pub fn most_played(&self, range: PlayRange, now: DateTime<Utc>) -> Vec<(&PlayStat, u32)> {
+        let mut stats: Vec<(&PlayStat, u32)> = self
+            .0
+            .values()
+            .map(|stat| (stat, stat.count_in(range, now)))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        stats.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| b.0.last_played().cmp(&a.0.last_played()))
+        });
+        stats
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyMix {
+    pub date: NaiveDate,
+    pub track_ids: Vec<String>,
+}
+
+impl DailyMix {
+    pub fn load() -> Option<Self> {
+        read_json(DAILY_MIX_FILE, "daily_mix")
+    }
+
+    pub fn save(&self) -> color_eyre::Result<()> {
+        save_json(DAILY_MIX_FILE, self)
+    }
+}
+
+fn read_json<T: DeserializeOwned>(file: &str, label: &str) -> Option<T> {
+    let contents = std::fs::read_to_string(get_data_dir().join(file)).ok()?;
+    serde_json::from_str(&contents)
+        .inspect_err(|e| tracing::warn!(error = %e, store = label, "state_parse_failed"))
+        .ok()
+}
+
+fn load_json<T: DeserializeOwned + Default>(file: &str, label: &str) -> T {
+    read_json(file, label).unwrap_or_default()
+}
+
+fn save_json(file: &str, value: &impl Serialize) -> color_eyre::Result<()> {
+    let path = get_data_dir().join(file);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(tmp, path)?;
+    Ok(())
+}
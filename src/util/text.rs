@@ -0,0 +1,95 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+
+    let mut result = String::new();
+    let mut current = 0;
+    for grapheme in text.graphemes(true) {
+        let w = grapheme.width();
+        if current + w + 1 > width {
+            break;
+        }
+        result.push_str(grapheme);
+        current += w;
+    }
+    result.push('…');
+    result
+}
+
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for word in paragraph.split_word_bounds() {
+            let w = word.width();
+            if line_width + w > width && !line.is_empty() {
+                lines.push(line.trim_end().to_string());
+                line.clear();
+                line_width = 0;
+                if word.trim().is_empty() {
+                    continue;
+                }
+            }
+            if w > width {
+                for grapheme in word.graphemes(true) {
+                    let gw = grapheme.width();
+                    if line_width + gw > width && !line.is_empty() {
+                        lines.push(std::mem::take(&mut line));
+                        line_width = 0;
+                    }
+                    line.push_str(grapheme);
+                    line_width += gw;
+                }
+            } else {
+                line.push_str(word);
+                line_width += w;
+            }
+        }
+        lines.push(line.trim_end().to_string());
+    }
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+pub fn strip_markdown(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line = line.trim();
+            let line = line.trim_start_matches('#').trim_start();
+            let line = match line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+                Some(item) => format!("• {item}"),
+                None => line.to_string(),
+            };
+            strip_links(&line).replace("**", "").replace("__", "")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn strip_links(line: &str) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        let Some(mid) = rest[start..].find("](").map(|i| start + i) else {
+            break;
+        };
+        let Some(end) = rest[mid..].find(')').map(|i| mid + i) else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        result.push_str(&rest[start + 1..mid]);
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
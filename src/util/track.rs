@@ -1,4 +1,21 @@
-use yandex_music::model::playlist::PlaylistTracks;
+use yandex_music::model::{album::Album, playlist::PlaylistTracks, track::Track};
+
+pub fn is_explicit(track: &Track) -> bool {
+    track.explicit == Some(true) || track.content_warning.is_some()
+}
+
+pub fn is_explicit_album(album: &Album) -> bool {
+    album.explicit == Some(true) || album.content_warning.is_some()
+}
+
+pub fn podcast_id(track: &Track) -> Option<String> {
+    let album = track.albums.first()?;
+    let is_podcast = album.meta_type.as_deref() == Some("podcast")
+        || track.item_type.as_deref() == Some("podcast-episode");
+    is_podcast
+        .then(|| album.id.map(|id| id.to_string()))
+        .flatten()
+}
 
 pub fn extract_ids(playlist_tracks: &PlaylistTracks) -> Vec<String> {
     match playlist_tracks {